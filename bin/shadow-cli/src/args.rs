@@ -1,18 +1,59 @@
 use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
-use shadow_clone::CloneArgs;
+use shadow_abi_diff::{AbiDiffArgs, CheckCompatibilityArgs, DiffAbiArgs};
+use shadow_audit_log::AuditLogArgs;
+use shadow_catalog::CatalogArgs;
+use shadow_check_rpc::CheckRpcArgs;
+use shadow_clone::{CloneArgs, ForkArgs, InspectIpfsArgs};
 use shadow_compile::CompileArgs;
-use shadow_config::ConfigArgs;
-use shadow_etherscan_fetch::FetchArgs;
+use shadow_completions::CompletionsArgs;
+use shadow_config::{
+    ArchiveConfigArgs, CleanConfigArgs, ConfigArgs, MigrateConfigArgs, PrintEnvArgs,
+};
+use shadow_contract_info::ContractInfoArgs;
+use shadow_convert::ConvertArgs;
+use shadow_copy::{CopyArgs, MergeArgs};
+use shadow_decode::{
+    DecodeLogArgs, DecodeReturndataArgs, DecodeRevertArgs, DecodeTxArgs, PullEventsArgs,
+};
+use shadow_etherscan_fetch::{FetchArgs, UpdateSourceArgs};
+use shadow_export_shadow_json::{ExportShadowJsonArgs, GenerateRethConfigArgs};
+use shadow_export_types::ExportTypesArgs;
+use shadow_find::FindArgs;
+use shadow_group_stats::GroupStatsArgs;
+use shadow_hash_group::HashGroupArgs;
+use shadow_import::ImportArgs;
 use shadow_init::InitArgs;
-use shadow_push::PushArgs;
+use shadow_lint::LintArgs;
+use shadow_migrate::MigrateArgs;
+use shadow_network_switch::NetworkSwitchArgs;
+use shadow_pack::{PackArgs, UnpackArgs};
+use shadow_publish_abi::PublishAbiArgs;
+use shadow_push::{BumpArgs, PinLocalArgs, PreviewPushArgs, PushArgs, SignArgs};
 
-use shadow_simulate::SimulateArgs;
+use shadow_registry_lookup::{RegistryLookupArgs, RegistrySearchArgs};
+use shadow_rpc_proxy::RpcProxyArgs;
+use shadow_scan_group::ScanGroupArgs;
+use shadow_schema::SchemaArgs;
+use shadow_set_creator::SetCreatorArgs;
+use shadow_show_abi::ShowAbiArgs;
+use shadow_show_chain_spec::ShowChainSpecArgs;
+use shadow_show_remappings::ShowRemappingsArgs;
+use shadow_simulate::{
+    BatchSimulateArgs, BundleSimulateArgs, EstimateGasArgs, GenerateFoundryTestArgs,
+    ReplayReceiptArgs, SimulateArgs, TraceArgs,
+};
+use shadow_source_map::SourceMapArgs;
+use shadow_summary::{ContractsArgs, SummaryArgs};
 use shadow_tracing::{
-    tracing_subscriber::filter::Directive, FileWorkerGuard, LayerInfo, LogFormat, ShadowTracer,
-    Tracer,
+    tracing_subscriber::filter::Directive, FileInfo, FileWorkerGuard, LayerInfo, LogFormat,
+    ShadowTracer, Tracer,
 };
+use shadow_untrack::UntrackArgs;
+use shadow_verify_bytecode::VerifyBytecodeArgs;
+use shadow_watch::WatchArgs;
 use std::{
     fmt::{self, Display},
+    path::{Path, PathBuf},
     str::FromStr,
 };
 use tracing::{level_filters::LevelFilter, Level};
@@ -38,6 +79,15 @@ pub(crate) struct LogArgs {
     )]
     pub(crate) color: ColorMode,
 
+    /// Also write logs to this file, as plain text. Defaults to `shadow_<timestamp>.log` in the
+    /// current directory if no path is given, or if the path is a directory.
+    #[clap(long, value_name = "PATH", global = true, num_args = 0..=1)]
+    pub(crate) log_file: Option<Option<PathBuf>>,
+
+    /// The size, in megabytes, at which the log file is rotated.
+    #[clap(long, value_name = "MB", global = true, default_value_t = 100)]
+    pub(crate) log_file_max_size: u64,
+
     /// The verbosity settings for the tracer.
     #[clap(flatten)]
     pub(crate) verbosity: Verbosity,
@@ -54,18 +104,52 @@ impl LogArgs {
         )
     }
 
-    /// Initializes tracing with the configured options from cli args.
-    pub(crate) fn init_tracing(&self) -> eyre::Result<Option<FileWorkerGuard>> {
+    /// Initializes tracing with the configured options from cli args. `config_log_file` is the
+    /// configured `log_file_path`, used when `--log-file` isn't passed.
+    pub(crate) fn init_tracing(
+        &self,
+        config_log_file: Option<&str>,
+    ) -> eyre::Result<Option<FileWorkerGuard>> {
         let mut tracer = ShadowTracer::new();
 
         let stdout = self.layer(self.log_stdout_format, self.log_stdout_filter.clone(), true);
         tracer = tracer.with_stdout(stdout);
 
+        let log_file = match &self.log_file {
+            Some(path) => Some(path.clone().unwrap_or_else(|| PathBuf::from("."))),
+            None => config_log_file.map(PathBuf::from),
+        };
+        if let Some(path) = log_file {
+            let path = if path.is_dir() { path.join(default_log_file_name()) } else { path };
+            let dir = path
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+            let file_name = path
+                .file_name()
+                .map_or_else(default_log_file_name, |name| name.to_string_lossy().to_string());
+
+            // the log file is always plain text, regardless of `--color` and the stdout format
+            let file = self.layer(LogFormat::Terminal, String::new(), false);
+            let file_info = FileInfo::new(dir, self.log_file_max_size * 1024 * 1024, MAX_LOG_FILES)
+                .with_file_name(&file_name);
+            tracer = tracer.with_file(file, file_info);
+        }
+
         let guard = tracer.init()?;
         Ok(guard)
     }
 }
 
+/// The number of rotated log files to keep alongside the current one
+const MAX_LOG_FILES: usize = 5;
+
+/// The name of the log file written when `--log-file` is given without a file name, e.g.
+/// `shadow_20240620_120000.log`
+fn default_log_file_name() -> String {
+    format!("shadow_{}.log", chrono::Local::now().format("%Y%m%d_%H%M%S"))
+}
+
 /// The color mode for the cli.
 #[derive(Debug, Copy, Clone, ValueEnum, Eq, PartialEq)]
 pub(crate) enum ColorMode {
@@ -141,6 +225,10 @@ pub(crate) struct Arguments {
     #[clap(subcommand)]
     pub(crate) sub: Subcommands,
 
+    /// The named RPC profile to use for `--rpc-url`, as set with `shadow config set-profile`.
+    #[clap(long, global = true, required = false)]
+    pub(crate) profile: Option<String>,
+
     #[clap(flatten)]
     pub(crate) logs: LogArgs,
 }
@@ -154,6 +242,13 @@ pub(crate) struct Arguments {
 pub(crate) enum Subcommands {
     #[clap(name = "config", about = "Display or edit your shadow CLI configuration.")]
     Config(ConfigArgs),
+    #[clap(
+        name = "print-env",
+        about = "Display the resolved configuration and where each value comes from"
+    )]
+    PrintEnv(PrintEnvArgs),
+    #[clap(name = "check-rpc", about = "Check that an RPC URL supports the methods shadow needs")]
+    CheckRpc(CheckRpcArgs),
     #[clap(
         name = "etherscan-source",
         alias = "fetch",
@@ -165,6 +260,11 @@ pub(crate) enum Subcommands {
         about = "Compile a shadowed contract with the original contract settings"
     )]
     Compile(CompileArgs),
+    #[clap(
+        name = "remote-compile",
+        about = "Compile a shadowed contract via a remote compiler, without a local forge installation"
+    )]
+    RemoteCompile(CompileArgs),
     #[clap(
         name = "init",
         about = "Initialize a new shadow contract group which may be pinned to IPFS"
@@ -178,10 +278,278 @@ pub(crate) enum Subcommands {
         about = "Simulates a transaction with shadow overrides."
     )]
     Simulate(SimulateArgs),
+    #[clap(
+        name = "estimate-gas",
+        about = "Estimates the gas used by a call with shadow overrides."
+    )]
+    EstimateGas(EstimateGasArgs),
+    #[clap(
+        name = "bundle-simulate",
+        about = "Simulates an ERC-4337 UserOperation with shadow overrides."
+    )]
+    BundleSimulate(BundleSimulateArgs),
+    #[clap(
+        name = "replay-receipt",
+        about = "Simulate the transaction a saved JSON transaction receipt belongs to"
+    )]
+    ReplayReceipt(ReplayReceiptArgs),
+    #[clap(
+        name = "decode-tx",
+        about = "Decodes a transaction's calldata using the ABIs in a shadow contract group."
+    )]
+    DecodeTx(DecodeTxArgs),
+    #[clap(
+        name = "decode-log",
+        about = "Decodes a raw log using the ABIs in a shadow contract group."
+    )]
+    DecodeLog(DecodeLogArgs),
+    #[clap(
+        name = "pull-events",
+        about = "Fetch and decode a contract's historical events using the ABIs in a shadow contract group"
+    )]
+    PullEvents(PullEventsArgs),
     #[clap(
         name = "clone",
         alias = "pull",
         about = "Clones a shadow contract group from IPFS and saves it to the local filesystem"
     )]
     Clone(CloneArgs),
+    #[clap(
+        name = "hash-group",
+        about = "Compute a deterministic content hash of a shadow contract group"
+    )]
+    HashGroup(HashGroupArgs),
+    #[clap(name = "pack", about = "Create a portable tarball of a shadow contract group")]
+    Pack(PackArgs),
+    #[clap(
+        name = "unpack",
+        about = "Extract a shadow contract group from a tarball created with `shadow pack`"
+    )]
+    Unpack(UnpackArgs),
+    #[clap(name = "copy", about = "Copy a shadow contract from one contract group to another")]
+    Copy(CopyArgs),
+    #[clap(
+        name = "merge",
+        about = "Merge all contracts from one shadow contract group into another"
+    )]
+    Merge(MergeArgs),
+    #[clap(
+        name = "untrack",
+        about = "Remove a contract from a shadow contract group without deleting its files"
+    )]
+    Untrack(UntrackArgs),
+    #[clap(name = "set-creator", about = "Set the creator address of a shadow contract group")]
+    SetCreator(SetCreatorArgs),
+    #[clap(
+        name = "registry-lookup",
+        about = "Look up a shadow contract group in the logs.xyz registry"
+    )]
+    RegistryLookup(RegistryLookupArgs),
+    #[clap(
+        name = "registry-search",
+        about = "Search the logs.xyz registry for shadow contract groups"
+    )]
+    RegistrySearch(RegistrySearchArgs),
+    #[clap(name = "lint", about = "Lint the source files of a shadow contract")]
+    Lint(LintArgs),
+    #[clap(name = "catalog", about = "Manage a local catalog of known shadow contract groups")]
+    Catalog(CatalogArgs),
+    #[clap(name = "schema", about = "Print the JSON schema of a shadow data format")]
+    Schema(SchemaArgs),
+    #[clap(
+        name = "summary",
+        about = "Show an overview of whether a shadow contract group is ready to push"
+    )]
+    Summary(SummaryArgs),
+    #[clap(
+        name = "abi-diff",
+        about = "Compare a shadow contract's ABI against a previous version"
+    )]
+    AbiDiff(AbiDiffArgs),
+    #[clap(
+        name = "watch",
+        about = "Recompile shadow contracts whenever their source files change"
+    )]
+    Watch(WatchArgs),
+    #[clap(
+        name = "import",
+        about = "Import a deployed contract from Hardhat or Foundry build artifacts"
+    )]
+    Import(ImportArgs),
+    #[clap(
+        name = "convert",
+        about = "Convert a shadow contract's source between flat and structured layouts"
+    )]
+    Convert(ConvertArgs),
+    #[clap(
+        name = "publish-abi",
+        about = "Register a shadow contract's function and event signatures with 4byte.directory"
+    )]
+    PublishAbi(PublishAbiArgs),
+    #[clap(
+        name = "audit-log",
+        about = "Display the local history of pushed shadow contract groups"
+    )]
+    AuditLog(AuditLogArgs),
+    #[clap(
+        name = "bump",
+        about = "Re-push a shadow contract group with updated metadata, without recompiling"
+    )]
+    Bump(BumpArgs),
+    #[clap(
+        name = "contract-info",
+        about = "Display the metadata of a contract in a shadow contract group"
+    )]
+    ContractInfo(ContractInfoArgs),
+    #[clap(
+        name = "show-remappings",
+        about = "Display the remappings used to resolve a shadow contract's imports"
+    )]
+    ShowRemappings(ShowRemappingsArgs),
+    #[clap(
+        name = "group-stats",
+        about = "Compute aggregate statistics about a shadow contract group"
+    )]
+    GroupStats(GroupStatsArgs),
+    #[clap(
+        name = "show-abi",
+        about = "Display the compiled ABI of a shadow contract in a readable format"
+    )]
+    ShowAbi(ShowAbiArgs),
+    #[clap(name = "completions", about = "Generate or install shell completion scripts")]
+    Completions(CompletionsArgs),
+    #[clap(name = "trace", about = "Trace a transaction with shadow overrides")]
+    Trace(TraceArgs),
+    #[clap(
+        name = "verify-bytecode",
+        about = "Check that a shadow contract's compiled bytecode matches its on-chain bytecode"
+    )]
+    VerifyBytecode(VerifyBytecodeArgs),
+    #[clap(
+        name = "find",
+        about = "Search for contracts matching a pattern across shadow contract groups"
+    )]
+    Find(FindArgs),
+    #[clap(
+        name = "check-compatibility",
+        about = "Check that a new version of a pushed contract group is backward-compatible"
+    )]
+    CheckCompatibility(CheckCompatibilityArgs),
+    #[clap(
+        name = "fork",
+        about = "Creates a modified copy of a shadow contract group pinned to IPFS"
+    )]
+    Fork(ForkArgs),
+    #[clap(
+        name = "preview-push",
+        about = "Show what `shadow push` would pin to IPFS, without pinning it"
+    )]
+    PreviewPush(PreviewPushArgs),
+    #[clap(
+        name = "export-shadow-json",
+        about = "Write a shadow-reth `shadow.json` from a contract group's compiled artifacts"
+    )]
+    ExportShadowJson(ExportShadowJsonArgs),
+    #[clap(
+        name = "clean-config",
+        about = "Find and remove stale or invalid values from your shadow CLI configuration."
+    )]
+    CleanConfig(CleanConfigArgs),
+    #[clap(
+        name = "generate-foundry-test",
+        about = "Generate a forge fork test which replays a transaction with shadow overrides"
+    )]
+    GenerateFoundryTest(GenerateFoundryTestArgs),
+    #[clap(
+        name = "decode-revert",
+        about = "Decode why a transaction reverted when simulated with shadow overrides"
+    )]
+    DecodeRevert(DecodeRevertArgs),
+    #[clap(
+        name = "network-switch",
+        about = "Switch to a new RPC URL and check that a shadow contract group's contracts exist on it"
+    )]
+    NetworkSwitch(NetworkSwitchArgs),
+    #[clap(
+        name = "migrate",
+        about = "Update a shadow contract group's info.json to the latest format"
+    )]
+    Migrate(MigrateArgs),
+    #[clap(
+        name = "pin-local",
+        about = "Compile a shadow contract group and pin it to a local IPFS node"
+    )]
+    PinLocal(PinLocalArgs),
+    #[clap(
+        name = "rpc-proxy",
+        about = "Start a local RPC proxy which serves shadow contracts' bytecode"
+    )]
+    RpcProxy(RpcProxyArgs),
+    #[clap(name = "diff-abi", about = "Compare two ABI files, and list the changes between them")]
+    DiffAbi(DiffAbiArgs),
+    #[clap(
+        name = "source-map",
+        about = "Map program counters in a shadow contract's bytecode back to its source"
+    )]
+    SourceMap(SourceMapArgs),
+    #[clap(
+        name = "generate-reth-config",
+        about = "Generate a shadow-reth `shadow.json` from a contract group's compiled artifacts"
+    )]
+    GenerateRethConfig(GenerateRethConfigArgs),
+    #[clap(
+        name = "update-source",
+        about = "Re-fetch a contract's verified source from Etherscan and re-apply your changes to it"
+    )]
+    UpdateSource(UpdateSourceArgs),
+    #[clap(
+        name = "batch-simulate",
+        about = "Simulate a list of transactions from a file with shadow overrides"
+    )]
+    BatchSimulate(BatchSimulateArgs),
+    #[clap(
+        name = "export-types",
+        about = "Generate TypeScript or Python type definitions from shadow contract ABIs"
+    )]
+    ExportTypes(ExportTypesArgs),
+    #[clap(
+        name = "inspect-ipfs",
+        about = "List the contents of a shadow contract group pinned to IPFS, without cloning it"
+    )]
+    InspectIpfs(InspectIpfsArgs),
+    #[clap(
+        name = "migrate-config",
+        about = "Migrate your shadow CLI configuration from an older version of the CLI"
+    )]
+    MigrateConfig(MigrateConfigArgs),
+    #[clap(
+        name = "sign",
+        about = "Sign or verify a message or EIP-712 typed data with any supported signer"
+    )]
+    Sign(SignArgs),
+    #[clap(
+        name = "show-chain-spec",
+        about = "Display the EVM spec used when simulating at a given block"
+    )]
+    ShowChainSpec(ShowChainSpecArgs),
+    #[clap(
+        name = "archive-config",
+        about = "Export your shadow CLI configuration to a portable file, or import one"
+    )]
+    ArchiveConfig(ArchiveConfigArgs),
+    #[clap(
+        name = "decode-returndata",
+        about = "Decode the return data of a call using the ABIs in a shadow contract group"
+    )]
+    DecodeReturndata(DecodeReturndataArgs),
+    #[clap(
+        name = "contracts",
+        about = "List the contracts in a shadow contract group, with filters"
+    )]
+    Contracts(ContractsArgs),
+    #[clap(
+        name = "scan-group",
+        about = "Check the ABIs of a shadow contract group against known vulnerability patterns"
+    )]
+    ScanGroup(ScanGroupArgs),
 }
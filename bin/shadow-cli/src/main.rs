@@ -4,19 +4,40 @@
 pub(crate) mod args;
 
 use args::{Arguments, Subcommands};
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use eyre::Result;
+use shadow_catalog::CatalogCommand;
 use shadow_common::version::*;
 use shadow_config::Configuration;
-use tracing::info;
+use tracing::{info, warn};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // parse command line arguments
     let args = Arguments::parse();
 
-    // init tracing
-    let _ = args.logs.init_tracing();
+    // load config
+    let mut config = Configuration::load()?;
+
+    // init tracing. the guard flushes the log file, if any, when it's dropped
+    let _guard = args.logs.init_tracing(config.log_file_path.as_deref());
+
+    // completion scripts are printed to stdout and run on every keypress, so they skip the
+    // version check below
+    if let Subcommands::Completions(subargs) = &args.sub {
+        return shadow_completions::completions(subargs.clone(), Arguments::command()).await;
+    }
+
+    // keys written by older versions are ignored, so point out that they need migrating
+    if !matches!(args.sub, Subcommands::MigrateConfig(_)) {
+        match Configuration::stale_keys() {
+            Ok(stale_keys) if !stale_keys.is_empty() => warn!(
+                "~/.shadow/config.json contains outdated keys ({}). Run `shadow migrate-config` to update it",
+                stale_keys.join(", ")
+            ),
+            _ => {}
+        }
+    }
 
     // spawn a new tokio runtime to get remote version while the main runtime is running
     let current_version = current_version();
@@ -26,19 +47,63 @@ async fn main() -> Result<()> {
         tokio::task::spawn(remote_version()).await??
     };
 
-    // load config
-    let config = Configuration::load()?;
+    // a selected RPC profile takes the place of the configured `rpc_url`, so it applies to
+    // every subcommand where `--rpc-url` isn't passed
+    match (&args.profile, &config.default_profile) {
+        (Some(profile), _) => config.rpc_url = Some(config.rpc_profile(profile)?),
+        (None, Some(profile)) => match config.rpc_profile(profile) {
+            Ok(rpc_url) => config.rpc_url = Some(rpc_url),
+            Err(e) => warn!("ignoring default_profile: {}", e),
+        },
+        (None, None) => {}
+    }
 
     match args.sub {
         Subcommands::Config(subargs) => shadow_config::config(subargs)?,
-        Subcommands::Init(subargs) => shadow_init::init(subargs).await?,
+        Subcommands::PrintEnv(subargs) => shadow_config::print_env(subargs)?,
+        Subcommands::CheckRpc(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_check_rpc::check_rpc(subargs).await?
+        }
+        Subcommands::Init(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_init::init(subargs).await?
+        }
         Subcommands::Compile(mut subargs) => {
             if let Some(rpc_url) = config.rpc_url {
                 if &subargs.rpc_url == "http://localhost:8545" {
                     subargs.rpc_url = rpc_url;
                 }
             }
+            (subargs.remote_compiler_url, subargs.logs_xyz_api_key) = remote_compiler_config(
+                (subargs.remote_compiler_url, subargs.logs_xyz_api_key),
+                (config.remote_compiler_url, config.logs_xyz_api_key),
+            );
+
+            shadow_compile::compile(subargs).await?
+        }
+        Subcommands::RemoteCompile(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+            (subargs.remote_compiler_url, subargs.logs_xyz_api_key) = remote_compiler_config(
+                (subargs.remote_compiler_url, subargs.logs_xyz_api_key),
+                (config.remote_compiler_url, config.logs_xyz_api_key),
+            );
 
+            subargs.remote = true;
             shadow_compile::compile(subargs).await?
         }
         Subcommands::Simulate(mut subargs) => {
@@ -50,6 +115,60 @@ async fn main() -> Result<()> {
 
             shadow_simulate::simulate(subargs).await?
         }
+        Subcommands::EstimateGas(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_simulate::estimate_gas(subargs).await?
+        }
+        Subcommands::BundleSimulate(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_simulate::bundle_simulate(subargs).await?
+        }
+        Subcommands::ReplayReceipt(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_simulate::replay_receipt(subargs).await?
+        }
+        Subcommands::DecodeTx(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_decode::decode_tx(subargs).await?
+        }
+        Subcommands::DecodeLog(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_decode::decode_log(subargs).await?
+        }
+        Subcommands::PullEvents(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_decode::pull_events(subargs).await?
+        }
         Subcommands::Fetch(mut subargs) => {
             if let Some(etherscan_api_key) = config.etherscan_api_key {
                 if subargs.etherscan_api_key.is_none() {
@@ -92,6 +211,16 @@ async fn main() -> Result<()> {
                     subargs.pinata_secret_api_key = Some(pinata_secret_api_key)
                 }
             }
+            if let Some(fleek_api_key) = config.fleek_api_key {
+                if subargs.fleek_api_key.is_none() {
+                    subargs.fleek_api_key = Some(fleek_api_key)
+                }
+            }
+            if let Some(fleek_api_secret) = config.fleek_api_secret {
+                if subargs.fleek_api_secret.is_none() {
+                    subargs.fleek_api_secret = Some(fleek_api_secret)
+                }
+            }
             if let Some(gateway_url) = config.ipfs_gateway_url {
                 subargs.ipfs_gateway_url = gateway_url;
             }
@@ -101,18 +230,286 @@ async fn main() -> Result<()> {
                 }
             }
 
+            (subargs.remote_compiler_url, subargs.logs_xyz_api_key) = remote_compiler_config(
+                (subargs.remote_compiler_url, subargs.logs_xyz_api_key),
+                (config.remote_compiler_url, config.logs_xyz_api_key),
+            );
+
             shadow_push::push(subargs).await?
         }
+        Subcommands::HashGroup(subargs) => shadow_hash_group::hash_group(subargs).await?,
+        Subcommands::Pack(subargs) => shadow_pack::pack(subargs).await?,
+        Subcommands::Unpack(subargs) => shadow_pack::unpack(subargs).await?,
+        Subcommands::Copy(subargs) => shadow_copy::copy(subargs).await?,
+        Subcommands::Merge(subargs) => shadow_copy::merge(subargs).await?,
+        Subcommands::Untrack(subargs) => shadow_untrack::untrack(subargs).await?,
+        Subcommands::SetCreator(subargs) => shadow_set_creator::set_creator(subargs).await?,
+        Subcommands::RegistryLookup(mut subargs) => {
+            if let Some(gateway_url) = config.ipfs_gateway_url {
+                subargs.ipfs_gateway_url = gateway_url;
+            }
+
+            shadow_registry_lookup::registry_lookup(subargs).await?
+        }
+        Subcommands::RegistrySearch(subargs) => {
+            shadow_registry_lookup::registry_search(subargs).await?
+        }
+        Subcommands::Lint(subargs) => shadow_lint::lint(subargs).await?,
+        Subcommands::Catalog(mut subargs) => {
+            if let (CatalogCommand::Sync(sync_args), Some(gateway_url)) =
+                (&mut subargs.command, config.ipfs_gateway_url)
+            {
+                sync_args.ipfs_gateway_url = gateway_url;
+            }
+
+            shadow_catalog::catalog(subargs).await?
+        }
+        Subcommands::Schema(subargs) => shadow_schema::schema(subargs).await?,
+        Subcommands::Summary(subargs) => shadow_summary::summary(subargs).await?,
+        Subcommands::AbiDiff(mut subargs) => {
+            if let Some(gateway_url) = config.ipfs_gateway_url {
+                subargs.ipfs_gateway_url = gateway_url;
+            }
+
+            shadow_abi_diff::abi_diff(subargs).await?
+        }
+        Subcommands::Watch(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_watch::watch(subargs).await?
+        }
+        Subcommands::Import(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_import::import(subargs).await?
+        }
+        Subcommands::Convert(subargs) => shadow_convert::convert(subargs).await?,
+        Subcommands::PublishAbi(subargs) => shadow_publish_abi::publish_abi(subargs).await?,
+        Subcommands::AuditLog(subargs) => shadow_audit_log::audit_log(subargs).await?,
+        Subcommands::Bump(mut subargs) => {
+            if let Some(pinata_api_key) = config.pinata_api_key {
+                if subargs.pinata_api_key.is_none() {
+                    subargs.pinata_api_key = Some(pinata_api_key)
+                }
+            }
+            if let Some(pinata_secret_api_key) = config.pinata_secret_api_key {
+                if subargs.pinata_secret_api_key.is_none() {
+                    subargs.pinata_secret_api_key = Some(pinata_secret_api_key)
+                }
+            }
+            if let Some(gateway_url) = config.ipfs_gateway_url {
+                subargs.ipfs_gateway_url = gateway_url;
+            }
+
+            shadow_push::bump(subargs).await?
+        }
+        Subcommands::ContractInfo(subargs) => shadow_contract_info::contract_info(subargs).await?,
+        Subcommands::ShowRemappings(subargs) => {
+            shadow_show_remappings::show_remappings(subargs).await?
+        }
+        Subcommands::GroupStats(subargs) => shadow_group_stats::group_stats(subargs).await?,
+        Subcommands::ShowAbi(subargs) => shadow_show_abi::show_abi(subargs).await?,
+        Subcommands::Completions(_) => unreachable!("handled before the version check"),
+        Subcommands::Trace(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_simulate::trace(subargs).await?
+        }
+        Subcommands::VerifyBytecode(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_verify_bytecode::verify_bytecode(subargs).await?
+        }
+        Subcommands::Find(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_find::find(subargs).await?
+        }
+        Subcommands::CheckCompatibility(mut subargs) => {
+            if let Some(gateway_url) = config.ipfs_gateway_url {
+                subargs.ipfs_gateway_url = gateway_url;
+            }
+
+            shadow_abi_diff::check_compatibility(subargs).await?
+        }
+        Subcommands::Fork(mut subargs) => {
+            if let Some(etherscan_api_key) = config.etherscan_api_key {
+                if subargs.etherscan_api_key.is_none() {
+                    subargs.etherscan_api_key = Some(etherscan_api_key);
+                }
+            }
+            if let Some(gateway_url) = config.ipfs_gateway_url {
+                subargs.ipfs_gateway_url = gateway_url;
+            }
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_clone::fork(subargs).await?
+        }
+        Subcommands::PreviewPush(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_push::preview_push(subargs).await?
+        }
+        Subcommands::ExportShadowJson(subargs) => {
+            shadow_export_shadow_json::export_shadow_json(subargs).await?
+        }
+        Subcommands::CleanConfig(subargs) => shadow_config::clean_config(subargs).await?,
+        Subcommands::GenerateFoundryTest(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_simulate::generate_foundry_test(subargs).await?
+        }
+        Subcommands::DecodeRevert(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_decode::decode_revert(subargs).await?
+        }
+        Subcommands::NetworkSwitch(mut subargs) => {
+            if subargs.previous_rpc_url.is_none() {
+                subargs.previous_rpc_url = config.rpc_url;
+            }
+
+            shadow_network_switch::network_switch(subargs).await?
+        }
+        Subcommands::Migrate(subargs) => shadow_migrate::migrate(subargs).await?,
+        Subcommands::PinLocal(mut subargs) => {
+            if let Some(api_url) = config.local_ipfs_api_url {
+                if &subargs.api_url == "http://127.0.0.1:5001" {
+                    subargs.api_url = api_url;
+                }
+            }
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_push::pin_local(subargs).await?
+        }
+        Subcommands::RpcProxy(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_rpc_proxy::rpc_proxy(subargs).await?
+        }
+        Subcommands::DiffAbi(mut subargs) => {
+            if let Some(gateway_url) = config.ipfs_gateway_url {
+                subargs.ipfs_gateway_url = gateway_url;
+            }
+
+            shadow_abi_diff::diff_abi(subargs).await?
+        }
+        Subcommands::SourceMap(subargs) => shadow_source_map::source_map(subargs).await?,
+        Subcommands::GenerateRethConfig(subargs) => {
+            shadow_export_shadow_json::generate_reth_config(subargs).await?
+        }
+        Subcommands::UpdateSource(mut subargs) => {
+            if let Some(etherscan_api_key) = config.etherscan_api_key {
+                if subargs.etherscan_api_key.is_none() {
+                    subargs.etherscan_api_key = Some(etherscan_api_key);
+                }
+            }
+
+            shadow_etherscan_fetch::update_source(subargs).await?
+        }
+        Subcommands::BatchSimulate(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_simulate::batch_simulate(subargs).await?
+        }
+        Subcommands::ExportTypes(subargs) => shadow_export_types::export_types(subargs).await?,
+        Subcommands::InspectIpfs(mut subargs) => {
+            if let Some(gateway_url) = config.ipfs_gateway_url {
+                subargs.ipfs_gateway_url = gateway_url;
+            }
+
+            shadow_clone::inspect_ipfs(subargs).await?
+        }
+        Subcommands::MigrateConfig(subargs) => shadow_config::migrate_config(subargs)?,
+        Subcommands::Sign(subargs) => shadow_push::sign(subargs).await?,
+        Subcommands::ShowChainSpec(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_show_chain_spec::show_chain_spec(subargs).await?
+        }
+        Subcommands::ArchiveConfig(subargs) => shadow_config::archive_config(subargs)?,
+        Subcommands::DecodeReturndata(mut subargs) => {
+            if let Some(rpc_url) = config.rpc_url {
+                if &subargs.rpc_url == "http://localhost:8545" {
+                    subargs.rpc_url = rpc_url;
+                }
+            }
+
+            shadow_decode::decode_returndata(subargs).await?
+        }
+        Subcommands::Contracts(subargs) => shadow_summary::contracts(subargs).await?,
+        Subcommands::ScanGroup(subargs) => shadow_scan_group::scan_group(subargs).await?,
     };
 
     // check if the version is up to date
-    if current_version.is_nightly() && current_version.ne(&remote_ver) {
+    if current_version.is_nightly() && current_version != remote_ver {
         info!("great news! A new nightly build is available!");
         info!("you can update now by running: `shadowup +nightly`");
-    } else if remote_ver.gt(&current_version) {
+    } else if remote_ver > current_version {
         info!("great news! An update is available!");
         info!("you can update now by running: `shadowup --version {}`", remote_ver);
     }
 
     Ok(())
 }
+
+/// Resolves the remote compiler's URL and API key, preferring the command line flags over the
+/// config. `compile`, `remote-compile` and `push` must all resolve them the same way.
+fn remote_compiler_config(
+    (url, api_key): (Option<String>, Option<String>),
+    (config_url, config_api_key): (Option<String>, Option<String>),
+) -> (Option<String>, Option<String>) {
+    (url.or(config_url), api_key.or(config_api_key))
+}
@@ -0,0 +1,126 @@
+use std::collections::BTreeSet;
+
+use alloy::primitives::Address;
+use eyre::{bail, Result};
+use tracing::info;
+
+use super::{GREEN_ANSI_COLOR, RED_ANSI_COLOR, RESET_ANSI_COLOR};
+use crate::{
+    ipfs::{get_abi_from_ipfs, get_group_info_from_ipfs},
+    AbiDiff, CheckCompatibilityArgs,
+};
+
+/// The `check-compatibility` subcommand. Compares the ABIs of every contract in two versions of
+/// a pushed contract group, and fails if the new version has breaking changes.
+pub async fn check_compatibility(args: CheckCompatibilityArgs) -> Result<()> {
+    info!("fetching contract groups from ipfs://{} and ipfs://{}", args.old_cid, args.new_cid);
+    let old_group = get_group_info_from_ipfs(&args.old_cid, &args.ipfs_gateway_url).await?;
+    let new_group = get_group_info_from_ipfs(&args.new_cid, &args.ipfs_gateway_url).await?;
+
+    let old_contracts = old_group
+        .contracts
+        .iter()
+        .map(|contract| (contract.chain_id, contract.address))
+        .collect::<BTreeSet<(u64, Address)>>();
+    let new_contracts = new_group
+        .contracts
+        .iter()
+        .map(|contract| (contract.chain_id, contract.address))
+        .collect::<BTreeSet<(u64, Address)>>();
+
+    let mut breaking = 0;
+    let mut additive = 0;
+    for (chain_id, address) in old_contracts.union(&new_contracts) {
+        let contract_path = format!("{}/{}", chain_id, address.to_string().to_lowercase());
+        let changes = match (
+            old_contracts.contains(&(*chain_id, *address)),
+            new_contracts.contains(&(*chain_id, *address)),
+        ) {
+            (true, false) => {
+                breaking += 1;
+                vec![format!("{RED_ANSI_COLOR}- contract removed{RESET_ANSI_COLOR}")]
+            }
+            (false, true) => {
+                additive += 1;
+                vec![format!("{GREEN_ANSI_COLOR}+ contract added{RESET_ANSI_COLOR}")]
+            }
+            _ => {
+                let old_abi =
+                    get_abi_from_ipfs(&args.old_cid, &contract_path, &args.ipfs_gateway_url)
+                        .await?;
+                let new_abi =
+                    get_abi_from_ipfs(&args.new_cid, &contract_path, &args.ipfs_gateway_url)
+                        .await?;
+                let diff = AbiDiff::new(&old_abi, &new_abi);
+
+                // removed events are breaking too, since downstream indexers may depend on them
+                breaking += diff.removed_events.len() +
+                    diff.changed_events.len() +
+                    diff.removed_functions.len() +
+                    diff.changed_functions.len();
+                additive += diff.added_events.len() + diff.added_functions.len();
+                describe_changes(&diff)
+            }
+        };
+
+        if !changes.is_empty() {
+            println!("{}:{}", chain_id, address);
+            for change in changes {
+                println!("  {change}");
+            }
+        }
+    }
+
+    println!(
+        "\n{} breaking change{}, {} additive change{}",
+        breaking,
+        if breaking == 1 { "" } else { "s" },
+        additive,
+        if additive == 1 { "" } else { "s" }
+    );
+
+    if breaking > 0 {
+        bail!("{} is not backward-compatible with {}", args.new_cid, args.old_cid);
+    }
+    if args.strict && additive > 0 {
+        bail!("{} changes the ABI of {}, and --strict is set", args.new_cid, args.old_cid);
+    }
+
+    info!("{} is backward-compatible with {}", args.new_cid, args.old_cid);
+    Ok(())
+}
+
+/// Describes each change in an ABI diff on its own line, highlighting breaking changes in red
+/// and additive changes in green
+fn describe_changes(diff: &AbiDiff) -> Vec<String> {
+    let mut changes = Vec::new();
+    for event in &diff.removed_events {
+        changes.push(format!("{RED_ANSI_COLOR}- event {event}{RESET_ANSI_COLOR}"));
+    }
+    for event in &diff.changed_events {
+        changes.push(format!(
+            "{RED_ANSI_COLOR}~ event {}: {} -> {}{RESET_ANSI_COLOR}",
+            event.name,
+            event.old.join(", "),
+            event.new.join(", ")
+        ));
+    }
+    for function in &diff.removed_functions {
+        changes.push(format!("{RED_ANSI_COLOR}- function {function}{RESET_ANSI_COLOR}"));
+    }
+    for function in &diff.changed_functions {
+        changes.push(format!(
+            "{RED_ANSI_COLOR}~ function {}: {} -> {}{RESET_ANSI_COLOR}",
+            function.name,
+            function.old.join(", "),
+            function.new.join(", ")
+        ));
+    }
+    for event in &diff.added_events {
+        changes.push(format!("{GREEN_ANSI_COLOR}+ event {event}{RESET_ANSI_COLOR}"));
+    }
+    for function in &diff.added_functions {
+        changes.push(format!("{GREEN_ANSI_COLOR}+ function {function}{RESET_ANSI_COLOR}"));
+    }
+    changes
+}
@@ -0,0 +1,130 @@
+use std::path::Path;
+
+use alloy::json_abi::JsonAbi;
+use eyre::{eyre, Result};
+use serde_json::Value;
+use shadow_common::ipfs::read_from_ipfs;
+use tracing::info;
+
+use super::{GREEN_ANSI_COLOR, RED_ANSI_COLOR, RESET_ANSI_COLOR};
+use crate::{AbiDiff, DiffAbiArgs};
+
+/// The `diff-abi` subcommand. Compares two ABIs, read from local files or IPFS, and lists the
+/// changes between them by category.
+pub async fn diff_abi(args: DiffAbiArgs) -> Result<()> {
+    let old = load_abi(&args.old, &args).await?;
+    let new = load_abi(&args.new, &args).await?;
+
+    let mut diff = AbiDiff::new(&old, &new);
+    if args.breaking_only {
+        diff.added_events.clear();
+        diff.added_functions.clear();
+    }
+
+    if args.json {
+        let mut output = serde_json::to_value(&diff)?;
+        output["breaking"] = diff.is_breaking().into();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if diff.is_empty() {
+        info!("no {}changes", if args.breaking_only { "breaking " } else { "" });
+        return Ok(());
+    }
+
+    let (changed_signatures, changed_outputs): (Vec<_>, Vec<_>) =
+        diff.changed_functions.iter().partition(|function| !function.outputs_only);
+    print_section(
+        "New events",
+        GREEN_ANSI_COLOR,
+        diff.added_events.iter().map(|event| format!("+ event {event}")),
+    );
+    print_section(
+        "Removed events",
+        RED_ANSI_COLOR,
+        diff.removed_events.iter().map(|event| format!("- event {event}")),
+    );
+    print_section(
+        "Changed event parameters",
+        RED_ANSI_COLOR,
+        diff.changed_events.iter().map(|event| {
+            format!("~ event {}: {} -> {}", event.name, event.old.join(", "), event.new.join(", "))
+        }),
+    );
+    print_section(
+        "New functions",
+        GREEN_ANSI_COLOR,
+        diff.added_functions.iter().map(|function| format!("+ function {function}")),
+    );
+    print_section(
+        "Removed functions",
+        RED_ANSI_COLOR,
+        diff.removed_functions.iter().map(|function| format!("- function {function}")),
+    );
+    print_section(
+        "Changed function signatures",
+        RED_ANSI_COLOR,
+        changed_signatures.iter().map(|function| {
+            format!(
+                "~ function {}: {} -> {}",
+                function.name,
+                function.old.join(", "),
+                function.new.join(", ")
+            )
+        }),
+    );
+    print_section(
+        "Changed return types",
+        RED_ANSI_COLOR,
+        changed_outputs.iter().map(|function| {
+            format!(
+                "~ function {}: {} -> {}",
+                function.name,
+                function.old.join(", "),
+                function.new.join(", ")
+            )
+        }),
+    );
+
+    Ok(())
+}
+
+/// Prints a titled list of changes in the given color, unless there are none
+fn print_section(title: &str, color: &str, changes: impl Iterator<Item = String>) {
+    let changes = changes.collect::<Vec<_>>();
+    if changes.is_empty() {
+        return;
+    }
+
+    println!("{title}:");
+    for change in changes {
+        println!("  {color}{change}{RESET_ANSI_COLOR}");
+    }
+}
+
+/// Loads an ABI from a local file, or from IPFS if no such file exists
+async fn load_abi(location: &str, args: &DiffAbiArgs) -> Result<JsonAbi> {
+    let value: Value = if Path::new(location).exists() {
+        serde_json::from_str(&std::fs::read_to_string(location)?)
+            .map_err(|e| eyre!("Failed to parse {}: {}", location, e))?
+    } else {
+        let ipfs_path = location.trim_start_matches("ipfs://");
+        info!("fetching ABI from ipfs://{}", ipfs_path);
+        read_from_ipfs(ipfs_path, &args.ipfs_gateway_url, !args.skip_integrity_check).await?
+    };
+
+    parse_abi(value).map_err(|e| eyre!("{} is not a valid ABI: {}", location, e))
+}
+
+/// Parses an ABI given as an array, as a build artifact with an `abi` field, or as a
+/// JSON-encoded string, as Etherscan returns it
+fn parse_abi(value: Value) -> Result<JsonAbi> {
+    match value {
+        Value::String(abi) => Ok(serde_json::from_str(&abi)?),
+        Value::Object(mut artifact) if artifact.contains_key("abi") => {
+            parse_abi(artifact.remove("abi").expect("abi should exist"))
+        }
+        abi => Ok(serde_json::from_value(abi)?),
+    }
+}
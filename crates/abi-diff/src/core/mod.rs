@@ -0,0 +1,128 @@
+mod check_compatibility;
+mod diff_abi;
+
+pub use check_compatibility::*;
+pub use diff_abi::*;
+
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use alloy::{json_abi::JsonAbi, primitives::Address};
+use eyre::{bail, eyre, Result};
+use shadow_common::{
+    compiler::compile_abi, forge::ensure_forge_installed, ShadowContractGroupInfo,
+    ShadowContractInfo, ShadowContractSettings, ShadowContractSource, BUILD_ARTIFACTS_HINT,
+};
+use tracing::{error, info};
+
+use crate::{ipfs::get_abi_from_ipfs, AbiDiff, AbiDiffArgs};
+
+/// ANSI color for red
+const RED_ANSI_COLOR: &str = "\x1b[31m";
+/// ANSI color for green
+const GREEN_ANSI_COLOR: &str = "\x1b[32m";
+/// Reset the ANSI color to the default
+const RESET_ANSI_COLOR: &str = "\x1b[0m";
+
+/// The `abi-diff` subcommand. Compares a shadow contract's compiled ABI against a pushed
+/// version of the contract, or the original contract, and reports added, removed and changed
+/// events and functions.
+pub async fn abi_diff(args: AbiDiffArgs) -> Result<()> {
+    let address: Address = args.address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let contract = group_info.contract(&address, args.chain_id)?;
+    let contract_path =
+        format!("{}/{}", contract.chain_id, contract.address.to_string().to_lowercase());
+
+    let abi_path = group_info.artifact_path().join(&contract_path).join("abi.json");
+    if !abi_path.exists() {
+        bail!("{} has not been built. {}", address, BUILD_ARTIFACTS_HINT);
+    }
+    let current: JsonAbi = serde_json::from_str(&std::fs::read_to_string(abi_path)?)?;
+
+    let reference = match &args.reference {
+        Some(ipfs_cid) => {
+            info!("fetching reference ABI from ipfs://{}", ipfs_cid);
+            get_abi_from_ipfs(ipfs_cid, &contract_path, &args.ipfs_gateway_url).await?
+        }
+        None => {
+            info!("compiling the original contract to compare against");
+            ensure_forge_installed()?;
+            get_original_abi(&group_info.contract_path(&address, Some(contract.chain_id))?)?
+        }
+    };
+
+    let diff = AbiDiff::new(&reference, &current);
+    if args.json {
+        let mut output = serde_json::to_value(&diff)?;
+        output["breaking"] = diff.is_breaking().into();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if diff.is_empty() {
+        info!("the ABIs are identical");
+        return Ok(());
+    }
+
+    for event in &diff.added_events {
+        println!("{GREEN_ANSI_COLOR}+ event {event}{RESET_ANSI_COLOR}");
+    }
+    for event in &diff.removed_events {
+        println!("- event {event}");
+    }
+    for event in &diff.changed_events {
+        println!("~ event {}", event.name);
+        for signature in &event.old {
+            println!("    - {signature}");
+        }
+        for signature in &event.new {
+            println!("    + {signature}");
+        }
+    }
+    for function in &diff.added_functions {
+        println!("{GREEN_ANSI_COLOR}+ function {function}{RESET_ANSI_COLOR}");
+    }
+    for function in &diff.removed_functions {
+        println!("{RED_ANSI_COLOR}- function {function}{RESET_ANSI_COLOR}");
+    }
+    for function in &diff.changed_functions {
+        println!("{RED_ANSI_COLOR}~ function {}", function.name);
+        for signature in &function.old {
+            println!("    - {signature}");
+        }
+        for signature in &function.new {
+            println!("    + {signature}");
+        }
+        print!("{RESET_ANSI_COLOR}");
+    }
+
+    if diff.is_breaking() {
+        println!("\n{RED_ANSI_COLOR}this change is breaking{RESET_ANSI_COLOR}");
+    }
+
+    Ok(())
+}
+
+/// Compiles the contract's `original.json` with its settings, and returns the ABI of the
+/// contract as it was before any shadow changes
+fn get_original_abi(contract_dir: &Path) -> Result<JsonAbi> {
+    let info = ShadowContractInfo::from_path(&contract_dir.join("info.json"))?;
+    let settings = ShadowContractSettings::from_path(&contract_dir.join("settings.json"))?;
+    let original_path = contract_dir.join("original.json");
+    let original: ShadowContractSource = serde_json::from_str(
+        &std::fs::read_to_string(&original_path)
+            .map_err(|e| eyre!("Failed to read {}: {}", original_path.display(), e))?,
+    )?;
+
+    compile_abi(&original, &settings, &info.name)
+        .map_err(|e| eyre!("Failed to compile the original contract: {}", e))
+}
@@ -0,0 +1,91 @@
+use clap::Parser;
+
+/// Arguments for the `abi-diff` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Compare a shadow contract's ABI against a previous version")]
+pub struct AbiDiffArgs {
+    /// The address of the contract to compare.
+    pub address: String,
+
+    /// The chain ID of the contract. Required if the address is tracked on multiple chains.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The IPFS CID of a pushed contract group to compare against. Defaults to the original
+    /// contract, compiled from its `original.json`.
+    #[clap(long = "ref", required = false)]
+    pub reference: Option<String>,
+
+    /// Whether to print the diff as JSON.
+    #[clap(long)]
+    pub json: bool,
+
+    /// Your preferred IPFS gateway, used to fetch the reference ABI.
+    #[clap(
+        long,
+        required = false,
+        default_value = "https://gateway.pinata.cloud/ipfs/",
+        hide_default_value = true
+    )]
+    pub ipfs_gateway_url: String,
+}
+
+/// Arguments for the `check-compatibility` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Check that a new version of a pushed contract group is backward-compatible")]
+pub struct CheckCompatibilityArgs {
+    /// The IPFS CID of the old version of the contract group.
+    pub old_cid: String,
+
+    /// The IPFS CID of the new version of the contract group.
+    pub new_cid: String,
+
+    /// Treat any change, including added events and functions, as breaking.
+    #[clap(long)]
+    pub strict: bool,
+
+    /// Your preferred IPFS gateway, used to fetch both contract groups.
+    #[clap(
+        long,
+        required = false,
+        default_value = "https://gateway.pinata.cloud/ipfs/",
+        hide_default_value = true
+    )]
+    pub ipfs_gateway_url: String,
+}
+
+/// Arguments for the `diff-abi` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Compare two ABI files, and list the changes between them")]
+pub struct DiffAbiArgs {
+    /// The old ABI. Either a local file, or an IPFS path such as `ipfs://<cid>/1/0x.../abi.json`.
+    pub old: String,
+
+    /// The new ABI. Either a local file, or an IPFS path such as `ipfs://<cid>/1/0x.../abi.json`.
+    pub new: String,
+
+    /// Only list breaking changes: removed or changed events and functions.
+    #[clap(long)]
+    pub breaking_only: bool,
+
+    /// Whether to print the diff as JSON.
+    #[clap(long)]
+    pub json: bool,
+
+    /// Your preferred IPFS gateway, used to fetch ABIs from IPFS.
+    #[clap(
+        long,
+        required = false,
+        default_value = "https://gateway.pinata.cloud/ipfs/",
+        hide_default_value = true
+    )]
+    pub ipfs_gateway_url: String,
+
+    /// Don't verify that content fetched from IPFS matches its CID.
+    #[clap(long)]
+    pub skip_integrity_check: bool,
+}
@@ -0,0 +1,280 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use alloy::json_abi::{Event, JsonAbi};
+use serde::Serialize;
+
+/// The differences between two versions of a contract's ABI
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AbiDiff {
+    /// Event signatures only in the new ABI, including new overloads of existing events
+    pub added_events: Vec<String>,
+    /// Event signatures only in the old ABI
+    pub removed_events: Vec<String>,
+    /// Events whose parameter types or indexing changed
+    pub changed_events: Vec<ChangedEvent>,
+    /// Function signatures only in the new ABI, including new overloads of existing functions
+    pub added_functions: Vec<String>,
+    /// Function signatures only in the old ABI
+    pub removed_functions: Vec<String>,
+    /// Functions whose inputs or outputs changed
+    pub changed_functions: Vec<ChangedFunction>,
+}
+
+/// An event whose parameters differ between two ABIs
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedEvent {
+    /// The name of the event
+    pub name: String,
+    /// The changed signatures in the old ABI, marking indexed parameters
+    pub old: Vec<String>,
+    /// The changed signatures in the new ABI, marking indexed parameters
+    pub new: Vec<String>,
+}
+
+/// A function whose signature differs between two ABIs
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedFunction {
+    /// The name of the function
+    pub name: String,
+    /// The changed signatures in the old ABI, including outputs
+    pub old: Vec<String>,
+    /// The changed signatures in the new ABI, including outputs
+    pub new: Vec<String>,
+    /// Whether only the return types changed, and the function can still be called with the
+    /// same inputs
+    pub outputs_only: bool,
+}
+
+impl AbiDiff {
+    /// Compares the `old` ABI against the `new` one. Overloads are compared by their input
+    /// types, so adding an overload is an addition rather than a change.
+    pub fn new(old: &JsonAbi, new: &JsonAbi) -> Self {
+        let mut diff = Self::default();
+        for (name, change) in diff_overloads(&event_signatures(old), &event_signatures(new)) {
+            match change {
+                OverloadChange::Added(signatures) => diff.added_events.extend(signatures),
+                OverloadChange::Removed(signatures) => diff.removed_events.extend(signatures),
+                OverloadChange::Changed { old, new, .. } => {
+                    diff.changed_events.push(ChangedEvent { name, old, new })
+                }
+            }
+        }
+        for (name, change) in diff_overloads(&function_signatures(old), &function_signatures(new)) {
+            match change {
+                OverloadChange::Added(signatures) => diff.added_functions.extend(signatures),
+                OverloadChange::Removed(signatures) => diff.removed_functions.extend(signatures),
+                OverloadChange::Changed { old, new, same_inputs } => diff
+                    .changed_functions
+                    .push(ChangedFunction { name, old, new, outputs_only: same_inputs }),
+            }
+        }
+
+        diff
+    }
+
+    /// Whether the diff removes or changes any functions, which would break existing callers
+    pub fn is_breaking(&self) -> bool {
+        !self.removed_functions.is_empty() || !self.changed_functions.is_empty()
+    }
+
+    /// Whether the ABIs are identical
+    pub fn is_empty(&self) -> bool {
+        self.added_events.is_empty() &&
+            self.removed_events.is_empty() &&
+            self.changed_events.is_empty() &&
+            self.added_functions.is_empty() &&
+            !self.is_breaking()
+    }
+}
+
+/// The full signatures of each overload of a function or event, keyed by name and then by the
+/// overload's input types
+type Overloads = BTreeMap<String, BTreeMap<String, String>>;
+
+/// A change to the overloads of a single function or event name
+enum OverloadChange {
+    /// Overloads only in the new ABI
+    Added(Vec<String>),
+    /// Overloads only in the old ABI
+    Removed(Vec<String>),
+    /// Overloads whose input types changed, or, if `same_inputs`, a single overload whose
+    /// outputs or indexing changed
+    Changed { old: Vec<String>, new: Vec<String>, same_inputs: bool },
+}
+
+/// Compares the overloads of each name. If a name loses overloads while gaining others, their
+/// input types changed. Otherwise, overloads are only added or removed.
+fn diff_overloads(old: &Overloads, new: &Overloads) -> Vec<(String, OverloadChange)> {
+    let only_in = |a: &BTreeMap<String, String>, b: &BTreeMap<String, String>| {
+        a.iter()
+            .filter(|(inputs, _)| !b.contains_key(*inputs))
+            .map(|(_, signature)| signature.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let empty = BTreeMap::new();
+    let mut changes = vec![];
+    for name in old.keys().chain(new.keys()).collect::<BTreeSet<_>>() {
+        let old_overloads = old.get(name).unwrap_or(&empty);
+        let new_overloads = new.get(name).unwrap_or(&empty);
+
+        let removed = only_in(old_overloads, new_overloads);
+        let added = only_in(new_overloads, old_overloads);
+        match (removed.is_empty(), added.is_empty()) {
+            (false, false) => changes.push((
+                name.clone(),
+                OverloadChange::Changed { old: removed, new: added, same_inputs: false },
+            )),
+            (false, true) => changes.push((name.clone(), OverloadChange::Removed(removed))),
+            (true, false) => changes.push((name.clone(), OverloadChange::Added(added))),
+            (true, true) => {}
+        }
+
+        for (inputs, old_signature) in old_overloads {
+            match new_overloads.get(inputs) {
+                Some(new_signature) if new_signature != old_signature => changes.push((
+                    name.clone(),
+                    OverloadChange::Changed {
+                        old: vec![old_signature.clone()],
+                        new: vec![new_signature.clone()],
+                        same_inputs: true,
+                    },
+                )),
+                _ => {}
+            }
+        }
+    }
+
+    changes
+}
+
+/// Groups an ABI's event signatures, marking indexed parameters, by event name and parameter
+/// types
+fn event_signatures(abi: &JsonAbi) -> Overloads {
+    abi.events
+        .iter()
+        .map(|(name, events)| {
+            (
+                name.clone(),
+                events.iter().map(|event| (event.signature(), event_signature(event))).collect(),
+            )
+        })
+        .collect()
+}
+
+/// An event's signature, with `indexed` after each indexed parameter's type, e.g.
+/// `Transfer(address indexed,address indexed,uint256)`
+fn event_signature(event: &Event) -> String {
+    let params = event
+        .inputs
+        .iter()
+        .map(|param| {
+            let ty = param.selector_type();
+            if param.indexed {
+                format!("{} indexed", ty)
+            } else {
+                ty.to_string()
+            }
+        })
+        .collect::<Vec<_>>();
+    format!(
+        "{}({}){}",
+        event.name,
+        params.join(","),
+        if event.anonymous { " anonymous" } else { "" }
+    )
+}
+
+/// Groups an ABI's function signatures, including outputs, by function name and input types
+fn function_signatures(abi: &JsonAbi) -> Overloads {
+    abi.functions
+        .iter()
+        .map(|(name, functions)| {
+            (
+                name.clone(),
+                functions.iter().map(|f| (f.signature(), f.signature_with_outputs())).collect(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(old: &[&str], new: &[&str]) -> AbiDiff {
+        AbiDiff::new(&JsonAbi::parse(old.iter()).unwrap(), &JsonAbi::parse(new.iter()).unwrap())
+    }
+
+    #[test]
+    fn test_identical_abis() {
+        let abi = ["function foo(uint256) returns (bool)", "event Foo(address indexed)"];
+        assert!(diff(&abi, &abi).is_empty());
+    }
+
+    #[test]
+    fn test_added_and_removed_functions() {
+        let added = diff(&["function foo(uint256)"], &["function foo(uint256)", "function bar()"]);
+        assert_eq!(added.added_functions, vec!["bar()".to_string()]);
+        assert!(!added.is_breaking());
+
+        let removed =
+            diff(&["function foo(uint256)", "function bar()"], &["function foo(uint256)"]);
+        assert_eq!(removed.removed_functions, vec!["bar()".to_string()]);
+        assert!(removed.is_breaking());
+    }
+
+    #[test]
+    fn test_changed_functions() {
+        let inputs = diff(&["function foo(uint256)"], &["function foo(address)"]);
+        assert_eq!(inputs.changed_functions.len(), 1);
+        assert_eq!(inputs.changed_functions[0].old, vec!["foo(uint256)".to_string()]);
+        assert_eq!(inputs.changed_functions[0].new, vec!["foo(address)".to_string()]);
+        assert!(!inputs.changed_functions[0].outputs_only);
+        assert!(inputs.is_breaking());
+
+        let outputs = diff(
+            &["function foo(uint256) returns (bool)"],
+            &["function foo(uint256) returns (uint8)"],
+        );
+        assert_eq!(outputs.changed_functions.len(), 1);
+        assert!(outputs.changed_functions[0].outputs_only);
+        assert!(outputs.is_breaking());
+    }
+
+    #[test]
+    fn test_added_overload() {
+        let diff =
+            diff(&["function foo(uint256)"], &["function foo(uint256)", "function foo(address)"]);
+        assert_eq!(diff.added_functions, vec!["foo(address)".to_string()]);
+        assert!(diff.changed_functions.is_empty());
+        assert!(!diff.is_breaking());
+    }
+
+    #[test]
+    fn test_removed_overload() {
+        let diff =
+            diff(&["function foo(uint256)", "function foo(address)"], &["function foo(uint256)"]);
+        assert_eq!(diff.removed_functions, vec!["foo(address)".to_string()]);
+        assert!(diff.is_breaking());
+    }
+
+    #[test]
+    fn test_events() {
+        let added = diff(&[], &["event Foo(address indexed)"]);
+        assert_eq!(added.added_events, vec!["Foo(address indexed)".to_string()]);
+
+        let removed = diff(&["event Foo(address indexed)"], &[]);
+        assert_eq!(removed.removed_events, vec!["Foo(address indexed)".to_string()]);
+
+        let indexing = diff(&["event Foo(address indexed)"], &["event Foo(address)"]);
+        assert_eq!(indexing.changed_events.len(), 1);
+        assert_eq!(indexing.changed_events[0].old, vec!["Foo(address indexed)".to_string()]);
+        assert_eq!(indexing.changed_events[0].new, vec!["Foo(address)".to_string()]);
+
+        let overload =
+            diff(&["event Foo(address)"], &["event Foo(address)", "event Foo(uint256,address)"]);
+        assert_eq!(overload.added_events, vec!["Foo(uint256,address)".to_string()]);
+        assert!(overload.changed_events.is_empty());
+    }
+}
@@ -0,0 +1,5 @@
+mod args;
+mod diff;
+
+pub use args::*;
+pub use diff::*;
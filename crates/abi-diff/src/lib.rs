@@ -0,0 +1,9 @@
+//! The `abi-diff`, `check-compatibility` and `diff-abi` subcommands
+
+mod core;
+mod interface;
+mod utils;
+
+pub use core::*;
+pub use interface::*;
+pub(crate) use utils::*;
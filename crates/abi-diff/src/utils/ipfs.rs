@@ -0,0 +1,31 @@
+use alloy::json_abi::JsonAbi;
+use eyre::{eyre, Result};
+use shadow_common::ShadowContractGroupInfo;
+
+/// Fetches a contract's `abi.json` from a contract group pinned to IPFS
+pub(crate) async fn get_abi_from_ipfs(
+    ipfs_cid: &str,
+    contract_path: &str,
+    base_gateway_url: &str,
+) -> Result<JsonAbi> {
+    let url = format!(
+        "{}/{}/{}/abi.json",
+        base_gateway_url.trim_end_matches('/'),
+        ipfs_cid,
+        contract_path
+    );
+    let response = reqwest::get(&url).await?;
+    if response.status().is_success() {
+        Ok(serde_json::from_str(&response.text().await?)?)
+    } else {
+        Err(eyre!("Failed to get {} from IPFS: {}", url, response.text().await?))
+    }
+}
+
+/// Fetches a contract group's `info.json` from IPFS
+pub(crate) async fn get_group_info_from_ipfs(
+    ipfs_cid: &str,
+    base_gateway_url: &str,
+) -> Result<ShadowContractGroupInfo> {
+    ShadowContractGroupInfo::from_ipfs(ipfs_cid, base_gateway_url, true).await
+}
@@ -0,0 +1,2 @@
+/// IPFS utilities used to fetch reference ABIs and contract groups
+pub(crate) mod ipfs;
@@ -0,0 +1,62 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use eyre::{eyre, Result};
+use shadow_common::audit::{audit_log_path, load_audit_log};
+use tracing::info;
+
+use crate::AuditLogArgs;
+
+/// The `audit-log` subcommand. Displays the history of pushes recorded in
+/// `~/.shadow/audit.jsonl`.
+pub async fn audit_log(args: AuditLogArgs) -> Result<()> {
+    let since = args.since.as_deref().map(parse_since).transpose()?;
+
+    let entries = load_audit_log()?
+        .into_iter()
+        .filter(|entry| since.map_or(true, |since| entry.timestamp >= since))
+        .filter(|entry| args.cid.as_ref().map_or(true, |cid| &entry.cid == cid))
+        .collect::<Vec<_>>();
+
+    if args.json {
+        for entry in &entries {
+            println!("{}", serde_json::to_string(entry)?);
+        }
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        info!("no matching entries in {}", audit_log_path()?.display());
+        return Ok(());
+    }
+
+    let name_width = entries.iter().map(|entry| entry.display_name.len()).max().unwrap_or(0).max(4);
+    let cid_width = entries.iter().map(|entry| entry.cid.len()).max().unwrap_or(0).max(3);
+    let chain_width = entries.iter().map(|entry| entry.chain.len()).max().unwrap_or(0).max(5);
+    println!(
+        "{:<20}  {:<name_width$}  {:<cid_width$}  {:<chain_width$}  {:<9}  ATTESTATION",
+        "TIMESTAMP", "NAME", "CID", "CHAIN", "CONTRACTS"
+    );
+    for entry in &entries {
+        println!(
+            "{:<20}  {:<name_width$}  {:<cid_width$}  {:<chain_width$}  {:<9}  {}",
+            entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            entry.display_name,
+            entry.cid,
+            entry.chain,
+            entry.contracts.len(),
+            entry.attestation_tx.as_deref().unwrap_or("-")
+        );
+    }
+
+    Ok(())
+}
+
+/// Parses the `--since` argument, either as a date (midnight UTC) or an RFC 3339 timestamp.
+fn parse_since(since: &str) -> Result<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).expect("midnight is valid").and_utc());
+    }
+
+    DateTime::parse_from_rfc3339(since)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|e| eyre!("invalid --since date '{}': {}", since, e))
+}
@@ -0,0 +1,19 @@
+use clap::Parser;
+
+/// Arguments for the `audit-log` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Display the local history of pushed shadow contract groups")]
+pub struct AuditLogArgs {
+    /// Only show entries on or after this date, either as `YYYY-MM-DD` or an RFC 3339
+    /// timestamp.
+    #[clap(long, required = false)]
+    pub since: Option<String>,
+
+    /// Only show entries for this IPFS CID.
+    #[clap(long, required = false)]
+    pub cid: Option<String>,
+
+    /// Print the matching entries as raw JSONL instead of a table.
+    #[clap(long)]
+    pub json: bool,
+}
@@ -0,0 +1,7 @@
+//! The `audit-log` subcommand
+
+mod core;
+mod interface;
+
+pub use core::*;
+pub use interface::*;
@@ -0,0 +1,110 @@
+use std::path::PathBuf;
+
+use eyre::{bail, Result};
+use tracing::{info, warn};
+
+use crate::{
+    load_catalog, save_catalog, CatalogAddArgs, CatalogArgs, CatalogCommand, CatalogEntry,
+    CatalogRemoveArgs, CatalogSyncArgs,
+};
+
+/// The `catalog` subcommand. Manages the local catalog of known contract groups in
+/// `~/.shadow/catalog.json`.
+pub async fn catalog(args: CatalogArgs) -> Result<()> {
+    match args.command {
+        CatalogCommand::Add(args) => add(args),
+        CatalogCommand::List => list(),
+        CatalogCommand::Remove(args) => remove(args),
+        CatalogCommand::Sync(args) => sync(args).await,
+    }
+}
+
+/// Adds a contract group to the catalog, replacing any existing entry with the same CID.
+fn add(args: CatalogAddArgs) -> Result<()> {
+    let local_path = args.path.map(|path| PathBuf::from(path).canonicalize()).transpose()?;
+
+    let mut catalog = load_catalog()?;
+    catalog.retain(|entry| entry.cid != args.ipfs_cid);
+    catalog.push(CatalogEntry {
+        name: args.name.unwrap_or_else(|| args.ipfs_cid.clone()),
+        cid: args.ipfs_cid.clone(),
+        added_at: chrono::Utc::now(),
+        local_path,
+    });
+    save_catalog(&catalog)?;
+
+    info!("added {} to the catalog", args.ipfs_cid);
+    Ok(())
+}
+
+/// Displays all contract groups in the catalog as a table.
+fn list() -> Result<()> {
+    let catalog = load_catalog()?;
+    if catalog.is_empty() {
+        info!("the catalog is empty. Add a contract group with `shadow catalog add <cid>`.");
+        return Ok(());
+    }
+
+    let name_width = catalog.iter().map(|entry| entry.name.len()).max().unwrap_or(0).max(4);
+    let cid_width = catalog.iter().map(|entry| entry.cid.len()).max().unwrap_or(0).max(3);
+    println!("{:<name_width$}  {:<cid_width$}  {:<10}  PATH", "NAME", "CID", "ADDED");
+    for entry in &catalog {
+        println!(
+            "{:<name_width$}  {:<cid_width$}  {:<10}  {}",
+            entry.name,
+            entry.cid,
+            entry.added_at.format("%Y-%m-%d"),
+            entry.local_path.as_ref().map_or("-".to_string(), |path| path.display().to_string())
+        );
+    }
+
+    Ok(())
+}
+
+/// Removes a contract group from the catalog.
+fn remove(args: CatalogRemoveArgs) -> Result<()> {
+    let mut catalog = load_catalog()?;
+    let len = catalog.len();
+    catalog.retain(|entry| entry.cid != args.ipfs_cid);
+    if catalog.len() == len {
+        bail!("{} is not in the catalog", args.ipfs_cid);
+    }
+    save_catalog(&catalog)?;
+
+    info!("removed {} from the catalog", args.ipfs_cid);
+    Ok(())
+}
+
+/// Checks that each entry's local path still exists and that its CID is still accessible via
+/// the IPFS gateway.
+async fn sync(args: CatalogSyncArgs) -> Result<()> {
+    let catalog = load_catalog()?;
+    let client = reqwest::Client::new();
+
+    let mut problems = 0;
+    for entry in &catalog {
+        if let Some(local_path) = &entry.local_path {
+            if !local_path.exists() {
+                warn!("{}: local path {} no longer exists", entry.name, local_path.display());
+                problems += 1;
+            }
+        }
+
+        let url =
+            format!("{}/{}/info.json", args.ipfs_gateway_url.trim_end_matches('/'), entry.cid);
+        match client.head(&url).send().await {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => {
+                warn!("{}: {} is not accessible ({})", entry.name, entry.cid, response.status());
+                problems += 1;
+            }
+            Err(e) => {
+                warn!("{}: failed to reach {}: {}", entry.name, url, e);
+                problems += 1;
+            }
+        }
+    }
+
+    info!("checked {} catalog entries, {} problems found", catalog.len(), problems);
+    Ok(())
+}
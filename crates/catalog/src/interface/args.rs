@@ -0,0 +1,57 @@
+use clap::{Parser, Subcommand};
+
+/// Arguments for the `catalog` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Manage a local catalog of known shadow contract groups")]
+pub struct CatalogArgs {
+    #[clap(subcommand)]
+    pub command: CatalogCommand,
+}
+
+/// The actions available under `shadow catalog`
+#[derive(Debug, Clone, Subcommand)]
+pub enum CatalogCommand {
+    /// Add a contract group to the catalog
+    Add(CatalogAddArgs),
+    /// List all contract groups in the catalog
+    List,
+    /// Remove a contract group from the catalog
+    Remove(CatalogRemoveArgs),
+    /// Check that each catalog entry's local path and IPFS CID are still reachable
+    Sync(CatalogSyncArgs),
+}
+
+/// Arguments for `shadow catalog add`
+#[derive(Debug, Clone, Parser)]
+pub struct CatalogAddArgs {
+    /// The IPFS CID of the contract group.
+    pub ipfs_cid: String,
+
+    /// A display name for the contract group.
+    #[clap(short, long, required = false)]
+    pub name: Option<String>,
+
+    /// The local path of the contract group, if it has been cloned.
+    #[clap(short, long, required = false)]
+    pub path: Option<String>,
+}
+
+/// Arguments for `shadow catalog remove`
+#[derive(Debug, Clone, Parser)]
+pub struct CatalogRemoveArgs {
+    /// The IPFS CID of the contract group to remove.
+    pub ipfs_cid: String,
+}
+
+/// Arguments for `shadow catalog sync`
+#[derive(Debug, Clone, Parser)]
+pub struct CatalogSyncArgs {
+    /// Your preferred IPFS gateway, used to check that each CID is still accessible.
+    #[clap(
+        long,
+        required = false,
+        default_value = "https://gateway.pinata.cloud/ipfs/",
+        hide_default_value = true
+    )]
+    pub ipfs_gateway_url: String,
+}
@@ -0,0 +1,53 @@
+#![allow(deprecated)]
+use std::{env::home_dir, path::PathBuf};
+
+use chrono::{DateTime, Utc};
+use eyre::{OptionExt, Result};
+use serde::{Deserialize, Serialize};
+
+/// A contract group tracked in `~/.shadow/catalog.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    /// The IPFS CID of the contract group
+    pub cid: String,
+    /// The display name of the contract group
+    pub name: String,
+    /// When the contract group was added to the catalog
+    pub added_at: DateTime<Utc>,
+    /// The local path of the contract group, if it has been cloned
+    pub local_path: Option<PathBuf>,
+}
+
+/// Returns the path to `~/.shadow/catalog.json`
+fn catalog_path() -> Result<PathBuf> {
+    let mut catalog_path = home_dir().ok_or_eyre("failed to get home directory")?;
+    catalog_path.push(".shadow");
+    catalog_path.push("catalog.json");
+
+    Ok(catalog_path)
+}
+
+/// Loads the catalog from disk. Returns an empty catalog if it doesn't exist yet.
+pub fn load_catalog() -> Result<Vec<CatalogEntry>> {
+    let catalog_path = catalog_path()?;
+    if !catalog_path.exists() {
+        return Ok(vec![]);
+    }
+
+    let catalog = std::fs::read_to_string(catalog_path)?;
+    Ok(serde_json::from_str(&catalog)?)
+}
+
+/// Saves the catalog to disk.
+pub fn save_catalog(catalog: &[CatalogEntry]) -> Result<()> {
+    let catalog_path = catalog_path()?;
+
+    // build the directory if it doesn't exist
+    if let Some(parent) = catalog_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(catalog_path, serde_json::to_string_pretty(catalog)?)?;
+
+    Ok(())
+}
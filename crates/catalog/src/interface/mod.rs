@@ -0,0 +1,5 @@
+mod args;
+mod catalog;
+
+pub use args::*;
+pub use catalog::*;
@@ -0,0 +1,96 @@
+use std::{fmt::Display, future::Future, time::Duration};
+
+use alloy::{
+    eips::BlockNumberOrTag,
+    network::AnyNetwork,
+    providers::{ext::TraceApi, Provider, ProviderBuilder},
+    rpc::types::trace::parity::TraceType,
+    transports::http::reqwest::Url,
+};
+use eyre::{bail, Result};
+use serde_json::Value;
+use tracing::{error, info, warn};
+
+use crate::CheckRpcArgs;
+
+/// The `check-rpc` subcommand. Calls the RPC methods that shadow relies on and reports their
+/// latency and results.
+pub async fn check_rpc(args: CheckRpcArgs) -> Result<()> {
+    let timeout = Duration::from_secs(args.timeout);
+    let provider =
+        ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+
+    info!("checking {}", args.rpc_url);
+    let chain_id = check("eth_chainId", timeout, provider.get_chain_id()).await;
+    let block_number = check("eth_blockNumber", timeout, provider.get_block_number()).await;
+    if chain_id.is_none() || block_number.is_none() {
+        bail!("{} is not a functional RPC URL", args.rpc_url);
+    }
+
+    let block_number = block_number.unwrap_or_default();
+    let traces = check("trace_replayBlockTransactions", timeout, async {
+        provider
+            .trace_replay_block_transactions(block_number.into(), &[TraceType::Trace])
+            .await
+            .map(|traces| format!("{} transactions", traces.len()))
+    })
+    .await;
+    if traces.is_none() {
+        warn!("trace APIs are unavailable. `shadow simulate` will fail with this RPC URL");
+    }
+
+    if args.full {
+        // debug_traceTransaction needs a transaction to trace, so use one from the latest block
+        let tx_hash = provider
+            .get_block_by_number(BlockNumberOrTag::Number(block_number), false)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|block| block.transactions.hashes().next().copied());
+
+        match tx_hash {
+            Some(tx_hash) => {
+                let debug = check("debug_traceTransaction", timeout, async {
+                    provider
+                        .client()
+                        .request::<_, Value>("debug_traceTransaction", (tx_hash,))
+                        .await
+                        .map(|_| format!("traced {}", tx_hash))
+                })
+                .await;
+                if debug.is_none() {
+                    warn!("debug APIs are unavailable with this RPC URL");
+                }
+            }
+            None => {
+                warn!("skipping debug_traceTransaction: block {} has no transactions", block_number)
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Awaits a single RPC call with a timeout, logging its latency and result
+async fn check<T, E, F>(method: &str, timeout: Duration, call: F) -> Option<T>
+where
+    T: Display,
+    E: Display,
+    F: Future<Output = Result<T, E>>,
+{
+    let start_time = std::time::Instant::now();
+    match tokio::time::timeout(timeout, call).await {
+        Ok(Ok(result)) => {
+            info!("{}: {} ({}ms)", method, result, start_time.elapsed().as_millis());
+            Some(result)
+        }
+        Ok(Err(e)) => {
+            error!("{}: failed after {}ms: {}", method, start_time.elapsed().as_millis(), e);
+            None
+        }
+        Err(_) => {
+            error!("{}: timed out after {}s", method, timeout.as_secs());
+            None
+        }
+    }
+}
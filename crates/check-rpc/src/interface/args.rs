@@ -0,0 +1,18 @@
+use clap::Parser;
+
+/// Arguments for the `check-rpc` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Check that an RPC URL supports the methods shadow needs")]
+pub struct CheckRpcArgs {
+    /// The RPC URL to check.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+
+    /// The maximum number of seconds to wait for each RPC call.
+    #[clap(long, default_value = "10")]
+    pub timeout: u64,
+
+    /// Whether to also check the `debug_*` endpoints.
+    #[clap(long)]
+    pub full: bool,
+}
@@ -0,0 +1,7 @@
+//! The `check-rpc` subcommand
+
+mod core;
+mod interface;
+
+pub use core::*;
+pub use interface::*;
@@ -0,0 +1,59 @@
+use std::{path::PathBuf, process::Command, str::FromStr};
+
+use eyre::{bail, Result};
+use shadow_common::{forge::ensure_forge_installed, ShadowContractGroupInfo};
+use tracing::info;
+
+use crate::{clone_contracts, CloneArgs, ForkArgs};
+
+/// The `fork` subcommand. Clones a shadow contract group from IPFS as a new contract group,
+/// which records the group it was forked from
+pub async fn fork(args: ForkArgs) -> Result<()> {
+    // ensure forge is installed on the system
+    if !args.no_clone {
+        ensure_forge_installed()?;
+    }
+
+    // get the contract group's metadata from IPFS
+    info!("fetching contract group metadata from IPFS...");
+    let original = ShadowContractGroupInfo::from_ipfs(
+        &args.ipfs_cid,
+        &args.ipfs_gateway_url,
+        !args.skip_integrity_check,
+    )
+    .await?;
+
+    let mut metadata = ShadowContractGroupInfo::default()
+        .with_display_name(&format!("Fork of {}", original.display_name));
+    metadata.contracts = original.contracts;
+    metadata.forked_from = Some(args.ipfs_cid.clone());
+
+    let parent = PathBuf::from_str(&args.output_dir)?;
+    let root = metadata.write_folder_structure(parent)?;
+
+    if !args.no_clone {
+        let clone_args = CloneArgs {
+            ipfs_cid: args.ipfs_cid.clone(),
+            etherscan_api_key: args.etherscan_api_key,
+            root: args.output_dir,
+            force: args.force,
+            ipfs_gateway_url: args.ipfs_gateway_url,
+            rpc_url: args.rpc_url,
+            blockscout_url: args.blockscout_url,
+            reth: false,
+            skip_integrity_check: args.skip_integrity_check,
+        };
+        clone_contracts(&clone_args, &metadata.contracts, &root).await?;
+    }
+
+    if args.git {
+        let status = Command::new("git").arg("init").arg("--quiet").current_dir(&root).status()?;
+        if !status.success() {
+            bail!("failed to initialize a git repository in {}", root.display());
+        }
+    }
+
+    info!("forked contract group {} to {}", args.ipfs_cid, root.display());
+
+    Ok(())
+}
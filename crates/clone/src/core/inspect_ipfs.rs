@@ -0,0 +1,55 @@
+use alloy::primitives::hex;
+use eyre::{eyre, Result};
+use shadow_common::ipfs::{format_size, list_ipfs_directory, read_file_from_ipfs};
+use tracing::info;
+
+use crate::InspectIpfsArgs;
+
+/// The `inspect-ipfs` subcommand. Lists the files of a shadow contract group pinned to IPFS as a
+/// tree, or prints one of them, entirely over the IPFS gateway and without writing to disk.
+pub async fn inspect_ipfs(args: InspectIpfsArgs) -> Result<()> {
+    if let Some(file) = &args.file {
+        let path = format!("{}/{}", args.ipfs_cid, file.trim_start_matches('/'));
+        let contents = read_file_from_ipfs(&path, &args.ipfs_gateway_url, true)
+            .await
+            .map_err(|e| eyre!("Failed to fetch {} from IPFS: {}", path, e))?;
+
+        // pretty-print JSON, such as the group's metadata, and fall back to hex for binary files
+        match serde_json::from_slice::<serde_json::Value>(&contents) {
+            Ok(json) => println!("{}", serde_json::to_string_pretty(&json)?),
+            Err(_) => match String::from_utf8(contents) {
+                Ok(text) => println!("{}", text),
+                Err(e) => println!("0x{}", hex::encode(e.as_bytes())),
+            },
+        }
+
+        return Ok(());
+    }
+
+    info!("listing the contents of {}", args.ipfs_cid);
+    let mut pending = list_ipfs_directory(&args.ipfs_cid, &args.ipfs_gateway_url)
+        .await?
+        .into_iter()
+        .rev()
+        .map(|entry| (1, entry))
+        .collect::<Vec<_>>();
+
+    // walk the tree depth-first, so each directory's entries are printed beneath it
+    let (mut files, mut total_size) = (0, 0);
+    println!("{}/", args.ipfs_cid);
+    while let Some((depth, entry)) = pending.pop() {
+        if entry.is_directory {
+            println!("{}{}/", "  ".repeat(depth), entry.name);
+            let children = list_ipfs_directory(&entry.cid, &args.ipfs_gateway_url).await?;
+            pending.extend(children.into_iter().rev().map(|child| (depth + 1, child)));
+        } else {
+            println!("{}{}  {}", "  ".repeat(depth), entry.name, format_size(entry.size));
+            files += 1;
+            total_size += entry.size;
+        }
+    }
+
+    info!("{} files, {} in total", files, format_size(total_size));
+
+    Ok(())
+}
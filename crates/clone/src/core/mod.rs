@@ -1,11 +1,23 @@
-use std::{path::PathBuf, str::FromStr};
+mod fork;
+mod inspect_ipfs;
 
-use crate::{ipfs::read_from_ipfs, CloneArgs};
+pub use fork::*;
+pub use inspect_ipfs::*;
+
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::CloneArgs;
 use eyre::Result;
-use shadow_common::{forge::ensure_forge_installed, ShadowContractGroupInfo, ShadowContractSource};
+use shadow_common::{
+    forge::ensure_forge_installed, ShadowContractEntry, ShadowContractGroupInfo,
+    ShadowContractSource,
+};
 use shadow_etherscan_fetch::FetchArgs;
 
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 /// The `clone` subcommand. Clones a shadow contract group from IPFS and saves it to the local
 /// filesystem
@@ -15,15 +27,37 @@ pub async fn clone(args: CloneArgs) -> Result<()> {
 
     // get the contract group's metadata from IPFS
     info!("fetching contract group metadata from IPFS...");
-    let metadata: ShadowContractGroupInfo =
-        read_from_ipfs(&format!("{}/info.json", args.ipfs_cid), &args.ipfs_gateway_url).await?;
+    let verify_integrity = !args.skip_integrity_check;
+    if !verify_integrity {
+        warn!("--skip-integrity-check is set. content from the IPFS gateway won't be verified");
+    }
+    let metadata = ShadowContractGroupInfo::from_ipfs(
+        &args.ipfs_cid,
+        &args.ipfs_gateway_url,
+        verify_integrity,
+    )
+    .await?;
 
     let parent = PathBuf::from_str(&args.root)?;
     let root = metadata.write_folder_structure(parent)?;
 
+    clone_contracts(&args, &metadata.contracts, &root).await?;
+
+    info!("successfully cloned contract group: {}", args.ipfs_cid);
+
+    Ok(())
+}
+
+/// Fetches each contract in a contract group pinned to IPFS with `shadow fetch`, and applies
+/// the group's source changes on top of the original source
+pub(crate) async fn clone_contracts(
+    args: &CloneArgs,
+    contracts: &[ShadowContractEntry],
+    root: &Path,
+) -> Result<()> {
     // for each contract in the group, call `shadow fetch` to build a working foundry environment
     // for each contract. we will apply source diffs later.
-    for contract in metadata.contracts {
+    for contract in contracts {
         info!("fetching contract: {}", contract.address);
         shadow_etherscan_fetch::fetch(FetchArgs {
             address: contract.address.to_string(),
@@ -33,19 +67,20 @@ pub async fn clone(args: CloneArgs) -> Result<()> {
             rpc_url: args.rpc_url.clone(),
             blockscout_url: args.blockscout_url.clone(),
             reth: args.reth,
+            follow_imports: false,
+            max_depth: 0,
+            chain_id: None,
         })
         .await?;
 
         // apply source diffs
         debug!("applying source diffs for contract: {}", contract.address);
-        let shadow_source: ShadowContractSource = read_from_ipfs(
-            &format!(
-                "{}/{}/{}/source.json",
-                args.ipfs_cid,
-                contract.chain_id,
-                contract.address.to_string().to_lowercase()
-            ),
+        let shadow_source = ShadowContractSource::from_ipfs(
+            &args.ipfs_cid,
+            contract.chain_id,
+            contract.address,
             &args.ipfs_gateway_url,
+            !args.skip_integrity_check,
         )
         .await?;
 
@@ -58,7 +93,5 @@ pub async fn clone(args: CloneArgs) -> Result<()> {
         info!("successfully cloned contract: {}", contract.address);
     }
 
-    info!("successfully cloned contract group: {}", args.ipfs_cid);
-
     Ok(())
 }
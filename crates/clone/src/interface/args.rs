@@ -37,6 +37,86 @@ pub struct CloneArgs {
     pub blockscout_url: Option<String>,
 
     /// Whether to save the compiled contract to './shadow.json' for use with shadow-reth.
+    /// Deprecated in favor of `shadow generate-reth-config`, and will be removed in the next
+    /// release.
     #[clap(long)]
     pub reth: bool,
+
+    /// Don't check the content served by the IPFS gateway against its CID. Only use this with a
+    /// gateway you trust.
+    #[clap(long)]
+    pub skip_integrity_check: bool,
+}
+
+/// Arguments for the `fork` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Creates a modified copy of a shadow contract group pinned to IPFS")]
+pub struct ForkArgs {
+    /// The ipfs CID of the contract group to fork.
+    pub ipfs_cid: String,
+
+    /// The path to the directory in which to save the forked contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub output_dir: String,
+
+    /// Initialize a git repository in the forked contract group.
+    #[clap(long)]
+    pub git: bool,
+
+    /// Only write the forked contract group's metadata, without fetching its contracts.
+    #[clap(long)]
+    pub no_clone: bool,
+
+    /// The API key to use for Etherscan.
+    #[clap(short, long, required = false)]
+    pub etherscan_api_key: Option<String>,
+
+    /// Whether to force overwrite the existing files.
+    #[clap(short, long)]
+    pub force: bool,
+
+    /// Your preferred IPFS gateway, used to fetch the contract group.
+    #[clap(
+        long,
+        required = false,
+        default_value = "https://gateway.pinata.cloud/ipfs/",
+        hide_default_value = true
+    )]
+    pub ipfs_gateway_url: String,
+
+    /// The RPC URL of the chain the contracts are deployed on.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+
+    /// The blockscan URL to use for fetching contract metadata
+    #[clap(short, long)]
+    pub blockscout_url: Option<String>,
+
+    /// Don't check the content served by the IPFS gateway against its CID. Only use this with a
+    /// gateway you trust.
+    #[clap(long)]
+    pub skip_integrity_check: bool,
+}
+
+/// Arguments for the `inspect-ipfs` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "List the contents of a shadow contract group pinned to IPFS, without cloning it")]
+pub struct InspectIpfsArgs {
+    /// The ipfs CID of the contract group to inspect.
+    pub ipfs_cid: String,
+
+    /// Print the contents of this file in the contract group, e.g. `info.json`, instead of
+    /// listing the group's files. JSON files are pretty-printed.
+    #[clap(long, required = false)]
+    pub file: Option<String>,
+
+    /// Your preferred IPFS gateway, used to fetch the contract group.
+    #[clap(
+        long,
+        alias = "gateway",
+        required = false,
+        default_value = "https://gateway.pinata.cloud/ipfs/",
+        hide_default_value = true
+    )]
+    pub ipfs_gateway_url: String,
 }
@@ -1,9 +1,7 @@
-//! The `fetch` subcommand
+//! The `clone`, `fork` and `inspect-ipfs` subcommands
 
 mod core;
 mod interface;
-mod utils;
 
 pub use core::*;
 pub use interface::*;
-pub(crate) use utils::*;
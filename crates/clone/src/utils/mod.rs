@@ -1,2 +0,0 @@
-/// ipfs utilities
-pub(crate) mod ipfs;
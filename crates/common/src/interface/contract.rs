@@ -1,21 +1,38 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeMap,
+    ops::Range,
+    path::{Component, Path, PathBuf},
+};
 
-use alloy::primitives::Address;
+use alloy::{
+    eips::{BlockId, BlockNumberOrTag},
+    network::Network,
+    primitives::Address,
+    providers::Provider,
+    rpc::types::trace::parity::{Action, LocalizedTransactionTrace, TraceOutput},
+    transports::Transport,
+};
 use alloy_chains::Chain;
-use eyre::Result;
+use eyre::{bail, OptionExt, Result};
 use foundry_block_explorers::contract::{ContractCreationData, ContractMetadata};
 use foundry_compilers::artifacts::{Libraries, RelativeRemapping, Remapping};
+use regex::Regex;
 use revm::primitives::B256;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::ipfs::read_from_ipfs;
+
 /// Contract information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ShadowContractInfo {
     /// The address of the contract
+    #[schemars(with = "String")]
     pub address: Address,
     /// The deployer of the contract
     #[serde(rename = "contractDeployer")]
+    #[schemars(with = "String")]
     pub contract_deployer: Address,
     /// The name of the contract
     pub name: String,
@@ -31,6 +48,7 @@ pub struct ShadowContractInfo {
     pub unique_events: u64,
     /// The deployment transaction hash
     #[serde(rename = "deploymentTransactionHash")]
+    #[schemars(with = "String")]
     pub deployment_transaction_hash: B256,
 }
 
@@ -54,6 +72,58 @@ impl ShadowContractInfo {
         }
     }
 
+    /// Creates a new instance of [`ShadowContractInfo`] purely from on-chain data, for
+    /// unverified contracts or chains without a supported block explorer. The RPC must be an
+    /// archive node which supports `trace_block`.
+    pub async fn from_rpc<T, N, P>(address: Address, provider: &P, chain: &Chain) -> Result<Self>
+    where
+        T: Transport + Clone,
+        N: Network,
+        P: Provider<T, N>,
+    {
+        // binary search for the first block at which the contract has code
+        let mut high = provider.get_block_number().await?;
+        if provider.get_code_at(address).block_id(BlockId::number(high)).await?.is_empty() {
+            bail!("{} is not a contract", address);
+        }
+        let mut low = 0;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if provider.get_code_at(address).block_id(BlockId::number(mid)).await?.is_empty() {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        // find the trace which created the contract in the deployment block. the deployer is
+        // whoever executed the CREATE, which may be a factory rather than the transaction sender
+        let traces: Vec<LocalizedTransactionTrace> =
+            provider.client().request("trace_block", (BlockNumberOrTag::Number(low),)).await?;
+        let (contract_deployer, deployment_transaction_hash) = traces
+            .into_iter()
+            .find_map(|trace| match (trace.trace.action, trace.trace.result) {
+                (Action::Create(create), Some(TraceOutput::Create(output)))
+                    if output.address == address =>
+                {
+                    Some((create.from, trace.transaction_hash?))
+                }
+                _ => None,
+            })
+            .ok_or_eyre(format!("failed to find the deployment of {} in block {}", address, low))?;
+
+        Ok(Self {
+            address,
+            contract_deployer,
+            name: address.to_string(),
+            network: chain.named().map(|n| n.to_string()).unwrap_or_else(|| "unknown".to_string()),
+            chain_id: chain.id(),
+            source: "rpc".to_string(),
+            unique_events: 0,
+            deployment_transaction_hash,
+        })
+    }
+
     /// Creates a new instance of [`ShadowContractInfo`] from the provided
     /// path to an info.json file
     pub fn from_path(path: &PathBuf) -> Result<Self> {
@@ -64,7 +134,7 @@ impl ShadowContractInfo {
 }
 
 /// Shadow contract source
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ShadowContractSource {
     /// The compiler version used to compile the contract
     #[serde(rename = "compilerVersion")]
@@ -72,6 +142,7 @@ pub struct ShadowContractSource {
     /// The language used to write the contract
     pub language: String,
     /// Remappings used to compile the contract
+    #[schemars(with = "Vec<String>")]
     pub remappings: Vec<RelativeRemapping>,
     /// The source code of the contract
     #[serde(rename = "contractFiles")]
@@ -79,7 +150,7 @@ pub struct ShadowContractSource {
 }
 
 /// Shadow contract source file
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ShadowContractSourceFile {
     /// The name of the file
     #[serde(rename = "fileName")]
@@ -88,6 +159,67 @@ pub struct ShadowContractSourceFile {
     pub content: String,
 }
 
+/// An import directive which doesn't resolve to a file in the source tree
+#[derive(Debug, Clone)]
+pub struct ImportError {
+    /// The name of the file containing the import
+    pub file_name: String,
+    /// The imported path, as written in the import directive
+    pub import: String,
+    /// Why the import couldn't be resolved
+    pub reason: String,
+}
+
+/// A change made to a single file of a [`ShadowContractSource`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShadowContractSourceFileDiff {
+    /// The name of the file
+    pub file_name: String,
+    /// The content of the file before the change, or `None` if it was added
+    pub original: Option<String>,
+    /// The content of the file after the change, or `None` if it was removed
+    pub modified: Option<String>,
+}
+
+/// The changes made to a [`ShadowContractSource`], see [`ShadowContractSource::diff`]
+#[derive(Debug, Clone, Default)]
+pub struct ShadowContractSourceDiff {
+    /// The files which were added, removed or modified, sorted by name
+    pub files: Vec<ShadowContractSourceFileDiff>,
+}
+
+impl ShadowContractSourceDiff {
+    /// Whether the diff contains no changes
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+impl ShadowContractSourceFile {
+    /// Finds the `import` directives in the file, skipping any which are commented out. Returns
+    /// the byte range of each imported path within [`ShadowContractSourceFile::content`], along
+    /// with the path itself.
+    pub fn imports(&self) -> Vec<(Range<usize>, String)> {
+        // comments are matched too, so imports inside them are consumed and skipped
+        let import_regex = Regex::new(
+            r#"(?s)/\*.*?\*/|//[^\n]*|import\s+(?:[^;"']*?\s+from\s+)?["']([^"']+)["']"#,
+        )
+        .expect("valid regex");
+
+        import_regex
+            .captures_iter(&self.content)
+            .filter_map(|c| c.get(1))
+            .map(|m| (m.range(), m.as_str().to_string()))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: import \"{}\" {}", self.file_name, self.import, self.reason)
+    }
+}
+
 impl ShadowContractSource {
     /// Creates a new instance of [`ShadowContractSource`] from the provided
     /// [`ContractMetadata`]
@@ -285,6 +417,84 @@ impl ShadowContractSource {
         Ok(())
     }
 
+    /// Checks that every `import` directive in the contract's Solidity files resolves to an
+    /// existing file in `root`. See [`ShadowContractSource::resolve_import`].
+    pub fn validate_imports(&self, root: &Path) -> Vec<ImportError> {
+        // vyper imports are module paths rather than files, so there's nothing to check
+        if self.language == "Vyper" {
+            return vec![];
+        }
+
+        self.contract_files
+            .iter()
+            .filter(|f| !f.file_name.ends_with(".vy"))
+            .flat_map(|file| {
+                file.imports().into_iter().filter_map(|(_, import)| {
+                    let resolved = root.join(self.resolve_import(&file.file_name, &import));
+                    (!resolved.is_file()).then(|| ImportError {
+                        file_name: file.file_name.clone(),
+                        import,
+                        reason: format!(
+                            "does not resolve to an existing file (expected {})",
+                            resolved.display()
+                        ),
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// Resolves an import in the given file to a path relative to the source root. Relative
+    /// imports are resolved against the importing file, and all other imports against the root
+    /// after applying the contract's remappings, as `forge` would.
+    pub fn resolve_import(&self, file_name: &str, import: &str) -> PathBuf {
+        let path = if import.starts_with("./") || import.starts_with("../") {
+            Path::new(file_name).parent().unwrap_or(Path::new("")).join(import)
+        } else {
+            self.remap(file_name, import).unwrap_or_else(|| PathBuf::from(import))
+        };
+
+        // normalize `.` and `..` without touching the filesystem
+        let mut normalized = PathBuf::new();
+        for component in path.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                component => normalized.push(component),
+            }
+        }
+        normalized
+    }
+
+    /// Applies the longest matching remapping to an import from the given file, if any match
+    fn remap(&self, file_name: &str, import: &str) -> Option<PathBuf> {
+        self.remappings
+            .iter()
+            .filter(|r| r.context.as_ref().map_or(true, |c| file_name.starts_with(c.as_str())))
+            .filter(|r| import.starts_with(r.name.as_str()))
+            .max_by_key(|r| r.name.len())
+            .map(|r| r.path.original().join(import[r.name.len()..].trim_start_matches('/')))
+    }
+
+    /// Fetches the [`ShadowContractSource`] of a contract in a contract group pinned to IPFS.
+    /// See [`read_from_ipfs`] for `verify_integrity`.
+    pub async fn from_ipfs(
+        cid: &str,
+        chain_id: u64,
+        address: Address,
+        base_gateway_url: &str,
+        verify_integrity: bool,
+    ) -> Result<Self> {
+        read_from_ipfs(
+            &format!("{}/{}/{}/source.json", cid, chain_id, address.to_string().to_lowercase()),
+            base_gateway_url,
+            verify_integrity,
+        )
+        .await
+    }
+
     /// Creates a new instance of [`ShadowContractSource`] from the provided
     /// path to /src directory and contract settings
     pub fn from_path(path: &PathBuf, contract_settings: &ShadowContractSettings) -> Result<Self> {
@@ -318,10 +528,121 @@ impl ShadowContractSource {
                 .collect::<Result<Vec<ShadowContractSourceFile>>>()?,
         })
     }
+
+    /// Computes the changes from this source to `modified`, file by file
+    pub fn diff(&self, modified: &Self) -> ShadowContractSourceDiff {
+        let original = self
+            .contract_files
+            .iter()
+            .map(|f| (f.file_name.as_str(), f.content.as_str()))
+            .collect::<BTreeMap<_, _>>();
+        let modified = modified
+            .contract_files
+            .iter()
+            .map(|f| (f.file_name.as_str(), f.content.as_str()))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut file_names = original.keys().chain(modified.keys()).collect::<Vec<_>>();
+        file_names.sort();
+        file_names.dedup();
+
+        ShadowContractSourceDiff {
+            files: file_names
+                .into_iter()
+                .filter(|name| original.get(*name) != modified.get(*name))
+                .map(|name| ShadowContractSourceFileDiff {
+                    file_name: name.to_string(),
+                    original: original.get(name).map(|c| c.to_string()),
+                    modified: modified.get(name).map(|c| c.to_string()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Applies a diff computed against another version of this source, merging each change
+    /// into this version's files. Returns the patched source, along with the names of files
+    /// whose changes couldn't be merged cleanly. Those files are left with git-style conflict
+    /// markers, or as they are in this source if the diff removed them.
+    pub fn patch(&self, diff: &ShadowContractSourceDiff) -> (Self, Vec<String>) {
+        let mut files = self
+            .contract_files
+            .iter()
+            .map(|f| (f.file_name.clone(), f.content.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let mut conflicts = vec![];
+
+        for change in &diff.files {
+            let current = files.get(&change.file_name).cloned();
+            match (&change.modified, current) {
+                // the file is unchanged from the diff's base, so the change applies as-is
+                (Some(modified), Some(current))
+                    if change.original.as_deref() == Some(current.as_str()) =>
+                {
+                    files.insert(change.file_name.clone(), modified.clone());
+                }
+                (Some(modified), Some(current)) => {
+                    let ancestor = change.original.as_deref().unwrap_or_default();
+                    match diffy::merge(ancestor, modified, &current) {
+                        Ok(merged) => {
+                            files.insert(change.file_name.clone(), merged);
+                        }
+                        Err(conflicted) => {
+                            files.insert(change.file_name.clone(), conflicted);
+                            conflicts.push(change.file_name.clone());
+                        }
+                    }
+                }
+                (Some(modified), None) => {
+                    // a modified file which no longer exists here can't be merged
+                    if change.original.is_some() {
+                        conflicts.push(change.file_name.clone());
+                    }
+                    files.insert(change.file_name.clone(), modified.clone());
+                }
+                (None, Some(current)) => {
+                    if change.original.as_deref() == Some(current.as_str()) {
+                        files.remove(&change.file_name);
+                    } else {
+                        // the removed file has changed here, so keep it
+                        conflicts.push(change.file_name.clone());
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+
+        (
+            Self {
+                contract_files: files
+                    .into_iter()
+                    .map(|(file_name, content)| ShadowContractSourceFile { file_name, content })
+                    .collect(),
+                ..self.clone()
+            },
+            conflicts,
+        )
+    }
 }
 
+/// EVM versions accepted by `solc`, in chronological order
+pub const EVM_VERSIONS: [&str; 13] = [
+    "homestead",
+    "tangerineWhistle",
+    "spuriousDragon",
+    "byzantium",
+    "constantinople",
+    "petersburg",
+    "istanbul",
+    "berlin",
+    "london",
+    "paris",
+    "shanghai",
+    "cancun",
+    "prague",
+];
+
 /// Shadow contract settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ShadowContractSettings {
     /// The optimizer settings
     pub optimizer: ShadowOptimizerSettings,
@@ -330,6 +651,7 @@ pub struct ShadowContractSettings {
     #[serde(rename = "outputSelection")]
     pub output_selection: Value,
     /// The libraries used by the contract
+    #[schemars(with = "BTreeMap<String, BTreeMap<String, String>>")]
     pub libraries: Libraries,
     /// The compiler version used to compile the contract
     #[serde(rename = "compilerVersion")]
@@ -346,7 +668,7 @@ pub struct ShadowContractSettings {
 }
 
 /// Optimizer settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ShadowOptimizerSettings {
     /// Whether the optimizer is enabled
     pub enabled: bool,
@@ -354,6 +676,31 @@ pub struct ShadowOptimizerSettings {
     pub runs: u64,
 }
 
+/// The network a Hardhat project forks when running its tests
+#[derive(Debug, Clone)]
+pub struct HardhatNetworkConfig {
+    /// The RPC URL to fork from
+    pub rpc_url: String,
+    /// The chain ID of the forked network
+    pub chain_id: u64,
+}
+
+/// The output selection used for every shadow contract
+fn default_output_selection() -> Value {
+    serde_json::json!({
+        "*": {
+            "*": [
+                "evm.bytecode",
+                "evm.deployedBytecode",
+                "devdoc",
+                "userdoc",
+                "metadata",
+                "abi"
+            ]
+        }
+    })
+}
+
 impl ShadowContractSettings {
     /// Creates a new instance of [`ShadowContractSettings`] from the provided
     /// [`ContractMetadata`]
@@ -364,18 +711,7 @@ impl ShadowContractSettings {
                 enabled: metadata.optimization_used > 0,
                 runs: metadata.runs,
             },
-            output_selection: serde_json::json!({
-                "*": {
-                    "*": [
-                        "evm.bytecode",
-                        "evm.deployedBytecode",
-                        "devdoc",
-                        "userdoc",
-                        "metadata",
-                        "abi"
-                    ]
-                }
-            }),
+            output_selection: default_output_selection(),
             libraries: metadata.settings().map(|s| s.libraries).unwrap_or_default(),
             compiler_version: metadata.compiler_version.clone(),
             constructor_arguments: metadata.constructor_arguments.to_vec(),
@@ -384,6 +720,107 @@ impl ShadowContractSettings {
         }
     }
 
+    /// Creates a new instance of [`ShadowContractSettings`] from the default profile of an
+    /// existing `foundry.toml`. Constructor arguments can't be recovered from the config, so
+    /// they are left empty.
+    pub fn from_foundry_config(config_path: &Path) -> Result<Self> {
+        let config: toml::Value = toml::from_str(&std::fs::read_to_string(config_path)?)?;
+        let profile = config
+            .get("profile")
+            .and_then(|profile| profile.get("default"))
+            .ok_or_eyre("foundry.toml has no [profile.default] section")?;
+
+        let compiler_version = profile
+            .get("solc_version")
+            .or_else(|| profile.get("solc"))
+            .and_then(|v| v.as_str())
+            .ok_or_eyre("foundry.toml must pin a solc_version")?;
+
+        let mut settings = Self {
+            optimizer: ShadowOptimizerSettings {
+                enabled: profile.get("optimizer").and_then(|v| v.as_bool()).unwrap_or(false),
+                runs: profile
+                    .get("optimizer_runs")
+                    .and_then(|v| v.as_integer())
+                    .unwrap_or(200)
+                    .try_into()?,
+            },
+            output_selection: default_output_selection(),
+            libraries: Libraries::default(),
+            compiler_version: format!("v{}", compiler_version.trim_start_matches('v')),
+            constructor_arguments: vec![],
+            evm_version: String::new(),
+            via_ir: profile.get("via_ir").and_then(|v| v.as_bool()).unwrap_or(false),
+        };
+        if let Some(evm_version) = profile.get("evm_version").and_then(|v| v.as_str()) {
+            settings.with_evm_version(evm_version)?;
+        }
+
+        Ok(settings)
+    }
+
+    /// Creates a new instance of [`ShadowContractSettings`] from a compiler version and the
+    /// `settings` object of a solc standard JSON input or metadata file, as found in Hardhat
+    /// build info and Foundry artifacts. Constructor arguments are left empty.
+    pub fn from_solc_settings(compiler_version: &str, settings: &Value) -> Result<Self> {
+        // standard JSON nests libraries by file, while metadata flattens them to `file:name`
+        let mut libraries = Libraries::default();
+        for (key, value) in settings["libraries"].as_object().into_iter().flatten() {
+            match value {
+                Value::String(address) => {
+                    let (file, name) = key.rsplit_once(':').ok_or_eyre("invalid library")?;
+                    libraries
+                        .libs
+                        .entry(PathBuf::from(file))
+                        .or_default()
+                        .insert(name.to_string(), address.clone());
+                }
+                Value::Object(libs) => {
+                    for (name, address) in libs {
+                        libraries.libs.entry(PathBuf::from(key)).or_default().insert(
+                            name.clone(),
+                            address.as_str().ok_or_eyre("invalid library")?.to_string(),
+                        );
+                    }
+                }
+                _ => bail!("invalid library: {}", key),
+            }
+        }
+
+        let mut shadow_settings = Self {
+            optimizer: ShadowOptimizerSettings {
+                enabled: settings["optimizer"]["enabled"].as_bool().unwrap_or(false),
+                runs: settings["optimizer"]["runs"].as_u64().unwrap_or(200),
+            },
+            output_selection: default_output_selection(),
+            libraries,
+            compiler_version: format!("v{}", compiler_version.trim_start_matches('v')),
+            constructor_arguments: vec![],
+            evm_version: String::new(),
+            via_ir: settings["viaIR"].as_bool().unwrap_or(false),
+        };
+        if let Some(evm_version) = settings["evmVersion"].as_str() {
+            shadow_settings.with_evm_version(evm_version)?;
+        }
+
+        Ok(shadow_settings)
+    }
+
+    /// Overrides the EVM version the contract is compiled for. Fails if `evm_version` is not
+    /// one of [`EVM_VERSIONS`].
+    pub fn with_evm_version(&mut self, evm_version: &str) -> Result<&mut Self> {
+        match EVM_VERSIONS.iter().find(|v| v.eq_ignore_ascii_case(evm_version)) {
+            Some(v) => self.evm_version = v.to_string(),
+            None => bail!(
+                "unknown EVM version '{}'. Expected one of: {}",
+                evm_version,
+                EVM_VERSIONS.join(", ")
+            ),
+        }
+
+        Ok(self)
+    }
+
     /// Writes the settings to a `foundry.toml` configuration file
     /// TODO @jon-becker: Eventually use the toml crate for this
     pub fn generate_config(&self, src_root: &Path) -> Result<()> {
@@ -419,6 +856,66 @@ impl ShadowContractSettings {
         Ok(())
     }
 
+    /// Writes the settings to a `hardhat.config.ts` configuration file. If `network_config` is
+    /// set, Hardhat's in-process network forks it. Artifacts are written to `out/hardhat`, so
+    /// they don't clobber `forge`'s.
+    pub fn generate_hardhat_config(
+        &self,
+        src_root: &Path,
+        network_config: Option<&HardhatNetworkConfig>,
+    ) -> Result<()> {
+        let config_path = src_root.join("hardhat.config.ts");
+
+        // hardhat expects a bare version, e.g. `0.8.19` rather than `v0.8.19+commit.7dd6d404`
+        let version = self.compiler_version.trim_start_matches('v');
+        let version = version.split_once('+').map_or(version, |(version, _)| version);
+
+        // libraries are given in standard JSON form, keyed by source file
+        let libraries = self
+            .libraries
+            .clone()
+            .with_stripped_file_prefixes(src_root)
+            .libs
+            .into_iter()
+            .map(|(path, libs)| (path.to_string_lossy().to_string(), libs))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut settings = vec![
+            format!(
+                "      optimizer: {{ enabled: {}, runs: {} }},",
+                self.optimizer.enabled, self.optimizer.runs
+            ),
+            format!("      viaIR: {},", self.via_ir),
+            "      metadata: { bytecodeHash: \"none\" },".to_string(),
+            format!("      libraries: {},", serde_json::to_string(&libraries)?),
+        ];
+        if !self.evm_version.is_empty() {
+            settings
+                .push(format!("      evmVersion: {},", serde_json::to_string(&self.evm_version)?));
+        }
+
+        let networks = match network_config {
+            Some(network) => format!(
+                "  networks: {{\n    hardhat: {{\n      chainId: {},\n      forking: {{ url: {} }},\n    }},\n  }},\n",
+                network.chain_id,
+                serde_json::to_string(&network.rpc_url)?
+            ),
+            None => String::new(),
+        };
+
+        let config = format!(
+            "import {{ HardhatUserConfig }} from \"hardhat/config\";\n\nconst config: HardhatUserConfig = {{\n  solidity: {{\n    version: \"{}\",\n    settings: {{\n{}\n    }},\n  }},\n  paths: {{\n    sources: \"./src\",\n    artifacts: \"./out/hardhat\",\n    cache: \"./cache/hardhat\",\n  }},\n{}}};\n\nexport default config;\n",
+            version,
+            settings.join("\n"),
+            networks
+        );
+
+        // overwrite `hardhat.config.ts` if it already exists
+        std::fs::write(config_path, config)?;
+
+        Ok(())
+    }
+
     /// Creates a new instance of [`ShadowContractSettings`] from the provided
     /// settings.json file
     pub fn from_path(settings_file: &PathBuf) -> Result<Self> {
@@ -427,3 +924,127 @@ impl ShadowContractSettings {
         Ok(settings)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> ShadowContractSettings {
+        ShadowContractSettings {
+            optimizer: ShadowOptimizerSettings { enabled: true, runs: 1000 },
+            output_selection: default_output_selection(),
+            libraries: Libraries::default(),
+            compiler_version: "v0.8.19+commit.7dd6d404".to_string(),
+            constructor_arguments: vec![],
+            evm_version: "paris".to_string(),
+            via_ir: false,
+        }
+    }
+
+    #[test]
+    fn test_generate_hardhat_config() {
+        let dir = tempdir::TempDir::new("shadow-hardhat").expect("failed to create temp dir");
+        settings().generate_hardhat_config(dir.path(), None).expect("failed to generate");
+
+        let config = std::fs::read_to_string(dir.path().join("hardhat.config.ts")).unwrap();
+        assert!(config.starts_with("import { HardhatUserConfig } from \"hardhat/config\";"));
+        assert!(config.contains("version: \"0.8.19\","));
+        assert!(config.contains("optimizer: { enabled: true, runs: 1000 },"));
+        assert!(config.contains("evmVersion: \"paris\","));
+        assert!(config.contains("libraries: {},"));
+        assert!(!config.contains("networks"));
+        assert!(config.trim_end().ends_with("export default config;"));
+    }
+
+    #[test]
+    fn test_generate_hardhat_config_with_fork() {
+        let dir = tempdir::TempDir::new("shadow-hardhat").expect("failed to create temp dir");
+        let mut settings = settings();
+        settings.evm_version = String::new();
+        let network = HardhatNetworkConfig {
+            rpc_url: "https://eth.example/\"key\"".to_string(),
+            chain_id: 1,
+        };
+        settings.generate_hardhat_config(dir.path(), Some(&network)).expect("failed to generate");
+
+        let config = std::fs::read_to_string(dir.path().join("hardhat.config.ts")).unwrap();
+        assert!(config.contains("chainId: 1,"));
+        assert!(config.contains("forking: { url: \"https://eth.example/\\\"key\\\"\" },"));
+        assert!(!config.contains("evmVersion"));
+    }
+
+    fn source(files: &[(&str, &str)]) -> ShadowContractSource {
+        ShadowContractSource {
+            compiler_version: "v0.8.19+commit.7dd6d404".to_string(),
+            language: "Solidity".to_string(),
+            remappings: vec![],
+            contract_files: files
+                .iter()
+                .map(|(file_name, content)| ShadowContractSourceFile {
+                    file_name: file_name.to_string(),
+                    content: content.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_source_diff_and_patch() {
+        let original = source(&[
+            ("src/Token.sol", "contract Token {\n    uint a;\n\n    uint b;\n}\n"),
+            ("src/Old.sol", "contract Old {}\n"),
+        ]);
+        let modified = source(&[
+            (
+                "src/Token.sol",
+                "contract Token {\n    uint a;\n\n    event Shadow();\n    uint b;\n}\n",
+            ),
+            ("src/Shadow.sol", "contract Shadow {}\n"),
+        ]);
+        let diff = original.diff(&modified);
+        assert_eq!(diff.files.len(), 3);
+        assert_eq!(diff.files[0].file_name, "src/Old.sol");
+        assert_eq!(diff.files[0].modified, None);
+        assert_eq!(diff.files[1].file_name, "src/Shadow.sol");
+        assert_eq!(diff.files[1].original, None);
+        assert!(original.diff(&original).is_empty());
+
+        // the updated source changed a different part of the modified file
+        let updated = source(&[
+            ("src/Token.sol", "contract Token {\n    uint256 a;\n\n    uint b;\n}\n"),
+            ("src/Old.sol", "contract Old {}\n"),
+        ]);
+        let (patched, conflicts) = updated.patch(&diff);
+        assert!(conflicts.is_empty());
+        let files = patched
+            .contract_files
+            .iter()
+            .map(|f| (f.file_name.as_str(), f.content.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            vec![
+                ("src/Shadow.sol", "contract Shadow {}\n"),
+                (
+                    "src/Token.sol",
+                    "contract Token {\n    uint256 a;\n\n    event Shadow();\n    uint b;\n}\n"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_source_patch_conflict() {
+        let original = source(&[("src/Token.sol", "contract Token {\n    uint a;\n}\n")]);
+        let modified = source(&[("src/Token.sol", "contract Token {\n    uint shadow;\n}\n")]);
+        let updated = source(&[("src/Token.sol", "contract Token {\n    uint256 a;\n}\n")]);
+
+        let (patched, conflicts) = updated.patch(&original.diff(&modified));
+        assert_eq!(conflicts, vec!["src/Token.sol".to_string()]);
+        let content = &patched.contract_files[0].content;
+        assert!(content.contains("<<<<<<<"));
+        assert!(content.contains("    uint shadow;"));
+        assert!(content.contains("    uint256 a;"));
+        assert!(content.contains(">>>>>>>"));
+    }
+}
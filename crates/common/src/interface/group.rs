@@ -1,34 +1,58 @@
 use std::{
     io::Write,
     path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
 
 use alloy::primitives::Address;
 use chrono::{DateTime, Utc};
-use eyre::{bail, OptionExt, Result};
-use futures::future::try_join_all;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use eyre::{bail, eyre, OptionExt, Result};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info};
-
-use crate::{compiler, ShadowContractInfo, ShadowContractSettings, ShadowContractSource};
+use serde_json::Value;
+use tokio::{sync::Semaphore, task::JoinSet};
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    compiler::{self, RemoteCompiler},
+    db::{JsonRpcDatabase, DEFAULT_PARALLEL_REQUESTS},
+    ipfs::read_from_ipfs,
+    migrations::{group_version, migrate, CURRENT_VERSION},
+    ShadowContractInfo, ShadowContractSettings, ShadowContractSource,
+};
 
 /// Contains the initial, default README.md file for a contract group
 pub const DEFAULT_README: &str = include_str!("../../templates/README.md");
 
+/// How to build the artifacts in [`ShadowContractGroupInfo::artifact_path`], for errors about
+/// missing artifacts. Only [`ShadowContractGroupInfo::prepare`] writes them, so `shadow compile`,
+/// which builds a single contract's `out/`, doesn't.
+pub const BUILD_ARTIFACTS_HINT: &str =
+    "Run `shadow preview-push` or `shadow sim` to build the contract group's artifacts.";
+
 /// Contract group information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ShadowContractGroupInfo {
+    /// The version of the `info.json` format. Groups created before it was versioned are
+    /// version 1.
+    #[serde(default = "default_version")]
+    pub version: u32,
     /// The display name of the contract group
     #[serde(rename = "displayName")]
     pub display_name: String,
     /// The address of the creator of the contract group
+    #[schemars(with = "Option<String>")]
     pub creator: Option<Address>,
     /// The date the contract group was created
     #[serde(rename = "creationDate")]
     pub creation_date: DateTime<Utc>,
     /// A list of contracts in the contract group
     pub contracts: Vec<ShadowContractEntry>,
+    /// The IPFS CID of the contract group this one was forked from, if any
+    #[serde(rename = "forkedFrom", default, skip_serializing_if = "Option::is_none")]
+    pub forked_from: Option<String>,
     /// The contract group's README.md file
     #[serde(skip)]
     readme: String,
@@ -37,10 +61,16 @@ pub struct ShadowContractGroupInfo {
     root: PathBuf,
 }
 
+/// The version of `info.json` files which predate versioning
+fn default_version() -> u32 {
+    1
+}
+
 /// A single contract in a contract group
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ShadowContractEntry {
     /// The address of the contract
+    #[schemars(with = "String")]
     pub address: Address,
     /// The chain id that the contract is deployed on
     pub chain_id: u64,
@@ -53,20 +83,72 @@ impl From<ShadowContractInfo> for ShadowContractEntry {
 }
 
 impl ShadowContractEntry {
-    /// Compiles the contract that this entry references
-    pub async fn compile(&self, rpc_url: &str, root: &Path, output: &Path) -> Result<()> {
+    /// The contract's directory, relative to the group root or an artifact directory
+    fn relative_path(&self) -> PathBuf {
+        PathBuf::from(self.chain_id.to_string()).join(self.address.to_string().to_lowercase())
+    }
+
+    /// The directory the contract's compiled artifacts are written to under `output`, e.g.
+    /// [`ShadowContractGroupInfo::artifact_path`]
+    pub fn output_path(&self, output: &Path) -> PathBuf {
+        output.join(self.relative_path())
+    }
+
+    /// Whether the contract's compiled artifacts (`bytecode.hex` and `abi.json`) exist under
+    /// `output`
+    pub fn is_compiled(&self, output: &Path) -> bool {
+        let output_path = self.output_path(output);
+        output_path.join("bytecode.hex").exists() && output_path.join("abi.json").exists()
+    }
+
+    /// Whether the contract isn't compiled under `output`, or any of its source files or its
+    /// compiler settings in the group at `root` have been modified since it was
+    pub fn needs_recompile(&self, root: &Path, output: &Path) -> bool {
+        if !self.is_compiled(output) {
+            return true;
+        }
+        let Ok(compiled_at) = std::fs::metadata(self.output_path(output).join("bytecode.hex"))
+            .and_then(|metadata| metadata.modified())
+        else {
+            return true;
+        };
+
+        // files whose modification time can't be read are assumed to have changed
+        let contract_path = root.join(self.relative_path());
+        walkdir::WalkDir::new(contract_path.join("src"))
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .chain(std::iter::once(contract_path.join("settings.json")))
+            .any(|path| {
+                std::fs::metadata(path)
+                    .and_then(|metadata| metadata.modified())
+                    .map_or(true, |modified| modified > compiled_at)
+            })
+    }
+
+    /// Compiles the contract that this entry references. If `remote` is set, the contract is
+    /// compiled via that remote compiler instead of a local `forge` installation.
+    /// Otherwise, RPC requests made while compiling are limited by `request_limiter`.
+    pub async fn compile(
+        &self,
+        rpc_url: &str,
+        remote: Option<&RemoteCompiler>,
+        root: &Path,
+        output: &Path,
+        request_limiter: Arc<Semaphore>,
+    ) -> Result<()> {
         let start_time = std::time::Instant::now();
 
         // build paths
-        let contract_path =
-            root.join(self.chain_id.to_string()).join(self.address.to_string().to_lowercase());
+        let contract_path = root.join(self.relative_path());
         let contract_info_path = contract_path.join("info.json");
         let contract_settings_path = contract_path.join("settings.json");
         let contract_src_path = contract_path.join("src");
         let contract_original_source_path = contract_path.join("original.json");
 
-        let contract_output_path =
-            output.join(self.chain_id.to_string()).join(self.address.to_string().to_lowercase());
+        let contract_output_path = self.output_path(output);
         let out_bytecode_file = contract_output_path.join("bytecode.hex");
         let out_abi_file = contract_output_path.join("abi.json");
         let out_settings_file = contract_output_path.join("settings.json");
@@ -87,8 +169,22 @@ impl ShadowContractEntry {
         );
 
         // compile the contract
-        let output =
-            compiler::compile(rpc_url, &contract_path, &contract_settings, &contract_info).await?;
+        let output = match remote {
+            Some(remote) => {
+                compiler::compile_remote(remote, &contract_path, &contract_settings, &contract_info)
+                    .await?
+            }
+            None => {
+                compiler::compile(
+                    rpc_url,
+                    &contract_path,
+                    &contract_settings,
+                    &contract_info,
+                    request_limiter,
+                )
+                .await?
+            }
+        };
 
         debug!("Compiled {} successfully in {:?}", contract_info.name, start_time.elapsed());
 
@@ -112,10 +208,12 @@ impl ShadowContractEntry {
 impl Default for ShadowContractGroupInfo {
     fn default() -> Self {
         Self {
+            version: CURRENT_VERSION,
             display_name: "Unnamed Contract Group".to_string(),
             creator: None,
             creation_date: Utc::now(),
             contracts: vec![],
+            forked_from: None,
             root: PathBuf::new(),
             readme: DEFAULT_README.to_string(),
         }
@@ -123,24 +221,74 @@ impl Default for ShadowContractGroupInfo {
 }
 
 impl ShadowContractGroupInfo {
+    /// Sets the display name of the contract group
+    pub fn with_display_name(mut self, name: &str) -> Self {
+        self.display_name = name.to_string();
+        self
+    }
+
+    /// Sets the address of the creator of the contract group
+    pub fn with_creator(mut self, address: Address) -> Self {
+        self.creator = Some(address);
+        self
+    }
+
+    /// Sets the contents of the contract group's README.md file
+    pub fn with_readme(mut self, content: &str) -> Self {
+        self.readme = content.to_string();
+        self
+    }
+
     /// Try to create a new instance of [`ShadowContractGroupInfo`] from the provided
-    /// path. Assumes the path is a directory containing a `info.json` file.
+    /// path. Assumes the path is a directory containing a `info.json` file. Older versions of
+    /// `info.json` are migrated in memory, and can be updated on disk with `shadow migrate`.
     pub fn from_path(path: &PathBuf) -> Result<Self> {
         let info_file = path.join("info.json");
-        let info_json = std::fs::read_to_string(info_file)?;
-        let mut info: Self = serde_json::from_str(&info_json)?;
+        let info_json: Value = serde_json::from_str(&std::fs::read_to_string(info_file)?)?;
+        let version = group_version(&info_json)?;
+        let mut info = migrate(info_json)?;
+        if version < CURRENT_VERSION {
+            warn!(
+                "{} uses version {} of the contract group format. Run `shadow migrate` to update it to version {}.",
+                path.join("info.json").display(),
+                version,
+                CURRENT_VERSION
+            );
+        }
 
         info.root.clone_from(path);
 
         Ok(info)
     }
 
+    /// Fetches the [`ShadowContractGroupInfo`] of a contract group pinned to IPFS. Unlike
+    /// [`ShadowContractGroupInfo::from_path`], the group has no local root. See
+    /// [`read_from_ipfs`] for `verify_integrity`. Older versions of `info.json` are migrated.
+    pub async fn from_ipfs(
+        cid: &str,
+        base_gateway_url: &str,
+        verify_integrity: bool,
+    ) -> Result<Self> {
+        migrate(
+            read_from_ipfs(&format!("{}/info.json", cid), base_gateway_url, verify_integrity)
+                .await?,
+        )
+    }
+
+    /// Writes the group information back to the group's `info.json` file.
+    pub fn save(&self) -> Result<()> {
+        let info_file = self.root.join("info.json");
+        let info_json = serde_json::to_string_pretty(self)?;
+        std::fs::write(info_file, info_json)?;
+
+        Ok(())
+    }
+
     /// Writes the folder structure of the contract group to the provided path.
     /// Returns the path to the created folder
     pub fn write_folder_structure(&self, parent: PathBuf) -> Result<PathBuf> {
         // parent/ContractGroup_06_20_2024_12_00
-        let formatted_date = self.creation_date.format("%m_%d_%Y_%H_%M");
-        let group_folder = parent.join(format!("ContractGroup_{}", formatted_date));
+        let group_folder = parent.join(self.folder_name());
         std::fs::create_dir_all(&group_folder)?;
 
         // write to group_folder/info.json
@@ -155,6 +303,85 @@ impl ShadowContractGroupInfo {
         Ok(group_folder)
     }
 
+    /// The name of the folder which [`Self::write_folder_structure`] creates, e.g.
+    /// `ContractGroup_06_20_2024_12_00`
+    fn folder_name(&self) -> String {
+        format!("ContractGroup_{}", self.creation_date.format("%m_%d_%Y_%H_%M"))
+    }
+
+    /// The path to the artifacts written by the last call to [`Self::prepare`]
+    pub fn artifact_path(&self) -> PathBuf {
+        self.root.join("out").join(self.folder_name())
+    }
+
+    /// Returns the compiled artifacts (`bytecode.hex` and `abi.json`) which are missing from
+    /// [`Self::artifact_path`] for the group's contracts.
+    pub fn missing_artifacts(&self) -> Vec<PathBuf> {
+        let artifact_path = self.artifact_path();
+        self.contracts
+            .iter()
+            .map(|contract| contract.output_path(&artifact_path))
+            .flat_map(|contract_path| {
+                [contract_path.join("bytecode.hex"), contract_path.join("abi.json")]
+            })
+            .filter(|path| !path.exists())
+            .collect()
+    }
+
+    /// Returns the group's contracts which aren't compiled in [`Self::artifact_path`], or whose
+    /// sources have changed since they were
+    pub fn stale_contracts(&self) -> Vec<&ShadowContractEntry> {
+        let artifact_path = self.artifact_path();
+        self.contracts
+            .iter()
+            .filter(|contract| contract.needs_recompile(&self.root, &artifact_path))
+            .collect()
+    }
+
+    /// Returns a contract in the group. `chain_id` is only required if the address is tracked on
+    /// multiple chains.
+    pub fn contract(
+        &self,
+        address: &Address,
+        chain_id: Option<u64>,
+    ) -> Result<&ShadowContractEntry> {
+        let matches = self
+            .contracts
+            .iter()
+            .filter(|c| &c.address == address && chain_id.map_or(true, |id| c.chain_id == id))
+            .collect::<Vec<_>>();
+
+        match matches.as_slice() {
+            [contract] => Ok(contract),
+            [] => bail!("contract {} is not tracked by this contract group", address),
+            _ => bail!(
+                "contract {} is tracked on multiple chains. Use --chain-id to pick one.",
+                address
+            ),
+        }
+    }
+
+    /// Returns the path to a contract in the group. `chain_id` is only required if the address
+    /// is tracked on multiple chains.
+    pub fn contract_path(&self, address: &Address, chain_id: Option<u64>) -> Result<PathBuf> {
+        let contract = self.contract(address, chain_id)?;
+        Ok(self.root.join(contract.relative_path()))
+    }
+
+    /// Removes a contract from the group, and writes the updated `info.json`. Unlike
+    /// [`Self::update_contracts`], the contract's files are left untouched, so it's tracked
+    /// again by the next scan. Returns whether the contract was tracked.
+    pub fn remove_contract(&mut self, address: Address, chain_id: u64) -> Result<bool> {
+        let tracked = self.contracts.len();
+        self.contracts.retain(|c| !(c.address == address && c.chain_id == chain_id));
+        if self.contracts.len() == tracked {
+            return Ok(false);
+        }
+
+        self.save()?;
+        Ok(true)
+    }
+
     /// Updates the group's contracts by scanning the contracts directory
     /// for new contracts
     pub fn update_contracts(&mut self) -> Result<()> {
@@ -228,6 +455,20 @@ impl ShadowContractGroupInfo {
     /// in the group and generates the proper folder structure which will be pinned
     /// to IPFS.
     pub async fn prepare(&mut self, rpc_url: &str) -> Result<PathBuf> {
+        self.prepare_with(rpc_url, None, None, DEFAULT_PARALLEL_REQUESTS).await
+    }
+
+    /// Same as [`Self::prepare`], but compiles via the given remote compiler if `remote` is set,
+    /// and compiles at most `parallelism` contracts at once. `parallelism` defaults to the
+    /// number of available CPUs. The contracts being compiled share at most `parallel_requests`
+    /// in-flight RPC requests.
+    pub async fn prepare_with(
+        &mut self,
+        rpc_url: &str,
+        remote: Option<&RemoteCompiler>,
+        parallelism: Option<usize>,
+        parallel_requests: usize,
+    ) -> Result<PathBuf> {
         // re-scan the contracts directory for new contracts
         let _ = &self.update_contracts()?;
 
@@ -239,24 +480,101 @@ impl ShadowContractGroupInfo {
         // copy `info.json` and `README.md` to the out directory, since this will be pinned
         let out_folder = self.write_folder_structure(out_dir)?;
 
-        // we need to compile each contract in the group. We can do this in parallel w/ rayon
-        info!("compiling {} shadow contracts", self.contracts.len());
-        let compile_futures = self
-            .contracts
-            .par_iter()
-            .map(|contract| contract.compile(rpc_url, &self.root, &out_folder))
-            .collect::<Vec<_>>();
+        // we need to compile each contract in the group. We can do this in parallel, limited
+        // by a semaphore
+        let parallelism = parallelism
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+            .max(1);
+        info!("compiling {} shadow contracts, {} at a time", self.contracts.len(), parallelism);
+
+        let progress = MultiProgress::new();
+        let overall = progress.add(ProgressBar::new(self.contracts.len() as u64));
+        overall.set_style(ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40} {pos}/{len} compiled",
+        )?);
+
+        let semaphore = Arc::new(Semaphore::new(parallelism));
+        let request_limiter = JsonRpcDatabase::request_limiter(parallel_requests);
+        let mut tasks = JoinSet::new();
+        for contract in self.contracts.clone() {
+            let bar = progress.insert_before(&overall, ProgressBar::new_spinner());
+            bar.set_style(ProgressStyle::with_template("{spinner} {prefix} {msg} [{elapsed}]")?);
+            bar.set_prefix(format!("{} (chain {})", contract.address, contract.chain_id));
+            bar.set_message("queued");
+
+            let (semaphore, request_limiter, overall) =
+                (semaphore.clone(), request_limiter.clone(), overall.clone());
+            let (rpc_url, remote) = (rpc_url.to_string(), remote.cloned());
+            let (root, out_folder) = (self.root.clone(), out_folder.clone());
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await?;
+                bar.set_message("compiling");
+                bar.reset_elapsed();
+                bar.enable_steady_tick(Duration::from_millis(100));
+
+                let result = contract
+                    .compile(&rpc_url, remote.as_ref(), &root, &out_folder, request_limiter)
+                    .await;
+                bar.finish_with_message(if result.is_ok() { "done" } else { "error" });
+                overall.inc(1);
+
+                result
+                    .map_err(|e| eyre!("{} (chain {}): {}", contract.address, contract.chain_id, e))
+            });
+        }
 
-        try_join_all(compile_futures).await?;
+        // collect every error, rather than stopping at the first
+        let mut errors = vec![];
+        while let Some(result) = tasks.join_next().await {
+            if let Err(e) = result? {
+                errors.push(e);
+            }
+        }
+        overall.finish();
+
+        if !errors.is_empty() {
+            for e in &errors {
+                error!("{}", e);
+            }
+            bail!(
+                "failed to compile {} of {} shadow contracts",
+                errors.len(),
+                self.contracts.len()
+            );
+        }
 
         info!("compiled all shadow contracts successfully");
 
         Ok(out_folder)
     }
+
+    /// Prepares the contract group for pinning to IPFS using the artifacts from a previous
+    /// call to [`Self::prepare`], without recompiling. The artifacts may be stale if contracts
+    /// have changed since they were built.
+    pub fn prepare_prebuilt(&mut self) -> Result<PathBuf> {
+        let missing = self.missing_artifacts();
+        if !missing.is_empty() {
+            bail!(
+                "missing compiled artifacts:\n{}\nrun `shadow push` without `--no-compile` to build them",
+                missing
+                    .iter()
+                    .map(|p| format!("  - {}", p.display()))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+
+        // refresh `info.json` and `README.md`, which may have changed during validation
+        self.readme = std::fs::read_to_string(self.root.join("README.md"))?;
+        let out_folder = self.write_folder_structure(self.root.join("out"))?;
+        info!("using prebuilt artifacts for {} shadow contracts", self.contracts.len());
+
+        Ok(out_folder)
+    }
 }
 
 /// Prompt the user for input w/ pretty colors :D
-fn prompt(text: &str) -> Result<Option<String>> {
+pub fn prompt(text: &str) -> Result<Option<String>> {
     let mut input = String::new();
     const YELLOW_ANSI_CODE: &str = "\u{001b}[33m";
     const LIGHT_GRAY_ANSI_CODE: &str = "\u{001b}[90m";
@@ -277,3 +595,91 @@ fn prompt(text: &str) -> Result<Option<String>> {
 
     Ok(None)
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{address, B256};
+
+    use super::*;
+
+    #[test]
+    fn test_remove_contract() {
+        let root = tempdir::TempDir::new("shadow-group").unwrap().into_path();
+        let address = address!("5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f");
+        std::fs::write(
+            root.join("info.json"),
+            serde_json::json!({
+                "version": CURRENT_VERSION,
+                "displayName": "Uniswap V2",
+                "creator": null,
+                "creationDate": "2024-06-01T12:00:00Z",
+                "contracts": [{ "address": address, "chain_id": 1 }],
+            })
+            .to_string(),
+        )
+        .unwrap();
+        std::fs::write(root.join("README.md"), DEFAULT_README).unwrap();
+        let contract_path = root.join("1").join(address.to_string().to_lowercase());
+        std::fs::create_dir_all(&contract_path).unwrap();
+        std::fs::write(
+            contract_path.join("info.json"),
+            serde_json::json!({
+                "address": address,
+                "contractDeployer": Address::ZERO,
+                "name": "UniswapV2Factory",
+                "network": "mainnet",
+                "chainId": 1,
+                "source": "etherscan",
+                "uniqueEvents": 1,
+                "deploymentTransactionHash": B256::ZERO,
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let mut group = ShadowContractGroupInfo::from_path(&root).unwrap();
+        assert!(!group.remove_contract(address, 8453).unwrap());
+        assert!(group.remove_contract(address, 1).unwrap());
+        assert!(!group.remove_contract(address, 1).unwrap());
+
+        // the removal is written to info.json
+        let mut group = ShadowContractGroupInfo::from_path(&root).unwrap();
+        assert!(group.contracts.is_empty());
+
+        // the contract's files are still on disk, so a scan tracks it again
+        group.update_contracts().unwrap();
+        assert_eq!(group.contracts.len(), 1);
+        assert_eq!(group.contracts[0].address, address);
+        assert_eq!(group.contracts[0].chain_id, 1);
+    }
+
+    #[test]
+    fn test_needs_recompile() {
+        let root = tempdir::TempDir::new("shadow-group").unwrap().into_path();
+        let output = root.join("out").join("ContractGroup");
+        let contract = ShadowContractEntry {
+            address: address!("5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f"),
+            chain_id: 1,
+        };
+        let source_path = root.join(contract.relative_path()).join("src");
+        std::fs::create_dir_all(&source_path).unwrap();
+        std::fs::write(source_path.join("Factory.sol"), "contract Factory {}").unwrap();
+        std::fs::write(root.join(contract.relative_path()).join("settings.json"), "{}").unwrap();
+        assert!(!contract.is_compiled(&output));
+        assert!(contract.needs_recompile(&root, &output));
+
+        let output_path = contract.output_path(&output);
+        std::fs::create_dir_all(&output_path).unwrap();
+        std::fs::write(output_path.join("bytecode.hex"), "0x00").unwrap();
+        assert!(!contract.is_compiled(&output));
+        std::fs::write(output_path.join("abi.json"), "[]").unwrap();
+        assert!(contract.is_compiled(&output));
+        assert!(!contract.needs_recompile(&root, &output));
+
+        // editing a source file after compiling makes the artifacts stale
+        let source =
+            std::fs::File::options().write(true).open(source_path.join("Factory.sol")).unwrap();
+        source.set_modified(std::time::SystemTime::now() + Duration::from_secs(60)).unwrap();
+        assert!(contract.needs_recompile(&root, &output));
+    }
+}
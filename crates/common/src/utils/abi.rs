@@ -0,0 +1,34 @@
+use std::path::Path;
+
+use alloy::{
+    json_abi::{Function, JsonAbi},
+    primitives::Selector,
+};
+use eyre::Result;
+
+/// Loads every `abi.json` file under `artifact_path`, e.g. the artifacts of a prepared contract
+/// group.
+pub fn get_abis(artifact_path: &Path) -> Result<Vec<JsonAbi>> {
+    walkdir::WalkDir::new(artifact_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.path().file_name().map(|f| f == "abi.json").unwrap_or(false))
+        .map(|e| {
+            // parse the json file
+            let json = std::fs::read_to_string(e.path())?;
+            let abi = serde_json::from_str::<JsonAbi>(&json)?;
+            Ok::<_, eyre::Report>(abi)
+        })
+        .collect::<Result<_, _>>()
+}
+
+/// Try to get the function ABI(s) for the given selector. Note: there may be multiple matching
+/// function signatures, so this function returns a Vec.
+pub fn try_get_function_abi(selector: &Selector, abis: &[JsonAbi]) -> Vec<Function> {
+    abis.iter()
+        .flat_map(|abi| abi.functions())
+        .filter(|function| &function.selector() == selector)
+        .cloned()
+        .collect::<Vec<_>>()
+}
@@ -0,0 +1,266 @@
+use std::fmt::{Display, Formatter};
+
+use alloy::primitives::Address;
+use alloy_json_abi::{Event, Function, JsonAbi, StateMutability};
+use serde::Serialize;
+
+/// How serious a [`Finding`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// A pattern worth cleaning up, but unlikely to be exploitable
+    Low,
+    /// A pattern which may hide, or make it harder to detect, unexpected behavior
+    Medium,
+    /// A pattern which may be directly exploitable
+    High,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Low => write!(f, "low"),
+            Severity::Medium => write!(f, "medium"),
+            Severity::High => write!(f, "high"),
+        }
+    }
+}
+
+/// A function or event flagged by a [`Rule`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleMatch {
+    /// The signature of the flagged function or event
+    pub item: String,
+    /// Why the item was flagged
+    pub message: String,
+}
+
+/// A [`RuleMatch`] in a specific contract
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    /// The address of the contract
+    pub address: Address,
+    /// The chain the contract is deployed on
+    pub chain_id: u64,
+    /// The signature of the flagged function or event
+    pub item: String,
+    /// The name of the rule which flagged the item
+    pub rule: &'static str,
+    /// The severity of the rule
+    pub severity: Severity,
+    /// Why the item was flagged
+    pub message: String,
+}
+
+/// A heuristic which flags potentially vulnerable patterns in a contract's ABI. Rules only see
+/// the ABI, so their findings are leads to review rather than confirmed vulnerabilities.
+pub trait Rule {
+    /// A short, kebab-case name for the rule
+    fn name(&self) -> &'static str;
+
+    /// The severity of the rule's findings
+    fn severity(&self) -> Severity;
+
+    /// Checks the ABI, returning every function or event which matches the rule
+    fn check(&self, abi: &JsonAbi) -> Vec<RuleMatch>;
+}
+
+/// Functions named like token transfers or approvals in a contract with no matching
+/// `Transfer`/`Approval` style event, so the movements can't be tracked off-chain
+#[derive(Debug, Clone, Copy)]
+pub struct UnloggedTransferRule;
+
+impl Rule for UnloggedTransferRule {
+    fn name(&self) -> &'static str {
+        "unlogged-transfer"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn check(&self, abi: &JsonAbi) -> Vec<RuleMatch> {
+        let has_event = |needle: &str| abi.events().any(|e| e.name.to_lowercase().contains(needle));
+        let has_transfer_event = has_event("transfer");
+        let has_approval_event = has_event("approv");
+
+        state_changing_functions(abi)
+            .filter_map(|function| {
+                let name = function.name.to_lowercase();
+                let missing = if name.contains("transfer") && !has_transfer_event {
+                    "transfer"
+                } else if name.contains("approve") && !has_approval_event {
+                    "approval"
+                } else {
+                    return None;
+                };
+
+                Some(RuleMatch {
+                    item: function.signature(),
+                    message: format!("the contract has no {} event for this function", missing),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Prefixes of function names which are usually restricted to an owner or admin
+const ADMIN_FUNCTION_PREFIXES: [&str; 10] = [
+    "set", "update", "pause", "unpause", "upgrade", "grant", "revoke", "mint", "withdraw", "rescue",
+];
+
+/// Admin-style functions in a contract which emits no access control events, such as
+/// `OwnershipTransferred` or `RoleGranted`, which suggests they may not be access controlled
+#[derive(Debug, Clone, Copy)]
+pub struct UnguardedAdminFunctionRule;
+
+impl Rule for UnguardedAdminFunctionRule {
+    fn name(&self) -> &'static str {
+        "unguarded-admin-function"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    fn check(&self, abi: &JsonAbi) -> Vec<RuleMatch> {
+        let has_access_control_event = abi.events().any(|event| {
+            let name = event.name.to_lowercase();
+            ["owner", "role", "admin"].iter().any(|needle| name.contains(needle))
+        });
+        if has_access_control_event {
+            return vec![];
+        }
+
+        state_changing_functions(abi)
+            .filter(|function| {
+                let name = function.name.to_lowercase();
+                ADMIN_FUNCTION_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+            })
+            .map(|function| RuleMatch {
+                item: function.signature(),
+                message: "admin function in a contract with no access control events".to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Event parameters which are usually filtered on, such as addresses, that aren't indexed even
+/// though the event has a free topic
+#[derive(Debug, Clone, Copy)]
+pub struct UnindexedEventParamRule;
+
+impl Rule for UnindexedEventParamRule {
+    fn name(&self) -> &'static str {
+        "unindexed-event-param"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn check(&self, abi: &JsonAbi) -> Vec<RuleMatch> {
+        abi.events()
+            .filter(|event| has_free_topic(event))
+            .flat_map(|event| {
+                event
+                    .inputs
+                    .iter()
+                    .filter(|input| !input.indexed && input.ty == "address")
+                    .map(|input| RuleMatch {
+                        item: event.signature(),
+                        message: format!("`{}` is not indexed", input.name),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// Whether another parameter of the event could be indexed. Anonymous events have 4 topics to
+/// spare, others 3, since the first is their selector.
+fn has_free_topic(event: &Event) -> bool {
+    let max_indexed = if event.anonymous { 4 } else { 3 };
+    event.inputs.iter().filter(|input| input.indexed).count() < max_indexed
+}
+
+/// The functions in the ABI which may change state
+fn state_changing_functions(abi: &JsonAbi) -> impl Iterator<Item = &Function> {
+    abi.functions().filter(|function| {
+        matches!(function.state_mutability, StateMutability::NonPayable | StateMutability::Payable)
+    })
+}
+
+/// The rules `shadow scan-group` checks by default
+pub fn default_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(UnloggedTransferRule),
+        Box::new(UnguardedAdminFunctionRule),
+        Box::new(UnindexedEventParamRule),
+    ]
+}
+
+/// Checks a contract's ABI against every rule, most severe findings first
+pub fn scan_abi(
+    address: Address,
+    chain_id: u64,
+    abi: &JsonAbi,
+    rules: &[Box<dyn Rule>],
+) -> Vec<Finding> {
+    let mut findings = rules
+        .iter()
+        .flat_map(|rule| {
+            rule.check(abi).into_iter().map(|rule_match| Finding {
+                address,
+                chain_id,
+                item: rule_match.item,
+                rule: rule.name(),
+                severity: rule.severity(),
+                message: rule_match.message,
+            })
+        })
+        .collect::<Vec<_>>();
+    findings.sort_by(|a, b| b.severity.cmp(&a.severity));
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_abi() {
+        let abi = JsonAbi::parse([
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function approve(address spender, uint256 amount) returns (bool)",
+            "function setFee(uint256 fee)",
+            "function balanceOf(address owner) view returns (uint256)",
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "event FeeSet(address setter, uint256 fee)",
+        ])
+        .unwrap();
+        let findings = scan_abi(Address::ZERO, 1, &abi, &default_rules());
+
+        let summary = findings
+            .iter()
+            .map(|finding| (finding.rule, finding.item.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("unguarded-admin-function", "setFee(uint256)"),
+                ("unlogged-transfer", "approve(address,uint256)"),
+                ("unindexed-event-param", "FeeSet(address,uint256)"),
+            ]
+        );
+
+        // an access control event clears the admin function finding
+        let abi = JsonAbi::parse([
+            "function setFee(uint256 fee)",
+            "event OwnershipTransferred(address indexed previousOwner, address indexed newOwner)",
+        ])
+        .unwrap();
+        assert!(UnguardedAdminFunctionRule.check(&abi).is_empty());
+    }
+}
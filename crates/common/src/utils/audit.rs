@@ -0,0 +1,79 @@
+#![allow(deprecated)]
+use std::{
+    env::home_dir,
+    fs::OpenOptions,
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+};
+
+use alloy::primitives::Address;
+use chrono::{DateTime, Utc};
+use eyre::{eyre, OptionExt, Result};
+use serde::{Deserialize, Serialize};
+
+/// A single push recorded in `~/.shadow/audit.jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// When the contract group was pushed
+    pub timestamp: DateTime<Utc>,
+    /// The display name of the contract group
+    pub display_name: String,
+    /// The IPFS CID the contract group was pinned at
+    pub cid: String,
+    /// The hash of the EAS attestation transaction, if one was broadcast
+    pub attestation_tx: Option<String>,
+    /// The chain the attestation was made on
+    pub chain: String,
+    /// The address of the creator of the contract group
+    pub creator: Option<Address>,
+    /// The addresses of the contracts which were compiled and pushed
+    pub contracts: Vec<Address>,
+    /// The IPFS CID of the push this entry replaces, if it was made with `shadow bump`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supersedes: Option<String>,
+}
+
+/// Returns the path to `~/.shadow/audit.jsonl`
+pub fn audit_log_path() -> Result<PathBuf> {
+    let mut audit_log_path = home_dir().ok_or_eyre("failed to get home directory")?;
+    audit_log_path.push(".shadow");
+    audit_log_path.push("audit.jsonl");
+
+    Ok(audit_log_path)
+}
+
+/// Appends an entry to the audit log. Existing entries are never modified.
+pub fn append_audit_entry(entry: &AuditEntry) -> Result<()> {
+    let audit_log_path = audit_log_path()?;
+
+    // build the directory if it doesn't exist
+    if let Some(parent) = audit_log_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(audit_log_path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+
+    Ok(())
+}
+
+/// Loads all entries from the audit log, oldest first. Returns an empty log if it doesn't exist
+/// yet.
+pub fn load_audit_log() -> Result<Vec<AuditEntry>> {
+    let audit_log_path = audit_log_path()?;
+    if !audit_log_path.exists() {
+        return Ok(vec![]);
+    }
+
+    let file = std::fs::File::open(&audit_log_path)?;
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.as_ref().map_or(true, |line| !line.trim().is_empty()))
+        .map(|(i, line)| {
+            serde_json::from_str(&line?).map_err(|e| {
+                eyre!("invalid entry on line {} of {}: {}", i + 1, audit_log_path.display(), e)
+            })
+        })
+        .collect()
+}
@@ -1,7 +1,7 @@
 use crate::{
     db::JsonRpcDatabase,
     env::{get_chain_spec, ReplayBlockEnv},
-    ShadowContractInfo, ShadowContractSettings,
+    ShadowContractInfo, ShadowContractSettings, ShadowContractSource,
 };
 use alloy::{
     hex::FromHex,
@@ -23,7 +23,9 @@ use serde_json::Value;
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
+    sync::Arc,
 };
+use tokio::sync::Semaphore;
 use tracing::{error, info};
 
 /// Compiler Output
@@ -38,20 +40,67 @@ pub struct CompilerOutput {
     pub bytecode: Bytes,
 }
 
-/// Compile a contract using the original settings.
+/// A single error reported by the compiler while building a contract
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CompilerError {
+    /// The source file the error is in, relative to the contract root
+    pub file: String,
+    /// The 1-indexed line the error starts on, or 0 if unknown
+    pub line: u32,
+    /// The 1-indexed column the error starts on, or 0 if unknown
+    pub column: u32,
+    /// The error message
+    pub message: String,
+    /// The compiler's error code, e.g. `2314`
+    pub error_code: String,
+}
+
+impl std::fmt::Display for CompilerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "error[{}]: {}\n  --> {}:{}:{}",
+            self.error_code, self.message, self.file, self.line, self.column
+        )
+    }
+}
+
+/// The errors reported by the compiler when a build fails. Returned by [`compile`], and may be
+/// recovered from the [`eyre::Report`] with `downcast_ref`.
+#[derive(Debug, Clone)]
+pub struct CompilerErrors(pub Vec<CompilerError>);
+
+impl std::fmt::Display for CompilerErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "build failed with {} errors", self.0.len())?;
+        for error in &self.0 {
+            write!(f, "\n{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CompilerErrors {}
+
+/// Compile a contract using the original settings. RPC requests made while simulating the
+/// deployment are limited by `request_limiter`, see [`JsonRpcDatabase::request_limiter`].
 /// TODO @jon-becker: Ensure vyper is supported
 pub async fn compile(
     rpc_url: &str,
     root: &PathBuf,
     settings: &ShadowContractSettings,
     metadata: &ShadowContractInfo,
+    request_limiter: Arc<Semaphore>,
 ) -> Result<CompilerOutput> {
     // create the artifact directory
     let build_artifact_dir = root.join("out");
     std::fs::create_dir_all(&build_artifact_dir)?;
 
-    // compile via forge
-    compile_contract(root).map_err(|e| eyre!("failed to compile: {}", e))?;
+    // compile via forge, on a blocking thread so that contracts compiled in parallel don't stall
+    // the runtime. build failures are returned as-is, so callers can recover the structured
+    // [`CompilerErrors`]
+    let forge_root = root.clone();
+    tokio::task::spawn_blocking(move || compile_contract(&forge_root)).await??;
 
     // find the contract artifact in the build directory
     let (contract_artifact, artifact_path) =
@@ -83,13 +132,15 @@ pub async fn compile(
         .get_block_by_number(block_number.into(), true)
         .await?
         .ok_or_eyre("block not found")?;
-    let replay_block_env = ReplayBlockEnv::from(block);
+    let replay_block_env = ReplayBlockEnv::from(block).with_chain_id(chain_id);
     let db = JsonRpcDatabase::try_new(
         replay_block_env.clone().into(),
+        chain_id,
         provider,
         HashMap::new(),
         HashMap::new(),
-    )?;
+    )?
+    .with_request_limiter(request_limiter);
 
     info!("constructing runtime bytecode");
     let initcode = construct_init_code(&contract_artifact, &settings.constructor_arguments)
@@ -118,6 +169,71 @@ pub async fn compile(
     Ok(compiler_output)
 }
 
+/// A remote compilation service, used by [`compile_remote`] instead of a local `forge`
+/// installation. There's no default service, so its URL must be configured.
+///
+/// Requests are `POST`ed as JSON, authenticated with an `Authorization: Bearer <api key>` header:
+/// `{ "info": ShadowContractInfo, "settings": ShadowContractSettings, "source":
+/// ShadowContractSource }`. The service must respond with a JSON [`CompilerOutput`], whose
+/// `bytecode` is the runtime bytecode produced by replaying the contract's original deployment.
+#[derive(Debug, Clone)]
+pub struct RemoteCompiler {
+    /// The URL which compilation requests are sent to
+    pub url: String,
+    /// The API key which requests are authenticated with
+    pub api_key: String,
+}
+
+impl RemoteCompiler {
+    /// Builds a remote compiler from the `--remote-compiler-url` and `--logs-xyz-api-key` values,
+    /// which default to the `remote_compiler_url` and `logs_xyz_api_key` config values.
+    pub fn new(url: Option<&str>, api_key: Option<&str>) -> Result<Self> {
+        let url = url.ok_or_eyre(
+            "remote compilation requires a compiler URL. Pass `--remote-compiler-url` or set `remote_compiler_url` with `shadow config`",
+        )?;
+        let api_key = api_key.ok_or_eyre(
+            "remote compilation requires an API key. Pass `--logs-xyz-api-key` or set `logs_xyz_api_key` with `shadow config`",
+        )?;
+
+        Ok(Self { url: url.to_string(), api_key: api_key.to_string() })
+    }
+}
+
+/// Compile a contract using the original settings via a [`RemoteCompiler`], rather than a local
+/// `forge` installation. The output is written to `out/` in the same format as [`compile`].
+pub async fn compile_remote(
+    remote: &RemoteCompiler,
+    root: &PathBuf,
+    settings: &ShadowContractSettings,
+    metadata: &ShadowContractInfo,
+) -> Result<CompilerOutput> {
+    // create the artifact directory
+    let build_artifact_dir = root.join("out");
+    std::fs::create_dir_all(&build_artifact_dir)?;
+
+    let source = ShadowContractSource::from_path(&root.join("src"), settings)?;
+
+    info!("compiling {} via {}", metadata.name, remote.url);
+    let response = reqwest::Client::new()
+        .post(&remote.url)
+        .bearer_auth(&remote.api_key)
+        .json(&serde_json::json!({ "info": metadata, "settings": settings, "source": source }))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(eyre!("remote compilation failed: {}", response.text().await?));
+    }
+    let compiler_output: CompilerOutput = response.json().await?;
+
+    // serialize and write the shadow artifact
+    std::fs::write(
+        build_artifact_dir.join(format!("{}.shadow.json", metadata.name)),
+        serde_json::to_string_pretty(&compiler_output)?,
+    )?;
+
+    Ok(compiler_output)
+}
+
 /// Construct the init code for the contract by concatenating the new contract
 /// bytecode with the original constructor arguments
 fn construct_init_code(
@@ -140,17 +256,68 @@ fn construct_init_code(
     Ok(Bytes::from(init_code))
 }
 
-/// Compiles all contracts at the given path by invoking the forge build command
+/// Compiles a contract's source with the given settings in a temporary directory, and returns
+/// the ABI of the contract named `contract_name`. Unlike [`compile`], the deployment isn't
+/// simulated, so this works offline, e.g. to get the ABI of a contract's `original.json`.
+pub fn compile_abi(
+    source: &ShadowContractSource,
+    settings: &ShadowContractSettings,
+    contract_name: &str,
+) -> Result<JsonAbi> {
+    let root = tempdir::TempDir::new("shadow-abi")?;
+    let root_path = root.path().to_path_buf();
+    source.write_source_to(&root_path)?;
+    settings.generate_config(&root_path)?;
+    compile_contract(&root_path)?;
+
+    let (artifact, _) = find_contract_artifact(&root_path.join("out"), contract_name)
+        .map_err(|e| eyre!("contract artifact not found: {}", e))?;
+
+    Ok(serde_json::from_value(artifact["abi"].clone())?)
+}
+
+/// Checks that the contracts at the given path compile, without emitting artifacts into the
+/// project. Build output is written to a temporary directory and discarded.
+pub fn validate_solidity_syntax(root: &Path) -> Result<()> {
+    let out_dir = tempdir::TempDir::new("shadow-check")?;
+    let output = std::process::Command::new("forge")
+        .arg("build")
+        .arg("--no-cache")
+        .arg("--out")
+        .arg(out_dir.path())
+        .current_dir(root)
+        .stderr(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        error!("{}", stderr);
+
+        eyre::bail!("syntax check failed");
+    }
+
+    Ok(())
+}
+
+/// Compiles all contracts at the given path by invoking the forge build command. If the build
+/// fails, the compiler's errors are parsed from `forge build --json` output and returned as
+/// [`CompilerErrors`], falling back to the raw stderr if they can't be parsed.
 fn compile_contract(root: &PathBuf) -> Result<()> {
     let output = std::process::Command::new("forge")
         .arg("build")
         .arg("--force")
         .arg("--no-cache")
+        .arg("--json")
         .current_dir(root)
         .stderr(std::process::Stdio::piped())
         .stdout(std::process::Stdio::piped())
         .output()?;
     if !output.status.success() {
+        let errors = parse_compiler_errors(root, &output.stdout);
+        if !errors.is_empty() {
+            return Err(CompilerErrors(errors).into());
+        }
+
         let stderr = String::from_utf8_lossy(&output.stderr);
         error!("{}", stderr);
 
@@ -160,8 +327,51 @@ fn compile_contract(root: &PathBuf) -> Result<()> {
     Ok(())
 }
 
-/// Find the contract artifact in the build artifact directory
-fn find_contract_artifact(
+/// Parses the errors from `forge build --json` output. Warnings are ignored.
+fn parse_compiler_errors(root: &Path, stdout: &[u8]) -> Vec<CompilerError> {
+    let Ok(output) = serde_json::from_slice::<Value>(stdout) else {
+        return vec![];
+    };
+
+    output["errors"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|error| error["severity"].as_str() == Some("error"))
+        .map(|error| {
+            let file = error["sourceLocation"]["file"].as_str().unwrap_or_default().to_string();
+            let (line, column) = error["sourceLocation"]["start"]
+                .as_u64()
+                .and_then(|start| {
+                    let source = std::fs::read_to_string(root.join(&file)).ok()?;
+                    line_and_column(&source, start as usize)
+                })
+                .unwrap_or_default();
+
+            CompilerError {
+                file,
+                line,
+                column,
+                message: error["message"].as_str().unwrap_or_default().to_string(),
+                error_code: error["errorCode"].as_str().unwrap_or_default().to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Converts a byte offset into `source` to a 1-indexed line and column. Returns `None` if the
+/// offset is out of range.
+pub fn line_and_column(source: &str, offset: usize) -> Option<(u32, u32)> {
+    let before = source.get(..offset)?;
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+
+    Some((line.try_into().ok()?, column.try_into().ok()?))
+}
+
+/// Find the contract artifact in the build artifact directory, returning it and its path. The
+/// artifact whose file name is closest to `contract_name` is used.
+pub fn find_contract_artifact(
     build_artifact_dir: &Path,
     contract_name: &str,
 ) -> Result<(Value, PathBuf)> {
@@ -205,7 +415,7 @@ fn build_deployment_env(
 ) -> Box<Env> {
     let mut cfg_env = revm::primitives::CfgEnv::default();
     cfg_env.limit_contract_code_size = Some(usize::MAX);
-    cfg_env.chain_id = 1u64;
+    cfg_env.chain_id = replay_block_env.chain_id();
     cfg_env.perf_analyse_created_bytecodes = AnalysisKind::Raw;
     Box::new(Env {
         cfg: cfg_env,
@@ -227,3 +437,17 @@ fn build_deployment_env(
         },
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_and_column() {
+        let source = "pragma solidity ^0.8.0;\ncontract A {\n    uint x;\n}\n";
+        assert_eq!(line_and_column(source, 0), Some((1, 1)));
+        assert_eq!(line_and_column(source, 24), Some((2, 1)));
+        assert_eq!(line_and_column(source, 41), Some((3, 5)));
+        assert_eq!(line_and_column(source, source.len() + 1), None);
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     collections::{hash_map::Entry, HashMap},
     sync::Arc,
+    time::Duration,
 };
 
 use alloy::{
@@ -14,12 +15,22 @@ use foundry_evm::backend::{BlockchainDb, BlockchainDbMeta, SharedBackend};
 use parking_lot::RwLock;
 use revm::{
     db::{AccountState, DbAccount},
-    primitives::{AccountInfo, Address, BlockEnv, Bytecode, B256, U256},
-    Database,
+    primitives::{Account, AccountInfo, Address, BlockEnv, Bytecode, B256, U256},
+    Database, DatabaseCommit,
 };
+use tokio::sync::{Semaphore, SemaphorePermit, TryAcquireError};
 use tracing::trace;
 
-use super::state::PartialBlockStateDiff;
+use super::{
+    rpc_cache::{CachedAccount, RpcCache},
+    state::PartialBlockStateDiff,
+};
+
+/// The default number of RPC requests a [`JsonRpcDatabase`] may have in flight at once
+pub const DEFAULT_PARALLEL_REQUESTS: usize = 10;
+
+/// How long to wait before retrying when every request permit is taken
+const PERMIT_RETRY_INTERVAL: Duration = Duration::from_millis(5);
 
 /// An ephemeral, in-memory database implementation
 /// which allows for overriding account bytecode.
@@ -35,17 +46,23 @@ pub struct JsonRpcDatabase {
     block_hashes: Arc<RwLock<HashMap<u64, B256>>>,
     /// Remote database
     remote_db: SharedBackend,
+    /// Limits the number of in-flight requests to the remote database. May be shared between
+    /// databases to limit their combined requests.
+    request_limiter: Arc<Semaphore>,
+    /// Values fetched from the remote database, which may be read from or written to disk
+    rpc_cache: Arc<RwLock<RpcCache>>,
 }
 
 impl JsonRpcDatabase {
     /// Create a new [`JsonRpcDatabase`] instance.
     pub fn try_new(
         block_env: BlockEnv,
+        chain_id: u64,
         provider: RootProvider<Http<Client>, AnyNetwork>,
         overrides: HashMap<Address, Bytecode>,
         partial_state: HashMap<Address, PartialBlockStateDiff>,
     ) -> Result<Self> {
-        let remote_db = shared_backend(block_env, provider)?;
+        let remote_db = shared_backend(block_env, chain_id, provider)?;
 
         Ok(Self {
             remote_db,
@@ -54,13 +71,91 @@ impl JsonRpcDatabase {
             accounts: Default::default(),
             contracts: Default::default(),
             block_hashes: Default::default(),
+            request_limiter: Self::request_limiter(DEFAULT_PARALLEL_REQUESTS),
+            rpc_cache: Default::default(),
         })
     }
 
+    /// Reads values from, and writes fetched values to, the given RPC cache, depending on its
+    /// mode. Fetched values are only written to disk by [`Self::save_rpc_cache`].
+    pub fn with_rpc_cache(mut self, rpc_cache: RpcCache) -> Self {
+        self.rpc_cache = Arc::new(RwLock::new(rpc_cache));
+        self
+    }
+
+    /// Writes the values fetched from the remote database to the RPC cache, if it's being
+    /// written
+    pub fn save_rpc_cache(&self) -> Result<()> {
+        self.rpc_cache.write().save()
+    }
+
+    /// Creates a request limiter which allows `parallel_requests` in-flight RPC requests, to be
+    /// shared between databases with [`Self::with_request_limiter`].
+    pub fn request_limiter(parallel_requests: usize) -> Arc<Semaphore> {
+        Arc::new(Semaphore::new(parallel_requests.max(1)))
+    }
+
+    /// Limits requests to the remote database with the given limiter. Requests beyond its limit
+    /// are queued until a permit is released.
+    pub fn with_request_limiter(mut self, request_limiter: Arc<Semaphore>) -> Self {
+        self.request_limiter = request_limiter;
+        self
+    }
+
+    /// Waits for a permit to make a request to the remote database. The EVM queries the
+    /// database synchronously, so the semaphore is polled rather than awaited.
+    fn request_permit(&self) -> Result<SemaphorePermit<'_>> {
+        loop {
+            match self.request_limiter.try_acquire() {
+                Ok(permit) => return Ok(permit),
+                Err(TryAcquireError::NoPermits) => std::thread::sleep(PERMIT_RETRY_INTERVAL),
+                Err(TryAcquireError::Closed) => bail!("RPC request limiter was closed"),
+            }
+        }
+    }
+
     /// Pop the partial state for the given address.
     pub fn partial_state(&mut self, address: Address) -> Option<PartialBlockStateDiff> {
         self.partial_state.remove(&address)
     }
+
+    /// Fetches an account from the RPC cache or the remote database
+    fn remote_basic(&self, address: Address) -> Result<Option<CachedAccount>> {
+        let cached = self.rpc_cache.read().account(&address)?;
+        if let Some(account) = cached {
+            return Ok(account);
+        }
+
+        let account = {
+            let _permit = self.request_permit()?;
+            foundry_evm::revm::DatabaseRef::basic_ref(&self.remote_db, address)?
+        }
+        .map(|info| CachedAccount {
+            balance: info.balance,
+            nonce: info.nonce,
+            code_hash: info.code_hash,
+            code: info.code.map(|code| code.bytes()),
+        });
+        self.rpc_cache.write().insert_account(address, account.clone());
+
+        Ok(account)
+    }
+
+    /// Fetches a storage slot from the RPC cache or the remote database
+    fn remote_storage(&self, address: Address, index: U256) -> Result<U256> {
+        let cached = self.rpc_cache.read().storage(&address, &index)?;
+        if let Some(value) = cached {
+            return Ok(value);
+        }
+
+        let value = {
+            let _permit = self.request_permit()?;
+            foundry_evm::revm::DatabaseRef::storage_ref(&self.remote_db, address, index)?
+        };
+        self.rpc_cache.write().insert_storage(address, index, value);
+
+        Ok(value)
+    }
 }
 
 impl Database for JsonRpcDatabase {
@@ -82,23 +177,27 @@ impl Database for JsonRpcDatabase {
         trace!(address = format!("{:?}", address), "missing account");
 
         // fetch the account from the remote database
-        let account = foundry_evm::revm::DatabaseRef::basic_ref(&self.remote_db, address)?
-            .map(|info| DbAccount {
-                info: AccountInfo {
-                    balance: partial_state.as_ref().and_then(|s| s.balance).unwrap_or(info.balance),
-                    nonce: partial_state
-                        .as_ref()
-                        .and_then(|s| s.nonce.map(|n| n.try_into().expect("U64 -> u64")))
-                        .unwrap_or(info.nonce),
-                    code_hash: info.code_hash,
-                    code: self
-                        .overrides
-                        .get(&address)
-                        .cloned()
-                        .or_else(|| info.code.map(|code| Bytecode::new_raw(code.bytes()))),
-                },
-                storage: partial_state.as_ref().map(|s| s.storage.clone()).unwrap_or_default(),
-                ..Default::default()
+        let account = self
+            .remote_basic(address)?
+            .map(|info| {
+                let mut account = DbAccount {
+                    info: AccountInfo {
+                        balance: info.balance,
+                        nonce: info.nonce,
+                        code_hash: info.code_hash,
+                        code: info.code.map(Bytecode::new_raw),
+                    },
+                    ..Default::default()
+                };
+                if let Some(partial_state) = &partial_state {
+                    partial_state.apply_to(&mut account.info, &mut account.storage);
+                }
+
+                // shadow bytecode takes precedence over code deployed earlier in the block
+                if let Some(code) = self.overrides.get(&address) {
+                    account.info.code = Some(code.clone());
+                }
+                account
             })
             .unwrap_or_else(DbAccount::new_not_existing);
 
@@ -124,13 +223,25 @@ impl Database for JsonRpcDatabase {
 
         trace!(code_hash = format!("{:?}", code_hash), "missing contract");
 
-        let contract =
-            foundry_evm::revm::DatabaseRef::code_by_hash_ref(&self.remote_db, code_hash)?;
+        // the cache's lock is released before fetched code is written to it
+        let cached = self.rpc_cache.read().code(&code_hash)?;
+        let contract = match cached {
+            Some(contract) => contract,
+            None => {
+                let contract = {
+                    let _permit = self.request_permit()?;
+                    foundry_evm::revm::DatabaseRef::code_by_hash_ref(&self.remote_db, code_hash)?
+                        .bytes()
+                };
+                self.rpc_cache.write().insert_code(code_hash, contract.clone());
+                contract
+            }
+        };
 
         // store the contract in the local database
-        self.contracts.write().insert(code_hash, Bytecode::new_raw(contract.bytes()));
+        self.contracts.write().insert(code_hash, Bytecode::new_raw(contract.clone()));
 
-        Ok(Bytecode::new_raw(contract.bytes()))
+        Ok(Bytecode::new_raw(contract))
     }
 
     /// Get storage value of address at index.
@@ -168,33 +279,30 @@ impl Database for JsonRpcDatabase {
                 );
 
                 // fetch the account from the remote db
-                let account_info =
-                    foundry_evm::revm::DatabaseRef::basic_ref(&self.remote_db, address)?;
-                if account_info.is_none() {
+                let Some(account_info) = self.remote_basic(address)? else {
                     entry.insert(DbAccount::default());
                     return Ok(U256::ZERO);
-                }
+                };
 
-                // fetch the storage slot from the remote db
-                let value =
-                    foundry_evm::revm::DatabaseRef::storage_ref(&self.remote_db, address, index)?;
-                let account_info = account_info.expect("impossible case: we should have exited");
                 let mut account: DbAccount = DbAccount {
                     info: AccountInfo {
-                        balance: partial_state
-                            .as_ref()
-                            .and_then(|s| s.balance)
-                            .unwrap_or(account_info.balance),
-                        nonce: partial_state
-                            .as_ref()
-                            .and_then(|s| s.nonce.map(|n| n.try_into().expect("U64 -> u64")))
-                            .unwrap_or(account_info.nonce),
+                        balance: account_info.balance,
+                        nonce: account_info.nonce,
                         code_hash: account_info.code_hash,
-                        code: account_info.code.map(|code| Bytecode::new_raw(code.bytes())),
+                        code: account_info.code.map(Bytecode::new_raw),
                     },
-                    storage: partial_state.as_ref().map(|s| s.storage.clone()).unwrap_or_default(),
+                    storage: HashMap::new(),
                     account_state: AccountState::Touched,
                 };
+                if let Some(partial_state) = &partial_state {
+                    partial_state.apply_to(&mut account.info, &mut account.storage);
+                }
+
+                // fetch the storage slot from the remote db, unless it was set earlier in the block
+                let value = match account.storage.get(&index) {
+                    Some(value) => *value,
+                    None => self.remote_storage(address, index)?,
+                };
                 account.storage.insert(index, value);
 
                 // write the account
@@ -211,8 +319,7 @@ impl Database for JsonRpcDatabase {
                 );
 
                 // fetch the storage slot from the remote db
-                let value =
-                    foundry_evm::revm::DatabaseRef::storage_ref(&self.remote_db, address, index)?;
+                let value = self.remote_storage(address, index)?;
 
                 // write the storage slot to the account
                 entry.into_mut().storage.insert(index, value);
@@ -232,7 +339,18 @@ impl Database for JsonRpcDatabase {
         trace!(number = number, "missing block hash");
 
         // fetch the block hash from the remote database
-        let hash = foundry_evm::revm::DatabaseRef::block_hash_ref(&self.remote_db, number)?;
+        let cached = self.rpc_cache.read().block_hash(number)?;
+        let hash = match cached {
+            Some(hash) => hash,
+            None => {
+                let hash = {
+                    let _permit = self.request_permit()?;
+                    foundry_evm::revm::DatabaseRef::block_hash_ref(&self.remote_db, number)?
+                };
+                self.rpc_cache.write().insert_block_hash(number, hash);
+                hash
+            }
+        };
 
         // store the block hash in the local database
         self.block_hashes.write().insert(number, hash);
@@ -241,8 +359,51 @@ impl Database for JsonRpcDatabase {
     }
 }
 
+impl DatabaseCommit for JsonRpcDatabase {
+    /// Writes the state changes of an executed transaction to the local database, so that
+    /// transactions executed afterwards see them. Mirrors [`revm::db::CacheDB`].
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        let mut accounts = self.accounts.write();
+        for (address, account) in changes {
+            if !account.is_touched() {
+                continue;
+            }
+            if account.is_selfdestructed() {
+                let db_account = accounts.entry(address).or_default();
+                db_account.storage.clear();
+                db_account.account_state = AccountState::NotExisting;
+                db_account.info = AccountInfo::default();
+                continue;
+            }
+
+            if let Some(code) = account.info.code.as_ref().filter(|code| !code.is_empty()) {
+                self.contracts
+                    .write()
+                    .entry(account.info.code_hash)
+                    .or_insert_with(|| code.clone());
+            }
+
+            let is_newly_created = account.is_created();
+            let db_account = accounts.entry(address).or_default();
+            db_account.info = account.info;
+            db_account.account_state = if is_newly_created {
+                db_account.storage.clear();
+                AccountState::StorageCleared
+            } else if db_account.account_state.is_storage_cleared() {
+                AccountState::StorageCleared
+            } else {
+                AccountState::Touched
+            };
+            db_account
+                .storage
+                .extend(account.storage.into_iter().map(|(key, slot)| (key, slot.present_value())));
+        }
+    }
+}
+
 fn shared_backend(
     block_env: BlockEnv,
+    chain_id: u64,
     provider: RootProvider<Http<Client>, AnyNetwork>,
 ) -> Result<SharedBackend> {
     // we need to mine the current block, so subtract 1
@@ -255,7 +416,7 @@ fn shared_backend(
     cfg_env.limit_contract_code_size = Some(usize::MAX);
     cfg_env.perf_analyse_created_bytecodes = foundry_evm::revm::primitives::AnalysisKind::Raw;
     cfg_env.disable_eip3607 = true;
-    cfg_env.chain_id = 1u64;
+    cfg_env.chain_id = chain_id;
 
     let meta = BlockchainDbMeta {
         cfg_env,
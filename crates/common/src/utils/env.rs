@@ -1,10 +1,31 @@
 use alloy::rpc::types::Block;
-use revm::primitives::{BlobExcessGasAndPrice, BlockEnv, SpecId, B256, U256};
+use revm::primitives::{Address, BlobExcessGasAndPrice, BlockEnv, SpecId, B256, U256};
 
-/// A wrapper around [`BlockEnv`]
+/// A wrapper around [`BlockEnv`], along with the chain ID of the block being replayed
 #[derive(Clone, Debug)]
 pub struct ReplayBlockEnv {
     inner: BlockEnv,
+    chain_id: u64,
+}
+
+impl ReplayBlockEnv {
+    /// Sets the chain ID of the block being replayed. Defaults to mainnet.
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Overrides the block's coinbase, e.g. to replay the block as if it were built by a
+    /// different block builder
+    pub fn with_coinbase(mut self, coinbase: Address) -> Self {
+        self.inner.coinbase = coinbase;
+        self
+    }
+
+    /// Returns the chain ID of the block being replayed
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
 }
 
 impl From<ReplayBlockEnv> for BlockEnv {
@@ -33,6 +54,7 @@ impl<T> From<Block<T>> for ReplayBlockEnv {
                     }
                 }),
             },
+            chain_id: 1,
         }
     }
 }
@@ -45,25 +67,52 @@ pub fn get_chain_spec(h: &u64, chain_id: &u64) -> SpecId {
     }
 }
 
+/// The first block of each Ethereum mainnet hardfork, in order. Ranges taken from
+/// https://github.com/ethereum/execution-specs
+pub const ETH_SPEC_TRANSITIONS: [(u64, SpecId); 16] = [
+    (0, SpecId::FRONTIER),
+    (200000, SpecId::HOMESTEAD),
+    (1150000, SpecId::DAO_FORK),
+    (1920000, SpecId::TANGERINE),
+    (2463000, SpecId::SPURIOUS_DRAGON),
+    (2675000, SpecId::BYZANTIUM),
+    (4370000, SpecId::PETERSBURG), // CONSTANTINOPLE
+    (7280000, SpecId::ISTANBUL),
+    (9200000, SpecId::MUIR_GLACIER),
+    (12244000, SpecId::BERLIN),
+    (12965000, SpecId::LONDON),
+    (13773000, SpecId::ARROW_GLACIER),
+    (15050000, SpecId::GRAY_GLACIER),
+    (15537394, SpecId::MERGE), // PARIS
+    (17034870, SpecId::SHANGHAI),
+    (19426587, SpecId::CANCUN), // LATEST
+];
+
 /// Given block height, get the [`SpecId`] at that block height
 pub fn get_eth_chain_spec(h: &u64) -> SpecId {
-    // ranges taken from https://github.com/ethereum/execution-specs
-    match h {
-        0..=199999 => SpecId::FRONTIER,
-        200000..=1149999 => SpecId::HOMESTEAD,
-        1150000..=1919999 => SpecId::DAO_FORK,
-        1920000..=2462999 => SpecId::TANGERINE,
-        2463000..=2674999 => SpecId::SPURIOUS_DRAGON,
-        2675000..=4369999 => SpecId::BYZANTIUM,
-        4370000..=7279999 => SpecId::PETERSBURG, // CONSTANTINOPLE
-        7280000..=9199999 => SpecId::ISTANBUL,
-        9200000..=12243999 => SpecId::MUIR_GLACIER,
-        12244000..=12964999 => SpecId::BERLIN,
-        12965000..=13772999 => SpecId::LONDON,
-        13773000..=15049999 => SpecId::ARROW_GLACIER,
-        15050000..=15537393 => SpecId::GRAY_GLACIER,
-        15537394..=17034869 => SpecId::MERGE, // PARIS
-        17034870..=19426586 => SpecId::SHANGHAI,
-        19426587..=u64::MAX => SpecId::CANCUN, // LATEST
+    get_eth_chain_spec_range(h).0
+}
+
+/// Given block height, get the [`SpecId`] at that block height, along with the first and last
+/// blocks it's active for. The last block is `None` for the latest spec.
+pub fn get_eth_chain_spec_range(h: &u64) -> (SpecId, u64, Option<u64>) {
+    let index = ETH_SPEC_TRANSITIONS.partition_point(|(start, _)| start <= h) - 1;
+    let (start, spec) = ETH_SPEC_TRANSITIONS[index];
+    let end = ETH_SPEC_TRANSITIONS.get(index + 1).map(|(next, _)| next - 1);
+    (spec, start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_eth_chain_spec_range() {
+        assert_eq!(get_eth_chain_spec_range(&0), (SpecId::FRONTIER, 0, Some(199999)));
+        assert_eq!(get_eth_chain_spec_range(&199999), (SpecId::FRONTIER, 0, Some(199999)));
+        assert_eq!(get_eth_chain_spec_range(&200000), (SpecId::HOMESTEAD, 200000, Some(1149999)));
+        assert_eq!(get_eth_chain_spec_range(&15537394), (SpecId::MERGE, 15537394, Some(17034869)));
+        assert_eq!(get_eth_chain_spec_range(&u64::MAX), (SpecId::CANCUN, 19426587, None));
+        assert_eq!(get_eth_chain_spec(&17034870), SpecId::SHANGHAI);
     }
 }
@@ -1,7 +1,5 @@
-use std::path::PathBuf;
-
 use alloy::{
-    dyn_abi::DecodedEvent,
+    dyn_abi::{DecodedEvent, EventExt},
     json_abi::{Event, JsonAbi},
 };
 use eyre::Result;
@@ -9,24 +7,32 @@ use revm::primitives::{Log, B256};
 
 /// Wrapper around a decoded event
 #[derive(Debug, Clone)]
-pub(crate) struct FullDecodedEvent {
-    pub(crate) inner: DecodedEvent,
-    pub(crate) event: Event,
-    pub(crate) log: Log,
-    pub(crate) transaction_log_index: usize,
+pub struct FullDecodedEvent {
+    /// The decoded event
+    pub inner: DecodedEvent,
+    /// The ABI of the event
+    pub event: Event,
+    /// The raw log
+    pub log: Log,
+    /// The index of the log within its transaction
+    pub transaction_log_index: usize,
 }
 
 /// Wrapper around a raw log
 #[derive(Debug, Clone)]
-pub(crate) struct FullRawEvent {
-    pub(crate) log: Log,
-    pub(crate) transaction_log_index: usize,
+pub struct FullRawEvent {
+    /// The raw log
+    pub log: Log,
+    /// The index of the log within its transaction
+    pub transaction_log_index: usize,
 }
 
 /// Wrapper enum for both raw and decoded events
 #[derive(Debug, Clone)]
-pub(crate) enum RawOrDecodedEvent {
+pub enum RawOrDecodedEvent {
+    /// A log which could not be decoded
     Raw(FullRawEvent),
+    /// A log which was decoded
     Decoded(FullDecodedEvent),
 }
 
@@ -46,7 +52,11 @@ Data                  : 0x{}
 "#,
                 log.transaction_log_index,
                 log.log.address,
-                log.log.topics()[0],
+                log.log
+                    .topics()
+                    .first()
+                    .map(|t| t.to_string())
+                    .unwrap_or_else(|| String::from("N/A")),
                 log.log
                     .topics()
                     .get(1)
@@ -123,7 +133,7 @@ impl std::fmt::Display for FullDecodedEvent {
 
 /// Try to get the event ABI(s) for the given event selector. Returns `None` if no event ABI is
 /// found. Note: there may be multiple matching event signatures, so this function returns a Vec.
-pub(crate) fn try_get_event_abi(selector: &B256, abis: &[JsonAbi]) -> Vec<Event> {
+pub fn try_get_event_abi(selector: &B256, abis: &[JsonAbi]) -> Vec<Event> {
     abis.iter()
         .flat_map(|abi| abi.events.iter())
         .flat_map(|(_, events)| events.iter())
@@ -132,18 +142,33 @@ pub(crate) fn try_get_event_abi(selector: &B256, abis: &[JsonAbi]) -> Vec<Event>
         .collect::<Vec<_>>()
 }
 
-pub(crate) fn get_abis(artifact_path: &PathBuf) -> Result<Vec<JsonAbi>> {
-    // walk the artifact_path recursively and collect all `.hex` files
-    walkdir::WalkDir::new(artifact_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| e.path().file_name().map(|f| f == "abi.json").unwrap_or(false))
-        .map(|e| {
-            // parse the json file
-            let json = std::fs::read_to_string(e.path()).unwrap();
-            let abi = serde_json::from_str::<JsonAbi>(&json)?;
-            Ok::<_, eyre::Report>(abi)
+/// Decodes the given logs using the provided ABIs. Logs which can't be decoded, including
+/// anonymous logs, are returned as [`RawOrDecodedEvent::Raw`].
+pub fn decode_logs(logs: &[Log], abis: &[JsonAbi]) -> Result<Vec<RawOrDecodedEvent>> {
+    logs.iter()
+        .enumerate()
+        .map(|(transaction_log_index, log)| {
+            // anonymous logs have no selector, so they're left undecoded
+            let events = match log.topics().first() {
+                Some(event_selector) => try_get_event_abi(event_selector, abis),
+                None => vec![],
+            };
+
+            for event in events {
+                if let Ok(decoded) = event.decode_log(log, true) {
+                    return Ok(RawOrDecodedEvent::Decoded(FullDecodedEvent {
+                        inner: decoded,
+                        event,
+                        log: log.clone(),
+                        transaction_log_index,
+                    }));
+                }
+            }
+
+            Ok::<_, eyre::Report>(RawOrDecodedEvent::Raw(FullRawEvent {
+                log: log.clone(),
+                transaction_log_index,
+            }))
         })
-        .collect::<Result<_, _>>()
+        .collect::<Result<Vec<_>, _>>()
 }
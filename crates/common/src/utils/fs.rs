@@ -0,0 +1,22 @@
+use std::path::Path;
+
+use eyre::{bail, Result};
+
+/// Recursively copies the contents of `source` into `destination`, creating any missing
+/// directories and overwriting existing files
+pub fn copy_dir(source: &Path, destination: &Path) -> Result<()> {
+    if !source.is_dir() {
+        bail!("{} does not exist", source.display());
+    }
+
+    for entry in walkdir::WalkDir::new(source).into_iter().filter_map(|e| e.ok()) {
+        let target = destination.join(entry.path().strip_prefix(source)?);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
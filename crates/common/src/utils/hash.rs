@@ -0,0 +1,108 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use eyre::Result;
+use sha2::{Digest, Sha256};
+
+use crate::ShadowContractSource;
+
+/// Directories which are build artifacts, and are never considered part of a contract group's
+/// content.
+pub const IGNORED_DIRECTORIES: [&str; 2] = ["out", "cache"];
+
+/// A single hashed file within a directory
+#[derive(Debug, Clone)]
+pub struct HashedFile {
+    /// The path of the file, relative to the hashed directory
+    pub path: PathBuf,
+    /// The SHA-256 hash of the file's content
+    pub hash: [u8; 32],
+}
+
+/// Compute the SHA-256 hash of the given bytes
+pub fn sha256(bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(bytes).into()
+}
+
+/// Compute the SHA-256 hash of the file at the given path
+pub fn sha256_file(path: &Path) -> Result<[u8; 32]> {
+    Ok(sha256(&std::fs::read(path)?))
+}
+
+/// Hash every file in the given directory, skipping [`IGNORED_DIRECTORIES`]. Files are returned
+/// sorted by their relative path, so the result is deterministic.
+pub fn hash_files(root: &Path) -> Result<Vec<HashedFile>> {
+    hash_files_excluding(root, &IGNORED_DIRECTORIES)
+}
+
+/// Hash every file in the given directory, skipping any directory with one of the given names.
+/// Files are returned sorted by their relative path, so the result is deterministic.
+pub fn hash_files_excluding(root: &Path, ignored_directories: &[&str]) -> Result<Vec<HashedFile>> {
+    let mut files = walkdir::WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0 ||
+                !(e.file_type().is_dir() &&
+                    ignored_directories.contains(&e.file_name().to_string_lossy().as_ref()))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| {
+            Ok(HashedFile {
+                path: e.path().strip_prefix(root)?.to_path_buf(),
+                hash: sha256_file(e.path())?,
+            })
+        })
+        .collect::<Result<Vec<HashedFile>>>()?;
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(files)
+}
+
+/// Compute a deterministic root hash over the given directory. Each file contributes its
+/// relative path and content hash, so renaming or moving a file changes the root hash.
+pub fn hash_directory(root: &Path) -> Result<[u8; 32]> {
+    let mut hasher = Sha256::new();
+    for file in hash_files(root)? {
+        // normalize path separators so the hash is the same on every platform
+        hasher.update(file.path.to_string_lossy().replace('\\', "/").as_bytes());
+        hasher.update([0u8]);
+        hasher.update(file.hash);
+    }
+
+    Ok(hasher.finalize().into())
+}
+
+/// Whether a contract's `src/` differs from the verified source in its `original.json`. New
+/// source files also count as a change.
+pub fn source_changed(contract_dir: &Path) -> Result<bool> {
+    let original: ShadowContractSource =
+        serde_json::from_str(&std::fs::read_to_string(contract_dir.join("original.json"))?)?;
+
+    let mut original_files = HashSet::new();
+    for file in &original.contract_files {
+        let path = contract_dir.join(&file.file_name);
+        if !path.exists() || sha256_file(&path)? != sha256(file.content.as_bytes()) {
+            return Ok(true);
+        }
+        original_files.insert(path);
+    }
+
+    // any new source files are also a change
+    let src_dir = contract_dir.join("src");
+    let has_new_files = walkdir::WalkDir::new(&src_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy();
+            name.ends_with(".sol") || name.ends_with(".vy")
+        })
+        .any(|e| !original_files.contains(e.path()));
+
+    Ok(has_new_files)
+}
@@ -0,0 +1,415 @@
+use cid::Cid;
+use eyre::{bail, eyre, OptionExt, Result};
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+
+/// The multicodec of raw blocks, which contain file data as-is
+const RAW_CODEC: u64 = 0x55;
+/// The multicodec of dag-pb blocks, which contain UnixFS directories and file chunks
+const DAG_PB_CODEC: u64 = 0x70;
+/// The multihash code of sha2-256
+const SHA2_256_CODE: u64 = 0x12;
+/// The UnixFS type of directories
+const UNIXFS_DIRECTORY: u64 = 1;
+/// The UnixFS type of HAMT-sharded directories, which split large directories across blocks
+const UNIXFS_HAMT_SHARD: u64 = 5;
+
+/// Content served by an IPFS gateway which doesn't match the hash in its CID
+#[derive(Debug, Clone)]
+pub struct IntegrityError {
+    /// The CID whose content didn't match
+    pub cid: String,
+}
+
+impl std::fmt::Display for IntegrityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the content served for {} does not match its hash. the IPFS gateway may be compromised",
+            self.cid
+        )
+    }
+}
+
+impl std::error::Error for IntegrityError {}
+
+/// An entry in a directory pinned to IPFS
+#[derive(Debug, Clone)]
+pub struct IpfsEntry {
+    /// The entry's name within its directory
+    pub name: String,
+    /// The entry's CID
+    pub cid: String,
+    /// The size of the file, or the total size of the directory's blocks
+    pub size: u64,
+    /// Whether the entry is a directory
+    pub is_directory: bool,
+}
+
+/// Builds the gateway URL of a path within IPFS, e.g. `<cid>/info.json`. Gateways may be given
+/// with or without a trailing `/ipfs/`.
+pub fn ipfs_url(path: &str, base_gateway_url: &str) -> String {
+    let gateway = base_gateway_url.trim_end_matches('/').trim_end_matches("/ipfs");
+    format!("{}/ipfs/{}", gateway, path.trim_start_matches('/'))
+}
+
+/// Fetches a JSON file from IPFS through the given gateway, and deserializes it. If
+/// `verify_integrity` is set, every block the file is made of is fetched individually and
+/// checked against its CID, so a gateway can't serve modified content.
+pub async fn read_from_ipfs<T>(
+    path: &str,
+    base_gateway_url: &str,
+    verify_integrity: bool,
+) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let body = read_file_from_ipfs(path, base_gateway_url, verify_integrity).await?;
+
+    serde_json::from_slice(&body)
+        .map_err(|e| eyre!("Failed to parse {} from IPFS: {}", ipfs_url(path, base_gateway_url), e))
+}
+
+/// Fetches a file's contents from IPFS through the given gateway. If `verify_integrity` is set,
+/// every block the file is made of is fetched individually and checked against its CID.
+pub async fn read_file_from_ipfs(
+    path: &str,
+    base_gateway_url: &str,
+    verify_integrity: bool,
+) -> Result<Vec<u8>> {
+    if verify_integrity {
+        read_verified(path, base_gateway_url).await
+    } else {
+        get(&ipfs_url(path, base_gateway_url), base_gateway_url, None).await
+    }
+}
+
+/// Lists the entries of a directory pinned to IPFS, e.g. `<cid>` or `<cid>/1`, through the
+/// given gateway. Every block is checked against its CID. Each entry which isn't a raw block
+/// is fetched too, to tell files and directories apart.
+pub async fn list_ipfs_directory(path: &str, base_gateway_url: &str) -> Result<Vec<IpfsEntry>> {
+    let cid = resolve_verified(path, base_gateway_url).await?;
+    if cid.codec() != DAG_PB_CODEC {
+        bail!("{} is not a directory", path);
+    }
+    let node = PbNode::decode(&fetch_block(&cid, base_gateway_url).await?)?;
+    match unixfs_metadata(&node.data)? {
+        (UNIXFS_DIRECTORY, _) => {}
+        (UNIXFS_HAMT_SHARD, _) => bail!("{} is a sharded directory, which isn't supported", path),
+        _ => bail!("{} is not a directory", path),
+    }
+
+    let mut entries = vec![];
+    for link in node.links {
+        let cid = Cid::try_from(link.hash.as_slice())
+            .map_err(|e| eyre!("Invalid CID in link {}: {}", link.name, e))?;
+        let (is_directory, size) = match cid.codec() {
+            RAW_CODEC => (false, link.size),
+            DAG_PB_CODEC => {
+                let child = PbNode::decode(&fetch_block(&cid, base_gateway_url).await?)?;
+                match unixfs_metadata(&child.data)? {
+                    (UNIXFS_DIRECTORY | UNIXFS_HAMT_SHARD, _) => (true, link.size),
+                    (_, file_size) => (false, file_size.unwrap_or(link.size)),
+                }
+            }
+            codec => bail!("{} has unsupported codec 0x{:x}", cid, codec),
+        };
+        entries.push(IpfsEntry { name: link.name, cid: cid.to_string(), size, is_directory });
+    }
+
+    Ok(entries)
+}
+
+/// Formats a size in bytes using the largest fitting binary unit
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Fetches a URL from an IPFS gateway, optionally with an `Accept` header
+async fn get(url: &str, base_gateway_url: &str, accept: Option<&str>) -> Result<Vec<u8>> {
+    let mut request = reqwest::Client::new().get(url);
+    if let Some(accept) = accept {
+        request = request.header("Accept", accept);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| eyre!("Failed to reach IPFS gateway {}: {}", base_gateway_url, e))?;
+
+    let status = response.status();
+    let body = response.bytes().await?;
+    if !status.is_success() {
+        return Err(eyre!(
+            "IPFS gateway returned {} for {}: {}",
+            status,
+            url,
+            String::from_utf8_lossy(&body)
+        ));
+    }
+
+    Ok(body.to_vec())
+}
+
+/// Resolves `<cid>/<path>` one block at a time, verifying each block, and returns the CID it
+/// points to
+async fn resolve_verified(path: &str, base_gateway_url: &str) -> Result<Cid> {
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+    let root = segments.next().ok_or_eyre("an IPFS path must start with a CID")?;
+    let mut cid = Cid::try_from(root).map_err(|e| eyre!("Invalid CID {}: {}", root, e))?;
+
+    // walk the directories to the file
+    for name in segments {
+        if cid.codec() != DAG_PB_CODEC {
+            bail!("{} is not a directory, so it has no entry {}", cid, name);
+        }
+        let node = PbNode::decode(&fetch_block(&cid, base_gateway_url).await?)?;
+        let link = node
+            .links
+            .into_iter()
+            .find(|link| link.name == name)
+            .ok_or_else(|| eyre!("{} has no entry {}", cid, name))?;
+        cid = Cid::try_from(link.hash.as_slice())
+            .map_err(|e| eyre!("Invalid CID in link {}: {}", name, e))?;
+    }
+
+    Ok(cid)
+}
+
+/// Resolves `<cid>/<path>` one block at a time, verifying each block, and returns the file's
+/// contents
+async fn read_verified(path: &str, base_gateway_url: &str) -> Result<Vec<u8>> {
+    let cid = resolve_verified(path, base_gateway_url).await?;
+
+    // a file is either a single raw block, or a tree of dag-pb nodes whose leaves hold its
+    // data in order
+    let mut data = vec![];
+    let mut pending = vec![cid];
+    while let Some(cid) = pending.pop() {
+        let block = fetch_block(&cid, base_gateway_url).await?;
+        match cid.codec() {
+            RAW_CODEC => data.extend_from_slice(&block),
+            DAG_PB_CODEC => {
+                let node = PbNode::decode(&block)?;
+                data.extend_from_slice(&unixfs_data(&node.data)?);
+                for link in node.links.iter().rev() {
+                    pending.push(
+                        Cid::try_from(link.hash.as_slice())
+                            .map_err(|e| eyre!("Invalid CID in {}: {}", cid, e))?,
+                    );
+                }
+            }
+            codec => bail!("{} has unsupported codec 0x{:x}", cid, codec),
+        }
+    }
+
+    Ok(data)
+}
+
+/// Fetches a single block from a trustless gateway, and checks it against its CID
+async fn fetch_block(cid: &Cid, base_gateway_url: &str) -> Result<Vec<u8>> {
+    let url = format!("{}?format=raw", ipfs_url(&cid.to_string(), base_gateway_url));
+    let block = get(&url, base_gateway_url, Some("application/vnd.ipld.raw")).await?;
+    verify_block(cid, &block)?;
+
+    Ok(block)
+}
+
+/// Checks that a block's hash matches the hash embedded in its CID
+fn verify_block(cid: &Cid, block: &[u8]) -> Result<()> {
+    let digest = match cid.hash().code() {
+        SHA2_256_CODE => Sha256::digest(block).to_vec(),
+        code => bail!("{} uses unsupported hash function 0x{:x}", cid, code),
+    };
+
+    if digest != cid.hash().digest() {
+        return Err(IntegrityError { cid: cid.to_string() }.into());
+    }
+
+    Ok(())
+}
+
+/// A dag-pb node
+#[derive(Debug, Default)]
+struct PbNode {
+    data: Vec<u8>,
+    links: Vec<PbLink>,
+}
+
+/// A link from a dag-pb node to another block
+#[derive(Debug, Default)]
+struct PbLink {
+    hash: Vec<u8>,
+    name: String,
+    /// The total size of the blocks the link points to
+    size: u64,
+}
+
+impl PbNode {
+    /// Decodes a dag-pb node from its protobuf encoding
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        let mut node = PbNode::default();
+        let mut reader = ProtoReader(bytes);
+        while let Some((field, value)) = reader.next_field()? {
+            match (field, value) {
+                (1, ProtoValue::Bytes(data)) => node.data = data.to_vec(),
+                (2, ProtoValue::Bytes(link)) => {
+                    let mut decoded = PbLink::default();
+                    let mut link_reader = ProtoReader(link);
+                    while let Some((field, value)) = link_reader.next_field()? {
+                        match (field, value) {
+                            (1, ProtoValue::Bytes(hash)) => decoded.hash = hash.to_vec(),
+                            (2, ProtoValue::Bytes(name)) => {
+                                decoded.name = String::from_utf8(name.to_vec())?
+                            }
+                            (3, ProtoValue::Varint(size)) => decoded.size = size,
+                            _ => {}
+                        }
+                    }
+                    node.links.push(decoded);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(node)
+    }
+}
+
+/// Returns the file data held by a UnixFS node. Directories and other types hold none.
+fn unixfs_data(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut reader = ProtoReader(bytes);
+    while let Some((field, value)) = reader.next_field()? {
+        if let (2, ProtoValue::Bytes(data)) = (field, value) {
+            return Ok(data.to_vec());
+        }
+    }
+
+    Ok(vec![])
+}
+
+/// Returns the type of a UnixFS node, and the size of the file it holds, if any
+fn unixfs_metadata(bytes: &[u8]) -> Result<(u64, Option<u64>)> {
+    let (mut kind, mut file_size) = (0, None);
+    let mut reader = ProtoReader(bytes);
+    while let Some((field, value)) = reader.next_field()? {
+        match (field, value) {
+            (1, ProtoValue::Varint(value)) => kind = value,
+            (3, ProtoValue::Varint(value)) => file_size = Some(value),
+            _ => {}
+        }
+    }
+
+    Ok((kind, file_size))
+}
+
+/// A decoded protobuf field value
+enum ProtoValue<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+}
+
+/// A minimal protobuf reader, supporting the wire types used by dag-pb and UnixFS
+struct ProtoReader<'a>(&'a [u8]);
+
+impl<'a> ProtoReader<'a> {
+    fn read_varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let (byte, rest) = self.0.split_first().ok_or_eyre("truncated protobuf varint")?;
+            self.0 = rest;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        bail!("protobuf varint is too long")
+    }
+
+    fn next_field(&mut self) -> Result<Option<(u64, ProtoValue<'a>)>> {
+        if self.0.is_empty() {
+            return Ok(None);
+        }
+
+        let key = self.read_varint()?;
+        let value = match key & 0x7 {
+            0 => ProtoValue::Varint(self.read_varint()?),
+            2 => {
+                let length = self.read_varint()? as usize;
+                if length > self.0.len() {
+                    bail!("truncated protobuf field");
+                }
+                let (bytes, rest) = self.0.split_at(length);
+                self.0 = rest;
+                ProtoValue::Bytes(bytes)
+            }
+            wire_type => bail!("unsupported protobuf wire type {}", wire_type),
+        };
+
+        Ok(Some((key >> 3, value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INFO_JSON: &[u8] = br#"{"displayName":"test"}"#;
+    const INFO_JSON_CID: &str = "bafkreig22vgqplb6rj7dmuprzzctnfrmjqeeq4mmiaupw26nq52vgupbty";
+
+    #[test]
+    fn test_ipfs_url() {
+        assert_eq!(
+            ipfs_url("Qm/info.json", "https://gateway.pinata.cloud/ipfs/"),
+            "https://gateway.pinata.cloud/ipfs/Qm/info.json"
+        );
+        assert_eq!(
+            ipfs_url("Qm/info.json", "https://ipfs.io"),
+            "https://ipfs.io/ipfs/Qm/info.json"
+        );
+    }
+
+    #[test]
+    fn test_verify_block() {
+        let cid = Cid::try_from(INFO_JSON_CID).unwrap();
+        assert!(verify_block(&cid, INFO_JSON).is_ok());
+
+        let error = verify_block(&cid, br#"{"displayName":"evil"}"#).unwrap_err();
+        assert!(error.downcast_ref::<IntegrityError>().is_some());
+    }
+
+    #[test]
+    fn test_decode_pb_node() {
+        let cid = Cid::try_from(INFO_JSON_CID).unwrap().to_bytes();
+
+        // a directory with a single `info.json` link
+        let mut link = vec![0x0a, cid.len() as u8];
+        link.extend_from_slice(&cid);
+        link.extend_from_slice(&[0x12, 9]);
+        link.extend_from_slice(b"info.json");
+        link.extend_from_slice(&[0x18, INFO_JSON.len() as u8]);
+        let mut node = vec![0x12, link.len() as u8];
+        node.extend_from_slice(&link);
+        node.extend_from_slice(&[0x0a, 2, 0x08, 0x01]);
+
+        let decoded = PbNode::decode(&node).unwrap();
+        assert_eq!(decoded.links.len(), 1);
+        assert_eq!(decoded.links[0].name, "info.json");
+        assert_eq!(decoded.links[0].hash, cid);
+        assert_eq!(decoded.links[0].size, INFO_JSON.len() as u64);
+        assert!(unixfs_data(&decoded.data).unwrap().is_empty());
+        assert_eq!(unixfs_metadata(&decoded.data).unwrap(), (UNIXFS_DIRECTORY, None));
+    }
+}
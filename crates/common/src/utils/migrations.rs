@@ -0,0 +1,103 @@
+use alloy::primitives::Address;
+use eyre::{bail, eyre, Result};
+use serde_json::Value;
+
+use crate::ShadowContractGroupInfo;
+
+/// The current version of the `info.json` format
+pub const CURRENT_VERSION: u32 = 2;
+
+/// Returns the format version of a contract group's `info.json`. Groups created before the
+/// format was versioned have no `version`, and are version 1.
+pub fn group_version(info: &Value) -> Result<u32> {
+    match info.get("version") {
+        None => Ok(1),
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| eyre!("invalid info.json version: {}", version)),
+    }
+}
+
+/// Migrates a contract group's `info.json` from any older version to [`CURRENT_VERSION`]
+pub fn migrate(info: Value) -> Result<ShadowContractGroupInfo> {
+    match group_version(&info)? {
+        1 => migrate_v1_to_v2(info),
+        CURRENT_VERSION => Ok(serde_json::from_value(info)?),
+        version => bail!(
+            "info.json is version {}, but this version of shadow only supports up to version {}. \
+             Try updating shadow with `shadowup`.",
+            version,
+            CURRENT_VERSION
+        ),
+    }
+}
+
+/// Migrates a version 1 `info.json` to version 2, which checksums the creator's and contracts'
+/// addresses, and adds a `version`.
+pub fn migrate_v1_to_v2(mut old: Value) -> Result<ShadowContractGroupInfo> {
+    if let Some(creator) = old.get_mut("creator").filter(|creator| !creator.is_null()) {
+        *creator = checksum(creator)?;
+    }
+    if let Some(contracts) = old.get_mut("contracts").and_then(Value::as_array_mut) {
+        for contract in contracts {
+            if let Some(address) = contract.get_mut("address") {
+                *address = checksum(address)?;
+            }
+        }
+    }
+
+    let info = old.as_object_mut().ok_or_else(|| eyre!("info.json must be a JSON object"))?;
+    info.insert("version".to_string(), Value::from(2));
+
+    Ok(serde_json::from_value(old)?)
+}
+
+/// Checksums an address in a JSON value
+fn checksum(address: &Value) -> Result<Value> {
+    let address: Address = address
+        .as_str()
+        .ok_or_else(|| eyre!("expected an address, found {}", address))?
+        .parse()
+        .map_err(|e| eyre!("invalid address: {}", e))?;
+
+    Ok(Value::from(address.to_checksum(None)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INFO_V1: &str = include_str!("../../tests/fixtures/info_v1.json");
+
+    #[test]
+    fn test_migrate_v1_to_v2() {
+        let old: Value = serde_json::from_str(INFO_V1).unwrap();
+        assert_eq!(group_version(&old).unwrap(), 1);
+
+        let info = migrate(old).unwrap();
+        assert_eq!(info.version, 2);
+        assert_eq!(info.display_name, "Uniswap V2");
+        assert_eq!(info.contracts.len(), 2);
+
+        // addresses are written checksummed
+        let migrated = serde_json::to_value(&info).unwrap();
+        assert_eq!(group_version(&migrated).unwrap(), CURRENT_VERSION);
+        assert_eq!(migrated["creator"], "0x6B175474E89094C44Da98b954EedeAC495271d0F");
+        assert_eq!(
+            migrated["contracts"][0]["address"],
+            "0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f"
+        );
+        assert_eq!(
+            migrated["contracts"][1]["address"],
+            "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D"
+        );
+    }
+
+    #[test]
+    fn test_migrate_newer_version() {
+        let mut info: Value = serde_json::from_str(INFO_V1).unwrap();
+        info["version"] = Value::from(CURRENT_VERSION + 1);
+        assert!(migrate(info).is_err());
+    }
+}
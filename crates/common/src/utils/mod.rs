@@ -1,3 +1,9 @@
+/// ABI utilities
+pub mod abi;
+/// Heuristic vulnerability checks for contract ABIs
+pub mod analysis;
+/// Local audit log of pushes
+pub mod audit;
 /// Blockscout API client
 pub mod blockscout;
 /// Shadow compilation utilities
@@ -6,8 +12,20 @@ pub mod compiler;
 pub mod db;
 /// `BlockEnv` wrapper
 pub mod env;
+/// Event decoding utilities
+pub mod event;
 /// `forge` management
 pub mod forge;
+/// Filesystem helpers
+pub mod fs;
+/// Content hashing
+pub mod hash;
+/// IPFS gateway utilities
+pub mod ipfs;
+/// `info.json` format migrations
+pub mod migrations;
+/// On-disk cache of values fetched by the ephemeral db
+pub mod rpc_cache;
 /// State
 pub mod state;
 /// Versioning
@@ -0,0 +1,225 @@
+#![allow(deprecated)]
+use std::{collections::BTreeMap, env::home_dir, path::PathBuf};
+
+use clap::ValueEnum;
+use eyre::{bail, eyre, OptionExt, Result};
+use revm::primitives::{Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+/// How a [`crate::db::JsonRpcDatabase`] uses the on-disk RPC cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum RpcCacheMode {
+    /// Fetch every value from the RPC
+    #[default]
+    None,
+    /// Only read values from the cache, failing if any are missing
+    Read,
+    /// Fetch every value from the RPC, and write them to the cache
+    Write,
+    /// Read values from the cache, falling back to the RPC and writing the fetched values
+    ReadWrite,
+}
+
+impl RpcCacheMode {
+    /// Whether values are read from the cache
+    pub fn reads(self) -> bool {
+        matches!(self, RpcCacheMode::Read | RpcCacheMode::ReadWrite)
+    }
+
+    /// Whether fetched values are written to the cache
+    pub fn writes(self) -> bool {
+        matches!(self, RpcCacheMode::Write | RpcCacheMode::ReadWrite)
+    }
+}
+
+/// An account, as fetched from the RPC
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedAccount {
+    pub balance: U256,
+    pub nonce: u64,
+    pub code_hash: B256,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+}
+
+/// The state values fetched from the RPC while replaying a block, stored at
+/// `~/.shadow/cache/rpc/<chain id>/<block number>.json`. Values are cached as the RPC returned
+/// them, before shadow overrides or partial-block state are applied, so the same cache can be
+/// used to replay any transaction in the block.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RpcCache {
+    /// Accounts which don't exist are cached as `None`
+    accounts: BTreeMap<Address, Option<CachedAccount>>,
+    storage: BTreeMap<Address, BTreeMap<U256, U256>>,
+    contracts: BTreeMap<B256, Bytes>,
+    block_hashes: BTreeMap<u64, B256>,
+    #[serde(skip)]
+    mode: RpcCacheMode,
+    #[serde(skip)]
+    path: PathBuf,
+    /// Whether values have been added since the cache was loaded
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl RpcCache {
+    /// Loads the cache for a block. In `read` mode, the cache must already exist.
+    pub fn load(chain_id: u64, block_number: u64, mode: RpcCacheMode) -> Result<Self> {
+        let path = home_dir()
+            .ok_or_eyre("failed to get home directory")?
+            .join(".shadow")
+            .join("cache")
+            .join("rpc")
+            .join(chain_id.to_string())
+            .join(format!("{}.json", block_number));
+
+        let mut cache = if mode.reads() && path.exists() {
+            let contents = std::fs::read_to_string(&path)?;
+            let cache: RpcCache = serde_json::from_str(&contents)
+                .map_err(|e| eyre!("Failed to parse RPC cache {}: {}", path.display(), e))?;
+            debug!("loaded RPC cache for block {} from {}", block_number, path.display());
+            cache
+        } else if mode == RpcCacheMode::Read {
+            bail!(
+                "there is no RPC cache for block {} on chain {}. Run with `--json-rpc-cache-mode write` to create one.",
+                block_number,
+                chain_id
+            );
+        } else {
+            RpcCache::default()
+        };
+        cache.mode = mode;
+        cache.path = path;
+
+        Ok(cache)
+    }
+
+    /// Looks up a value in the cache. Returns `None` if it should be fetched from the RPC, or
+    /// fails in `read` mode if it's missing.
+    fn lookup<T: Clone>(
+        &self,
+        value: Option<&T>,
+        description: impl FnOnce() -> String,
+    ) -> Result<Option<T>> {
+        if !self.mode.reads() {
+            return Ok(None);
+        }
+
+        match value {
+            Some(value) => Ok(Some(value.clone())),
+            None if self.mode == RpcCacheMode::Read => {
+                bail!("{} is missing from the RPC cache", description())
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns a cached account, if it should be read from the cache. The inner `None` is an
+    /// account which doesn't exist.
+    pub fn account(&self, address: &Address) -> Result<Option<Option<CachedAccount>>> {
+        self.lookup(self.accounts.get(address), || format!("account {}", address))
+    }
+
+    /// Returns a cached storage slot, if it should be read from the cache
+    pub fn storage(&self, address: &Address, index: &U256) -> Result<Option<U256>> {
+        self.lookup(self.storage.get(address).and_then(|slots| slots.get(index)), || {
+            format!("storage slot {} of {}", index, address)
+        })
+    }
+
+    /// Returns cached code, if it should be read from the cache
+    pub fn code(&self, code_hash: &B256) -> Result<Option<Bytes>> {
+        self.lookup(self.contracts.get(code_hash), || format!("code with hash {}", code_hash))
+    }
+
+    /// Returns a cached block hash, if it should be read from the cache
+    pub fn block_hash(&self, number: u64) -> Result<Option<B256>> {
+        self.lookup(self.block_hashes.get(&number), || format!("the hash of block {}", number))
+    }
+
+    /// Caches an account fetched from the RPC, if the cache is being written
+    pub fn insert_account(&mut self, address: Address, account: Option<CachedAccount>) {
+        if self.mode.writes() {
+            self.accounts.insert(address, account);
+            self.dirty = true;
+        }
+    }
+
+    /// Caches a storage slot fetched from the RPC, if the cache is being written
+    pub fn insert_storage(&mut self, address: Address, index: U256, value: U256) {
+        if self.mode.writes() {
+            self.storage.entry(address).or_default().insert(index, value);
+            self.dirty = true;
+        }
+    }
+
+    /// Caches code fetched from the RPC, if the cache is being written
+    pub fn insert_code(&mut self, code_hash: B256, code: Bytes) {
+        if self.mode.writes() {
+            self.contracts.insert(code_hash, code);
+            self.dirty = true;
+        }
+    }
+
+    /// Caches a block hash fetched from the RPC, if the cache is being written
+    pub fn insert_block_hash(&mut self, number: u64, hash: B256) {
+        if self.mode.writes() {
+            self.block_hashes.insert(number, hash);
+            self.dirty = true;
+        }
+    }
+
+    /// Writes the cache to disk, if values have been added to it
+    pub fn save(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string(self)?)?;
+        self.dirty = false;
+        info!("wrote RPC cache to {}", self.path.display());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_cache_modes() {
+        let address = Address::repeat_byte(0x11);
+        let account = CachedAccount {
+            balance: U256::from(1),
+            nonce: 2,
+            code_hash: B256::repeat_byte(0x22),
+            code: None,
+        };
+
+        // values are only cached when writing
+        let mut cache = RpcCache { mode: RpcCacheMode::None, ..Default::default() };
+        cache.insert_account(address, Some(account.clone()));
+        assert!(!cache.dirty);
+        assert_eq!(cache.account(&address).unwrap(), None);
+
+        let mut cache = RpcCache { mode: RpcCacheMode::ReadWrite, ..Default::default() };
+        assert_eq!(cache.storage(&address, &U256::ZERO).unwrap(), None);
+        cache.insert_account(address, Some(account.clone()));
+        cache.insert_storage(address, U256::ZERO, U256::from(3));
+        assert_eq!(cache.account(&address).unwrap(), Some(Some(account)));
+        assert_eq!(cache.storage(&address, &U256::ZERO).unwrap(), Some(U256::from(3)));
+
+        // the cache survives a round trip to disk, and missing values are errors in read mode
+        let mut cache: RpcCache =
+            serde_json::from_str(&serde_json::to_string(&cache).unwrap()).unwrap();
+        cache.mode = RpcCacheMode::Read;
+        assert_eq!(cache.storage(&address, &U256::ZERO).unwrap(), Some(U256::from(3)));
+        assert!(cache.storage(&address, &U256::from(1)).is_err());
+        assert!(cache.block_hash(1).is_err());
+    }
+}
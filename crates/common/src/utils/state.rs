@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
-use alloy::primitives::U64;
-use revm::primitives::U256;
+use alloy::{
+    primitives::{keccak256, Bytes, U64},
+    rpc::types::trace::parity::{AccountDiff, ChangedType, Delta},
+};
+use revm::primitives::{AccountInfo, Bytecode, B256, U256};
 
 /// State diff
 #[derive(Debug, Clone, Default)]
@@ -10,6 +13,250 @@ pub struct PartialBlockStateDiff {
     pub balance: Option<U256>,
     /// Current nonce after partial block execution. None if the nonce was not touched.
     pub nonce: Option<U64>,
+    /// Current code after partial block execution. None if the code was not touched.
+    pub code: Option<Bytes>,
     /// Current storage diff after partial block execution.
     pub storage: HashMap<U256, U256>,
 }
+
+impl PartialBlockStateDiff {
+    /// Applies one transaction's changes to the account on top of the diff. Values the
+    /// transaction removed, e.g. by self-destructing the account, are reset to zero or empty.
+    pub fn apply_account_diff(&mut self, diff: &AccountDiff) {
+        if let Some(balance) = delta_value(&diff.balance, U256::ZERO) {
+            self.balance = Some(balance);
+        }
+
+        if let Some(nonce) = delta_value(&diff.nonce, U64::ZERO) {
+            self.nonce = Some(nonce);
+        }
+
+        if let Some(code) = delta_value(&diff.code, Bytes::new()) {
+            self.code = Some(code);
+        }
+
+        diff.storage.iter().for_each(|(slot, value)| {
+            if let Some(value) = delta_value(value, B256::ZERO) {
+                self.storage.insert(U256::from_be_bytes(slot.0), U256::from_be_bytes(value.0));
+            }
+        });
+    }
+
+    /// Applies the partial-block state transitions on top of an account's `info` and `storage`.
+    /// Untouched balances, nonces and code are left as-is, and storage slots in the diff
+    /// overwrite any existing values.
+    pub fn apply_to(&self, info: &mut AccountInfo, storage: &mut HashMap<U256, U256>) {
+        if let Some(balance) = self.balance {
+            info.balance = balance;
+        }
+
+        if let Some(nonce) = self.nonce {
+            info.nonce = nonce.try_into().expect("U64 -> u64");
+        }
+
+        if let Some(code) = &self.code {
+            info.code_hash = keccak256(code);
+            info.code = Some(Bytecode::new_raw(code.clone()));
+        }
+
+        storage.extend(self.storage.iter().map(|(slot, value)| (*slot, *value)));
+    }
+}
+
+/// The value after a [`Delta`], or None if it's unchanged. Removed values are replaced with
+/// `removed`.
+fn delta_value<T: Clone>(delta: &Delta<T>, removed: T) -> Option<T> {
+    match delta {
+        Delta::Unchanged => None,
+        Delta::Added(value) | Delta::Changed(ChangedType { to: value, .. }) => Some(value.clone()),
+        Delta::Removed(_) => Some(removed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use revm::primitives::KECCAK_EMPTY;
+
+    use super::*;
+
+    fn account_info() -> AccountInfo {
+        AccountInfo { balance: U256::from(100), nonce: 5, ..Default::default() }
+    }
+
+    #[test]
+    fn test_apply_untouched_diff() {
+        let mut info = account_info();
+        let mut storage = HashMap::from([(U256::from(1), U256::from(10))]);
+
+        PartialBlockStateDiff::default().apply_to(&mut info, &mut storage);
+
+        assert_eq!(info, account_info());
+        assert_eq!(storage, HashMap::from([(U256::from(1), U256::from(10))]));
+    }
+
+    #[test]
+    fn test_apply_balance_only() {
+        let mut info = account_info();
+        let mut storage = HashMap::new();
+
+        let diff = PartialBlockStateDiff { balance: Some(U256::from(42)), ..Default::default() };
+        diff.apply_to(&mut info, &mut storage);
+
+        assert_eq!(info.balance, U256::from(42));
+        assert_eq!(info.nonce, 5);
+        assert!(storage.is_empty());
+    }
+
+    #[test]
+    fn test_apply_nonce_only() {
+        let mut info = account_info();
+        let mut storage = HashMap::new();
+
+        let diff = PartialBlockStateDiff { nonce: Some(U64::from(7)), ..Default::default() };
+        diff.apply_to(&mut info, &mut storage);
+
+        assert_eq!(info.balance, U256::from(100));
+        assert_eq!(info.nonce, 7);
+    }
+
+    #[test]
+    fn test_apply_storage_merge() {
+        let mut info = account_info();
+        let mut storage = HashMap::from([
+            (U256::from(1), U256::from(10)),
+            (U256::from(2), U256::from(20)),
+            (U256::from(3), U256::from(30)),
+        ]);
+
+        // slot 1 is changed, slot 2 is cleared, slot 4 is added and slot 3 is untouched
+        let diff = PartialBlockStateDiff {
+            storage: HashMap::from([
+                (U256::from(1), U256::from(11)),
+                (U256::from(2), U256::ZERO),
+                (U256::from(4), U256::from(40)),
+            ]),
+            ..Default::default()
+        };
+        diff.apply_to(&mut info, &mut storage);
+
+        assert_eq!(info, account_info());
+        assert_eq!(
+            storage,
+            HashMap::from([
+                (U256::from(1), U256::from(11)),
+                (U256::from(2), U256::ZERO),
+                (U256::from(3), U256::from(30)),
+                (U256::from(4), U256::from(40)),
+            ])
+        );
+    }
+
+    /// An account diff which leaves everything unchanged
+    fn account_diff() -> AccountDiff {
+        AccountDiff {
+            balance: Delta::Unchanged,
+            code: Delta::Unchanged,
+            nonce: Delta::Unchanged,
+            storage: BTreeMap::new(),
+        }
+    }
+
+    fn slot(value: u64) -> B256 {
+        B256::from(U256::from(value))
+    }
+
+    fn contract_info() -> AccountInfo {
+        let code = Bytes::from_static(&[0x60, 0x00]);
+        AccountInfo {
+            code_hash: keccak256(&code),
+            code: Some(Bytecode::new_raw(code)),
+            ..account_info()
+        }
+    }
+
+    #[test]
+    fn test_apply_changed_account() {
+        let new_code = Bytes::from_static(&[0x60, 0x01]);
+        let mut diff = PartialBlockStateDiff::default();
+        diff.apply_account_diff(&AccountDiff {
+            balance: Delta::Changed(ChangedType { from: U256::from(100), to: U256::from(42) }),
+            nonce: Delta::Changed(ChangedType { from: U64::from(5), to: U64::from(6) }),
+            code: Delta::Changed(ChangedType {
+                from: Bytes::from_static(&[0x60, 0x00]),
+                to: new_code.clone(),
+            }),
+            storage: BTreeMap::from([(
+                slot(1),
+                Delta::Changed(ChangedType { from: slot(10), to: slot(11) }),
+            )]),
+        });
+
+        let mut info = contract_info();
+        let mut storage = HashMap::from([(U256::from(1), U256::from(10))]);
+        diff.apply_to(&mut info, &mut storage);
+
+        assert_eq!(info.balance, U256::from(42));
+        assert_eq!(info.nonce, 6);
+        assert_eq!(info.code_hash, keccak256(&new_code));
+        assert_eq!(info.code, Some(Bytecode::new_raw(new_code)));
+        assert_eq!(storage, HashMap::from([(U256::from(1), U256::from(11))]));
+    }
+
+    #[test]
+    fn test_apply_removed_account() {
+        let mut diff = PartialBlockStateDiff::default();
+        diff.apply_account_diff(&AccountDiff {
+            balance: Delta::Removed(U256::from(100)),
+            nonce: Delta::Removed(U64::from(5)),
+            code: Delta::Removed(Bytes::from_static(&[0x60, 0x00])),
+            storage: BTreeMap::from([(slot(1), Delta::Removed(slot(10)))]),
+        });
+
+        let mut info = contract_info();
+        let mut storage = HashMap::from([(U256::from(1), U256::from(10))]);
+        diff.apply_to(&mut info, &mut storage);
+
+        // removed values are zeroed, rather than left as they were before the block
+        assert_eq!(info.balance, U256::ZERO);
+        assert_eq!(info.nonce, 0);
+        assert_eq!(info.code_hash, KECCAK_EMPTY);
+        assert!(info.code.as_ref().is_some_and(|code| code.is_empty()));
+        assert_eq!(storage, HashMap::from([(U256::from(1), U256::ZERO)]));
+    }
+
+    #[test]
+    fn test_apply_successive_account_diffs() {
+        let mut diff = PartialBlockStateDiff::default();
+
+        // the account is created by one transaction, and changed by the next
+        diff.apply_account_diff(&AccountDiff {
+            balance: Delta::Added(U256::from(1)),
+            nonce: Delta::Added(U64::from(1)),
+            code: Delta::Added(Bytes::from_static(&[0x60, 0x00])),
+            storage: BTreeMap::from([(slot(1), Delta::Added(slot(10)))]),
+        });
+        diff.apply_account_diff(&AccountDiff {
+            balance: Delta::Changed(ChangedType { from: U256::from(1), to: U256::from(2) }),
+            storage: BTreeMap::from([
+                (slot(1), Delta::Changed(ChangedType { from: slot(10), to: slot(11) })),
+                (slot(2), Delta::Added(slot(20))),
+            ]),
+            ..account_diff()
+        });
+
+        let mut info = AccountInfo::default();
+        let mut storage = HashMap::new();
+        diff.apply_to(&mut info, &mut storage);
+
+        // values the second transaction didn't touch are kept from the first
+        assert_eq!(info.balance, U256::from(2));
+        assert_eq!(info.nonce, 1);
+        assert_eq!(info.code_hash, keccak256([0x60, 0x00]));
+        assert_eq!(
+            storage,
+            HashMap::from([(U256::from(1), U256::from(11)), (U256::from(2), U256::from(20))])
+        );
+    }
+}
@@ -1,6 +1,6 @@
-use std::fmt::Display;
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
 
-use eyre::Result;
+use eyre::{eyre, Result};
 use serde_json::Value;
 
 static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
@@ -107,43 +107,55 @@ impl PartialEq for Version {
     }
 }
 
-impl Version {
-    /// greater than
-    pub fn gt(&self, other: &Version) -> bool {
-        self.major > other.major ||
-            (self.major == other.major && self.minor > other.minor) ||
-            (self.major == other.major && self.minor == other.minor && self.patch > other.patch)
-    }
-
-    /// greater than or equal to
-    pub fn gte(&self, other: &Version) -> bool {
-        self.major > other.major ||
-            (self.major == other.major && self.minor > other.minor) ||
-            (self.major == other.major && self.minor == other.minor && self.patch >= other.patch)
-    }
+impl Eq for Version {}
 
-    /// less than
-    pub fn lt(&self, other: &Version) -> bool {
-        self.major < other.major ||
-            (self.major == other.major && self.minor < other.minor) ||
-            (self.major == other.major && self.minor == other.minor && self.patch < other.patch)
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
+}
 
-    /// less than or equal to
-    pub fn lte(&self, other: &Version) -> bool {
-        self.major < other.major ||
-            (self.major == other.major && self.minor < other.minor) ||
-            (self.major == other.major && self.minor == other.minor && self.patch <= other.patch)
+impl Ord for Version {
+    /// Orders by major, minor and patch. Versions which differ only by channel are ordered by
+    /// channel, with stable versions first, to stay consistent with [`PartialEq`].
+    fn cmp(&self, other: &Version) -> Ordering {
+        (self.major, self.minor, self.patch, &self.channel).cmp(&(
+            other.major,
+            other.minor,
+            other.patch,
+            &other.channel,
+        ))
     }
+}
 
-    /// not equal to
-    pub fn ne(&self, other: &Version) -> bool {
-        self.major != other.major ||
-            self.minor != other.minor ||
-            self.patch != other.patch ||
-            self.channel != other.channel
+impl FromStr for Version {
+    type Err = eyre::Report;
+
+    /// Parses a version string such as `1.2.3` or `v1.2.3+nightly.abc1234`. This is the inverse
+    /// of [`Display`].
+    fn from_str(s: &str) -> Result<Self> {
+        let (version_string, channel) = match s.trim().trim_start_matches('v').split_once('+') {
+            Some((_, "")) => return Err(eyre!("invalid version '{}': empty channel", s)),
+            Some((version_string, channel)) => (version_string, Some(channel.to_string())),
+            None => (s.trim().trim_start_matches('v'), None),
+        };
+
+        let version_parts = version_string
+            .split('.')
+            .map(|part| part.parse::<u32>())
+            .collect::<Result<Vec<u32>, _>>()
+            .map_err(|e| eyre!("invalid version '{}': {}", s, e))?;
+
+        match version_parts.as_slice() {
+            [major, minor, patch] => {
+                Ok(Version { major: *major, minor: *minor, patch: *patch, channel })
+            }
+            _ => Err(eyre!("invalid version '{}': expected <major>.<minor>.<patch>", s)),
+        }
     }
+}
 
+impl Version {
     /// if the version is a nightly version
     pub fn is_nightly(&self) -> bool {
         self.channel.is_some() && self.channel.as_ref().unwrap().starts_with("nightly.")
@@ -161,11 +173,11 @@ mod tests {
         let v3 = Version { major: 2, minor: 2, patch: 5, channel: None };
         let v4 = Version { major: 1, minor: 4, patch: 4, channel: None };
 
-        assert!(v1.gt(&v2));
-        assert!(v1.gt(&v3));
-        assert!(v1.gt(&v4));
-        assert!(!v2.gt(&v1));
-        assert!(!v1.gt(&v1));
+        assert!(v1 > v2);
+        assert!(v1 > v3);
+        assert!(v1 > v4);
+        assert!(v2 <= v1);
+        assert_eq!(v1.cmp(&v1), Ordering::Equal);
     }
 
     #[test]
@@ -173,9 +185,9 @@ mod tests {
         let v1 = Version { major: 2, minor: 3, patch: 4, channel: None };
         let v2 = Version { major: 2, minor: 3, patch: 4, channel: None };
 
-        assert!(v1.gte(&v2));
-        assert!(v2.gte(&v1));
-        assert!(v1.gte(&Version { major: 1, minor: 0, patch: 0, channel: None }));
+        assert!(v1 >= v2);
+        assert!(v2 >= v1);
+        assert!(v1 >= Version { major: 1, minor: 0, patch: 0, channel: None });
     }
 
     #[test]
@@ -185,11 +197,11 @@ mod tests {
         let v3 = Version { major: 2, minor: 4, patch: 4, channel: None };
         let v4 = Version { major: 3, minor: 3, patch: 4, channel: None };
 
-        assert!(v1.lt(&v2));
-        assert!(v1.lt(&v3));
-        assert!(v1.lt(&v4));
-        assert!(!v2.lt(&v1));
-        assert!(!v1.lt(&v1));
+        assert!(v1 < v2);
+        assert!(v1 < v3);
+        assert!(v1 < v4);
+        assert!(v2 >= v1);
+        assert_eq!(v1.cmp(&v1), Ordering::Equal);
     }
 
     #[test]
@@ -197,9 +209,9 @@ mod tests {
         let v1 = Version { major: 2, minor: 3, patch: 4, channel: None };
         let v2 = Version { major: 2, minor: 3, patch: 4, channel: None };
 
-        assert!(v1.lte(&v2));
-        assert!(v2.lte(&v1));
-        assert!(v1.lte(&Version { major: 3, minor: 0, patch: 0, channel: None }));
+        assert!(v1 <= v2);
+        assert!(v2 <= v1);
+        assert!(v1 <= Version { major: 3, minor: 0, patch: 0, channel: None });
     }
 
     #[test]
@@ -208,8 +220,8 @@ mod tests {
         let v2 = Version { major: 2, minor: 3, patch: 4, channel: None };
         let v3 = Version { major: 2, minor: 3, patch: 5, channel: None };
 
-        assert!(v1.eq(&v2));
-        assert!(!v1.eq(&v3));
+        assert!(v1 == v2);
+        assert!(v1 != v3);
     }
 
     #[test]
@@ -218,9 +230,9 @@ mod tests {
         let v2 = Version { major: 2, minor: 3, patch: 5, channel: None };
         let v3 = Version { major: 3, minor: 3, patch: 4, channel: None };
 
-        assert!(v1.ne(&v2));
-        assert!(v1.ne(&v3));
-        assert!(!v1.ne(&Version { major: 2, minor: 3, patch: 4, channel: None }));
+        assert!(v1 != v2);
+        assert!(v1 != v3);
+        assert!(v1 == Version { major: 2, minor: 3, patch: 4, channel: None });
     }
 
     #[test]
@@ -230,6 +242,45 @@ mod tests {
         assert_eq!(version.to_string(), "2.3.4");
     }
 
+    #[test]
+    fn test_version_from_str_stable() {
+        let version = Version::from_str("1.2.3").expect("failed to parse version");
+
+        assert_eq!(version, Version { major: 1, minor: 2, patch: 3, channel: None });
+        assert_eq!(Version::from_str("v1.2.3").expect("failed to parse version"), version);
+    }
+
+    #[test]
+    fn test_version_from_str_nightly() {
+        let version = Version::from_str("1.2.3+nightly.abc1234").expect("failed to parse version");
+
+        assert!(version.is_nightly());
+        assert_eq!(version.to_string(), "1.2.3+nightly.abc1234");
+    }
+
+    #[test]
+    fn test_version_from_str_malformed() {
+        assert!(Version::from_str("").is_err());
+        assert!(Version::from_str("1.2").is_err());
+        assert!(Version::from_str("1.2.3.4").is_err());
+        assert!(Version::from_str("1.x.3").is_err());
+        assert!(Version::from_str("1.2.3+").is_err());
+    }
+
+    #[test]
+    fn test_version_ord() {
+        let mut versions = ["1.10.0", "1.2.3+nightly.abc1234", "0.9.9", "1.2.3"]
+            .iter()
+            .map(|v| Version::from_str(v).expect("failed to parse version"))
+            .collect::<Vec<_>>();
+        versions.sort();
+
+        assert_eq!(
+            versions.iter().map(|v| v.to_string()).collect::<Vec<_>>(),
+            ["0.9.9", "1.2.3", "1.2.3+nightly.abc1234", "1.10.0"]
+        );
+    }
+
     #[test]
     fn test_version_current() {
         let version = current_version();
@@ -1,36 +1,141 @@
-use std::{collections::HashMap, path::PathBuf, str::FromStr};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use crate::CompileArgs;
-use eyre::{eyre, Result};
+use alloy::dyn_abi::DynSolType;
+use eyre::{bail, eyre, Result};
 use shadow_common::{
-    compiler, forge::ensure_forge_installed, ShadowContractInfo, ShadowContractSettings,
+    compiler::{self, CompilerErrors, RemoteCompiler},
+    db::JsonRpcDatabase,
+    forge::ensure_forge_installed,
+    fs::copy_dir,
+    ShadowContractInfo, ShadowContractSettings, ShadowContractSource,
 };
-use tracing::info;
+use tracing::{error, info, warn};
+
+/// ANSI color for red
+const RED_ANSI_COLOR: &str = "\x1b[31m";
+/// ANSI color for blue
+const BLUE_ANSI_COLOR: &str = "\x1b[34m";
+/// Reset the ANSI color to the default
+const RESET_ANSI_COLOR: &str = "\x1b[0m";
 
 /// The `compile` subcommand. Compiles a shadowed contract with the original contract settings.
 pub async fn compile(args: CompileArgs) -> Result<()> {
-    // ensure forge is installed on the system
-    ensure_forge_installed()?;
+    // ensure forge is installed on the system, unless we're compiling remotely
+    if !args.remote {
+        ensure_forge_installed()?;
+    }
 
     let root_dir = PathBuf::from_str(&args.root)?;
     let settings_path = root_dir.join("settings.json");
     let info_path = root_dir.join("info.json");
 
     // ensure settings and info.json exist, load them
-    let settings: ShadowContractSettings = serde_json::from_slice(&std::fs::read(settings_path)
+    let mut settings: ShadowContractSettings = serde_json::from_slice(&std::fs::read(&settings_path)
         .map_err(|e| eyre!("expected settings.json in root directory. you may need to run `shadow fetch` first: {}", e))?
     )?;
     let info: ShadowContractInfo = serde_json::from_slice(&std::fs::read(info_path)
         .map_err(|e| eyre!("expected info.json in root directory. you may need to run `shadow fetch` first: {}", e))?
     )?;
 
+    // check the source tree's imports, if requested. remappings aren't stored alongside the
+    // source files, so they're taken from `source.json`
+    if args.check_imports {
+        let mut source = ShadowContractSource::from_path(&root_dir, &settings)?;
+        if let Ok(original) = std::fs::read(root_dir.join("source.json")) {
+            source.remappings =
+                serde_json::from_slice::<ShadowContractSource>(&original)?.remappings;
+        }
+
+        let import_errors = source.validate_imports(&root_dir);
+        if !import_errors.is_empty() {
+            import_errors.iter().for_each(|e| error!("{}", e));
+            bail!("found {} unresolved imports", import_errors.len());
+        }
+        info!("all imports resolved");
+    }
+
+    // override the EVM version, if requested. `forge` reads it from `foundry.toml`, so the
+    // config is regenerated for this build, and the original file is restored when the guard is
+    // dropped, even if the build fails.
+    let original_settings = settings.clone();
+    let config_guard = match &args.evm_version {
+        Some(evm_version) => {
+            let guard = FoundryConfigGuard::new(&root_dir)?;
+            settings.with_evm_version(evm_version)?;
+            settings.generate_config(&root_dir)?;
+            info!("overriding EVM version with {}", settings.evm_version);
+            Some(guard)
+        }
+        None => None,
+    };
+
+    // override the constructor arguments, if requested. they're only written back to
+    // `settings.json` when `--save` is passed.
+    let constructor_args = match (&args.constructor_args, &args.constructor_args_abi) {
+        (Some(hex), _) => Some(
+            hex::decode(hex.trim_start_matches("0x"))
+                .map_err(|e| eyre!("Invalid constructor arguments: {}", e))?,
+        ),
+        (None, Some(abi)) => Some(encode_constructor_args(&abi[0], &abi[1])?),
+        (None, None) => None,
+    };
+    if let Some(constructor_args) = constructor_args {
+        info!("overriding constructor arguments with 0x{}", hex::encode(&constructor_args));
+        settings.constructor_arguments = constructor_args;
+
+        if args.save {
+            let mut saved_settings = original_settings.clone();
+            saved_settings.constructor_arguments = settings.constructor_arguments.clone();
+            std::fs::write(&settings_path, serde_json::to_string_pretty(&saved_settings)?)?;
+            info!("saved constructor arguments to {}", settings_path.display());
+        }
+    }
+
     // compile the contract with the original settings
     let start_time = std::time::Instant::now();
     info!("compiling contract {} with {}...", info.name, settings.compiler_version);
-    let compiler_output = compiler::compile(&args.rpc_url, &root_dir, &settings, &info).await?;
+    let compiler_output = if args.remote {
+        let remote = RemoteCompiler::new(
+            args.remote_compiler_url.as_deref(),
+            args.logs_xyz_api_key.as_deref(),
+        )?;
+        compiler::compile_remote(&remote, &root_dir, &settings, &info).await
+    } else {
+        compiler::compile(
+            &args.rpc_url,
+            &root_dir,
+            &settings,
+            &info,
+            JsonRpcDatabase::request_limiter(args.parallel_requests),
+        )
+        .await
+    };
+    drop(config_guard);
+    let compiler_output =
+        compiler_output.map_err(|e| match e.downcast_ref::<CompilerErrors>() {
+            Some(errors) => {
+                print_compiler_errors(&root_dir, errors);
+                eyre!("build failed with {} errors", errors.0.len())
+            }
+            None => e,
+        })?;
     info!("compiled successfully in {}ms", start_time.elapsed().as_millis());
 
+    // preserve forge's full build output, which is otherwise overwritten by the next build
+    if args.save_artifacts {
+        let artifacts_dir = PathBuf::from_str(&args.artifacts_dir)?;
+        copy_dir(&root_dir.join("out"), &artifacts_dir)
+            .map_err(|e| eyre!("Failed to save build artifacts: {}", e))?;
+        info!("saved build artifacts to {}", artifacts_dir.display());
+    }
+
     if args.reth {
+        warn!("--reth is deprecated and will be removed in the next release. use `shadow generate-reth-config` instead.");
         // check for `shadow.json` in the root directory and load it if it exists.
         let mut reth_config = match std::fs::read_to_string("shadow.json") {
             Ok(contents) => {
@@ -53,3 +158,82 @@ pub async fn compile(args: CompileArgs) -> Result<()> {
 
     Ok(())
 }
+
+/// Prints compiler errors to stderr in the style of `rustc`, with the offending source line
+fn print_compiler_errors(root: &Path, errors: &CompilerErrors) {
+    for error in &errors.0 {
+        eprintln!(
+            "{RED_ANSI_COLOR}error[{}]{RESET_ANSI_COLOR}: {}",
+            error.error_code, error.message
+        );
+        eprintln!(
+            "  {BLUE_ANSI_COLOR}-->{RESET_ANSI_COLOR} {}:{}:{}",
+            error.file, error.line, error.column
+        );
+
+        let source_line = std::fs::read_to_string(root.join(&error.file)).ok().and_then(|source| {
+            source.lines().nth((error.line as usize).checked_sub(1)?).map(str::to_string)
+        });
+        if let Some(source_line) = source_line {
+            let gutter = " ".repeat(error.line.to_string().len());
+            eprintln!("{BLUE_ANSI_COLOR}{gutter} |{RESET_ANSI_COLOR}");
+            eprintln!("{BLUE_ANSI_COLOR}{} |{RESET_ANSI_COLOR} {}", error.line, source_line);
+            eprintln!(
+                "{BLUE_ANSI_COLOR}{gutter} |{RESET_ANSI_COLOR} {}{RED_ANSI_COLOR}^{RESET_ANSI_COLOR}",
+                " ".repeat((error.column as usize).saturating_sub(1))
+            );
+        }
+        eprintln!();
+    }
+}
+
+/// Restores a contract's `foundry.toml` to its exact original contents when dropped, removing it
+/// if it didn't exist
+struct FoundryConfigGuard {
+    path: PathBuf,
+    original: Option<Vec<u8>>,
+}
+
+impl FoundryConfigGuard {
+    fn new(root_dir: &Path) -> Result<Self> {
+        let path = root_dir.join("foundry.toml");
+        let original = match std::fs::read(&path) {
+            Ok(original) => Some(original),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => bail!("failed to read {}: {}", path.display(), e),
+        };
+
+        Ok(Self { path, original })
+    }
+}
+
+impl Drop for FoundryConfigGuard {
+    fn drop(&mut self) {
+        let restored = match &self.original {
+            Some(original) => std::fs::write(&self.path, original),
+            None => std::fs::remove_file(&self.path),
+        };
+        if let Err(e) = restored {
+            error!("failed to restore {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// ABI-encodes constructor arguments from their human-readable types and values, e.g.
+/// `(address,uint256)` and `(0x0000000000000000000000000000000000000001,100)`. The surrounding
+/// parentheses are optional.
+fn encode_constructor_args(types: &str, values: &str) -> Result<Vec<u8>> {
+    let (ty, values) = match DynSolType::parse(types.trim()) {
+        Ok(ty @ DynSolType::Tuple(_)) => (ty, values.trim().to_string()),
+        _ => (
+            DynSolType::parse(&format!("({})", types.trim()))
+                .map_err(|e| eyre!("Invalid constructor argument types: {}", e))?,
+            format!("({})", values.trim()),
+        ),
+    };
+
+    let value =
+        ty.coerce_str(&values).map_err(|e| eyre!("Invalid constructor argument values: {}", e))?;
+
+    Ok(value.abi_encode_params())
+}
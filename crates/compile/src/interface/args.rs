@@ -13,6 +13,66 @@ pub struct CompileArgs {
     pub rpc_url: String,
 
     /// Whether to save the compiled contract to './shadow.json' for use with shadow-reth.
+    /// Deprecated in favor of `shadow generate-reth-config`, and will be removed in the next
+    /// release.
     #[clap(long)]
     pub reth: bool,
+
+    /// Compile for this EVM version instead of the one the contract was originally deployed
+    /// with, e.g. `shanghai` or `cancun`.
+    #[clap(long, required = false)]
+    pub evm_version: Option<String>,
+
+    /// Compile via a remote compiler instead of a local `forge` installation.
+    #[clap(long)]
+    pub remote: bool,
+
+    /// The URL of the remote compiler to use with `--remote`. Defaults to your configured
+    /// `remote_compiler_url`.
+    #[clap(long, required = false)]
+    pub remote_compiler_url: Option<String>,
+
+    /// The API key to use for remote compilation. Defaults to your configured `logs_xyz_api_key`.
+    #[clap(long, required = false)]
+    pub logs_xyz_api_key: Option<String>,
+
+    /// Check that every import in the contract's source resolves before compiling, rather than
+    /// waiting for `forge` to fail.
+    #[clap(long)]
+    pub check_imports: bool,
+
+    /// Compile with these ABI-encoded constructor arguments, as a hex string, instead of the
+    /// ones the contract was originally deployed with.
+    #[clap(long, required = false)]
+    pub constructor_args: Option<String>,
+
+    /// Compile with these constructor arguments, given as their types and values, e.g.
+    /// `--constructor-args-abi "(address,uint256)"
+    /// "(0x0000000000000000000000000000000000000001,100)"`.
+    #[clap(
+        long,
+        num_args = 2,
+        value_names = ["TYPES", "VALUES"],
+        required = false,
+        conflicts_with = "constructor_args"
+    )]
+    pub constructor_args_abi: Option<Vec<String>>,
+
+    /// Save the overridden constructor arguments to `settings.json`.
+    #[clap(long)]
+    pub save: bool,
+
+    /// The maximum number of RPC requests to have in flight at once while simulating the
+    /// deployment. Further requests are queued.
+    #[clap(long, default_value = "10")]
+    pub parallel_requests: usize,
+
+    /// Copy forge's full build output, including source maps, metadata and debug info, to
+    /// `--artifacts-dir` after compiling.
+    #[clap(long, conflicts_with = "remote")]
+    pub save_artifacts: bool,
+
+    /// The directory to copy forge's build output to with `--save-artifacts`.
+    #[clap(long, default_value = "./shadow-artifacts/", requires = "save_artifacts")]
+    pub artifacts_dir: String,
 }
@@ -0,0 +1,58 @@
+use clap_complete::Shell;
+use eyre::{bail, Result};
+use tracing::{info, warn};
+
+use crate::{
+    generate_script,
+    shell::{completion_path, script_hash, INSTALLABLE_SHELLS},
+    CompletionsCheckArgs,
+};
+
+/// The `completions check` subcommand. Regenerates the completion script for each shell with
+/// completions installed, and reports whether the installed script is out of date, replacing it
+/// if `--update` is set.
+pub fn check_completions(args: CompletionsCheckArgs, command: clap::Command) -> Result<()> {
+    let shells = match args.shell {
+        Some(shell) => vec![shell],
+        None => INSTALLABLE_SHELLS
+            .into_iter()
+            .filter(|shell| completion_path(*shell).map_or(false, |path| path.exists()))
+            .collect::<Vec<Shell>>(),
+    };
+    if shells.is_empty() {
+        bail!("no installed completions found. Run `shadow completions <shell> --install`");
+    }
+
+    let mut out_of_date = vec![];
+    for shell in shells {
+        let path = completion_path(shell)?;
+        let installed = match std::fs::read_to_string(&path) {
+            Ok(installed) => installed,
+            Err(_) => bail!(
+                "no completions installed at {}. Run `shadow completions {} --install`",
+                path.display(),
+                shell
+            ),
+        };
+
+        let script = generate_script(shell, command.clone());
+        if script_hash(&installed) == script_hash(&script) {
+            info!("{} completions at {} are up to date", shell, path.display());
+        } else if args.update {
+            std::fs::write(&path, script)?;
+            info!("updated {} completions at {}", shell, path.display());
+        } else {
+            warn!("{} completions at {} are out of date", shell, path.display());
+            out_of_date.push(shell.to_string());
+        }
+    }
+
+    if !out_of_date.is_empty() {
+        bail!(
+            "completions for {} need updating. Run `shadow completions check --update`",
+            out_of_date.join(", ")
+        );
+    }
+
+    Ok(())
+}
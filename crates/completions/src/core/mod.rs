@@ -0,0 +1,130 @@
+mod check;
+
+pub use check::*;
+
+use std::path::PathBuf;
+
+use clap_complete::Shell;
+use eyre::{bail, OptionExt, Result};
+use shadow_common::{prompt, ShadowContractGroupInfo};
+use tracing::{info, warn};
+
+use crate::{
+    shell::{
+        completion_path, completions_loaded, dynamic_completions, rc_file, rc_file_configured,
+        script_hash,
+    },
+    CompletionKind, CompletionsArgs, CompletionsCommand,
+};
+
+/// The `completions` subcommand. Prints, installs, or checks the completion script for a shell.
+/// `command` is the CLI's top-level command, which the script is generated from.
+pub async fn completions(args: CompletionsArgs, command: clap::Command) -> Result<()> {
+    if let Some(kind) = args.complete {
+        return complete(kind);
+    }
+    if let Some(CompletionsCommand::Check(check_args)) = args.command {
+        return check_completions(check_args, command);
+    }
+
+    let shell = args.shell.ok_or_eyre("a shell is required")?;
+    if args.installed {
+        return check_installed(shell, command);
+    }
+
+    let script = generate_script(shell, command);
+    if !args.install {
+        print!("{}", script);
+        return Ok(());
+    }
+
+    let path = completion_path(shell)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, script)?;
+    info!("installed {} completions to {}", shell, path.display());
+
+    // the shell may not load scripts from the completion directory by default
+    if let Some((rc_path, snippet)) = rc_file(shell)? {
+        let rc_contents = std::fs::read_to_string(&rc_path).unwrap_or_default();
+        if !rc_file_configured(shell, &rc_contents) {
+            let answer = prompt(&format!(
+                "{} doesn't load completions from {}. Add them to it? (y/N): ",
+                rc_path.display(),
+                path.parent().unwrap_or(&path).display()
+            ))?;
+            if answer.as_deref() == Some("y") {
+                std::fs::write(
+                    &rc_path,
+                    format!("{}\n# shadow completions\n{}\n", rc_contents, snippet),
+                )?;
+                info!("updated {}", rc_path.display());
+            } else {
+                warn!(
+                    "add the following to {} to load completions:\n{}",
+                    rc_path.display(),
+                    snippet
+                );
+            }
+        }
+    }
+
+    info!("restart your shell, or run `shadow completions {} --installed` to check", shell);
+    Ok(())
+}
+
+/// Generates the completion script for `shell`, including dynamic completions
+pub(crate) fn generate_script(shell: Shell, mut command: clap::Command) -> String {
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut command, "shadow", &mut script);
+
+    format!("{}{}", String::from_utf8_lossy(&script), dynamic_completions(shell))
+}
+
+/// Checks that the installed completion script is up to date and loaded by the shell
+fn check_installed(shell: Shell, command: clap::Command) -> Result<()> {
+    let path = completion_path(shell)?;
+    match std::fs::read_to_string(&path) {
+        Ok(installed)
+            if script_hash(&installed) == script_hash(&generate_script(shell, command)) => {}
+        Ok(_) => bail!(
+            "the completions at {} are out of date. Run `shadow completions {} --install`",
+            path.display(),
+            shell
+        ),
+        Err(_) => bail!(
+            "no completions installed at {}. Run `shadow completions {} --install`",
+            path.display(),
+            shell
+        ),
+    }
+
+    if !completions_loaded(shell)? {
+        bail!("completions are installed at {}, but {} isn't loading them", path.display(), shell);
+    }
+
+    info!("{} completions are installed and loaded", shell);
+    Ok(())
+}
+
+/// Prints the values to complete for `kind`, one per line. Errors are ignored, since there's
+/// nowhere to show them mid-completion.
+fn complete(kind: CompletionKind) -> Result<()> {
+    match kind {
+        CompletionKind::Addresses => {
+            if let Ok(group_info) = ShadowContractGroupInfo::from_path(&PathBuf::from(".")) {
+                for contract in &group_info.contracts {
+                    println!("{:#x}", contract.address);
+                }
+            }
+        }
+        CompletionKind::Cids => {
+            for entry in shadow_catalog::load_catalog().unwrap_or_default() {
+                println!("{}", entry.cid);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+
+/// Values which are completed dynamically, by calling back into `shadow`
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum CompletionKind {
+    /// The addresses of the contracts in the contract group in the current directory
+    Addresses,
+    /// The IPFS CIDs of the contract groups in the local catalog
+    Cids,
+}
+
+/// Arguments for the `completions` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(
+    about = "Generate or install shell completion scripts",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+pub struct CompletionsArgs {
+    /// The shell to generate completions for.
+    #[clap(required_unless_present = "complete")]
+    pub shell: Option<Shell>,
+
+    /// Write the completion script to the shell's completion directory, instead of printing it.
+    #[clap(long, conflicts_with = "installed")]
+    pub install: bool,
+
+    /// Check that the completion script is installed and loaded by the shell.
+    #[clap(long)]
+    pub installed: bool,
+
+    /// Print the values to complete for the given kind, one per line. Used by the completion
+    /// scripts.
+    #[clap(long, hide = true, conflicts_with_all = ["install", "installed"])]
+    pub complete: Option<CompletionKind>,
+
+    #[clap(subcommand)]
+    pub command: Option<CompletionsCommand>,
+}
+
+/// Subcommands of the `completions` subcommand
+#[derive(Debug, Clone, Subcommand)]
+pub enum CompletionsCommand {
+    /// Check whether the installed completion scripts are up to date
+    #[clap(name = "check")]
+    Check(CompletionsCheckArgs),
+}
+
+/// Arguments for the `completions check` subcommand
+#[derive(Debug, Clone, Parser)]
+pub struct CompletionsCheckArgs {
+    /// The shell to check. Defaults to every shell with completions installed.
+    pub shell: Option<Shell>,
+
+    /// Replace any out of date completion scripts with the current ones.
+    #[clap(long)]
+    pub update: bool,
+}
@@ -0,0 +1,2 @@
+/// Shell-specific install locations and scripts
+pub(crate) mod shell;
@@ -0,0 +1,131 @@
+#![allow(deprecated)]
+use std::{env::home_dir, path::PathBuf, process::Command};
+
+use clap_complete::Shell;
+use eyre::{bail, OptionExt, Result};
+use shadow_common::hash::sha256;
+
+/// The shells which completions can be installed for
+pub(crate) const INSTALLABLE_SHELLS: [Shell; 3] = [Shell::Bash, Shell::Zsh, Shell::Fish];
+
+/// Returns the path the completion script for `shell` is installed to
+pub(crate) fn completion_path(shell: Shell) -> Result<PathBuf> {
+    let home = home_dir().ok_or_eyre("failed to get home directory")?;
+    Ok(match shell {
+        Shell::Bash => home.join(".bash_completion.d").join("shadow"),
+        Shell::Zsh => home.join(".zsh").join("completions").join("_shadow"),
+        Shell::Fish => home.join(".config").join("fish").join("completions").join("shadow.fish"),
+        _ => bail!("installing completions for {} is not supported. Print the script with `shadow completions {}` and install it manually.", shell, shell),
+    })
+}
+
+/// Hashes a completion script, ignoring comment lines so version or date stamps don't mark an
+/// otherwise identical script as changed
+pub(crate) fn script_hash(script: &str) -> [u8; 32] {
+    let content = script
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    sha256(content.as_bytes())
+}
+
+/// Returns the shell's rc file, and the lines it needs to load completions from
+/// [`completion_path`]. Fish loads its completion directory automatically.
+pub(crate) fn rc_file(shell: Shell) -> Result<Option<(PathBuf, &'static str)>> {
+    let home = home_dir().ok_or_eyre("failed to get home directory")?;
+    Ok(match shell {
+        Shell::Bash => Some((
+            home.join(".bashrc"),
+            r#"for f in ~/.bash_completion.d/*; do [ -f "$f" ] && . "$f"; done"#,
+        )),
+        Shell::Zsh => Some((
+            home.join(".zshrc"),
+            "fpath=(~/.zsh/completions $fpath)\nautoload -Uz compinit && compinit",
+        )),
+        _ => None,
+    })
+}
+
+/// Whether the rc file already loads completions from the completion directory
+pub(crate) fn rc_file_configured(shell: Shell, rc_contents: &str) -> bool {
+    match shell {
+        Shell::Bash => rc_contents.contains(".bash_completion.d"),
+        Shell::Zsh => rc_contents.contains(".zsh/completions"),
+        _ => true,
+    }
+}
+
+/// Shell code appended to the generated completion script, which completes contract addresses
+/// and catalog CIDs by calling `shadow completions --complete`
+pub(crate) fn dynamic_completions(shell: Shell) -> &'static str {
+    match shell {
+        Shell::Bash => {
+            r#"
+_shadow_dynamic() {
+    _shadow "$@"
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    if [[ ${#COMPREPLY[@]} -eq 0 ]]; then
+        if [[ "${COMP_WORDS[1]}" == "clone" ]]; then
+            COMPREPLY=($(compgen -W "$(shadow completions --complete cids 2>/dev/null)" -- "$cur"))
+        elif [[ "$cur" == 0x* ]]; then
+            COMPREPLY=($(compgen -W "$(shadow completions --complete addresses 2>/dev/null)" -- "$cur"))
+        fi
+    fi
+}
+complete -F _shadow_dynamic -o bashdefault -o default shadow
+"#
+        }
+        Shell::Zsh => {
+            r#"
+_shadow_dynamic() {
+    if [[ "${words[2]}" == "clone" && $CURRENT -eq 3 ]]; then
+        compadd -- ${(f)"$(shadow completions --complete cids 2>/dev/null)"}
+    elif [[ "$PREFIX" == 0x* ]]; then
+        compadd -- ${(f)"$(shadow completions --complete addresses 2>/dev/null)"}
+    else
+        _shadow "$@"
+    fi
+}
+compdef _shadow_dynamic shadow
+"#
+        }
+        Shell::Fish => {
+            r#"
+complete -c shadow -n "__fish_seen_subcommand_from clone" -f -a "(shadow completions --complete cids 2>/dev/null)"
+complete -c shadow -n "string match -q -- '0x*' (commandline -ct)" -f -a "(shadow completions --complete addresses 2>/dev/null)"
+"#
+        }
+        _ => "",
+    }
+}
+
+/// Asks the shell whether it has loaded completions for `shadow`, in a new interactive session
+pub(crate) fn completions_loaded(shell: Shell) -> Result<bool> {
+    let output = match shell {
+        Shell::Bash => Command::new("bash").args(["-ic", "complete -p shadow"]).output()?,
+        Shell::Zsh => {
+            Command::new("zsh").args(["-ic", "print -r -- ${_comps[shadow]}"]).output()?
+        }
+        Shell::Fish => Command::new("fish").args(["-c", "complete -C'shadow '"]).output()?,
+        _ => bail!("checking completions for {} is not supported", shell),
+    };
+
+    Ok(output.status.success() && !String::from_utf8_lossy(&output.stdout).trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_hash_ignores_comments() {
+        let script = "#compdef shadow\n# generated 2024-06-01\n_shadow() {\n}\n";
+        let restamped = "#compdef shadow\n  # generated 2024-07-15\n_shadow() {\n}\n";
+        let changed = "#compdef shadow\n_shadow() {\n    _arguments\n}\n";
+
+        assert_eq!(script_hash(script), script_hash(restamped));
+        assert_ne!(script_hash(script), script_hash(changed));
+    }
+}
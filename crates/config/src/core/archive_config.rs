@@ -0,0 +1,309 @@
+use std::{collections::BTreeMap, path::Path};
+
+use crate::{
+    constants::{GREEN_ANSI_COLOR, RESET_ANSI_COLOR, SENSITIVE_KEYS, YELLOW_ANSI_COLOR},
+    ArchiveConfigArgs, Configuration,
+};
+use eyre::{bail, eyre, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Written at the top of TOML archives, since JSON has no comments
+const TOML_HEADER: &str =
+    "# Exported by `shadow archive-config`. Import it with `shadow archive-config --import <path>`.
+# The values of the keys listed in `sensitive` are secrets. Unless exported with
+# `--include-secrets`, they're `${ENV_VAR}` placeholders, which are read from the environment
+# on import.
+";
+
+/// The file written by `archive-config --export`, and read by `--import`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ConfigArchive {
+    /// The keys whose values are secrets
+    #[serde(default)]
+    sensitive: Vec<String>,
+    /// Configuration values, as in `~/.shadow/config.json`. Unset values are omitted.
+    config: BTreeMap<String, Value>,
+}
+
+/// What happened to a value in an archive when it was imported
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ImportOutcome {
+    Imported,
+    Unchanged,
+    /// The value is already set to something else, and `--overwrite` wasn't passed
+    Conflict,
+    /// The value has a placeholder for an environment variable which isn't set
+    MissingEnvVar(String),
+}
+
+/// The `archive-config` command exports the configuration to a portable file, so it can be
+/// shared with other team members, or merges such a file into the configuration.
+pub fn archive_config(args: ArchiveConfigArgs) -> Result<()> {
+    // env values are deliberately ignored, so they're neither exported nor saved to disk
+    let mut config = Configuration::load_from_disk()?.unwrap_or_default();
+
+    if let Some(path) = &args.export {
+        let archive = build_archive(&config, args.include_secrets);
+        let contents = if is_toml(path) {
+            format!("{}\n{}", TOML_HEADER, toml::to_string_pretty(&archive)?)
+        } else {
+            serde_json::to_string_pretty(&archive)?
+        };
+        std::fs::write(path, contents)?;
+
+        println!(
+            "{GREEN_ANSI_COLOR}Success: {RESET_ANSI_COLOR}exported {} values to {}.",
+            archive.config.len(),
+            path
+        );
+        if !args.include_secrets && !archive.sensitive.is_empty() {
+            println!(
+                "Sensitive values were replaced with placeholders: {}. Use `--include-secrets` to export them.",
+                archive.sensitive.join(", ")
+            );
+        }
+        return Ok(());
+    }
+
+    let Some(path) = &args.import else {
+        bail!("either --export or --import must be provided");
+    };
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| eyre!("failed to read config archive {}: {}", path, e))?;
+    let archive: ConfigArchive =
+        if is_toml(path) { toml::from_str(&contents)? } else { serde_json::from_str(&contents)? };
+
+    let outcomes =
+        merge_archive(&mut config, archive, args.overwrite, |name| std::env::var(name).ok())?;
+    let mut imported = 0;
+    for (key, outcome) in &outcomes {
+        match outcome {
+            ImportOutcome::Imported => {
+                println!("- imported {}", key);
+                imported += 1;
+            }
+            ImportOutcome::Unchanged => {}
+            ImportOutcome::Conflict => println!(
+                "{YELLOW_ANSI_COLOR}- skipped {}{RESET_ANSI_COLOR}, which is already set. Use `--overwrite` to replace it.",
+                key
+            ),
+            ImportOutcome::MissingEnvVar(name) => println!(
+                "{YELLOW_ANSI_COLOR}- skipped {}{RESET_ANSI_COLOR}, since the environment variable {} isn't set.",
+                key, name
+            ),
+        }
+    }
+    if imported > 0 {
+        config.save()?;
+    }
+
+    println!(
+        "\n{GREEN_ANSI_COLOR}Success: {RESET_ANSI_COLOR}imported {} values into ~/.shadow/config.json.",
+        imported
+    );
+
+    Ok(())
+}
+
+/// Whether an archive path should be read and written as TOML, rather than JSON
+fn is_toml(path: &str) -> bool {
+    Path::new(path).extension().is_some_and(|extension| extension == "toml")
+}
+
+/// The placeholder a sensitive value is exported as, unless `--include-secrets` is passed
+fn placeholder(env_var: &str) -> Value {
+    Value::String(format!("${{{}}}", env_var))
+}
+
+/// The environment variable an RPC profile's placeholder refers to, e.g. `BASE_RPC_URL`
+fn profile_env_var(name: &str) -> String {
+    format!("{}_RPC_URL", name.to_uppercase().replace('-', "_"))
+}
+
+/// Builds an archive of the set values in `config`, with sensitive values replaced by
+/// placeholders unless `include_secrets` is set. Placeholders are named after the environment
+/// variables the CLI already reads, so importing them on a configured machine just works.
+fn build_archive(config: &Configuration, include_secrets: bool) -> ConfigArchive {
+    let mut archive = ConfigArchive::default();
+    for (key, value) in config.entries() {
+        let Some(value) = value else {
+            continue;
+        };
+
+        let value = if !SENSITIVE_KEYS.contains(&key) {
+            Value::String(value.clone())
+        } else {
+            archive.sensitive.push(key.to_string());
+            if include_secrets {
+                Value::String(value.clone())
+            } else {
+                placeholder(&key.to_uppercase())
+            }
+        };
+        archive.config.insert(key.to_string(), value);
+    }
+
+    // like `rpc_url`, profile URLs often embed a provider API key
+    if !config.rpc_profiles.is_empty() {
+        archive.sensitive.push("rpc_profiles".to_string());
+        let profiles = config
+            .rpc_profiles
+            .iter()
+            .map(|(name, url)| {
+                let url = if include_secrets {
+                    Value::String(url.clone())
+                } else {
+                    placeholder(&profile_env_var(name))
+                };
+                (name.clone(), url)
+            })
+            .collect::<Map<_, _>>();
+        archive.config.insert("rpc_profiles".to_string(), Value::Object(profiles));
+    }
+
+    archive
+}
+
+/// Merges an archive into `config`, substituting `${ENV_VAR}` placeholders with `env`. Values
+/// which are already set are only replaced if `overwrite` is set. Returns what happened to each
+/// value, without saving the configuration.
+fn merge_archive(
+    config: &mut Configuration,
+    archive: ConfigArchive,
+    overwrite: bool,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<Vec<(String, ImportOutcome)>> {
+    // each value to import, along with its current value
+    let mut values = vec![];
+    for (key, value) in archive.config {
+        if key == "rpc_profiles" {
+            let Value::Object(profiles) = value else {
+                bail!("rpc_profiles must be an object of profile names to RPC URLs");
+            };
+            for (name, url) in profiles {
+                let Value::String(url) = url else {
+                    bail!("the URL of RPC profile '{}' must be a string", name);
+                };
+                let current = config.rpc_profiles.get(&name).cloned();
+                values.push((format!("rpc_profiles.{}", name), url, current));
+            }
+            continue;
+        }
+
+        let Value::String(value) = value else {
+            bail!("the value of '{}' must be a string", key);
+        };
+        let current = config
+            .entries()
+            .into_iter()
+            .find(|(entry_key, _)| *entry_key == key)
+            .ok_or_else(|| eyre!("invalid key '{}'", key))?
+            .1
+            .cloned();
+        values.push((key, value, current));
+    }
+
+    let mut outcomes = vec![];
+    for (key, value, current) in values {
+        let value = match substitute_env(&value, &env) {
+            Ok(value) => value,
+            Err(name) => {
+                outcomes.push((key, ImportOutcome::MissingEnvVar(name)));
+                continue;
+            }
+        };
+
+        let outcome = match current {
+            Some(current) if current == value => ImportOutcome::Unchanged,
+            Some(_) if !overwrite => ImportOutcome::Conflict,
+            _ => {
+                match key.strip_prefix("rpc_profiles.") {
+                    Some(name) => {
+                        config.rpc_profiles.insert(name.to_string(), value);
+                    }
+                    None => config.set_value(&key, &value)?,
+                }
+                ImportOutcome::Imported
+            }
+        };
+        outcomes.push((key, outcome));
+    }
+
+    Ok(outcomes)
+}
+
+/// Replaces each `${NAME}` in a value with the environment variable `NAME`. Returns the name of
+/// the first variable which isn't set, if any.
+fn substitute_env(
+    value: &str,
+    env: impl Fn(&str) -> Option<String>,
+) -> std::result::Result<String, String> {
+    let mut substituted = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(length) = rest[start + 2..].find('}') else {
+            break;
+        };
+
+        let name = &rest[start + 2..start + 2 + length];
+        substituted.push_str(&rest[..start]);
+        substituted.push_str(&env(name).ok_or_else(|| name.to_string())?);
+        rest = &rest[start + 3 + length..];
+    }
+    substituted.push_str(rest);
+
+    Ok(substituted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substitute_env() {
+        let env = |name: &str| (name == "KEY").then(|| "secret".to_string());
+        assert_eq!(substitute_env("plain", env), Ok("plain".to_string()));
+        assert_eq!(
+            substitute_env("https://rpc.example/${KEY}/${KEY}", env),
+            Ok("https://rpc.example/secret/secret".to_string())
+        );
+        assert_eq!(substitute_env("${MISSING}", env), Err("MISSING".to_string()));
+        assert_eq!(substitute_env("${unterminated", env), Ok("${unterminated".to_string()));
+    }
+
+    #[test]
+    fn test_archive_round_trip() {
+        let mut exported = Configuration {
+            etherscan_api_key: Some("etherscan".to_string()),
+            ipfs_gateway_url: Some("https://gateway.example".to_string()),
+            ..Default::default()
+        };
+        exported.rpc_profiles.insert("base".to_string(), "https://base.example/key".to_string());
+
+        let archive = build_archive(&exported, false);
+        assert_eq!(archive.sensitive, vec!["etherscan_api_key", "rpc_profiles"]);
+        assert_eq!(archive.config["etherscan_api_key"], "${ETHERSCAN_API_KEY}");
+        assert_eq!(archive.config["rpc_profiles"]["base"], "${BASE_RPC_URL}");
+
+        // archives survive being written as TOML
+        let archive: ConfigArchive =
+            toml::from_str(&toml::to_string_pretty(&archive).unwrap()).unwrap();
+
+        let mut config = Configuration {
+            ipfs_gateway_url: Some("https://other.example".to_string()),
+            ..Default::default()
+        };
+        let env = |name: &str| (name == "BASE_RPC_URL").then(|| "https://base.example".to_string());
+        let outcomes = merge_archive(&mut config, archive, false, env).unwrap();
+        assert!(outcomes.contains(&(
+            "etherscan_api_key".to_string(),
+            ImportOutcome::MissingEnvVar("ETHERSCAN_API_KEY".to_string())
+        )));
+        assert!(outcomes.contains(&("ipfs_gateway_url".to_string(), ImportOutcome::Conflict)));
+        assert!(outcomes.contains(&("rpc_profiles.base".to_string(), ImportOutcome::Imported)));
+        assert_eq!(config.etherscan_api_key, None);
+        assert_eq!(config.ipfs_gateway_url.as_deref(), Some("https://other.example"));
+        assert_eq!(config.rpc_profiles["base"], "https://base.example");
+    }
+}
@@ -0,0 +1,81 @@
+use std::io::Write;
+
+use crate::{
+    constants::{GREEN_ANSI_COLOR, RED_ANSI_COLOR, RESET_ANSI_COLOR, YELLOW_ANSI_COLOR},
+    CleanConfigArgs, Configuration,
+};
+use eyre::Result;
+
+/// The `clean-config` command checks each value in `~/.shadow/config.json` against the service
+/// it's used with, and offers to remove those which are invalid.
+pub async fn clean_config(args: CleanConfigArgs) -> Result<()> {
+    if args.reset {
+        if args.yes || confirm("Delete ~/.shadow/config.json?")? {
+            Configuration::reset()?;
+            println!("{GREEN_ANSI_COLOR}Success: {RESET_ANSI_COLOR}deleted ~/.shadow/config.json.");
+        }
+        return Ok(());
+    }
+
+    let Some(mut config) = Configuration::load_from_disk()? else {
+        println!("~/.shadow/config.json doesn't exist, so there's nothing to clean.");
+        return Ok(());
+    };
+
+    println!("Checking ~/.shadow/config.json...\n");
+    let invalid = config.validate().await;
+    if invalid.is_empty() {
+        println!("{GREEN_ANSI_COLOR}Success: {RESET_ANSI_COLOR}every value is valid.");
+        return Ok(());
+    }
+
+    // values whose service couldn't be reached weren't actually checked, so they're kept
+    let (unreachable, invalid): (Vec<_>, Vec<_>) =
+        invalid.into_iter().partition(|entry| entry.unreachable);
+    for entry in &unreachable {
+        println!(
+            "{YELLOW_ANSI_COLOR}Unreachable, skipped: {RESET_ANSI_COLOR}{}: {}",
+            entry.key, entry.reason
+        );
+    }
+
+    let mut removed = 0;
+    for entry in &invalid {
+        println!("{RED_ANSI_COLOR}Invalid: {RESET_ANSI_COLOR}{}: {}", entry.key, entry.reason);
+        if args.yes || confirm(&format!("Remove {}?", entry.key))? {
+            config.unset(&entry.key)?;
+            removed += 1;
+        }
+    }
+    println!(
+        "\n{GREEN_ANSI_COLOR}Success: {RESET_ANSI_COLOR}removed {} of {} invalid values.",
+        removed,
+        invalid.len()
+    );
+    if !unreachable.is_empty() {
+        println!(
+            "{} values couldn't be checked, and were kept. Run `shadow clean-config` again once their services are reachable.",
+            unreachable.len()
+        );
+    }
+
+    // values set in the environment take precedence over the file, and can't be removed here
+    let env_config = Configuration::load_from_env()?;
+    for (key, value) in env_config.entries() {
+        if value.is_some() {
+            println!("{YELLOW_ANSI_COLOR}Warning: {RESET_ANSI_COLOR}{key} is also set in the environment, which overrides ~/.shadow/config.json.");
+        }
+    }
+
+    Ok(())
+}
+
+/// Asks a yes or no question on stdin. Anything but `y` or `yes` is a no.
+fn confirm(question: &str) -> Result<bool> {
+    print!("{question} [y/N] ");
+    std::io::stdout().flush()?;
+
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    Ok(matches!(input.trim().to_lowercase().as_str(), "y" | "yes"))
+}
@@ -0,0 +1,32 @@
+use crate::{
+    constants::{GREEN_ANSI_COLOR, RESET_ANSI_COLOR},
+    Configuration, MigrateConfigArgs,
+};
+use eyre::Result;
+
+/// The `migrate-config` command updates `~/.shadow/config.json` from the format used by older
+/// versions of the CLI: keys which were renamed are moved to their new names, keys which are no
+/// longer used are removed, and missing keys are added with their defaults.
+pub fn migrate_config(_args: MigrateConfigArgs) -> Result<()> {
+    let Some(changes) = Configuration::migrate()? else {
+        println!("~/.shadow/config.json doesn't exist, so there's nothing to migrate.");
+        return Ok(());
+    };
+
+    if changes.is_empty() {
+        println!(
+            "{GREEN_ANSI_COLOR}Success: {RESET_ANSI_COLOR}~/.shadow/config.json is already up to date."
+        );
+        return Ok(());
+    }
+
+    for change in &changes {
+        println!("- {}", change);
+    }
+    println!(
+        "\n{GREEN_ANSI_COLOR}Success: {RESET_ANSI_COLOR}made {} changes to ~/.shadow/config.json.",
+        changes.len()
+    );
+
+    Ok(())
+}
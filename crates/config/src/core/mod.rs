@@ -1,6 +1,16 @@
+mod archive_config;
+mod clean_config;
+mod migrate_config;
+mod print_env;
+
+pub use archive_config::*;
+pub use clean_config::*;
+pub use migrate_config::*;
+pub use print_env::*;
+
 use crate::{
     constants::{GREEN_ANSI_COLOR, RED_ANSI_COLOR, RESET_ANSI_COLOR},
-    ConfigArgs, Configuration,
+    ConfigArgs, ConfigCommand, Configuration,
 };
 use eyre::Result;
 
@@ -12,6 +22,18 @@ pub fn config(args: ConfigArgs) -> Result<()> {
         return Ok(());
     }
 
+    if let Some(ConfigCommand::SetProfile(profile)) = args.command {
+        let mut config = Configuration::load()?;
+        match config.set_profile(&profile.name, &profile.url) {
+            Ok(_) => println!(
+                "{GREEN_ANSI_COLOR}Success: {RESET_ANSI_COLOR}profile '{}' set to '{}'. Use it with `--profile {}`.",
+                profile.name, profile.url, profile.name
+            ),
+            Err(e) => println!("{RED_ANSI_COLOR}Error: {RESET_ANSI_COLOR}{}", e),
+        };
+        return Ok(());
+    }
+
     if !args.key.is_empty() {
         if !args.value.is_empty() {
             let mut config = Configuration::load()?;
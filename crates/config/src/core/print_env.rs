@@ -0,0 +1,66 @@
+use crate::{
+    constants::{
+        GRAY_ANSI_COLOR, GREEN_ANSI_COLOR, RESET_ANSI_COLOR, SENSITIVE_KEYS, YELLOW_ANSI_COLOR,
+    },
+    Configuration, PrintEnvArgs,
+};
+use eyre::Result;
+use std::collections::BTreeMap;
+
+/// The `print-env` command displays the resolved configuration, and whether each value was
+/// loaded from disk or from the environment.
+pub fn print_env(args: PrintEnvArgs) -> Result<()> {
+    let disk_config = Configuration::load_from_disk()?;
+    let env_config = Configuration::load_from_env()?;
+
+    let disk_entries = disk_config.as_ref().map(|c| c.entries());
+    if disk_entries.as_ref().map_or(true, |entries| entries.iter().all(|(_, v)| v.is_none())) {
+        println!("{YELLOW_ANSI_COLOR}Warning: {RESET_ANSI_COLOR}~/.shadow/config.json is missing or empty. Only environment variables are active.\n");
+    }
+
+    for (i, (key, env_value)) in env_config.entries().into_iter().enumerate() {
+        let disk_value = disk_entries.as_ref().and_then(|entries| entries[i].1);
+        let (value, source) = match (env_value, disk_value) {
+            (Some(value), _) => (value, "env"),
+            (None, Some(value)) => (value, "disk"),
+            (None, None) => {
+                println!("{GREEN_ANSI_COLOR}{key}{RESET_ANSI_COLOR}: {GRAY_ANSI_COLOR}unset{RESET_ANSI_COLOR}");
+                continue;
+            }
+        };
+
+        let value = if args.reveal || !SENSITIVE_KEYS.contains(&key) {
+            value.to_string()
+        } else {
+            mask(value)
+        };
+        println!("{GREEN_ANSI_COLOR}{key}{RESET_ANSI_COLOR}: {value} {GRAY_ANSI_COLOR}({source}){RESET_ANSI_COLOR}");
+    }
+
+    // RPC profiles are merged by name, with env taking precedence. like `rpc_url`, their URLs
+    // are masked
+    let mut profiles = disk_config
+        .map(|c| c.rpc_profiles.into_iter().map(|(name, url)| (name, (url, "disk"))).collect())
+        .unwrap_or_else(BTreeMap::new);
+    profiles.extend(env_config.rpc_profiles.into_iter().map(|(name, url)| (name, (url, "env"))));
+    for (name, (url, source)) in profiles {
+        let url = if args.reveal { url } else { mask(&url) };
+        println!("{GREEN_ANSI_COLOR}rpc_profiles.{name}{RESET_ANSI_COLOR}: {url} {GRAY_ANSI_COLOR}({source}){RESET_ANSI_COLOR}");
+    }
+
+    Ok(())
+}
+
+/// Masks all but the first and last 4 characters of a sensitive value
+fn mask(value: &str) -> String {
+    let chars = value.chars().collect::<Vec<_>>();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+
+    format!(
+        "{}...{}",
+        chars[..4].iter().collect::<String>(),
+        chars[chars.len() - 4..].iter().collect::<String>()
+    )
+}
@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 /// Arguments for the `config` subcommand
 #[derive(Debug, Clone, Parser)]
@@ -18,4 +18,75 @@ pub struct ConfigArgs {
     /// Whether to enter interactive mode.
     #[clap(long, short)]
     pub interactive: bool,
+
+    #[clap(subcommand)]
+    pub command: Option<ConfigCommand>,
+}
+
+/// Subcommands of the `config` subcommand
+#[derive(Debug, Clone, Subcommand)]
+pub enum ConfigCommand {
+    /// Add or replace a named RPC URL, which can be selected with `--profile <name>`
+    #[clap(name = "set-profile")]
+    SetProfile(SetProfileArgs),
+}
+
+/// Arguments for the `config set-profile` subcommand
+#[derive(Debug, Clone, Parser)]
+pub struct SetProfileArgs {
+    /// The name of the profile, e.g. `base`.
+    pub name: String,
+
+    /// The profile's RPC URL.
+    pub url: String,
+}
+
+/// Arguments for the `print-env` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Display the resolved shadow CLI configuration and where each value comes from.")]
+pub struct PrintEnvArgs {
+    /// Whether to show sensitive values, such as API keys, in full.
+    #[clap(long)]
+    pub reveal: bool,
+}
+
+/// Arguments for the `clean-config` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Find and remove stale or invalid values from your shadow CLI configuration.")]
+pub struct CleanConfigArgs {
+    /// Remove every invalid value without prompting.
+    #[clap(long, short)]
+    pub yes: bool,
+
+    /// Delete `~/.shadow/config.json` entirely.
+    #[clap(long)]
+    pub reset: bool,
+}
+
+/// Arguments for the `migrate-config` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Migrate your shadow CLI configuration from an older version of the CLI.")]
+pub struct MigrateConfigArgs {}
+
+/// Arguments for the `archive-config` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Export your shadow CLI configuration to a portable file, or import one.")]
+pub struct ArchiveConfigArgs {
+    /// Export the configuration to this file. Written as TOML if it ends in `.toml`, and JSON
+    /// otherwise.
+    #[clap(long, required_unless_present = "import", conflicts_with = "import")]
+    pub export: Option<String>,
+
+    /// Merge the values in this file into the configuration. `${ENV_VAR}` placeholders are
+    /// replaced with the value of the environment variable.
+    #[clap(long)]
+    pub import: Option<String>,
+
+    /// Export sensitive values, such as API keys and RPC URLs, rather than placeholders.
+    #[clap(long, requires = "export")]
+    pub include_secrets: bool,
+
+    /// Replace values which are already set when importing.
+    #[clap(long, requires = "import")]
+    pub overwrite: bool,
 }
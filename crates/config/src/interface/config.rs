@@ -1,9 +1,19 @@
 #![allow(deprecated)]
-use std::{env::home_dir, io::Write};
+use std::{collections::HashMap, env::home_dir, io::Write};
 
-use crate::constants::{GREEN_ANSI_COLOR, PURPLE_ANSI_COLOR, RESET_ANSI_COLOR};
+use crate::{
+    constants::{GREEN_ANSI_COLOR, PURPLE_ANSI_COLOR, RESET_ANSI_COLOR},
+    validate::{
+        check_etherscan_api_key, check_pinata_api_keys, check_rpc_url, check_url, CheckFailure,
+    },
+};
 use eyre::{eyre, OptionExt, Result};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Keys used by older versions of the CLI, and the keys which replaced them
+const RENAMED_KEYS: [(&str, &str); 2] =
+    [("ipfs_api_key", "pinata_api_key"), ("ipfs_secret_api_key", "pinata_secret_api_key")];
 
 /// The [`Configuration`] struct represents the configuration of the CLI.
 #[derive(Deserialize, Serialize, Debug, Default)]
@@ -16,8 +26,60 @@ pub struct Configuration {
     pub pinata_api_key: Option<String>,
     /// The secret API key to use for IPFS interactions.
     pub pinata_secret_api_key: Option<String>,
+    /// The API key to use when pinning with Fleek instead of Pinata.
+    pub fleek_api_key: Option<String>,
+    /// The API secret to use when pinning with Fleek instead of Pinata.
+    pub fleek_api_secret: Option<String>,
+    /// The URL of a local IPFS node's HTTP API, used by `shadow pin-local`. Defaults to
+    /// `http://127.0.0.1:5001`.
+    pub local_ipfs_api_url: Option<String>,
     /// Your RPC URL.
     pub rpc_url: Option<String>,
+    /// The API key to use for logs.xyz interactions, such as remote compilation.
+    pub logs_xyz_api_key: Option<String>,
+    /// The URL of the remote compiler used by `--remote`.
+    pub remote_compiler_url: Option<String>,
+    /// Named RPC URLs, selected with `--profile <name>`.
+    #[serde(default)]
+    pub rpc_profiles: HashMap<String, String>,
+    /// The RPC profile to use when neither `--rpc-url` nor `--profile` is given.
+    pub default_profile: Option<String>,
+    /// A file to also write logs to, as with `--log-file`. A directory may be given, in which
+    /// case a timestamped log file is created in it.
+    pub log_file_path: Option<String>,
+}
+
+/// A configuration value which failed validation
+#[derive(Debug, Clone)]
+pub struct InvalidEntry {
+    /// The configuration key, or `rpc_profiles.<name>` for an RPC profile
+    pub key: String,
+    /// Why the value is invalid
+    pub reason: String,
+    /// Whether the value's service couldn't be reached, so the value wasn't actually checked.
+    /// These values are reported, but never removed.
+    pub unreachable: bool,
+}
+
+/// A change made to `~/.shadow/config.json` by [`Configuration::migrate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigMigration {
+    /// A key used by an older version was renamed to its current name
+    Renamed { from: String, to: String },
+    /// A key which isn't used by this version was removed
+    Removed(String),
+    /// A missing key was added with its default value
+    Added(String),
+}
+
+impl std::fmt::Display for ConfigMigration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigMigration::Renamed { from, to } => write!(f, "renamed {} to {}", from, to),
+            ConfigMigration::Removed(key) => write!(f, "removed {}", key),
+            ConfigMigration::Added(key) => write!(f, "added {}", key),
+        }
+    }
 }
 
 #[allow(deprecated)]
@@ -46,19 +108,138 @@ impl Configuration {
             pinata_secret_api_key: env_config
                 .pinata_secret_api_key
                 .or(config.pinata_secret_api_key),
+            fleek_api_key: env_config.fleek_api_key.or(config.fleek_api_key),
+            fleek_api_secret: env_config.fleek_api_secret.or(config.fleek_api_secret),
+            local_ipfs_api_url: env_config.local_ipfs_api_url.or(config.local_ipfs_api_url),
             rpc_url: env_config.rpc_url.or(config.rpc_url),
+            logs_xyz_api_key: env_config.logs_xyz_api_key.or(config.logs_xyz_api_key),
+            remote_compiler_url: env_config.remote_compiler_url.or(config.remote_compiler_url),
+            // profiles from env override profiles on disk with the same name
+            rpc_profiles: config.rpc_profiles.into_iter().chain(env_config.rpc_profiles).collect(),
+            default_profile: env_config.default_profile.or(config.default_profile),
+            log_file_path: env_config.log_file_path.or(config.log_file_path),
         };
 
         Ok(config)
     }
 
-    /// Loads configuration from env with envy
-    fn load_from_env() -> Result<Self> {
-        envy::from_env::<Configuration>().map_err(Into::into)
+    /// Loads configuration from `~/.shadow/config.json`, if it exists
+    pub(crate) fn load_from_disk() -> Result<Option<Self>> {
+        let mut config_path = home_dir().ok_or_eyre("failed to get home directory")?;
+        config_path.push(".shadow");
+        config_path.push("config.json");
+
+        if !config_path.exists() {
+            return Ok(None);
+        }
+
+        let config = std::fs::read_to_string(config_path)?;
+        Ok(Some(serde_json::from_str(&config)?))
+    }
+
+    /// Loads `~/.shadow/config.json` as raw JSON, if it exists
+    fn load_raw_from_disk() -> Result<Option<Map<String, Value>>> {
+        let mut config_path = home_dir().ok_or_eyre("failed to get home directory")?;
+        config_path.push(".shadow");
+        config_path.push("config.json");
+
+        if !config_path.exists() {
+            return Ok(None);
+        }
+
+        let config = std::fs::read_to_string(config_path)?;
+        Ok(Some(serde_json::from_str(&config)?))
+    }
+
+    /// Returns the keys in `~/.shadow/config.json` which aren't used by this version of the CLI,
+    /// such as those written by older versions
+    pub fn stale_keys() -> Result<Vec<String>> {
+        let known_keys = Self::known_keys();
+        Ok(Self::load_raw_from_disk()?
+            .map(|raw| {
+                raw.keys().filter(|key| !known_keys.contains(&key.as_str())).cloned().collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Migrates `~/.shadow/config.json` from an older version of the CLI, and saves it. Returns
+    /// the changes made, or None if it doesn't exist.
+    pub fn migrate() -> Result<Option<Vec<ConfigMigration>>> {
+        let Some(raw) = Self::load_raw_from_disk()? else {
+            return Ok(None);
+        };
+
+        let (config, changes) = Self::migrate_json(raw)?;
+        if !changes.is_empty() {
+            config.save()?;
+        }
+
+        Ok(Some(changes))
+    }
+
+    /// Renames the keys used by older versions to their current names, removes unknown keys,
+    /// and adds missing keys with their defaults
+    fn migrate_json(mut raw: Map<String, Value>) -> Result<(Self, Vec<ConfigMigration>)> {
+        let mut changes = vec![];
+        for (from, to) in RENAMED_KEYS {
+            let Some(value) = raw.remove(from) else {
+                continue;
+            };
+
+            // a value set under the current name takes precedence
+            if raw.get(to).map_or(true, Value::is_null) {
+                raw.insert(to.to_string(), value);
+                changes
+                    .push(ConfigMigration::Renamed { from: from.to_string(), to: to.to_string() });
+            } else {
+                changes.push(ConfigMigration::Removed(from.to_string()));
+            }
+        }
+
+        let known_keys = Self::known_keys();
+        raw.retain(|key, _| {
+            let known = known_keys.contains(&key.as_str());
+            if !known {
+                changes.push(ConfigMigration::Removed(key.clone()));
+            }
+            known
+        });
+        for key in known_keys {
+            if !raw.contains_key(key) {
+                changes.push(ConfigMigration::Added(key.to_string()));
+            }
+        }
+
+        let config = serde_json::from_value(Value::Object(raw))
+            .map_err(|e| eyre!("~/.shadow/config.json has an invalid value: {}", e))?;
+        Ok((config, changes))
+    }
+
+    /// Returns every key used by this version of the CLI
+    fn known_keys() -> Vec<&'static str> {
+        Self::default()
+            .entries()
+            .into_iter()
+            .map(|(key, _)| key)
+            .chain(std::iter::once("rpc_profiles"))
+            .collect()
+    }
+
+    /// Loads configuration from env with envy. RPC profiles are read from
+    /// `SHADOW_RPC_PROFILES`, as a JSON object of profile names to RPC URLs.
+    pub(crate) fn load_from_env() -> Result<Self> {
+        let mut config = envy::from_env::<Configuration>()?;
+        if let Ok(profiles) = std::env::var("SHADOW_RPC_PROFILES") {
+            config.rpc_profiles = serde_json::from_str(&profiles).map_err(|e| {
+                eyre!("SHADOW_RPC_PROFILES must be a JSON object of names to RPC URLs: {}", e)
+            })?;
+        }
+
+        Ok(config)
     }
 
     /// Saves the configuration to disk.
-    fn save(&self) -> Result<()> {
+    pub(crate) fn save(&self) -> Result<()> {
         let mut config_path = home_dir().ok_or_eyre("failed to get home directory")?;
         config_path.push(".shadow");
 
@@ -74,19 +255,156 @@ impl Configuration {
         Ok(())
     }
 
+    /// Returns each configuration key alongside its value
+    pub(crate) fn entries(&self) -> [(&'static str, Option<&String>); 12] {
+        [
+            ("etherscan_api_key", self.etherscan_api_key.as_ref()),
+            ("ipfs_gateway_url", self.ipfs_gateway_url.as_ref()),
+            ("pinata_api_key", self.pinata_api_key.as_ref()),
+            ("pinata_secret_api_key", self.pinata_secret_api_key.as_ref()),
+            ("fleek_api_key", self.fleek_api_key.as_ref()),
+            ("fleek_api_secret", self.fleek_api_secret.as_ref()),
+            ("local_ipfs_api_url", self.local_ipfs_api_url.as_ref()),
+            ("rpc_url", self.rpc_url.as_ref()),
+            ("logs_xyz_api_key", self.logs_xyz_api_key.as_ref()),
+            ("remote_compiler_url", self.remote_compiler_url.as_ref()),
+            ("default_profile", self.default_profile.as_ref()),
+            ("log_file_path", self.log_file_path.as_ref()),
+        ]
+    }
+
+    /// Checks each configured value against the service it's used with, and returns those which
+    /// are invalid or whose service was unreachable. Values which can't be checked, like the
+    /// logs.xyz API key, are assumed valid.
+    pub async fn validate(&self) -> Vec<InvalidEntry> {
+        let mut checks = vec![];
+        if let Some(rpc_url) = &self.rpc_url {
+            checks.push(("rpc_url".to_string(), check_rpc_url(rpc_url).await));
+        }
+        for (name, url) in &self.rpc_profiles {
+            checks.push((format!("rpc_profiles.{}", name), check_rpc_url(url).await));
+        }
+        if let Some(profile) = &self.default_profile {
+            let result = if self.rpc_profiles.contains_key(profile) {
+                Ok(())
+            } else {
+                Err(CheckFailure::Invalid(format!("there is no RPC profile named '{}'", profile)))
+            };
+            checks.push(("default_profile".to_string(), result));
+        }
+        if let Some(gateway_url) = &self.ipfs_gateway_url {
+            checks.push(("ipfs_gateway_url".to_string(), check_url(gateway_url).await));
+        }
+        if let Some(compiler_url) = &self.remote_compiler_url {
+            checks.push(("remote_compiler_url".to_string(), check_url(compiler_url).await));
+        }
+        if let Some(api_key) = &self.etherscan_api_key {
+            checks.push(("etherscan_api_key".to_string(), check_etherscan_api_key(api_key).await));
+        }
+        if let (Some(api_key), Some(secret_api_key)) =
+            (&self.pinata_api_key, &self.pinata_secret_api_key)
+        {
+            if let Err(reason) = check_pinata_api_keys(api_key, secret_api_key).await {
+                checks.push(("pinata_api_key".to_string(), Err(reason.clone())));
+                checks.push(("pinata_secret_api_key".to_string(), Err(reason)));
+            }
+        }
+
+        checks
+            .into_iter()
+            .filter_map(|(key, result)| {
+                result.err().map(|failure| match failure {
+                    CheckFailure::Invalid(reason) => {
+                        InvalidEntry { key, reason, unreachable: false }
+                    }
+                    CheckFailure::Unreachable(reason) => {
+                        InvalidEntry { key, reason, unreachable: true }
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Removes a value, and saves the configuration. `rpc_profiles.<name>` removes a profile.
+    pub fn unset(&mut self, key: &str) -> Result<()> {
+        match key {
+            "etherscan_api_key" => self.etherscan_api_key = None,
+            "ipfs_gateway_url" => self.ipfs_gateway_url = None,
+            "rpc_url" => self.rpc_url = None,
+            "pinata_api_key" => self.pinata_api_key = None,
+            "pinata_secret_api_key" => self.pinata_secret_api_key = None,
+            "fleek_api_key" => self.fleek_api_key = None,
+            "fleek_api_secret" => self.fleek_api_secret = None,
+            "local_ipfs_api_url" => self.local_ipfs_api_url = None,
+            "logs_xyz_api_key" => self.logs_xyz_api_key = None,
+            "remote_compiler_url" => self.remote_compiler_url = None,
+            "default_profile" => self.default_profile = None,
+            "log_file_path" => self.log_file_path = None,
+            _ => match key.strip_prefix("rpc_profiles.") {
+                Some(name) if self.rpc_profiles.remove(name).is_some() => {}
+                _ => return Err(eyre!("invalid key '{}'", key)),
+            },
+        };
+
+        self.save()
+    }
+
+    /// Deletes `~/.shadow/config.json`, if it exists.
+    pub fn reset() -> Result<()> {
+        let mut config_path = home_dir().ok_or_eyre("failed to get home directory")?;
+        config_path.push(".shadow");
+        config_path.push("config.json");
+
+        if config_path.exists() {
+            std::fs::remove_file(config_path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the RPC URL of a named profile
+    pub fn rpc_profile(&self, name: &str) -> Result<String> {
+        self.rpc_profiles.get(name).cloned().ok_or_else(|| {
+            eyre!(
+                "unknown RPC profile '{}'. Add it with `shadow config set-profile {} <url>`",
+                name,
+                name
+            )
+        })
+    }
+
+    /// Adds or replaces a named RPC profile
+    pub fn set_profile(&mut self, name: &str, url: &str) -> Result<()> {
+        self.rpc_profiles.insert(name.to_string(), url.to_string());
+        self.save()
+    }
+
     /// Set a value
     pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        self.set_value(key, value)?;
+        self.save()?;
+
+        Ok(())
+    }
+
+    /// Sets a value without saving the configuration
+    pub(crate) fn set_value(&mut self, key: &str, value: &str) -> Result<()> {
         match key {
             "etherscan_api_key" => self.etherscan_api_key = Some(value.to_string()),
             "ipfs_gateway_url" => self.ipfs_gateway_url = Some(value.to_string()),
             "rpc_url" => self.rpc_url = Some(value.to_string()),
             "pinata_api_key" => self.pinata_api_key = Some(value.to_string()),
             "pinata_secret_api_key" => self.pinata_secret_api_key = Some(value.to_string()),
+            "fleek_api_key" => self.fleek_api_key = Some(value.to_string()),
+            "fleek_api_secret" => self.fleek_api_secret = Some(value.to_string()),
+            "local_ipfs_api_url" => self.local_ipfs_api_url = Some(value.to_string()),
+            "logs_xyz_api_key" => self.logs_xyz_api_key = Some(value.to_string()),
+            "remote_compiler_url" => self.remote_compiler_url = Some(value.to_string()),
+            "default_profile" => self.default_profile = Some(value.to_string()),
+            "log_file_path" => self.log_file_path = Some(value.to_string()),
             _ => return Err(eyre!("invalid key '{}'", key)),
         };
 
-        self.save()?;
-
         Ok(())
     }
 
@@ -149,7 +467,7 @@ impl Configuration {
 
         // rpc_url
         print!(
-            "{GREEN_ANSI_COLOR}4.{RESET_ANSI_COLOR} Set a new RPC URL (default: {:?}): ",
+            "{GREEN_ANSI_COLOR}5.{RESET_ANSI_COLOR} Set a new RPC URL (default: {:?}): ",
             config.rpc_url
         );
         std::io::stdout().flush().unwrap();
@@ -159,6 +477,18 @@ impl Configuration {
             input.clear();
         }
 
+        // logs_xyz_api_key
+        print!(
+            "{GREEN_ANSI_COLOR}6.{RESET_ANSI_COLOR} Set a new logs.xyz API key (default: {:?}): ",
+            config.logs_xyz_api_key
+        );
+        std::io::stdout().flush().unwrap();
+        std::io::stdin().read_line(input)?;
+        if !input.trim().is_empty() {
+            config.logs_xyz_api_key = Some(input.trim().to_string());
+            input.clear();
+        }
+
         println!(
             "\n{GREEN_ANSI_COLOR}Configuration set!{RESET_ANSI_COLOR}\n{}",
             serde_json::to_string_pretty(&config)?
@@ -169,3 +499,35 @@ impl Configuration {
         Ok(config)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_json() {
+        let raw = serde_json::json!({
+            "ipfs_api_key": "key",
+            "ipfs_secret_api_key": "old secret",
+            "pinata_secret_api_key": "secret",
+            "wallet_address": "0x0000000000000000000000000000000000000000",
+            "contract_group_name": "group",
+            "rpc_url": "http://localhost:8545",
+        });
+        let Value::Object(raw) = raw else { unreachable!() };
+
+        let (config, changes) = Configuration::migrate_json(raw).unwrap();
+        assert_eq!(config.pinata_api_key.as_deref(), Some("key"));
+        assert_eq!(config.pinata_secret_api_key.as_deref(), Some("secret"));
+        assert_eq!(config.rpc_url.as_deref(), Some("http://localhost:8545"));
+        assert!(changes.contains(&ConfigMigration::Renamed {
+            from: "ipfs_api_key".to_string(),
+            to: "pinata_api_key".to_string()
+        }));
+        assert!(changes.contains(&ConfigMigration::Removed("ipfs_secret_api_key".to_string())));
+        assert!(changes.contains(&ConfigMigration::Removed("wallet_address".to_string())));
+        assert!(changes.contains(&ConfigMigration::Removed("contract_group_name".to_string())));
+        assert!(changes.contains(&ConfigMigration::Added("rpc_profiles".to_string())));
+        assert!(!changes.contains(&ConfigMigration::Added("rpc_url".to_string())));
+    }
+}
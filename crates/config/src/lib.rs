@@ -1,4 +1,5 @@
-//! The `config` subcommand
+//! The `config`, `print-env`, `clean-config`, `migrate-config` and `archive-config`
+//! subcommands
 
 mod core;
 mod interface;
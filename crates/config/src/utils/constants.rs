@@ -4,5 +4,20 @@ pub(crate) const PURPLE_ANSI_COLOR: &str = "\x1b[35m";
 pub(crate) const RED_ANSI_COLOR: &str = "\x1b[31m";
 /// ANSI color for green
 pub(crate) const GREEN_ANSI_COLOR: &str = "\x1b[32m";
+/// ANSI color for yellow
+pub(crate) const YELLOW_ANSI_COLOR: &str = "\x1b[33m";
+/// ANSI color for gray
+pub(crate) const GRAY_ANSI_COLOR: &str = "\x1b[90m";
 /// Reset the ANSI color to the default
 pub(crate) const RESET_ANSI_COLOR: &str = "\x1b[0m";
+/// Configuration keys whose values are masked by `print-env` unless `--reveal` is passed.
+/// RPC URLs are included since they often embed a provider API key.
+pub(crate) const SENSITIVE_KEYS: [&str; 7] = [
+    "etherscan_api_key",
+    "pinata_api_key",
+    "pinata_secret_api_key",
+    "fleek_api_key",
+    "fleek_api_secret",
+    "rpc_url",
+    "logs_xyz_api_key",
+];
@@ -1,2 +1,4 @@
 /// Constants used by the `config` subcommand.
 pub(crate) mod constants;
+/// Checks configuration values against the services they're used with.
+pub(crate) mod validate;
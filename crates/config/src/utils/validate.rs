@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde_json::Value;
+
+/// How long to wait for each service to respond before skipping a value
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Why a configuration value failed its check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum CheckFailure {
+    /// The service rejected the value, e.g. with a 404 or a JSON-RPC error
+    Invalid(String),
+    /// The service couldn't be reached or failed to respond, e.g. when offline or rate limited,
+    /// so the value couldn't be checked
+    Unreachable(String),
+}
+
+/// Returns a client for validating configuration values
+fn client() -> reqwest::Client {
+    reqwest::Client::builder().timeout(TIMEOUT).build().unwrap_or_default()
+}
+
+/// Transport errors and timeouts say nothing about the value itself
+fn request_failed(e: reqwest::Error) -> CheckFailure {
+    CheckFailure::Unreachable(format!("request failed: {}", e))
+}
+
+/// Classifies an HTTP error status. Only a missing or unauthorized resource means the value is
+/// wrong; rate limits and server errors are transient.
+fn status_failure(status: StatusCode) -> Option<CheckFailure> {
+    match status {
+        StatusCode::NOT_FOUND | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            Some(CheckFailure::Invalid(format!("responded with {}", status)))
+        }
+        _ if status.is_success() => None,
+        _ => Some(CheckFailure::Unreachable(format!("responded with {}", status))),
+    }
+}
+
+/// Checks that an RPC URL responds to `eth_chainId`
+pub(crate) async fn check_rpc_url(url: &str) -> Result<(), CheckFailure> {
+    let response = client()
+        .post(url)
+        .header("content-type", "application/json")
+        .body(r#"{"jsonrpc":"2.0","id":1,"method":"eth_chainId","params":[]}"#)
+        .send()
+        .await
+        .map_err(request_failed)?;
+    let status = response.status();
+    if let Some(CheckFailure::Invalid(reason)) = status_failure(status) {
+        return Err(CheckFailure::Invalid(reason));
+    }
+
+    // JSON-RPC errors are often returned with a non-2xx status, so the body is checked first
+    let body = serde_json::from_str::<Value>(&response.text().await.unwrap_or_default()).ok();
+    match body {
+        Some(body) if body.get("error").is_some() => {
+            Err(CheckFailure::Invalid(format!("eth_chainId failed: {}", body["error"])))
+        }
+        Some(body) if body.get("result").is_some() && status.is_success() => Ok(()),
+        _ => Err(status_failure(status).unwrap_or_else(|| {
+            CheckFailure::Unreachable("did not respond with JSON-RPC".to_string())
+        })),
+    }
+}
+
+/// Checks that a URL is reachable and doesn't 404
+pub(crate) async fn check_url(url: &str) -> Result<(), CheckFailure> {
+    let response = client().get(url).send().await.map_err(request_failed)?;
+    match status_failure(response.status()) {
+        Some(failure) => Err(failure),
+        None => Ok(()),
+    }
+}
+
+/// Checks that Etherscan accepts an API key
+pub(crate) async fn check_etherscan_api_key(api_key: &str) -> Result<(), CheckFailure> {
+    let response = client()
+        .get("https://api.etherscan.io/api")
+        .query(&[("module", "stats"), ("action", "ethsupply"), ("apikey", api_key)])
+        .send()
+        .await
+        .map_err(request_failed)?;
+
+    let body: Value =
+        serde_json::from_str(&response.text().await.unwrap_or_default()).map_err(|_| {
+            CheckFailure::Unreachable("Etherscan did not respond with JSON".to_string())
+        })?;
+    match body.get("result").and_then(Value::as_str) {
+        Some(result) if result.contains("Invalid API Key") => {
+            Err(CheckFailure::Invalid("Etherscan rejected the API key".to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that Pinata accepts an API key and secret
+pub(crate) async fn check_pinata_api_keys(
+    api_key: &str,
+    secret_api_key: &str,
+) -> Result<(), CheckFailure> {
+    let response = client()
+        .get("https://api.pinata.cloud/data/testAuthentication")
+        .header("pinata_api_key", api_key)
+        .header("pinata_secret_api_key", secret_api_key)
+        .send()
+        .await
+        .map_err(request_failed)?;
+    match response.status() {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            Err(CheckFailure::Invalid("Pinata rejected the API keys".to_string()))
+        }
+        _ => Ok(()),
+    }
+}
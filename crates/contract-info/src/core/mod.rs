@@ -0,0 +1,146 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use alloy::{json_abi::JsonAbi, primitives::Address};
+use eyre::{eyre, Result};
+use serde_json::json;
+use shadow_common::{
+    ShadowContractGroupInfo, ShadowContractInfo, ShadowContractSettings, ShadowContractSource,
+};
+use tracing::error;
+
+use crate::ContractInfoArgs;
+
+/// The `contract-info` subcommand. Displays a contract's deployment info, compiler settings,
+/// source overview, and ABI summary. Works entirely from local files.
+pub async fn contract_info(args: ContractInfoArgs) -> Result<()> {
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let contract_dir = match &args.address {
+        Some(address) => {
+            let address: Address = address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+            let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+                error!("This is not part of a shadow contract group.");
+                eyre!("Failed to load shadow contract group: {}", e)
+            })?;
+            group_info.contract_path(&address, args.chain_id)?
+        }
+        None => root_dir,
+    };
+
+    let info = ShadowContractInfo::from_path(&contract_dir.join("info.json")).map_err(|e| {
+        eyre!(
+            "Failed to load contract info from {}. Pass an address, or run this from a contract directory: {}",
+            contract_dir.display(),
+            e
+        )
+    })?;
+    let settings = ShadowContractSettings::from_path(&contract_dir.join("settings.json"))?;
+    let source = ShadowContractSource::from_path(&contract_dir.join("src"), &settings)?;
+    let abi = load_abi(&contract_dir, &info)?;
+
+    let mut file_lines = source
+        .contract_files
+        .iter()
+        .map(|file| (file.file_name.clone(), file.content.lines().count()))
+        .collect::<Vec<_>>();
+    file_lines.sort();
+    let total_lines = file_lines.iter().map(|(_, lines)| lines).sum::<usize>();
+
+    if args.json {
+        let file_lines = file_lines
+            .iter()
+            .map(|(file, lines)| (file.clone(), json!(lines)))
+            .collect::<serde_json::Map<_, _>>();
+        let report = json!({
+            "info": info,
+            "settings": settings,
+            "source": {
+                "language": source.language,
+                "files": source.contract_files.len(),
+                "lines": total_lines,
+                "fileLines": file_lines,
+            },
+            "abi": abi.as_ref().map(|abi| json!({
+                "functions": abi.functions().count(),
+                "events": abi.events().count(),
+            })),
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("deployment");
+    println!("  name:                {}", info.name);
+    println!("  address:             {}", info.address);
+    println!("  chain:               {} ({})", info.network, info.chain_id);
+    println!("  deployer:            {}", info.contract_deployer);
+    println!("  deployment tx:       {}", info.deployment_transaction_hash);
+    println!("  source:              {}", info.source);
+    println!("  unique events:       {}", info.unique_events);
+    println!();
+
+    println!("compiler settings");
+    println!("  compiler version:    {}", settings.compiler_version);
+    println!("  evm version:         {}", settings.evm_version);
+    println!(
+        "  optimizer:           {}",
+        if settings.optimizer.enabled {
+            format!("enabled ({} runs)", settings.optimizer.runs)
+        } else {
+            "disabled".to_string()
+        }
+    );
+    println!("  via ir:              {}", settings.via_ir);
+    println!(
+        "  libraries:           {}",
+        settings.libraries.libs.values().map(|l| l.len()).sum::<usize>()
+    );
+    println!("  constructor args:    {} bytes", settings.constructor_arguments.len());
+    println!();
+
+    println!("source");
+    println!("  language:            {}", source.language);
+    println!("  files:               {}", file_lines.len());
+    println!("  lines:               {}", total_lines);
+    for (file, lines) in &file_lines {
+        println!("    {:<40} {}", file, lines);
+    }
+    println!();
+
+    println!("abi");
+    match &abi {
+        Some(abi) => {
+            println!("  functions:           {}", abi.functions().count());
+            println!("  events:              {}", abi.events().count());
+        }
+        None => println!("  not compiled"),
+    }
+
+    Ok(())
+}
+
+/// Loads the contract's compiled ABI, if it has been compiled. Contract directories are
+/// `<group>/<chain_id>/<address>`, so the group is found two levels up.
+fn load_abi(contract_dir: &Path, info: &ShadowContractInfo) -> Result<Option<JsonAbi>> {
+    let Some(group_dir) =
+        contract_dir.canonicalize()?.parent().and_then(Path::parent).map(Path::to_path_buf)
+    else {
+        return Ok(None);
+    };
+    let Ok(group_info) = ShadowContractGroupInfo::from_path(&group_dir) else {
+        return Ok(None);
+    };
+
+    let abi_path = group_info
+        .artifact_path()
+        .join(info.chain_id.to_string())
+        .join(info.address.to_string().to_lowercase())
+        .join("abi.json");
+    if !abi_path.exists() {
+        return Ok(None);
+    }
+
+    Ok(Some(serde_json::from_str(&std::fs::read_to_string(abi_path)?)?))
+}
@@ -0,0 +1,22 @@
+use clap::Parser;
+
+/// Arguments for the `contract-info` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Display the metadata of a contract in a shadow contract group")]
+pub struct ContractInfoArgs {
+    /// The address of the contract. If omitted, `--root` must be a contract directory, and the
+    /// address is read from its `info.json`.
+    pub address: Option<String>,
+
+    /// The chain ID of the contract. Required if the address is tracked on multiple chains.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The path to the shadow contract group, or to a contract directory within it.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// Print all metadata as a single JSON object.
+    #[clap(long)]
+    pub json: bool,
+}
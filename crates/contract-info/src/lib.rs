@@ -0,0 +1,7 @@
+//! The `contract-info` subcommand
+
+mod core;
+mod interface;
+
+pub use core::*;
+pub use interface::*;
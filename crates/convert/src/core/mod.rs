@@ -0,0 +1,260 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use alloy::primitives::Address;
+use eyre::{bail, eyre, OptionExt, Result};
+use shadow_common::{
+    ShadowContractGroupInfo, ShadowContractSettings, ShadowContractSource, ShadowContractSourceFile,
+};
+use tracing::{error, info, warn};
+
+use crate::{ConvertArgs, SourceLayout};
+
+/// The `convert` subcommand. Moves a shadow contract's source files between a flat and a
+/// structured layout, rewriting imports so the contract still compiles.
+pub async fn convert(args: ConvertArgs) -> Result<()> {
+    let address: Address = args.contract.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let contract_dir = group_info.contract_path(&address, args.chain_id)?;
+
+    // the files on disk may have been edited since `source.json` was written, so they're the
+    // source of truth. only the remappings are taken from `source.json`
+    let settings = ShadowContractSettings::from_path(&contract_dir.join("settings.json"))?;
+    let mut source = read_source(&contract_dir.join("source.json"))?;
+    source.contract_files =
+        ShadowContractSource::from_path(&contract_dir, &settings)?.contract_files;
+    let original = read_source(&contract_dir.join("original.json"))?;
+
+    // only the contract's own files in `src/` move. dependencies in `lib/` stay where they are,
+    // along with the remappings which point to them
+    let (renames, remappings) = match args.to {
+        SourceLayout::Flat => {
+            let mut remappings = source.remappings.clone();
+            remappings.retain(|r| !r.path.original().starts_with("src"));
+            (flat_layout(&source)?, remappings)
+        }
+        SourceLayout::Structured => (structured_layout(&source, &original), original.remappings),
+    };
+    let renamed =
+        |file_name: &str| renames.get(file_name).cloned().unwrap_or_else(|| file_name.to_string());
+
+    // rewrite every import of a file which moved, and every relative import from a file which
+    // moved, as a relative import to the file's new location. other imports still resolve
+    let mut rewritten_imports = 0;
+    let contract_files = source
+        .contract_files
+        .iter()
+        .map(|file| {
+            let file_name = renamed(&file.file_name);
+            let mut content = file.content.clone();
+
+            // replace from the end so the earlier ranges stay valid
+            for (range, import) in file.imports().into_iter().rev() {
+                let target =
+                    source.resolve_import(&file.file_name, &import).to_string_lossy().to_string();
+                if !source.contract_files.iter().any(|f| f.file_name == target) {
+                    warn!(
+                        "{}: import \"{}\" doesn't resolve to a file in the source tree, leaving it unchanged",
+                        file.file_name, import
+                    );
+                    continue;
+                }
+
+                let new_target = renamed(&target);
+                let is_relative = import.starts_with("./") || import.starts_with("../");
+                if new_target == target && (!is_relative || file_name == file.file_name) {
+                    continue;
+                }
+
+                let new_import = relative_import(&file_name, &new_target);
+                if new_import != import {
+                    content.replace_range(range, &new_import);
+                    rewritten_imports += 1;
+                }
+            }
+
+            ShadowContractSourceFile { file_name, content }
+        })
+        .collect::<Vec<_>>();
+
+    let moves = renames.iter().filter(|(from, to)| from != to).collect::<Vec<_>>();
+    if args.dry_run {
+        moves.iter().for_each(|(from, to)| println!("{} -> {}", from, to));
+        info!(
+            "would move {} files and rewrite {} imports (dry run)",
+            moves.len(),
+            rewritten_imports
+        );
+        return Ok(());
+    }
+
+    // remove the files which moved, and any directories they leave empty
+    for (from, _) in &moves {
+        if renames.values().any(|to| to == *from) {
+            continue;
+        }
+
+        let path = contract_dir.join(from);
+        std::fs::remove_file(&path)?;
+        for dir in path.ancestors().skip(1).take_while(|dir| *dir != contract_dir.as_path()) {
+            if std::fs::remove_dir(dir).is_err() {
+                break;
+            }
+        }
+    }
+
+    // write the new layout, along with its remappings
+    source.contract_files = contract_files;
+    source.remappings = remappings;
+    source.write_source_to(&contract_dir)?;
+    std::fs::write(contract_dir.join("source.json"), serde_json::to_string_pretty(&source)?)?;
+    update_foundry_config_remappings(&contract_dir, &source)?;
+
+    info!("moved {} files and rewrote {} imports", moves.len(), rewritten_imports);
+    Ok(())
+}
+
+/// Reads a `source.json` or `original.json` file
+fn read_source(path: &Path) -> Result<ShadowContractSource> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| eyre!("failed to read {}: {}", path.display(), e))?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Whether a source file is one of the contract's own files, rather than a dependency in `lib/`
+/// or a test or script
+fn is_contract_file(file_name: &str) -> bool {
+    Path::new(file_name).starts_with("src")
+}
+
+/// Maps every file in `src/` to `src/<file name>`. Fails if two files share a name.
+fn flat_layout(source: &ShadowContractSource) -> Result<BTreeMap<String, String>> {
+    let mut renames = BTreeMap::new();
+    let mut flat_names: HashMap<String, &str> = HashMap::new();
+
+    for file in source.contract_files.iter().filter(|f| is_contract_file(&f.file_name)) {
+        let name = Path::new(&file.file_name)
+            .file_name()
+            .ok_or_eyre("invalid file name")?
+            .to_string_lossy();
+        let flat_name = format!("src/{}", name);
+
+        if let Some(existing) = flat_names.insert(flat_name.clone(), &file.file_name) {
+            bail!(
+                "{} and {} would both be moved to {}. Rename one of them first",
+                existing,
+                file.file_name,
+                flat_name
+            );
+        }
+        renames.insert(file.file_name.clone(), flat_name);
+    }
+
+    Ok(renames)
+}
+
+/// Maps every file in `src/` back to the path of the file with the same name in `src/` in
+/// `original.json`. Files which aren't in `original.json`, or whose name is ambiguous, stay where
+/// they are.
+fn structured_layout(
+    source: &ShadowContractSource,
+    original: &ShadowContractSource,
+) -> BTreeMap<String, String> {
+    let file_name = |path: &str| {
+        Path::new(path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+    };
+
+    let mut original_paths: HashMap<String, Vec<&str>> = HashMap::new();
+    for file in original.contract_files.iter().filter(|f| is_contract_file(&f.file_name)) {
+        original_paths.entry(file_name(&file.file_name)).or_default().push(&file.file_name);
+    }
+
+    source
+        .contract_files
+        .iter()
+        .filter(|file| is_contract_file(&file.file_name))
+        .map(|file| {
+            let new_name = match original_paths.get(&file_name(&file.file_name)).map(Vec::as_slice)
+            {
+                Some([original_path]) => original_path.to_string(),
+                Some(_) => {
+                    warn!(
+                        "{} matches multiple original files, leaving it in place",
+                        file.file_name
+                    );
+                    file.file_name.clone()
+                }
+                None => file.file_name.clone(),
+            };
+            (file.file_name.clone(), new_name)
+        })
+        .collect()
+}
+
+/// Replaces the remappings in the contract's `foundry.toml` with those of the new layout, if it
+/// sets any. `forge` reads them alongside `remappings.txt`, and prefers them.
+fn update_foundry_config_remappings(
+    contract_dir: &Path,
+    source: &ShadowContractSource,
+) -> Result<()> {
+    let config_path = contract_dir.join("foundry.toml");
+    let Ok(config) = std::fs::read_to_string(&config_path) else {
+        return Ok(());
+    };
+    let mut config: toml::Table = toml::from_str(&config)
+        .map_err(|e| eyre!("failed to parse {}: {}", config_path.display(), e))?;
+
+    let Some(profile) = config
+        .get_mut("profile")
+        .and_then(|profile| profile.get_mut("default"))
+        .and_then(|profile| profile.as_table_mut())
+        .filter(|profile| profile.contains_key("remappings"))
+    else {
+        return Ok(());
+    };
+    let remappings = source
+        .remappings
+        .iter()
+        .map(|r| {
+            toml::Value::String(format!(
+                "{}{}={}",
+                r.name,
+                if !r.name.ends_with('/') { "/" } else { "" },
+                r.path.original().display()
+            ))
+        })
+        .collect();
+    profile.insert("remappings".to_string(), toml::Value::Array(remappings));
+
+    std::fs::write(&config_path, toml::to_string(&config)?)?;
+    Ok(())
+}
+
+/// Builds a relative import from one source file to another, e.g. `./Token.sol` or
+/// `../lib/Math.sol`
+fn relative_import(from: &str, to: &str) -> String {
+    let from_dir =
+        Path::new(from).parent().map(|p| p.components().collect::<Vec<_>>()).unwrap_or_default();
+    let to = Path::new(to).components().collect::<Vec<_>>();
+    let common = from_dir.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut path = PathBuf::new();
+    (common..from_dir.len()).for_each(|_| path.push(".."));
+    to[common..].iter().for_each(|component| path.push(component));
+
+    let path = path.to_string_lossy().to_string();
+    if path.starts_with("..") {
+        path
+    } else {
+        format!("./{}", path)
+    }
+}
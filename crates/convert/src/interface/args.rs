@@ -0,0 +1,35 @@
+use clap::{Parser, ValueEnum};
+
+/// Arguments for the `convert` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Convert a shadow contract's source between flat and structured layouts")]
+pub struct ConvertArgs {
+    /// The layout to convert the contract's source to.
+    #[clap(long, value_enum)]
+    pub to: SourceLayout,
+
+    /// The address of the contract to convert.
+    #[clap(long)]
+    pub contract: String,
+
+    /// The chain ID of the contract. Required if the address is tracked on multiple chains.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// Print the files which would move, without changing anything.
+    #[clap(long)]
+    pub dry_run: bool,
+}
+
+/// The layouts a shadow contract's source can be converted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SourceLayout {
+    /// Every source file directly in `src/`
+    Flat,
+    /// The directory structure the contract was fetched with, from `original.json`
+    Structured,
+}
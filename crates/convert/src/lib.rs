@@ -0,0 +1,7 @@
+//! The `convert` subcommand
+
+mod core;
+mod interface;
+
+pub use core::*;
+pub use interface::*;
@@ -0,0 +1,71 @@
+use std::{path::PathBuf, str::FromStr};
+
+use eyre::{eyre, Result};
+use shadow_common::{fs::copy_dir, prompt, ShadowContractGroupInfo};
+use tracing::{error, info, warn};
+
+use crate::{ConflictStrategy, MergeArgs};
+
+/// The `merge` subcommand. Copies every contract from one contract group into another. The
+/// destination group keeps its display name and creator.
+pub async fn merge(args: MergeArgs) -> Result<()> {
+    // both directories must be shadow contract groups
+    let source_dir = PathBuf::from_str(&args.source)?;
+    let source_group = ShadowContractGroupInfo::from_path(&source_dir).map_err(|e| {
+        error!("{} is not a shadow contract group.", source_dir.display());
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let into_dir = PathBuf::from_str(&args.into)?;
+    let mut into_group = ShadowContractGroupInfo::from_path(&into_dir).map_err(|e| {
+        error!("{} is not a shadow contract group.", into_dir.display());
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    let (mut merged, mut skipped) = (0, 0);
+    for contract in &source_group.contracts {
+        let source = source_group.contract_path(&contract.address, Some(contract.chain_id))?;
+        let destination = into_dir
+            .join(contract.chain_id.to_string())
+            .join(contract.address.to_string().to_lowercase());
+
+        let conflict = into_group
+            .contracts
+            .iter()
+            .any(|c| c.address == contract.address && c.chain_id == contract.chain_id);
+        if conflict {
+            let overwrite = match args.conflict_strategy {
+                ConflictStrategy::Skip => false,
+                ConflictStrategy::Overwrite => true,
+                ConflictStrategy::Prompt => prompt(&format!(
+                    "{} (chain {}) exists in both groups. Overwrite it? (y/N) ",
+                    contract.address, contract.chain_id
+                ))?
+                .is_some_and(|answer| answer.to_lowercase() == "y"),
+            };
+
+            if !overwrite {
+                warn!(
+                    "skipping {} (chain {}), which exists in both groups",
+                    contract.address, contract.chain_id
+                );
+                skipped += 1;
+                continue;
+            }
+            std::fs::remove_dir_all(&destination)?;
+        }
+
+        copy_dir(&source, &destination)?;
+        merged += 1;
+    }
+
+    into_group.update_contracts()?;
+
+    info!(
+        "merged {} contracts into {} ({} skipped). It now has {} contracts.",
+        merged,
+        into_dir.display(),
+        skipped,
+        into_group.contracts.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,82 @@
+mod merge;
+
+pub use merge::*;
+
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use alloy::primitives::Address;
+use eyre::{bail, eyre, Result};
+use shadow_common::{fs::copy_dir, ShadowContractGroupInfo};
+use tracing::{error, info};
+
+use crate::CopyArgs;
+
+/// The `copy` subcommand. Copies a shadow contract from one contract group to another.
+pub async fn copy(args: CopyArgs) -> Result<()> {
+    let address: Address = args.address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+
+    // both directories must be shadow contract groups
+    let from_dir = PathBuf::from_str(&args.from)?;
+    ShadowContractGroupInfo::from_path(&from_dir).map_err(|e| {
+        error!("{} is not a shadow contract group.", from_dir.display());
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let to_dir = PathBuf::from_str(&args.to)?;
+    let mut to_group = ShadowContractGroupInfo::from_path(&to_dir).map_err(|e| {
+        error!("{} is not a shadow contract group.", to_dir.display());
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    let chain_id = match args.chain_id {
+        Some(chain_id) => chain_id,
+        None => find_chain_id(&from_dir, &address)?,
+    };
+
+    let address_dir = address.to_string().to_lowercase();
+    let source = from_dir.join(chain_id.to_string()).join(&address_dir);
+    if !source.join("info.json").exists() {
+        bail!("contract {} (chain {}) not found in {}", address, chain_id, from_dir.display());
+    }
+
+    let destination = to_dir.join(chain_id.to_string()).join(&address_dir);
+    if destination.exists() {
+        if !args.force {
+            bail!(
+                "contract {} already exists in {}. Use --force to overwrite it.",
+                address,
+                to_dir.display()
+            );
+        }
+        std::fs::remove_dir_all(&destination)?;
+    }
+
+    copy_dir(&source, &destination)?;
+    to_group.update_contracts()?;
+
+    info!("copied {} (chain {}) to {}", address, chain_id, destination.display());
+    Ok(())
+}
+
+/// Finds the chain ID under which the given address exists in a contract group. Fails if the
+/// address is missing, or exists on more than one chain.
+fn find_chain_id(root: &Path, address: &Address) -> Result<u64> {
+    let address_dir = address.to_string().to_lowercase();
+    let chain_ids = std::fs::read_dir(root)?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().to_string_lossy().parse::<u64>().ok())
+        .filter(|chain_id| root.join(chain_id.to_string()).join(&address_dir).is_dir())
+        .collect::<Vec<_>>();
+
+    match chain_ids.as_slice() {
+        [chain_id] => Ok(*chain_id),
+        [] => bail!("contract {} not found in {}", address, root.display()),
+        _ => bail!(
+            "contract {} exists on multiple chains ({:?}). Use --chain-id to pick one.",
+            address,
+            chain_ids
+        ),
+    }
+}
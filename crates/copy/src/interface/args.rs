@@ -0,0 +1,55 @@
+use clap::{Parser, ValueEnum};
+
+/// Arguments for the `copy` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Copy a shadow contract from one contract group to another")]
+pub struct CopyArgs {
+    /// The path to the shadow contract group to copy the contract from.
+    #[clap(long)]
+    pub from: String,
+
+    /// The path to the shadow contract group to copy the contract to.
+    #[clap(long)]
+    pub to: String,
+
+    /// The address of the contract to copy.
+    #[clap(short, long)]
+    pub address: String,
+
+    /// The chain ID of the contract to copy. Required if the address exists on multiple chains
+    /// in the source group.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// Whether to overwrite the contract if it already exists in the destination group.
+    #[clap(long)]
+    pub force: bool,
+}
+
+/// Arguments for the `merge` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Merge all contracts from one shadow contract group into another")]
+pub struct MergeArgs {
+    /// The path to the shadow contract group to merge contracts from.
+    #[clap(long)]
+    pub source: String,
+
+    /// The path to the shadow contract group to merge contracts into.
+    #[clap(long)]
+    pub into: String,
+
+    /// What to do when a contract exists in both groups.
+    #[clap(long, value_enum, default_value = "skip")]
+    pub conflict_strategy: ConflictStrategy,
+}
+
+/// How `shadow merge` resolves a contract which exists in both groups
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConflictStrategy {
+    /// Keep the destination group's copy
+    Skip,
+    /// Replace the destination group's copy with the source group's
+    Overwrite,
+    /// Ask for each conflicting contract
+    Prompt,
+}
@@ -0,0 +1,7 @@
+//! The `copy` and `merge` subcommands
+
+mod core;
+mod interface;
+
+pub use core::*;
+pub use interface::*;
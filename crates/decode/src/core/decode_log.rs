@@ -0,0 +1,68 @@
+use alloy::{
+    hex::FromHex,
+    network::AnyNetwork,
+    primitives::{Address, Bytes, Log, TxHash, B256},
+    providers::{Provider, ProviderBuilder},
+    transports::http::reqwest::Url,
+};
+use eyre::{eyre, OptionExt, Result};
+use shadow_common::event::decode_logs;
+use tracing::info;
+
+use crate::{group::get_group_abis, DecodeLogArgs};
+
+/// The `decode-log` subcommand. Decodes raw logs using the ABIs in a shadow contract group.
+pub async fn decode_log(args: DecodeLogArgs) -> Result<()> {
+    // ensure args are valid
+    args.validate().map_err(|e| eyre!("Invalid arguments: {}", e))?;
+
+    let logs = match &args.tx {
+        Some(transaction_hash) => {
+            let tx_hash: TxHash =
+                transaction_hash.parse().map_err(|e| eyre!("Invalid transaction hash: {}", e))?;
+            let provider =
+                ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+
+            info!("fetching transaction receipt for {}", tx_hash);
+            let receipt = provider
+                .get_transaction_receipt(tx_hash)
+                .await?
+                .ok_or_eyre("transaction receipt not found")?;
+            receipt.inner.inner.logs().iter().map(|log| log.inner.clone()).collect::<Vec<_>>()
+        }
+        None => {
+            let address = match &args.address {
+                Some(address) => address.parse().map_err(|e| eyre!("Invalid address: {}", e))?,
+                None => Address::ZERO,
+            };
+            let topics = args
+                .topics
+                .iter()
+                .map(|topic| topic.parse::<B256>().map_err(|e| eyre!("Invalid topic: {}", e)))
+                .collect::<Result<Vec<_>>>()?;
+            let data = Bytes::from_hex(&args.data).map_err(|e| eyre!("Invalid data: {}", e))?;
+
+            vec![Log::new(address, topics, data).ok_or_eyre("a log may have at most 4 topics")?]
+        }
+    };
+
+    if logs.is_empty() {
+        return Err(eyre!("the transaction emitted no logs"));
+    }
+
+    // every matching event is attempted, so overloaded events decode with whichever ABI fits
+    let abis = get_group_abis(&args.root)?;
+    let decoded = decode_logs(&logs, &abis)?
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| args.log_index.map_or(true, |log_index| log_index == *i))
+        .map(|(_, event)| event.to_string())
+        .collect::<Vec<_>>();
+    if decoded.is_empty() {
+        return Err(eyre!("log index out of range: the transaction emitted {} logs", logs.len()));
+    }
+
+    info!("decoded logs:\n{}", decoded.join("\n"));
+
+    Ok(())
+}
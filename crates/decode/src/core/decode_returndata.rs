@@ -0,0 +1,123 @@
+use std::{path::PathBuf, str::FromStr};
+
+use alloy::{
+    dyn_abi::FunctionExt,
+    hex::FromHex,
+    json_abi::JsonAbi,
+    network::AnyNetwork,
+    primitives::{Address, Bytes, Selector, TxHash},
+    providers::{ext::TraceApi, Provider, ProviderBuilder},
+    rpc::types::trace::parity::TraceType,
+    transports::http::reqwest::Url,
+};
+use eyre::{bail, eyre, OptionExt, Result};
+use shadow_common::{abi::try_get_function_abi, ShadowContractGroupInfo};
+use tracing::{error, info, warn};
+
+use crate::{
+    format::print_decoded, group::get_group_abis, http::lookup_function_signatures,
+    DecodeReturndataArgs,
+};
+
+/// The `decode-returndata` subcommand. Decodes the return data of a call using the output types
+/// of the matching function in a shadow contract group's ABIs, printing each value like
+/// `cast decode-abi`.
+pub async fn decode_returndata(args: DecodeReturndataArgs) -> Result<()> {
+    // ensure args are valid
+    args.validate().map_err(|e| eyre!("Invalid arguments: {}", e))?;
+
+    let mut address = args
+        .address
+        .as_deref()
+        .map(|address| address.parse::<Address>())
+        .transpose()
+        .map_err(|e| eyre!("Invalid address: {}", e))?;
+    let mut selector = args
+        .selector
+        .as_deref()
+        .map(|selector| Selector::from_hex(selector))
+        .transpose()
+        .map_err(|e| eyre!("Invalid selector: {}", e))?;
+
+    let data = match (&args.data, &args.tx) {
+        (Some(data), _) => {
+            Bytes::from_hex(data).map_err(|e| eyre!("Invalid return data: {}", e))?
+        }
+        (None, Some(tx)) => {
+            let tx_hash: TxHash =
+                tx.parse().map_err(|e| eyre!("Invalid transaction hash: {}", e))?;
+            let provider =
+                ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+
+            // the called contract and function default to the transaction's
+            info!("fetching transaction details for {}", tx_hash);
+            let transaction = provider
+                .get_transaction_by_hash(tx_hash)
+                .await?
+                .ok_or_eyre("transaction not found")?;
+            address = address.or(transaction.to);
+            if selector.is_none() {
+                selector = Some(
+                    transaction
+                        .input
+                        .get(..4)
+                        .map(Selector::from_slice)
+                        .ok_or_eyre("the transaction's calldata has no function selector")?,
+                );
+            }
+
+            info!("fetching the return data of {}", tx_hash);
+            provider.trace_replay_transaction(tx_hash, &[TraceType::Trace]).await?.output
+        }
+        (None, None) => unreachable!("validated above"),
+    };
+    let selector = selector.expect("validated above");
+
+    let abis = match address {
+        Some(address) => contract_abi(&args.root, &address, args.chain_id)?.into_iter().collect(),
+        None => get_group_abis(&args.root)?,
+    };
+    for function in try_get_function_abi(&selector, &abis) {
+        if let Ok(values) = function.abi_decode_output(&data, true) {
+            print_decoded(&function, &values);
+            return Ok(());
+        }
+    }
+
+    // 4byte.directory only knows functions' input types, so the output can't be fully decoded
+    warn!("{} not found in the contract group's ABIs, trying 4byte.directory", selector);
+    match lookup_function_signatures(&selector).await?.first() {
+        Some(signature) => warn!(
+            "{} matches {}, but its return types are unknown. Showing the raw return data.",
+            selector, signature
+        ),
+        None => {
+            error!("failed to find a function with selector {}", selector);
+            bail!("no matching function signature found");
+        }
+    }
+    for word in data.chunks(32) {
+        println!("0x{}", alloy::hex::encode(word));
+    }
+
+    Ok(())
+}
+
+/// Loads the ABI of a single contract from the contract group's latest build, if it's compiled
+fn contract_abi(root: &str, address: &Address, chain_id: Option<u64>) -> Result<Option<JsonAbi>> {
+    let root_dir = PathBuf::from_str(root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    let artifact_path = group_info.artifact_path();
+    let contract = group_info.contract(address, chain_id)?;
+    if !contract.is_compiled(&artifact_path) {
+        warn!("{} has not been built yet. run `shadow sim` or `shadow push` to build it", address);
+        return Ok(None);
+    }
+
+    let abi = std::fs::read_to_string(contract.output_path(&artifact_path).join("abi.json"))?;
+    Ok(Some(serde_json::from_str(&abi)?))
+}
@@ -0,0 +1,56 @@
+use alloy::{hex::FromHex, primitives::Bytes};
+use eyre::{bail, eyre, OptionExt, Result};
+use revm::primitives::ExecutionResult;
+use shadow_simulate::SimulateArgs;
+use tracing::{info, warn};
+
+use crate::{group::get_group_abis, revert::decode_revert_data, DecodeRevertArgs};
+
+/// The `decode-revert` subcommand. Simulates a transaction with shadow overrides and decodes
+/// its revert data as an `Error(string)`, a `Panic(uint256)`, or a custom error from the
+/// shadow contract group's ABIs.
+pub async fn decode_revert(args: DecodeRevertArgs) -> Result<()> {
+    // ensure args are valid
+    args.validate().map_err(|e| eyre!("Invalid arguments: {}", e))?;
+
+    let data = match (&args.data, &args.transaction_hash) {
+        (Some(data), _) => {
+            Bytes::from_hex(data).map_err(|e| eyre!("Invalid revert data: {}", e))?
+        }
+        (None, Some(transaction_hash)) => {
+            let result = shadow_simulate::simulate_result(SimulateArgs {
+                chain_id: args.chain_id,
+                ..SimulateArgs::new(
+                    transaction_hash.clone(),
+                    args.root.clone(),
+                    args.rpc_url.clone(),
+                )
+            })
+            .await?
+            .ok_or_eyre("failed to simulate transaction")?;
+
+            match result {
+                ExecutionResult::Revert { output, .. } => output,
+                ExecutionResult::Halt { reason, .. } => {
+                    bail!("transaction halted without revert data: {:?}", reason)
+                }
+                ExecutionResult::Success { .. } => {
+                    info!("transaction succeeded, there is nothing to decode");
+                    return Ok(());
+                }
+            }
+        }
+        (None, None) => unreachable!("validated above"),
+    };
+
+    // the group's ABIs are only used for custom errors, so raw data can be decoded outside
+    // of a group
+    let abis = get_group_abis(&args.root).unwrap_or_else(|e| {
+        warn!("custom errors won't be decoded: {}", e);
+        vec![]
+    });
+
+    println!("{}", decode_revert_data(&data, &abis));
+
+    Ok(())
+}
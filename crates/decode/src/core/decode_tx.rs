@@ -0,0 +1,168 @@
+use alloy::{
+    dyn_abi::{DynSolValue, JsonAbiExt},
+    hex::FromHex,
+    json_abi::Function,
+    network::AnyNetwork,
+    primitives::{Bytes, Selector, TxHash},
+    providers::{Provider, ProviderBuilder},
+    transports::http::reqwest::Url,
+};
+use eyre::{bail, eyre, OptionExt, Result};
+use shadow_common::abi::try_get_function_abi;
+use tracing::{error, info, warn};
+
+use crate::{
+    format::print_decoded, group::get_group_abis, http::lookup_function_signatures, DecodeTxArgs,
+};
+
+/// The `decode-tx` subcommand. Decodes transaction calldata using the ABIs in a shadow
+/// contract group, falling back to 4byte.directory.
+pub async fn decode_tx(args: DecodeTxArgs) -> Result<()> {
+    // ensure args are valid
+    args.validate().map_err(|e| eyre!("Invalid arguments: {}", e))?;
+
+    let calldata = match (&args.calldata, &args.transaction_hash) {
+        (Some(calldata), _) => {
+            Bytes::from_hex(calldata).map_err(|e| eyre!("Invalid calldata: {}", e))?
+        }
+        (None, Some(transaction_hash)) => {
+            let tx_hash: TxHash =
+                transaction_hash.parse().map_err(|e| eyre!("Invalid transaction hash: {}", e))?;
+            let provider =
+                ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+
+            info!("fetching transaction details for {}", tx_hash);
+            provider
+                .get_transaction_by_hash(tx_hash)
+                .await?
+                .ok_or_eyre("transaction not found")?
+                .input
+        }
+        (None, None) => unreachable!("validated above"),
+    };
+
+    if calldata.len() < 4 {
+        bail!("calldata is too short to contain a function selector");
+    }
+    let selector = Selector::from_slice(&calldata[..4]);
+
+    // try the group's ABIs first
+    let abis = get_group_abis(&args.root)?;
+    if let Some((function, values)) =
+        decode_calldata(try_get_function_abi(&selector, &abis), &calldata)
+    {
+        print_decoded(&function, &values);
+        return Ok(());
+    }
+
+    // fall back to 4byte.directory. these signatures have no argument names
+    warn!("{} not found in the contract group's ABIs, trying 4byte.directory", selector);
+    let functions = lookup_function_signatures(&selector)
+        .await?
+        .iter()
+        .filter_map(|signature| Function::parse(signature).ok())
+        .collect::<Vec<_>>();
+    if let Some((function, values)) = decode_calldata(functions, &calldata) {
+        print_decoded(&function, &values);
+        return Ok(());
+    }
+
+    error!("failed to decode calldata with selector {}", selector);
+    bail!("no matching function signature found");
+}
+
+/// Decodes calldata with the first of `functions` whose inputs it matches. The functions are
+/// expected to share the calldata's selector, which may have several signatures.
+fn decode_calldata(
+    functions: Vec<Function>,
+    calldata: &[u8],
+) -> Option<(Function, Vec<DynSolValue>)> {
+    functions.into_iter().find_map(|function| {
+        let values = function.abi_decode_input(&calldata[4..], true).ok()?;
+        Some((function, values))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        json_abi::JsonAbi,
+        primitives::{Address, B256, U256},
+    };
+
+    use super::*;
+    use crate::format::format_value;
+
+    fn erc20_abi() -> JsonAbi {
+        JsonAbi::parse([
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function approve(address spender, uint256 amount) returns (bool)",
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode_calldata_selector_lookup() {
+        let abis = [erc20_abi()];
+        let approve = abis[0].function("approve").unwrap()[0].clone();
+        let calldata = approve
+            .abi_encode_input(&[
+                DynSolValue::Address(Address::repeat_byte(0x11)),
+                DynSolValue::Uint(U256::from(100), 256),
+            ])
+            .unwrap();
+
+        // the selector picks `approve`, rather than `transfer`, which has the same inputs
+        let selector = Selector::from_slice(&calldata[..4]);
+        let (function, values) =
+            decode_calldata(try_get_function_abi(&selector, &abis), &calldata).unwrap();
+        assert_eq!(function.name, "approve");
+        assert_eq!(values[1], DynSolValue::Uint(U256::from(100), 256));
+
+        // selectors which aren't in the ABIs find nothing
+        let unknown = Selector::from([0xde, 0xad, 0xbe, 0xef]);
+        assert!(try_get_function_abi(&unknown, &abis).is_empty());
+        assert!(decode_calldata(try_get_function_abi(&unknown, &abis), &calldata).is_none());
+    }
+
+    #[test]
+    fn test_decode_calldata_skips_mismatched_signatures() {
+        let function = Function::parse("set(uint256)").unwrap();
+        let calldata = function.abi_encode_input(&[DynSolValue::Uint(U256::from(1), 256)]).unwrap();
+
+        // a signature whose inputs the calldata doesn't match, as 4byte.directory may return
+        let mismatched = Function::parse("set(string)").unwrap();
+        let (decoded, values) = decode_calldata(vec![mismatched, function], &calldata).unwrap();
+        assert_eq!(decoded.signature(), "set(uint256)");
+        assert_eq!(values, vec![DynSolValue::Uint(U256::from(1), 256)]);
+    }
+
+    #[test]
+    fn test_decode_calldata_tuple_and_array() {
+        let function = Function::parse("submit((address,uint256[]),bytes32[2])").unwrap();
+        let calldata = function
+            .abi_encode_input(&[
+                DynSolValue::Tuple(vec![
+                    DynSolValue::Address(Address::repeat_byte(0x11)),
+                    DynSolValue::Array(vec![
+                        DynSolValue::Uint(U256::from(1), 256),
+                        DynSolValue::Uint(U256::from(2), 256),
+                    ]),
+                ]),
+                DynSolValue::FixedArray(vec![
+                    DynSolValue::FixedBytes(B256::repeat_byte(0xaa), 32),
+                    DynSolValue::FixedBytes(B256::repeat_byte(0xbb), 32),
+                ]),
+            ])
+            .unwrap();
+
+        let (_, values) = decode_calldata(vec![function], &calldata).unwrap();
+        assert_eq!(
+            values.iter().map(format_value).collect::<Vec<_>>(),
+            [
+                format!("({}, [1, 2])", Address::repeat_byte(0x11).to_checksum(None)),
+                format!("[0x{}, 0x{}]", "aa".repeat(32), "bb".repeat(32)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,11 @@
+mod decode_log;
+mod decode_returndata;
+mod decode_revert;
+mod decode_tx;
+mod pull_events;
+
+pub use decode_log::*;
+pub use decode_returndata::*;
+pub use decode_revert::*;
+pub use decode_tx::*;
+pub use pull_events::*;
@@ -0,0 +1,163 @@
+use alloy::{
+    network::AnyNetwork,
+    primitives::Address,
+    providers::{Provider, ProviderBuilder},
+    rpc::types::{Filter, Log},
+    transports::http::reqwest::Url,
+};
+use eyre::{eyre, Result};
+use serde_json::{json, Map, Value};
+use shadow_common::event::{decode_logs, FullRawEvent, RawOrDecodedEvent};
+use tracing::info;
+
+use crate::{format::format_value, group::get_group_abis, PullEventsArgs};
+
+/// The maximum number of blocks requested in a single `eth_getLogs` call
+const BLOCK_CHUNK_SIZE: u64 = 10_000;
+
+/// The `pull-events` subcommand. Fetches the logs a contract emitted in a block range, and
+/// decodes them using the ABIs in a shadow contract group.
+pub async fn pull_events(args: PullEventsArgs) -> Result<()> {
+    let address: Address = args.address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+    let abis = get_group_abis(&args.root)?;
+
+    let provider =
+        ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+    let to_block = match args.to_block {
+        Some(to_block) => to_block,
+        None => provider.get_block_number().await?,
+    };
+
+    let mut events = vec![];
+    for from_block in (args.from_block..=to_block).step_by(BLOCK_CHUNK_SIZE as usize) {
+        let chunk_to_block = (from_block + BLOCK_CHUNK_SIZE - 1).min(to_block);
+        info!("fetching logs for blocks {} to {}", from_block, chunk_to_block);
+
+        let filter = Filter::new().address(address).from_block(from_block).to_block(chunk_to_block);
+        let logs = provider.get_logs(&filter).await?;
+
+        events.extend(
+            logs.into_iter()
+                .map(|log| {
+                    // anonymous logs can't be decoded, so they're output raw
+                    let event = decode_logs(&[log.inner.clone()], &abis)
+                        .ok()
+                        .and_then(|mut decoded| decoded.pop())
+                        .unwrap_or_else(|| {
+                            RawOrDecodedEvent::Raw(FullRawEvent {
+                                log: log.inner.clone(),
+                                transaction_log_index: 0,
+                            })
+                        });
+                    (log, event)
+                })
+                .filter(|(_, event)| match (&args.event_name, event) {
+                    (None, _) => true,
+                    (Some(name), RawOrDecodedEvent::Decoded(decoded)) => {
+                        &decoded.event.name == name
+                    }
+                    (Some(_), RawOrDecodedEvent::Raw(_)) => false,
+                }),
+        );
+    }
+    info!("found {} events", events.len());
+
+    let output = if args.json {
+        serde_json::to_string_pretty(
+            &events.iter().map(|(log, event)| event_to_json(log, event)).collect::<Vec<_>>(),
+        )?
+    } else {
+        events.iter().map(|(log, event)| event_to_string(log, event)).collect::<Vec<_>>().join("\n")
+    };
+
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, output)?;
+            info!("wrote events to {}", path);
+        }
+        None => println!("{}", output),
+    }
+
+    Ok(())
+}
+
+/// Formats an event as JSON. Undecodable events have a `null` signature and arguments.
+fn event_to_json(log: &Log, event: &RawOrDecodedEvent) -> Value {
+    let (signature, arguments) = match event {
+        RawOrDecodedEvent::Decoded(decoded) => (
+            Some(decoded.event.signature()),
+            Some(Value::Object(
+                named_arguments(event)
+                    .into_iter()
+                    .map(|(name, value)| (name, Value::String(value)))
+                    .collect::<Map<_, _>>(),
+            )),
+        ),
+        RawOrDecodedEvent::Raw(_) => (None, None),
+    };
+
+    json!({
+        "blockNumber": log.block_number,
+        "transactionHash": log.transaction_hash,
+        "logIndex": log.log_index,
+        "address": log.inner.address,
+        "topics": log.inner.topics(),
+        "data": log.inner.data.data,
+        "signature": signature,
+        "arguments": arguments,
+    })
+}
+
+/// Formats an event as human-readable text, falling back to the raw topics and data for
+/// undecodable events
+fn event_to_string(log: &Log, event: &RawOrDecodedEvent) -> String {
+    let header = format!(
+        "block {} | transaction {} | log {}",
+        log.block_number.unwrap_or_default(),
+        log.transaction_hash.unwrap_or_default(),
+        log.log_index.unwrap_or_default()
+    );
+
+    match event {
+        RawOrDecodedEvent::Decoded(decoded) => format!(
+            "{}\n  {}\n{}",
+            header,
+            decoded.event.signature(),
+            named_arguments(event)
+                .into_iter()
+                .map(|(name, value)| format!("    {}: {}", name, value))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+        RawOrDecodedEvent::Raw(_) => format!(
+            "{}\n  undecoded\n{}\n    data: {}",
+            header,
+            log.inner
+                .topics()
+                .iter()
+                .enumerate()
+                .map(|(i, topic)| format!("    topic {}: {}", i, topic))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            log.inner.data.data
+        ),
+    }
+}
+
+/// Returns a decoded event's arguments in ABI order, paired with their names
+fn named_arguments(event: &RawOrDecodedEvent) -> Vec<(String, String)> {
+    let RawOrDecodedEvent::Decoded(decoded) = event else { return vec![] };
+
+    let (mut indexed, mut body) = (decoded.inner.indexed.iter(), decoded.inner.body.iter());
+    decoded
+        .event
+        .inputs
+        .iter()
+        .enumerate()
+        .filter_map(|(i, input)| {
+            let value = if input.indexed { indexed.next() } else { body.next() }?;
+            let name = if input.name.is_empty() { format!("arg{}", i) } else { input.name.clone() };
+            Some((name, format_value(value)))
+        })
+        .collect()
+}
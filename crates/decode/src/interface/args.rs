@@ -0,0 +1,205 @@
+use clap::Parser;
+use eyre::{bail, Result};
+
+/// Arguments for the `decode-tx` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Decode a transaction's calldata using the ABIs in a shadow contract group")]
+pub struct DecodeTxArgs {
+    /// The hash of the transaction to decode.
+    pub transaction_hash: Option<String>,
+
+    /// Decode this calldata directly, rather than fetching a transaction.
+    #[clap(long, required = false)]
+    pub calldata: Option<String>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain to fetch the transaction from.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+}
+
+impl DecodeTxArgs {
+    /// Validates the configuration arguments.
+    pub fn validate(&self) -> Result<()> {
+        if self.transaction_hash.is_some() == self.calldata.is_some() {
+            bail!("either a transaction hash or --calldata must be provided");
+        }
+
+        Ok(())
+    }
+}
+
+/// Arguments for the `decode-log` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Decode a raw log using the ABIs in a shadow contract group")]
+pub struct DecodeLogArgs {
+    /// The address of the contract which emitted the log.
+    #[clap(long, required = false)]
+    pub address: Option<String>,
+
+    /// The log's topics, comma separated. The first topic is the event selector.
+    #[clap(long, value_delimiter = ',', required = false)]
+    pub topics: Vec<String>,
+
+    /// The log's data.
+    #[clap(long, default_value = "0x")]
+    pub data: String,
+
+    /// Decode the logs emitted by this transaction, rather than a manually provided log.
+    #[clap(long, required = false)]
+    pub tx: Option<String>,
+
+    /// The index of the log to decode within the transaction. If not set, all of the
+    /// transaction's logs are decoded.
+    #[clap(long, required = false, requires = "tx")]
+    pub log_index: Option<usize>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain to fetch the transaction from.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+}
+
+impl DecodeLogArgs {
+    /// Validates the configuration arguments.
+    pub fn validate(&self) -> Result<()> {
+        if self.tx.is_none() && self.topics.is_empty() {
+            bail!("either --tx or --topics must be provided");
+        }
+        if self.tx.is_some() && !self.topics.is_empty() {
+            bail!("--tx and --topics cannot be used together");
+        }
+
+        Ok(())
+    }
+}
+
+/// Arguments for the `pull-events` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(
+    about = "Fetch and decode a contract's historical events using the ABIs in a shadow contract group"
+)]
+pub struct PullEventsArgs {
+    /// The address of the contract whose events to fetch.
+    #[clap(long)]
+    pub address: String,
+
+    /// The first block to fetch events from.
+    #[clap(long)]
+    pub from_block: u64,
+
+    /// The last block to fetch events from. Defaults to the latest block.
+    #[clap(long, required = false)]
+    pub to_block: Option<u64>,
+
+    /// Only output events with this name.
+    #[clap(long, required = false)]
+    pub event_name: Option<String>,
+
+    /// Whether to output the events as JSON.
+    #[clap(long)]
+    pub json: bool,
+
+    /// Write the events to this file instead of stdout.
+    #[clap(short, long, required = false)]
+    pub output: Option<String>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain to fetch the events from.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+}
+
+/// Arguments for the `decode-revert` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Decode why a transaction reverted when simulated with shadow overrides")]
+pub struct DecodeRevertArgs {
+    /// The hash of the transaction to simulate and decode the revert of.
+    pub transaction_hash: Option<String>,
+
+    /// Decode this revert data directly, rather than simulating a transaction.
+    #[clap(long, required = false)]
+    pub data: Option<String>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain to simulate the transaction on.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+
+    /// The chain ID of the chain to simulate the transaction on. Fetched from the RPC if not
+    /// provided.
+    #[clap(long, required = false)]
+    pub chain_id: Option<u64>,
+}
+
+impl DecodeRevertArgs {
+    /// Validates the configuration arguments.
+    pub fn validate(&self) -> Result<()> {
+        if self.transaction_hash.is_some() == self.data.is_some() {
+            bail!("either a transaction hash or --data must be provided");
+        }
+
+        Ok(())
+    }
+}
+
+/// Arguments for the `decode-returndata` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Decode the return data of a call using the ABIs in a shadow contract group")]
+pub struct DecodeReturndataArgs {
+    /// The address of the contract which returned the data. If set, only its ABI is searched.
+    #[clap(long, required = false)]
+    pub address: Option<String>,
+
+    /// The selector of the function which returned the data. Defaults to the selector of
+    /// `--tx`'s calldata.
+    #[clap(long, required = false)]
+    pub selector: Option<String>,
+
+    /// The return data to decode.
+    #[clap(long, required = false)]
+    pub data: Option<String>,
+
+    /// Decode the return data of this transaction's top-level call, fetched with
+    /// `trace_replayTransaction`, rather than `--data`.
+    #[clap(long, required = false)]
+    pub tx: Option<String>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain to fetch the transaction from.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+
+    /// The chain ID of the contract. Required if `--address` is tracked on multiple chains.
+    #[clap(long, required = false)]
+    pub chain_id: Option<u64>,
+}
+
+impl DecodeReturndataArgs {
+    /// Validates the configuration arguments.
+    pub fn validate(&self) -> Result<()> {
+        if self.data.is_some() == self.tx.is_some() {
+            bail!("either --data or --tx must be provided");
+        }
+        if self.selector.is_none() && self.tx.is_none() {
+            bail!("--selector is required when decoding --data");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+//! The `decode-tx`, `decode-log`, `pull-events`, `decode-revert` and `decode-returndata`
+//! subcommands
+
+mod core;
+mod interface;
+mod utils;
+
+pub use core::*;
+pub use interface::*;
+pub(crate) use utils::*;
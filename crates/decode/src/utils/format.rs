@@ -0,0 +1,34 @@
+use alloy::{dyn_abi::DynSolValue, json_abi::Function};
+use tracing::info;
+
+/// Formats a decoded value the same way `cast decode-calldata` does
+pub(crate) fn format_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Address(address) => address.to_checksum(None),
+        DynSolValue::Function(function) => function.to_string(),
+        DynSolValue::Bool(b) => b.to_string(),
+        DynSolValue::Int(i, _) => i.to_string(),
+        DynSolValue::Uint(u, _) => u.to_string(),
+        DynSolValue::FixedBytes(word, size) => format!("0x{}", hex::encode(&word[..*size])),
+        DynSolValue::Bytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        DynSolValue::String(s) => format!("{:?}", s),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+            format!("[{}]", values.iter().map(format_value).collect::<Vec<_>>().join(", "))
+        }
+        // tuples, and structs when EIP-712 support is enabled
+        _ => match value.as_fixed_seq() {
+            Some(values) => {
+                format!("({})", values.iter().map(format_value).collect::<Vec<_>>().join(", "))
+            }
+            None => format!("{:?}", value),
+        },
+    }
+}
+
+/// Prints the decoded values of a function's inputs or outputs, one per line
+pub(crate) fn print_decoded(function: &Function, values: &[DynSolValue]) {
+    info!("{}", function.full_signature());
+    for value in values {
+        println!("{}", format_value(value));
+    }
+}
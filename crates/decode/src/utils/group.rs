@@ -0,0 +1,23 @@
+use std::{path::PathBuf, str::FromStr};
+
+use alloy::json_abi::JsonAbi;
+use eyre::{eyre, Result};
+use shadow_common::{abi::get_abis, ShadowContractGroupInfo};
+use tracing::{error, warn};
+
+/// Loads the ABIs from the contract group's latest build, if there is one
+pub(crate) fn get_group_abis(root: &str) -> Result<Vec<JsonAbi>> {
+    let root_dir = PathBuf::from_str(root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    let artifact_path = group_info.artifact_path();
+    if !artifact_path.exists() {
+        warn!("the contract group has not been built yet. run `shadow sim` or `shadow push` to build it");
+        return Ok(vec![]);
+    }
+
+    get_abis(&artifact_path)
+}
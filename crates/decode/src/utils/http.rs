@@ -0,0 +1,36 @@
+use alloy::primitives::Selector;
+use eyre::{eyre, Result};
+use serde::Deserialize;
+
+/// A page of results from the 4byte.directory signature API
+#[derive(Debug, Deserialize)]
+struct FourByteResponse {
+    results: Vec<FourByteSignature>,
+}
+
+/// A single signature from the 4byte.directory signature API
+#[derive(Debug, Deserialize)]
+struct FourByteSignature {
+    text_signature: String,
+}
+
+/// Looks up the text signatures matching a function selector on 4byte.directory, e.g.
+/// `transfer(address,uint256)`.
+pub(crate) async fn lookup_function_signatures(selector: &Selector) -> Result<Vec<String>> {
+    let response = reqwest::Client::new()
+        .get("https://www.4byte.directory/api/v1/signatures/")
+        .query(&[("hex_signature", selector.to_string())])
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(eyre!(
+            "Failed to look up {} on 4byte.directory: {}",
+            selector,
+            response.text().await?
+        ));
+    }
+
+    let response: FourByteResponse = response.json().await?;
+    Ok(response.results.into_iter().map(|r| r.text_signature).collect())
+}
@@ -0,0 +1,8 @@
+/// Value formatting utilities
+pub(crate) mod format;
+/// Contract group utilities
+pub(crate) mod group;
+/// HTTP utilities used to look up unknown selectors
+pub(crate) mod http;
+/// Revert data decoding utilities
+pub(crate) mod revert;
@@ -0,0 +1,163 @@
+use alloy::{
+    dyn_abi::{DynSolType, DynSolValue, Specifier},
+    json_abi::JsonAbi,
+    primitives::{Bytes, U256},
+};
+
+use crate::format::format_value;
+
+/// The selector of `Error(string)`, used by `require` and `revert` with a message
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// The selector of `Panic(uint256)`, used by failed assertions and arithmetic errors
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Revert data, decoded
+#[derive(Debug, Clone)]
+pub(crate) enum DecodedRevert {
+    /// An `Error(string)` revert
+    Error(String),
+    /// A `Panic(uint256)` revert, with its code
+    Panic(U256),
+    /// A custom error from the group's ABIs
+    Custom { signature: String, values: Vec<DynSolValue> },
+    /// Revert data which couldn't be decoded
+    Raw(Bytes),
+}
+
+impl std::fmt::Display for DecodedRevert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodedRevert::Error(message) => write!(f, "Error({:?})", message),
+            DecodedRevert::Panic(code) => {
+                write!(f, "Panic(0x{:x}): {}", code, panic_reason(code))
+            }
+            DecodedRevert::Custom { signature, values } => {
+                write!(f, "{}", signature)?;
+                for value in values {
+                    write!(f, "\n  {}", format_value(value))?;
+                }
+                Ok(())
+            }
+            DecodedRevert::Raw(data) if data.is_empty() => write!(f, "reverted without data"),
+            DecodedRevert::Raw(data) => write!(f, "unknown revert data: {}", data),
+        }
+    }
+}
+
+/// Decodes revert data, trying `Error(string)`, then `Panic(uint256)`, then the custom errors
+/// in `abis`. Falls back to the raw data if none of them match.
+pub(crate) fn decode_revert_data(data: &[u8], abis: &[JsonAbi]) -> DecodedRevert {
+    if data.len() < 4 {
+        return DecodedRevert::Raw(Bytes::copy_from_slice(data));
+    }
+    let (selector, params) = data.split_at(4);
+
+    if selector == ERROR_SELECTOR {
+        if let Some(DynSolValue::String(message)) = decode_single(DynSolType::String, params) {
+            return DecodedRevert::Error(message);
+        }
+    }
+
+    if selector == PANIC_SELECTOR {
+        if let Some(DynSolValue::Uint(code, _)) = decode_single(DynSolType::Uint(256), params) {
+            return DecodedRevert::Panic(code);
+        }
+    }
+
+    for error in abis.iter().flat_map(|abi| abi.errors()) {
+        if error.selector().as_slice() != selector {
+            continue;
+        }
+        let Ok(types) = error.inputs.iter().map(|input| input.resolve()).collect::<Result<_, _>>()
+        else {
+            continue;
+        };
+        if let Ok(DynSolValue::Tuple(values)) = DynSolType::Tuple(types).abi_decode_sequence(params)
+        {
+            return DecodedRevert::Custom { signature: error.signature(), values };
+        }
+    }
+
+    DecodedRevert::Raw(Bytes::copy_from_slice(data))
+}
+
+/// Decodes parameters consisting of a single value of the given type
+fn decode_single(ty: DynSolType, params: &[u8]) -> Option<DynSolValue> {
+    match DynSolType::Tuple(vec![ty]).abi_decode_sequence(params) {
+        Ok(DynSolValue::Tuple(mut values)) if values.len() == 1 => values.pop(),
+        _ => None,
+    }
+}
+
+/// Describes a Solidity panic code
+fn panic_reason(code: &U256) -> &'static str {
+    match u64::try_from(*code) {
+        Ok(0x00) => "generic compiler panic",
+        Ok(0x01) => "assertion failed",
+        Ok(0x11) => "arithmetic overflow or underflow",
+        Ok(0x12) => "division or modulo by zero",
+        Ok(0x21) => "invalid enum value",
+        Ok(0x22) => "invalid storage byte array encoding",
+        Ok(0x31) => "pop on an empty array",
+        Ok(0x32) => "array index out of bounds",
+        Ok(0x41) => "out of memory",
+        Ok(0x51) => "call to an uninitialized internal function",
+        _ => "unknown panic code",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Address;
+
+    use super::*;
+
+    /// ABI-encodes the selector followed by the values
+    fn revert_data(selector: &[u8], values: Vec<DynSolValue>) -> Vec<u8> {
+        [selector, &DynSolValue::Tuple(values).abi_encode_params()].concat()
+    }
+
+    #[test]
+    fn test_decode_revert_data_builtin() {
+        let data = revert_data(&ERROR_SELECTOR, vec![DynSolValue::String("not owner".to_string())]);
+        assert_eq!(decode_revert_data(&data, &[]).to_string(), "Error(\"not owner\")");
+
+        let data = revert_data(&PANIC_SELECTOR, vec![DynSolValue::Uint(U256::from(0x11), 256)]);
+        assert_eq!(
+            decode_revert_data(&data, &[]).to_string(),
+            "Panic(0x11): arithmetic overflow or underflow"
+        );
+    }
+
+    #[test]
+    fn test_decode_revert_data_custom() {
+        let abis =
+            [JsonAbi::parse(["error Unauthorized(address caller, (uint256,bool) info)"]).unwrap()];
+        let error = abis[0].errors().next().unwrap();
+        let values = vec![
+            DynSolValue::Address(Address::repeat_byte(0x11)),
+            DynSolValue::Tuple(vec![
+                DynSolValue::Uint(U256::from(7), 256),
+                DynSolValue::Bool(true),
+            ]),
+        ];
+        let data = revert_data(error.selector().as_slice(), values.clone());
+
+        match decode_revert_data(&data, &abis) {
+            DecodedRevert::Custom { signature, values: decoded } => {
+                assert_eq!(signature, "Unauthorized(address,(uint256,bool))");
+                assert_eq!(decoded, values);
+            }
+            decoded => panic!("expected a custom error, got {:?}", decoded),
+        }
+
+        // without the ABI, the data can't be decoded
+        assert!(matches!(decode_revert_data(&data, &[]), DecodedRevert::Raw(_)));
+    }
+
+    #[test]
+    fn test_decode_revert_data_raw() {
+        assert_eq!(decode_revert_data(&[], &[]).to_string(), "reverted without data");
+        assert!(matches!(decode_revert_data(&[0x12, 0x34], &[]), DecodedRevert::Raw(_)));
+    }
+}
@@ -0,0 +1,45 @@
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
+
+use eyre::{eyre, Result};
+use tracing::info;
+
+use crate::{collect_bytecode, GenerateRethConfigArgs};
+
+/// The `generate-reth-config` subcommand. Writes the compiled bytecode of every contract in a
+/// shadow contract group to a shadow-reth `shadow.json`, optionally merging it into an existing
+/// one.
+pub async fn generate_reth_config(args: GenerateRethConfigArgs) -> Result<()> {
+    let group_dir = PathBuf::from_str(&args.group)?;
+    let bytecode = collect_bytecode(&group_dir, args.chain_id)?;
+    let collected = bytecode.len();
+
+    // contracts from this group replace any existing entries at the same address
+    let mut reth_config = BTreeMap::new();
+    if args.merge {
+        match std::fs::read_to_string(&args.output) {
+            Ok(contents) => {
+                reth_config = serde_json::from_str::<BTreeMap<String, String>>(&contents)
+                    .map_err(|e| eyre!("Failed to parse {}: {}", args.output, e))?;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(eyre!("Failed to read {}: {}", args.output, e)),
+        }
+    }
+    let existing = reth_config.len();
+    reth_config.extend(bytecode);
+
+    std::fs::write(&args.output, serde_json::to_string_pretty(&reth_config)?)?;
+    if args.merge {
+        info!(
+            "merged {} contracts into {} ({} existing, {} total)",
+            collected,
+            args.output,
+            existing,
+            reth_config.len()
+        );
+    } else {
+        info!("wrote {} contracts to {}", collected, args.output);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use alloy::primitives::Address;
+use eyre::{bail, eyre, Result};
+use shadow_common::{ShadowContractGroupInfo, BUILD_ARTIFACTS_HINT};
+use tracing::{error, info, warn};
+
+use crate::ExportShadowJsonArgs;
+
+mod generate_reth_config;
+pub use generate_reth_config::*;
+
+/// The `export-shadow-json` subcommand. Writes the compiled bytecode of every contract in a
+/// shadow contract group to a `shadow.json` file for use with shadow-reth.
+pub async fn export_shadow_json(args: ExportShadowJsonArgs) -> Result<()> {
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let reth_config = collect_bytecode(&root_dir, args.chain_id)?;
+
+    std::fs::write(&args.output, serde_json::to_string_pretty(&reth_config)?)?;
+    info!("wrote {} contracts to {}", reth_config.len(), args.output);
+
+    Ok(())
+}
+
+/// Collects the compiled bytecode of every contract in a shadow contract group's `out/`
+/// directory, keyed by address, optionally only for contracts on the given chain.
+pub(crate) fn collect_bytecode(
+    root_dir: &Path,
+    chain_id: Option<u64>,
+) -> Result<BTreeMap<String, String>> {
+    // root dir must be a shadow contract group
+    let group_info = ShadowContractGroupInfo::from_path(root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    for contract in group_info.stale_contracts() {
+        if chain_id.map_or(true, |filter| filter == contract.chain_id) {
+            warn!(
+                "{} on chain {} has changed since it was last built. {}",
+                contract.address, contract.chain_id, BUILD_ARTIFACTS_HINT
+            );
+        }
+    }
+
+    let out_dir = root_dir.join("out");
+    if !out_dir.exists() {
+        bail!("{} does not exist. {}", out_dir.display(), BUILD_ARTIFACTS_HINT);
+    }
+
+    // artifacts are laid out as `out/<group>/<chain id>/<address>/bytecode.hex`
+    let mut reth_config = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(&out_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() == "bytecode.hex")
+    {
+        let contract_dir = entry.path().parent();
+        let address = contract_dir
+            .and_then(|dir| dir.file_name())
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse::<Address>().ok());
+        let contract_chain_id = contract_dir
+            .and_then(|dir| dir.parent())
+            .and_then(|dir| dir.file_name())
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse::<u64>().ok());
+        let (Some(address), Some(contract_chain_id)) = (address, contract_chain_id) else {
+            warn!("skipping {}, which is not in a contract directory", entry.path().display());
+            continue;
+        };
+        if chain_id.map_or(false, |filter| filter != contract_chain_id) {
+            continue;
+        }
+
+        let bytecode = std::fs::read_to_string(entry.path())?;
+        let bytecode = bytecode.trim().trim_start_matches("0x");
+        reth_config.insert(address.to_string(), format!("0x{}", bytecode));
+    }
+
+    if reth_config.is_empty() {
+        bail!("no built contracts found in {}. {}", out_dir.display(), BUILD_ARTIFACTS_HINT);
+    }
+
+    Ok(reth_config)
+}
@@ -0,0 +1,39 @@
+use clap::Parser;
+
+/// Arguments for the `export-shadow-json` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Write a shadow-reth `shadow.json` from a contract group's compiled artifacts")]
+pub struct ExportShadowJsonArgs {
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The path to write the `shadow.json` file to.
+    #[clap(short, long, default_value = "shadow.json", required = false)]
+    pub output: String,
+
+    /// Only export contracts on this chain.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+}
+
+/// Arguments for the `generate-reth-config` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Generate a shadow-reth `shadow.json` from a contract group's compiled artifacts")]
+pub struct GenerateRethConfigArgs {
+    /// The path to write the `shadow.json` file to.
+    #[clap(short, long, default_value = "shadow.json", required = false)]
+    pub output: String,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub group: String,
+
+    /// Only include contracts on this chain.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// Merge the group's contracts into an existing `shadow.json`, rather than overwriting it.
+    #[clap(short, long)]
+    pub merge: bool,
+}
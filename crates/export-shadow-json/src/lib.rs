@@ -0,0 +1,7 @@
+//! The `export-shadow-json` and `generate-reth-config` subcommands
+
+mod core;
+mod interface;
+
+pub use core::*;
+pub use interface::*;
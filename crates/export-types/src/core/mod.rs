@@ -0,0 +1,107 @@
+use std::{collections::HashSet, path::PathBuf, str::FromStr};
+
+use alloy::{json_abi::JsonAbi, primitives::Address};
+use eyre::{bail, eyre, Result};
+use shadow_common::{ShadowContractGroupInfo, ShadowContractInfo, BUILD_ARTIFACTS_HINT};
+use tracing::{error, info, warn};
+
+use crate::{naming::identifier, python, typescript, ExportTypesArgs, TypeLanguage};
+
+/// The `export-types` subcommand. Generates a TypeScript or Python module of type definitions
+/// for each contract in a shadow contract group from its compiled ABI, along with an index
+/// module.
+pub async fn export_types(args: ExportTypesArgs) -> Result<()> {
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    let contracts = match &args.address {
+        Some(address) => {
+            let address: Address = address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+            vec![group_info.contract(&address, args.chain_id)?.clone()]
+        }
+        None => group_info
+            .contracts
+            .iter()
+            .filter(|contract| args.chain_id.map_or(true, |id| contract.chain_id == id))
+            .cloned()
+            .collect(),
+    };
+
+    let output_dir = PathBuf::from_str(&args.output_dir)?;
+    std::fs::create_dir_all(&output_dir)?;
+    let extension = match args.language {
+        TypeLanguage::Typescript => "ts",
+        TypeLanguage::Python => "py",
+    };
+
+    let mut modules = vec![];
+    let mut module_names = HashSet::new();
+    for contract in &contracts {
+        let abi_path = group_info
+            .artifact_path()
+            .join(contract.chain_id.to_string())
+            .join(contract.address.to_string().to_lowercase())
+            .join("abi.json");
+        if !abi_path.exists() {
+            if args.address.is_some() {
+                bail!("{} has not been built. {}", contract.address, BUILD_ARTIFACTS_HINT);
+            }
+            warn!("skipping {}, which has not been built", contract.address);
+            continue;
+        }
+        let abi: JsonAbi = serde_json::from_str(&std::fs::read_to_string(&abi_path)?)
+            .map_err(|e| eyre!("Failed to parse {}: {}", abi_path.display(), e))?;
+        let info = ShadowContractInfo::from_path(
+            &group_info
+                .contract_path(&contract.address, Some(contract.chain_id))?
+                .join("info.json"),
+        )?;
+
+        // contracts may share a name, e.g. proxies, so later ones are qualified by address
+        let mut module = identifier(&info.name);
+        if !module_names.insert(module.clone()) {
+            module = format!("{}_{}", module, &contract.address.to_string()[2..10].to_lowercase());
+            module_names.insert(module.clone());
+        }
+
+        let source = match args.language {
+            TypeLanguage::Typescript => {
+                typescript::generate(&info.name, &contract.address, contract.chain_id, &abi)?
+            }
+            TypeLanguage::Python => {
+                python::generate(&info.name, &contract.address, contract.chain_id, &abi)
+            }
+        };
+        let path = output_dir.join(format!("{}.{}", module, extension));
+        std::fs::write(&path, source)?;
+        info!("wrote types for {} to {}", info.name, path.display());
+        modules.push(module);
+    }
+
+    if modules.is_empty() {
+        bail!("no built contracts found. {}", BUILD_ARTIFACTS_HINT);
+    }
+
+    // an index module re-exports every contract's module
+    let (index_path, index) = match args.language {
+        TypeLanguage::Typescript => (
+            output_dir.join("index.ts"),
+            modules
+                .iter()
+                .map(|module| format!("export * as {} from \"./{}\";\n", module, module))
+                .collect::<String>(),
+        ),
+        TypeLanguage::Python => (
+            output_dir.join("__init__.py"),
+            modules.iter().map(|module| format!("from . import {}\n", module)).collect(),
+        ),
+    };
+    std::fs::write(&index_path, index)?;
+    info!("wrote types for {} contracts to {}", modules.len(), output_dir.display());
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use clap::{Parser, ValueEnum};
+
+/// Arguments for the `export-types` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Generate TypeScript or Python type definitions from shadow contract ABIs")]
+pub struct ExportTypesArgs {
+    /// The language to generate type definitions for.
+    #[clap(short, long, value_enum)]
+    pub language: TypeLanguage,
+
+    /// Only generate types for the contract at this address, rather than every contract in the
+    /// group.
+    #[clap(short, long, required = false)]
+    pub address: Option<String>,
+
+    /// Only generate types for contracts on this chain. Required with `--address` if the
+    /// address is tracked on multiple chains.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The directory to write the generated files to.
+    #[clap(short, long, default_value = "types", required = false)]
+    pub output_dir: String,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+}
+
+/// The languages type definitions can be generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TypeLanguage {
+    /// TypeScript definitions for use with `viem`
+    Typescript,
+    /// Python dataclasses, decoded with `eth-abi`
+    Python,
+}
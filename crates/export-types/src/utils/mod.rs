@@ -0,0 +1,6 @@
+/// Naming and ABI type helpers shared by the generators
+pub(crate) mod naming;
+/// Python type definition generation
+pub(crate) mod python;
+/// TypeScript type definition generation
+pub(crate) mod typescript;
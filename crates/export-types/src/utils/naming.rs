@@ -0,0 +1,53 @@
+/// Disambiguates overloaded names, suffixing every occurrence after the first with its index,
+/// e.g. `Transfer` and `Transfer_1`
+pub(crate) fn unique_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut seen = std::collections::HashMap::<&str, usize>::new();
+    names
+        .into_iter()
+        .map(|name| {
+            let count = seen.entry(name).or_default();
+            *count += 1;
+            match *count {
+                1 => name.to_string(),
+                n => format!("{}_{}", name, n - 1),
+            }
+        })
+        .collect()
+}
+
+/// Uppercases the first character of a name, e.g. `transferFrom` to `TransferFrom`
+pub(crate) fn pascal_case(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Turns an arbitrary name into a valid identifier, e.g. for a contract's module name
+pub(crate) fn identifier(name: &str) -> String {
+    let identifier =
+        name.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '_').collect::<String>();
+    match identifier.chars().next() {
+        None => "Contract".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("_{}", identifier),
+        Some(_) => identifier,
+    }
+}
+
+/// Splits an array type into its element type, e.g. `uint256[2][]` into `uint256[2]`. None if
+/// the type isn't an array.
+pub(crate) fn array_element(ty: &str) -> Option<&str> {
+    ty.strip_suffix(']').and_then(|ty| ty.rfind('[').map(|i| &ty[..i]))
+}
+
+/// The bit width of an integer type, e.g. 256 for `uint256` or `uint`
+pub(crate) fn int_bits(ty: &str) -> usize {
+    ty.trim_start_matches('u').trim_start_matches("int").parse().unwrap_or(256)
+}
+
+/// Whether an indexed event parameter of this type is only stored as its keccak256 hash, which
+/// is the case for every reference type
+pub(crate) fn is_hashed_when_indexed(ty: &str) -> bool {
+    ty == "string" || ty == "bytes" || ty.starts_with("tuple") || ty.ends_with(']')
+}
@@ -0,0 +1,234 @@
+use alloy::{
+    json_abi::{JsonAbi, Param},
+    primitives::{hex, Address},
+};
+
+use crate::naming::{array_element, is_hashed_when_indexed, pascal_case, unique_names};
+
+/// Python keywords, and the generated class attributes, which can't be used as field names
+const RESERVED_NAMES: [&str; 37] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield", "TOPIC", "SELECTOR",
+];
+
+/// Decodes a raw log with the field table generated for each event. Indexed values of reference
+/// types are only stored as their hash, so they're decoded as `bytes32`.
+const PARSE_EVENT: &str = r#"def _to_bytes(value: Union[bytes, str]) -> bytes:
+    if isinstance(value, str):
+        return bytes.fromhex(value[2:] if value.startswith("0x") else value)
+    return bytes(value)
+
+
+def parse_event(log: RawLog) -> ShadowEvent:
+    """Decodes a log emitted by the contract. Raises a ValueError if it isn't one of its events."""
+    topics = [_to_bytes(topic) for topic in log["topics"]]
+    if not topics or topics[0] not in _EVENTS:
+        raise ValueError("unknown event")
+
+    event, fields = _EVENTS[topics[0]]
+    body = iter(decode([ty for _, ty, indexed in fields if not indexed], _to_bytes(log["data"])))
+    indexed_topics = iter(topics[1:])
+    values = {}
+    for name, ty, indexed in fields:
+        values[name] = decode([ty], next(indexed_topics))[0] if indexed else next(body)
+    return event(**values)
+"#;
+
+/// Generates a Python module for a contract, with a dataclass for each event and function, and a
+/// `parse_event` helper which decodes logs with `eth-abi`
+pub(crate) fn generate(name: &str, address: &Address, chain_id: u64, abi: &JsonAbi) -> String {
+    let mut lines = vec![
+        format!(
+            "# Generated by `shadow export-types` from the ABI of {} ({} on chain {}). Do not edit.",
+            name, address, chain_id
+        ),
+        "from dataclasses import dataclass".to_string(),
+        "from typing import Any, ClassVar, Dict, List, Sequence, Tuple, TypedDict, Union"
+            .to_string(),
+        String::new(),
+        "from eth_abi import decode".to_string(),
+        String::new(),
+        format!("ADDRESS = \"{}\"", address),
+        format!("CHAIN_ID = {}", chain_id),
+        String::new(),
+        String::new(),
+        "class RawLog(TypedDict):".to_string(),
+        "    \"\"\"A raw log, as returned by `eth_getLogs`. Topics and data may be bytes or hex \
+         strings.\"\"\""
+            .to_string(),
+        String::new(),
+        "    topics: Sequence[Union[bytes, str]]".to_string(),
+        "    data: Union[bytes, str]".to_string(),
+    ];
+
+    // anonymous events have no selector topic, so they can't be told apart when parsing
+    let events = abi.events().collect::<Vec<_>>();
+    let event_names = unique_names(events.iter().map(|event| event.name.as_str()));
+    let mut parsed_events = vec![];
+    for (event, type_name) in events.iter().zip(&event_names) {
+        let class_name = format!("{}Event", type_name);
+        let mut fields = vec![];
+        let mut field_table = vec![];
+        for (i, input) in event.inputs.iter().enumerate() {
+            let field_name = field_name(&input.name, i);
+            let (py_type, abi_type) = if input.indexed && is_hashed_when_indexed(&input.ty) {
+                ("bytes".to_string(), "bytes32".to_string())
+            } else {
+                (py_type(&input.ty, &input.components), input.selector_type().to_string())
+            };
+            fields.push(format!("    {}: {}", field_name, py_type));
+            field_table.push(format!(
+                "(\"{}\", \"{}\", {})",
+                field_name,
+                abi_type,
+                if input.indexed { "True" } else { "False" }
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push(String::new());
+        lines.push("@dataclass".to_string());
+        lines.push(format!("class {}:", class_name));
+        lines.push(format!("    \"\"\"`{}`\"\"\"", event.full_signature()));
+        lines.push(String::new());
+        if !event.anonymous {
+            lines.push(format!(
+                "    TOPIC: ClassVar[bytes] = bytes.fromhex(\"{}\")",
+                hex::encode(event.selector())
+            ));
+            if !fields.is_empty() {
+                lines.push(String::new());
+            }
+            parsed_events.push((class_name, field_table));
+        } else if fields.is_empty() {
+            lines.push("    pass".to_string());
+        }
+        lines.extend(fields);
+    }
+
+    let functions = abi.functions().collect::<Vec<_>>();
+    let function_names = unique_names(functions.iter().map(|function| function.name.as_str()));
+    for (function, type_name) in functions.iter().zip(&function_names) {
+        lines.push(String::new());
+        lines.push(String::new());
+        lines.push("@dataclass".to_string());
+        lines.push(format!("class {}Call:", pascal_case(type_name)));
+        lines.push(format!("    \"\"\"`{}`\"\"\"", function.full_signature()));
+        lines.push(String::new());
+        lines.push(format!(
+            "    SELECTOR: ClassVar[bytes] = bytes.fromhex(\"{}\")",
+            hex::encode(function.selector())
+        ));
+        if !function.inputs.is_empty() {
+            lines.push(String::new());
+        }
+        for (i, input) in function.inputs.iter().enumerate() {
+            lines.push(format!(
+                "    {}: {}",
+                field_name(&input.name, i),
+                py_type(&input.ty, &input.components)
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push(String::new());
+    lines.push(format!(
+        "ShadowEvent = {}",
+        match parsed_events.len() {
+            0 => "Any".to_string(),
+            1 => parsed_events[0].0.clone(),
+            _ => format!(
+                "Union[{}]",
+                parsed_events.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(", ")
+            ),
+        }
+    ));
+    lines.push(String::new());
+    lines.push(
+        "# the fields of each event, as (field name, ABI type, whether it's indexed)".to_string(),
+    );
+    lines.push("_EVENTS: Dict[bytes, Tuple[type, List[Tuple[str, str, bool]]]] = {".to_string());
+    for (class_name, field_table) in &parsed_events {
+        lines.push(format!(
+            "    {}.TOPIC: ({}, [{}]),",
+            class_name,
+            class_name,
+            field_table.join(", ")
+        ));
+    }
+    lines.push("}".to_string());
+    lines.push(String::new());
+    lines.push(String::new());
+
+    format!("{}\n{}", lines.join("\n"), PARSE_EVENT)
+}
+
+/// The Python type `eth-abi` decodes an ABI type to
+fn py_type(ty: &str, components: &[Param]) -> String {
+    if let Some(element) = array_element(ty) {
+        return format!("Tuple[{}, ...]", py_type(element, components));
+    }
+
+    match ty {
+        "address" | "string" => "str".to_string(),
+        "bool" => "bool".to_string(),
+        "tuple" => format!(
+            "Tuple[{}]",
+            components
+                .iter()
+                .map(|component| py_type(&component.ty, &component.components))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ty if ty.starts_with("uint") || ty.starts_with("int") => "int".to_string(),
+        ty if ty.starts_with("bytes") => "bytes".to_string(),
+        _ => "Any".to_string(),
+    }
+}
+
+/// The dataclass field name for a parameter, avoiding Python keywords and unnamed parameters
+fn field_name(name: &str, index: usize) -> String {
+    if name.is_empty() {
+        format!("arg{}", index)
+    } else if RESERVED_NAMES.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::address;
+
+    use super::*;
+
+    #[test]
+    fn test_generate_python() {
+        let abi = JsonAbi::parse([
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "event Named(string indexed name, uint8[] kinds)",
+            "function transfer(address to, uint256 amount) returns (bool)",
+        ])
+        .unwrap();
+        let source =
+            generate("Token", &address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"), 1, &abi);
+
+        assert!(source.contains("class TransferEvent:"));
+        assert!(source.contains("    from_: str\n    to: str\n    value: int\n"));
+        assert!(source.contains("    name: bytes\n    kinds: Tuple[int, ...]\n"));
+        assert!(source.contains(
+            "    TOPIC: ClassVar[bytes] = bytes.fromhex(\"ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef\")"
+        ));
+        assert!(source.contains("class TransferCall:"));
+        assert!(source.contains("    SELECTOR: ClassVar[bytes] = bytes.fromhex(\"a9059cbb\")"));
+        assert!(source.contains("ShadowEvent = Union[NamedEvent, TransferEvent]"));
+        assert!(source.contains(
+            "    TransferEvent.TOPIC: (TransferEvent, [(\"from_\", \"address\", True), (\"to\", \"address\", True), (\"value\", \"uint256\", False)]),"
+        ));
+        assert!(source.contains("def parse_event(log: RawLog) -> ShadowEvent:"));
+    }
+}
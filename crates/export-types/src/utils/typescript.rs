@@ -0,0 +1,193 @@
+use alloy::{
+    json_abi::{JsonAbi, Param},
+    primitives::Address,
+};
+use eyre::Result;
+
+use crate::naming::{array_element, int_bits, is_hashed_when_indexed, pascal_case, unique_names};
+
+/// Generates a TypeScript module for a contract, with its ABI, an interface for each event and
+/// function, and a `parseEvent` helper. Types follow the conventions of `viem`, which the
+/// module decodes logs with.
+pub(crate) fn generate(
+    name: &str,
+    address: &Address,
+    chain_id: u64,
+    abi: &JsonAbi,
+) -> Result<String> {
+    let mut lines = vec![
+        format!(
+            "// Generated by `shadow export-types` from the ABI of {} ({} on chain {}). Do not edit.",
+            name, address, chain_id
+        ),
+        "import { decodeEventLog, type Address, type Hex } from \"viem\";".to_string(),
+        String::new(),
+        format!("export const address: Address = \"{}\";", address),
+        format!("export const chainId = {};", chain_id),
+        String::new(),
+        format!("export const abi = {} as const;", serde_json::to_string_pretty(abi)?),
+        String::new(),
+        "/** A raw log, as returned by `eth_getLogs` */".to_string(),
+        "export interface RawLog {".to_string(),
+        "  topics: readonly Hex[];".to_string(),
+        "  data: Hex;".to_string(),
+        "}".to_string(),
+    ];
+
+    // anonymous events have no selector topic, so they can't be told apart when parsing
+    let events = abi.events().collect::<Vec<_>>();
+    let event_names = unique_names(events.iter().map(|event| event.name.as_str()));
+    let mut parsed_events = vec![];
+    for (event, type_name) in events.iter().zip(&event_names) {
+        let args = event
+            .inputs
+            .iter()
+            .map(|input| {
+                let ty = if input.indexed && is_hashed_when_indexed(&input.ty) {
+                    "Hex".to_string()
+                } else {
+                    ts_type(&input.ty, &input.components)
+                };
+                (input.name.as_str(), ty)
+            })
+            .collect::<Vec<_>>();
+
+        lines.push(String::new());
+        lines.push(format!("/** `{}` */", event.full_signature()));
+        lines.push(format!("export interface {}Event {{", type_name));
+        lines.push(format!("  eventName: \"{}\";", event.name));
+        lines.push(format!("  args: {};", ts_fields(&args)));
+        lines.push("}".to_string());
+        if !event.anonymous {
+            parsed_events.push(format!("{}Event", type_name));
+        }
+    }
+
+    let functions = abi.functions().collect::<Vec<_>>();
+    let function_names = unique_names(functions.iter().map(|function| function.name.as_str()));
+    for (function, type_name) in functions.iter().zip(&function_names) {
+        let args = function
+            .inputs
+            .iter()
+            .map(|input| ts_type(&input.ty, &input.components))
+            .collect::<Vec<_>>();
+        let result = match function.outputs.as_slice() {
+            [] => "void".to_string(),
+            [output] => ts_type(&output.ty, &output.components),
+            outputs => format!(
+                "readonly [{}]",
+                outputs
+                    .iter()
+                    .map(|output| ts_type(&output.ty, &output.components))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+
+        lines.push(String::new());
+        lines.push(format!("/** `{}` */", function.full_signature()));
+        lines.push(format!("export interface {}Call {{", pascal_case(type_name)));
+        lines.push(format!("  functionName: \"{}\";", function.name));
+        lines.push(format!("  args: readonly [{}];", args.join(", ")));
+        lines.push(format!("  result: {};", result));
+        lines.push("}".to_string());
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "export type ShadowEvent = {};",
+        if parsed_events.is_empty() { "never".to_string() } else { parsed_events.join(" | ") }
+    ));
+    lines.push(String::new());
+    lines.push(format!(
+        "/** Decodes a log emitted by {}. Throws if it isn't one of the contract's events. */",
+        name
+    ));
+    lines.push("export function parseEvent(log: RawLog): ShadowEvent {".to_string());
+    lines.push("  return decodeEventLog({".to_string());
+    lines.push("    abi,".to_string());
+    lines.push("    topics: log.topics as [Hex, ...Hex[]],".to_string());
+    lines.push("    data: log.data,".to_string());
+    lines.push("  }) as unknown as ShadowEvent;".to_string());
+    lines.push("}".to_string());
+
+    Ok(format!("{}\n", lines.join("\n")))
+}
+
+/// The TypeScript type `viem` decodes an ABI type to
+fn ts_type(ty: &str, components: &[Param]) -> String {
+    if let Some(element) = array_element(ty) {
+        return format!("readonly {}[]", ts_type(element, components));
+    }
+
+    match ty {
+        "address" => "Address".to_string(),
+        "bool" => "boolean".to_string(),
+        "string" => "string".to_string(),
+        "tuple" => ts_fields(
+            &components
+                .iter()
+                .map(|component| {
+                    (component.name.as_str(), ts_type(&component.ty, &component.components))
+                })
+                .collect::<Vec<_>>(),
+        ),
+        // integers which fit in a double are decoded to a `number`
+        ty if ty.starts_with("uint") || ty.starts_with("int") => {
+            if int_bits(ty) <= 48 { "number" } else { "bigint" }.to_string()
+        }
+        _ => "Hex".to_string(),
+    }
+}
+
+/// Formats named fields as an object type, or as a tuple if any of them are unnamed, matching
+/// how `viem` decodes them
+fn ts_fields(fields: &[(&str, String)]) -> String {
+    if fields.is_empty() {
+        return "readonly []".to_string();
+    }
+    if fields.iter().any(|(name, _)| name.is_empty()) {
+        return format!(
+            "readonly [{}]",
+            fields.iter().map(|(_, ty)| ty.as_str()).collect::<Vec<_>>().join(", ")
+        );
+    }
+
+    format!(
+        "{{ {} }}",
+        fields.iter().map(|(name, ty)| format!("{}: {}", name, ty)).collect::<Vec<_>>().join("; ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::address;
+
+    use super::*;
+
+    #[test]
+    fn test_generate_typescript() {
+        let abi = JsonAbi::parse([
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "event Named(string indexed name, uint8 kind)",
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function getPosition(uint256 id) returns ((address owner, int24[] ticks), uint128)",
+        ])
+        .unwrap();
+        let source =
+            generate("Token", &address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"), 1, &abi)
+                .unwrap();
+
+        assert!(source.contains("export interface TransferEvent {"));
+        assert!(source.contains("  args: { from: Address; to: Address; value: bigint };"));
+        assert!(source.contains("  args: { name: Hex; kind: number };"));
+        assert!(source.contains("export interface TransferCall {"));
+        assert!(source.contains("  args: readonly [Address, bigint];"));
+        assert!(source.contains("  result: boolean;"));
+        assert!(source.contains(
+            "  result: readonly [{ owner: Address; ticks: readonly number[] }, bigint];"
+        ));
+        assert!(source.contains("export type ShadowEvent = NamedEvent | TransferEvent;"));
+        assert!(source.contains("export function parseEvent(log: RawLog): ShadowEvent {"));
+    }
+}
@@ -1,11 +1,22 @@
-use std::{collections::HashMap, path::PathBuf, str::FromStr};
+mod update_source;
+
+pub use update_source::*;
+
+use std::{collections::HashMap, future::Future, path::PathBuf, pin::Pin, str::FromStr};
 
 use crate::FetchArgs;
+use alloy::{
+    network::AnyNetwork, primitives::Address, providers::ProviderBuilder,
+    transports::http::reqwest::Url,
+};
 use alloy_chains::{Chain, NamedChain};
 use eyre::{eyre, Result};
 use foundry_block_explorers::Client as EtherscanClient;
 use shadow_common::{
-    blockscout::Client as BlockscoutClient, compiler, forge::ensure_forge_installed,
+    blockscout::Client as BlockscoutClient,
+    compiler,
+    db::{JsonRpcDatabase, DEFAULT_PARALLEL_REQUESTS},
+    forge::ensure_forge_installed,
     ShadowContractGroupInfo, ShadowContractInfo, ShadowContractSettings, ShadowContractSource,
 };
 use tracing::{error, info, trace, warn};
@@ -49,7 +60,8 @@ pub async fn fetch(args: FetchArgs) -> Result<()> {
     }
 
     // fetch contract metadata and creation data
-    let address = args.address.parse().map_err(|_| eyre!("Invalid address: {}", args.address))?;
+    let address: Address =
+        args.address.parse().map_err(|_| eyre!("Invalid address: {}", args.address))?;
     let (metadata, creation_data) = if let Some(blockscout_url) = args.blockscout_url {
         let client = BlockscoutClient::new(&blockscout_url);
         let metadata = client.contract_source_code(address).await?;
@@ -58,7 +70,15 @@ pub async fn fetch(args: FetchArgs) -> Result<()> {
 
         (metadata, creation_data)
     } else {
-        let client = EtherscanClient::new(chain, args.etherscan_api_key.unwrap_or_default())?;
+        let client =
+            match EtherscanClient::new(chain, args.etherscan_api_key.clone().unwrap_or_default()) {
+                Ok(client) => client,
+                Err(e) => {
+                    warn!("no block explorer is available for chain {}: {}", chain, e);
+                    return fetch_from_rpc(&args.rpc_url, address, &chain, &output_dir, group_info)
+                        .await;
+                }
+            };
         let metadata = client.contract_source_code(address).await?;
         let creation_data = client.contract_creation_data(address).await?;
         info!("successfully fetched contract information from etherscan");
@@ -112,9 +132,12 @@ pub async fn fetch(args: FetchArgs) -> Result<()> {
         group_info.update_contracts()?;
     }
 
-    let compiler_output = compiler::compile(&args.rpc_url, &output_dir, &settings, &info).await?;
+    let request_limiter = JsonRpcDatabase::request_limiter(DEFAULT_PARALLEL_REQUESTS);
+    let compiler_output =
+        compiler::compile(&args.rpc_url, &output_dir, &settings, &info, request_limiter).await?;
 
     if args.reth {
+        warn!("--reth is deprecated and will be removed in the next release. use `shadow generate-reth-config` instead.");
         // check for `shadow.json` in the root directory and load it if it exists.
         let mut reth_config = match std::fs::read_to_string("shadow.json") {
             Ok(contents) => {
@@ -124,7 +147,8 @@ pub async fn fetch(args: FetchArgs) -> Result<()> {
         };
 
         // update the reth config with the new contract
-        reth_config.insert(args.address, format!("0x{}", hex::encode(&compiler_output.bytecode)));
+        reth_config
+            .insert(args.address.clone(), format!("0x{}", hex::encode(&compiler_output.bytecode)));
 
         // write the updated reth config to `shadow.json`
         let reth_config_json = serde_json::to_string_pretty(&reth_config)?;
@@ -132,6 +156,85 @@ pub async fn fetch(args: FetchArgs) -> Result<()> {
         info!("updated shadow-reth configuration");
     }
 
+    if args.follow_imports {
+        if group_info.is_none() {
+            warn!("--follow-imports requires a shadow contract group. skipping linked libraries.");
+        } else if args.max_depth == 0 {
+            warn!("reached --max-depth. skipping linked libraries of {}", args.address);
+        } else {
+            fetch_libraries(&args, &settings, &chain).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetches every deployed library the contract links against into the same contract group,
+/// skipping any which are already part of it.
+async fn fetch_libraries(
+    args: &FetchArgs,
+    settings: &ShadowContractSettings,
+    chain: &Chain,
+) -> Result<()> {
+    let root = PathBuf::from_str(&args.root)?;
+    for address in settings.libraries.libs.values().flat_map(|libs| libs.values()) {
+        let address: Address =
+            address.parse().map_err(|_| eyre!("Invalid library address: {}", address))?;
+        if root.join(chain.id().to_string()).join(address.to_string().to_lowercase()).exists() {
+            continue;
+        }
+
+        info!("fetching linked library {}", address);
+        fetch_boxed(FetchArgs {
+            address: address.to_string(),
+            force: false,
+            reth: false,
+            max_depth: args.max_depth - 1,
+            ..args.clone()
+        })
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Boxes [`fetch`], so it can be called recursively
+fn fetch_boxed(args: FetchArgs) -> Pin<Box<dyn Future<Output = Result<()>>>> {
+    Box::pin(fetch(args))
+}
+
+/// Fetches a contract's information purely from on-chain data, for chains without a supported
+/// block explorer. No source code is available, so only `info.json` and an empty foundry project
+/// are written.
+async fn fetch_from_rpc(
+    rpc_url: &str,
+    address: Address,
+    chain: &Chain,
+    output_dir: &PathBuf,
+    mut group_info: Option<ShadowContractGroupInfo>,
+) -> Result<()> {
+    info!("fetching contract information from {}", rpc_url);
+    let provider = ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(rpc_url)?);
+    let info = ShadowContractInfo::from_rpc(address, &provider, chain).await?;
+
+    info!("writing contract to {}", output_dir.display());
+
+    // initialize foundry project structure
+    init_via_forge(output_dir).map_err(|e| eyre!("failed to initialize foundry project: {}", e))?;
+
+    std::fs::write(output_dir.join("info.json"), serde_json::to_string_pretty(&info)?)?;
+    for dir in ["src", "test", "script"] {
+        std::fs::remove_dir_all(output_dir.join(dir))?;
+        std::fs::create_dir_all(output_dir.join(dir))?;
+    }
+
+    // update shadow contract group info
+    if let Some(group_info) = group_info.as_mut() {
+        group_info.update_contracts()?;
+    }
+
+    warn!("no verified source is available for {}. add its source to src/ and a settings.json before compiling", address);
+
     Ok(())
 }
 
@@ -0,0 +1,125 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use alloy::primitives::Address;
+use alloy_chains::Chain;
+use eyre::{bail, eyre, Result};
+use foundry_block_explorers::Client as EtherscanClient;
+use shadow_common::{
+    ShadowContractGroupInfo, ShadowContractInfo, ShadowContractSource, ShadowContractSourceFile,
+};
+use tracing::{error, info, warn};
+
+use crate::UpdateSourceArgs;
+
+/// The `update-source` subcommand. Re-fetches a contract's verified source from Etherscan, and
+/// re-applies the changes made to its `src/` since it was fetched, like a rebase. Files which
+/// can't be merged cleanly are left with conflict markers.
+pub async fn update_source(args: UpdateSourceArgs) -> Result<()> {
+    let address: Address =
+        args.address.parse().map_err(|_| eyre!("Invalid address: {}", args.address))?;
+
+    // the contract is either part of a group, or `--root` is the contract's directory
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let contract_dir = match ShadowContractGroupInfo::from_path(&root_dir) {
+        Ok(group_info) => group_info.contract_path(&address, args.chain_id)?,
+        Err(_) => root_dir,
+    };
+    let original_path = contract_dir.join("original.json");
+    if !original_path.exists() {
+        error!("{} does not contain a fetched contract.", contract_dir.display());
+        bail!("{} does not exist", original_path.display());
+    }
+
+    let info = ShadowContractInfo::from_path(&contract_dir.join("info.json"))?;
+    if info.address != address {
+        bail!("{} contains {}, not {}", contract_dir.display(), info.address, address);
+    }
+    let original: ShadowContractSource =
+        serde_json::from_str(&std::fs::read_to_string(&original_path)?)
+            .map_err(|e| eyre!("Failed to parse {}: {}", original_path.display(), e))?;
+    let current = read_working_source(&contract_dir, &original)?;
+
+    // fetch the verified source as it is now
+    let chain = Chain::from_id(info.chain_id);
+    let client = EtherscanClient::new(chain, args.etherscan_api_key.unwrap_or_default())
+        .map_err(|e| eyre!("no block explorer is available for chain {}: {}", chain, e))?;
+    let metadata = client.contract_source_code(address).await?;
+    let updated = ShadowContractSource::new(&metadata)?;
+    info!("successfully fetched contract information from etherscan");
+
+    let upstream = original.diff(&updated);
+    if upstream.is_empty() {
+        info!("the verified source of {} hasn't changed", address);
+        return Ok(());
+    }
+    info!("{} files changed in the verified source", upstream.files.len());
+
+    let changes = original.diff(&current);
+    let (patched, conflicts) = updated.patch(&changes);
+
+    // remove files which no longer exist, and write the rest
+    let patched_files =
+        patched.contract_files.iter().map(|f| f.file_name.as_str()).collect::<HashSet<_>>();
+    for file in &current.contract_files {
+        if !patched_files.contains(file.file_name.as_str()) {
+            std::fs::remove_file(contract_dir.join(&file.file_name))?;
+        }
+    }
+    patched.write_source_to(&contract_dir)?;
+    std::fs::write(&original_path, serde_json::to_string_pretty(&updated)?)?;
+    std::fs::write(contract_dir.join("source.json"), serde_json::to_string_pretty(&patched)?)?;
+
+    if !conflicts.is_empty() {
+        for file_name in &conflicts {
+            warn!("conflict in {}", file_name);
+        }
+        error!("Fix the conflicts in {}, then run `shadow compile`.", contract_dir.display());
+        bail!("failed to re-apply changes to {} files", conflicts.len());
+    }
+
+    info!("re-applied {} changed files to the updated source", changes.files.len());
+    Ok(())
+}
+
+/// Reads the contract's source files as they are on disk: the files in `original.json` which
+/// still exist, along with any new files in `src/`
+fn read_working_source(
+    contract_dir: &Path,
+    original: &ShadowContractSource,
+) -> Result<ShadowContractSource> {
+    let mut contract_files = vec![];
+    let mut seen = HashSet::new();
+    for file in &original.contract_files {
+        let path = contract_dir.join(&file.file_name);
+        if path.exists() {
+            contract_files.push(ShadowContractSourceFile {
+                file_name: file.file_name.clone(),
+                content: std::fs::read_to_string(&path)?,
+            });
+        }
+        seen.insert(path);
+    }
+
+    for entry in walkdir::WalkDir::new(contract_dir.join("src"))
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy();
+            name.ends_with(".sol") || name.ends_with(".vy")
+        })
+        .filter(|e| !seen.contains(e.path()))
+    {
+        contract_files.push(ShadowContractSourceFile {
+            file_name: entry.path().strip_prefix(contract_dir)?.to_string_lossy().to_string(),
+            content: std::fs::read_to_string(entry.path())?,
+        });
+    }
+
+    Ok(ShadowContractSource { contract_files, ..original.clone() })
+}
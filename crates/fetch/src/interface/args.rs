@@ -5,6 +5,7 @@ use alloy::{
 };
 use alloy_chains::Chain;
 use clap::Parser;
+use tracing::warn;
 
 /// Arguments for the `fetch` subcommand
 #[derive(Debug, Clone, Parser)]
@@ -34,13 +35,37 @@ pub struct FetchArgs {
     pub blockscout_url: Option<String>,
 
     /// Whether to save the compiled contract to './shadow.json' for use with shadow-reth.
+    /// Deprecated in favor of `shadow generate-reth-config`, and will be removed in the next
+    /// release.
     #[clap(long)]
     pub reth: bool,
+
+    /// Whether to also fetch any deployed libraries the contract links against, adding them to
+    /// the same contract group.
+    #[clap(long)]
+    pub follow_imports: bool,
+
+    /// How many levels of linked libraries to fetch with --follow-imports.
+    #[clap(long, default_value = "3", requires = "follow_imports")]
+    pub max_depth: usize,
+
+    /// Use this chain ID instead of the one reported by the RPC, e.g. when fetching through a
+    /// local fork whose chain ID differs from the chain the contract was deployed on.
+    #[clap(long, required = false)]
+    pub chain_id: Option<u64>,
 }
 
 impl FetchArgs {
-    /// Try to get the chain ID from the RPC URL
+    /// Try to get the chain ID from the RPC URL, unless it's overridden with `--chain-id`
     pub async fn try_get_chain(&self) -> eyre::Result<Chain> {
+        if let Some(chain_id) = self.chain_id {
+            warn!(
+                "using chain ID {} from --chain-id instead of detecting it from the RPC",
+                chain_id
+            );
+            return Ok(Chain::from_id_unchecked(chain_id));
+        }
+
         let provider =
             ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&self.rpc_url)?);
 
@@ -52,3 +77,27 @@ impl FetchArgs {
         Ok(Chain::from_id_unchecked(chain_id))
     }
 }
+
+/// Arguments for the `update-source` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(
+    about = "Re-fetch a contract's verified source from Etherscan and re-apply your changes to it"
+)]
+pub struct UpdateSourceArgs {
+    /// The address of the contract to update
+    pub address: String,
+
+    /// The chain the contract is on. Only required if the address is tracked on multiple chains
+    /// in the contract group.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The API key to use for Etherscan.
+    #[clap(short, long, required = false)]
+    pub etherscan_api_key: Option<String>,
+
+    /// The path to the shadow contract group, or to the contract's directory if it isn't part of
+    /// one.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+}
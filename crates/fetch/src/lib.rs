@@ -1,4 +1,4 @@
-//! The `fetch` subcommand
+//! The `fetch` and `update-source` subcommands
 
 mod core;
 mod interface;
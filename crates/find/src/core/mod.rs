@@ -0,0 +1,137 @@
+#![allow(deprecated)]
+use std::{
+    env::home_dir,
+    path::{Path, PathBuf},
+};
+
+use eyre::{bail, eyre, OptionExt, Result};
+use regex::Regex;
+use shadow_common::{ShadowContractEntry, ShadowContractGroupInfo, ShadowContractInfo};
+use shadow_simulate::SimulateArgs;
+use tracing::{info, warn};
+
+use crate::FindArgs;
+
+/// Directories which never contain shadow contract groups, and are expensive to walk
+const SKIPPED_DIRS: [&str; 4] = ["out", "cache", "node_modules", ".git"];
+
+/// A contract group with contracts matching the search criteria
+struct GroupMatch {
+    path: PathBuf,
+    info: ShadowContractGroupInfo,
+    contracts: Vec<(ShadowContractEntry, Option<String>)>,
+}
+
+/// The `find` subcommand. Searches a directory for shadow contract groups containing contracts
+/// which match the given criteria.
+pub async fn find(args: FindArgs) -> Result<()> {
+    let name_regex = args
+        .name
+        .as_deref()
+        .map(Regex::new)
+        .transpose()
+        .map_err(|e| eyre!("Invalid name pattern: {}", e))?;
+    let address_pattern = args.address.as_deref().map(str::to_lowercase);
+
+    let root = match &args.root {
+        Some(root) => PathBuf::from(root),
+        None => home_dir().ok_or_eyre("failed to get home directory")?.join(".shadow"),
+    };
+    if !root.is_dir() {
+        bail!("{} is not a directory", root.display());
+    }
+
+    info!("searching {} for shadow contract groups", root.display());
+    let mut matches = Vec::new();
+    for group_path in find_groups(&root) {
+        let Ok(info) = ShadowContractGroupInfo::from_path(&group_path) else { continue };
+
+        let contracts = info
+            .contracts
+            .iter()
+            .filter(|contract| args.chain_id.map_or(true, |chain_id| contract.chain_id == chain_id))
+            .filter(|contract| {
+                address_pattern.as_ref().map_or(true, |pattern| {
+                    contract.address.to_string().to_lowercase().contains(pattern.as_str())
+                })
+            })
+            .map(|contract| (contract.clone(), contract_name(&group_path, contract)))
+            .filter(|(_, name)| {
+                name_regex
+                    .as_ref()
+                    .map_or(true, |regex| name.as_ref().map_or(false, |name| regex.is_match(name)))
+            })
+            .collect::<Vec<_>>();
+
+        if !contracts.is_empty() {
+            matches.push(GroupMatch { path: group_path, info, contracts });
+        }
+    }
+
+    if matches.is_empty() {
+        info!("no matching contracts found");
+        return Ok(());
+    }
+
+    for group in &matches {
+        println!("{} ({})", group.info.display_name, group.path.display());
+        for (contract, name) in &group.contracts {
+            println!(
+                "  {}:{}  {}",
+                contract.chain_id,
+                contract.address,
+                name.as_deref().unwrap_or("<unknown>")
+            );
+        }
+    }
+
+    if args.open {
+        for group in &matches {
+            if let Err(e) = open::that(&group.path) {
+                warn!("failed to open {}: {}", group.path.display(), e);
+            }
+        }
+    }
+
+    if let Some(transaction_hash) = args.simulate {
+        let [group] = matches.as_slice() else {
+            bail!(
+                "{} groups matched, but --simulate needs exactly one. Narrow the search",
+                matches.len()
+            );
+        };
+
+        shadow_simulate::simulate(SimulateArgs::new(
+            transaction_hash,
+            group.path.display().to_string(),
+            args.rpc_url,
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Returns the directories under `root` which contain an `info.json` file, and may be shadow
+/// contract groups
+fn find_groups(root: &Path) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 ||
+                !entry.file_name().to_str().map_or(false, |name| SKIPPED_DIRS.contains(&name))
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file() && entry.file_name() == "info.json")
+        .filter_map(|entry| entry.path().parent().map(Path::to_path_buf))
+        .collect()
+}
+
+/// Returns the name of a contract in a group, from its `info.json` file
+fn contract_name(group_path: &Path, contract: &ShadowContractEntry) -> Option<String> {
+    let info_path = group_path
+        .join(contract.chain_id.to_string())
+        .join(contract.address.to_string().to_lowercase())
+        .join("info.json");
+    ShadowContractInfo::from_path(&info_path).ok().map(|info| info.name)
+}
@@ -0,0 +1,35 @@
+use clap::Parser;
+
+/// Arguments for the `find` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Search for contracts matching a pattern across shadow contract groups")]
+pub struct FindArgs {
+    /// Only match contracts whose address contains this pattern, case-insensitively.
+    #[clap(short, long, required = false)]
+    pub address: Option<String>,
+
+    /// Only match contracts on this chain.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// Only match contracts whose name matches this regular expression.
+    #[clap(short, long, required = false)]
+    pub name: Option<String>,
+
+    /// The directory to search for shadow contract groups. Defaults to `~/.shadow`.
+    #[clap(short, long, required = false)]
+    pub root: Option<String>,
+
+    /// Open each matching group's directory in the file manager.
+    #[clap(long)]
+    pub open: bool,
+
+    /// Simulate this transaction using the matching group as the root. Only one group may
+    /// match.
+    #[clap(long, required = false)]
+    pub simulate: Option<String>,
+
+    /// The RPC URL to simulate the transaction on, when `--simulate` is set.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+}
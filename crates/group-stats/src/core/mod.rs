@@ -0,0 +1,109 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use alloy::json_abi::JsonAbi;
+use eyre::{eyre, Result};
+use serde::Serialize;
+use shadow_common::{hash::source_changed, ShadowContractGroupInfo};
+use tracing::error;
+
+use crate::GroupStatsArgs;
+
+/// Aggregate statistics about a contract group
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GroupStats {
+    /// The number of contracts in the group
+    contracts: usize,
+    /// The number of contracts on each chain, keyed by chain ID
+    chains: BTreeMap<u64, usize>,
+    /// The number of lines in every contract's `src/` files
+    source_lines: usize,
+    /// The number of contracts whose source differs from `original.json`
+    modified_contracts: usize,
+    /// The number of contracts with compiled artifacts
+    compiled_contracts: usize,
+    /// The number of distinct function signatures across all compiled ABIs
+    unique_functions: usize,
+    /// The number of distinct event signatures across all compiled ABIs
+    unique_events: usize,
+    /// The total size of all compiled runtime bytecode, in bytes
+    bytecode_size: usize,
+}
+
+/// The `group-stats` subcommand. Computes aggregate statistics about a contract group from its
+/// local files.
+pub async fn group_stats(args: GroupStatsArgs) -> Result<()> {
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let artifact_path = group_info.artifact_path();
+
+    let mut stats = GroupStats { contracts: group_info.contracts.len(), ..Default::default() };
+    let mut functions = BTreeSet::new();
+    let mut events = BTreeSet::new();
+    for contract in &group_info.contracts {
+        let contract_dir = group_info.contract_path(&contract.address, Some(contract.chain_id))?;
+        let output_dir = artifact_path
+            .join(contract.chain_id.to_string())
+            .join(contract.address.to_string().to_lowercase());
+
+        *stats.chains.entry(contract.chain_id).or_default() += 1;
+        stats.source_lines += count_source_lines(&contract_dir.join("src"))?;
+        if source_changed(&contract_dir).unwrap_or(false) {
+            stats.modified_contracts += 1;
+        }
+
+        let (Ok(abi), Ok(bytecode)) = (
+            std::fs::read_to_string(output_dir.join("abi.json")),
+            std::fs::read_to_string(output_dir.join("bytecode.hex")),
+        ) else {
+            continue;
+        };
+        let abi: JsonAbi = serde_json::from_str(&abi)?;
+        functions.extend(abi.functions().map(|f| f.signature()));
+        events.extend(abi.events().map(|e| e.signature()));
+        stats.bytecode_size += bytecode.trim().trim_start_matches("0x").len() / 2;
+        stats.compiled_contracts += 1;
+    }
+    stats.unique_functions = functions.len();
+    stats.unique_events = events.len();
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+
+    println!("contracts:          {}", stats.contracts);
+    for (chain_id, count) in &stats.chains {
+        println!("  chain {:<11} {}", format!("{}:", chain_id), count);
+    }
+    println!("modified:           {}/{}", stats.modified_contracts, stats.contracts);
+    println!("source lines:       {}", stats.source_lines);
+    println!("compiled:           {}/{}", stats.compiled_contracts, stats.contracts);
+    println!("unique functions:   {}", stats.unique_functions);
+    println!("unique events:      {}", stats.unique_events);
+    println!("bytecode size:      {} bytes", stats.bytecode_size);
+
+    Ok(())
+}
+
+/// Counts the lines in every `.sol` and `.vy` file in the given directory
+fn count_source_lines(src_dir: &Path) -> Result<usize> {
+    walkdir::WalkDir::new(src_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy();
+            name.ends_with(".sol") || name.ends_with(".vy")
+        })
+        .map(|e| Ok(std::fs::read_to_string(e.path())?.lines().count()))
+        .sum()
+}
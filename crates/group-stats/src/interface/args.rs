@@ -0,0 +1,14 @@
+use clap::Parser;
+
+/// Arguments for the `group-stats` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Compute aggregate statistics about a shadow contract group")]
+pub struct GroupStatsArgs {
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// Print the statistics as a JSON object.
+    #[clap(long)]
+    pub json: bool,
+}
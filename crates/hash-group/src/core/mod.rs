@@ -0,0 +1,40 @@
+use std::{path::PathBuf, str::FromStr};
+
+use eyre::{bail, eyre, Result};
+use shadow_common::{hash::hash_directory, ShadowContractGroupInfo};
+use tracing::{error, info};
+
+use crate::HashGroupArgs;
+
+/// The `hash-group` subcommand. Computes a deterministic SHA-256 hash over the content of a
+/// shadow contract group, optionally comparing it against another group.
+pub async fn hash_group(args: HashGroupArgs) -> Result<()> {
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let root_hash = hash_group_at(&root_dir)?;
+    info!("{}: 0x{}", root_dir.display(), hex::encode(root_hash));
+
+    if let Some(compare) = args.compare {
+        let compare_dir = PathBuf::from_str(&compare)?;
+        let compare_hash = hash_group_at(&compare_dir)?;
+        info!("{}: 0x{}", compare_dir.display(), hex::encode(compare_hash));
+
+        if root_hash != compare_hash {
+            error!("contract groups do not match");
+            bail!("contract group hashes differ");
+        }
+
+        info!("contract groups match");
+    }
+
+    Ok(())
+}
+
+/// Ensures the given path is a shadow contract group, and hashes its content
+fn hash_group_at(path: &PathBuf) -> Result<[u8; 32]> {
+    ShadowContractGroupInfo::from_path(path).map_err(|e| {
+        error!("{} is not a shadow contract group.", path.display());
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    hash_directory(path)
+}
@@ -0,0 +1,14 @@
+use clap::Parser;
+
+/// Arguments for the `hash-group` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Compute a deterministic content hash of a shadow contract group")]
+pub struct HashGroupArgs {
+    /// The path to the shadow contract group to hash.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The path to another shadow contract group to compare against.
+    #[clap(long, required = false)]
+    pub compare: Option<String>,
+}
@@ -0,0 +1,7 @@
+//! The `hash-group` subcommand
+
+mod core;
+mod interface;
+
+pub use core::*;
+pub use interface::*;
@@ -0,0 +1,183 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use alloy::{
+    network::AnyNetwork,
+    primitives::{Address, Bytes},
+    providers::{Provider, ProviderBuilder},
+    transports::http::reqwest::Url,
+};
+use alloy_chains::Chain;
+use eyre::{bail, eyre, Result};
+use foundry_compilers::artifacts::Remapping;
+use shadow_common::{
+    ShadowContractGroupInfo, ShadowContractInfo, ShadowContractSettings, ShadowContractSource,
+    ShadowContractSourceFile,
+};
+use tracing::{info, warn};
+
+use crate::{artifact::ImportedArtifact, deployment::HardhatDeployment, ImportArgs};
+
+/// The `import` subcommand. Adds a deployed contract to a shadow contract group from an existing
+/// Hardhat or Foundry project's build artifacts, rather than fetching it from a block explorer.
+pub async fn import(args: ImportArgs) -> Result<()> {
+    let address: Address = args.address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+    let chain = Chain::from_id(args.chain_id);
+    let project_root = PathBuf::from_str(&args.artifacts_dir)?;
+
+    info!("reading {} artifact for {}", if args.forge { "foundry" } else { "hardhat" }, args.name);
+    let artifact = if args.forge {
+        ImportedArtifact::from_forge(&project_root, &args.name)?
+    } else {
+        ImportedArtifact::from_hardhat(&project_root, &args.name)?
+    };
+
+    // hardhat-deploy records the deployer and constructor arguments, so we only fall back to
+    // the RPC when there's no deployment
+    let deployment = if args.forge {
+        None
+    } else {
+        let network = args.network.clone().unwrap_or_else(|| {
+            chain.named().map(|n| n.to_string()).unwrap_or_else(|| chain.id().to_string())
+        });
+        HardhatDeployment::from_project(&project_root, &network, &args.name)?
+    };
+    if let Some(deployment) = &deployment {
+        if deployment.address != address {
+            bail!("the deployment of {} is at {}, not {}", args.name, deployment.address, address);
+        }
+    }
+
+    let mut info = match &deployment {
+        Some(deployment) => ShadowContractInfo {
+            address,
+            contract_deployer: deployment.receipt.from,
+            name: args.name.clone(),
+            network: chain.named().map(|n| n.to_string()).unwrap_or_else(|| "unknown".to_string()),
+            chain_id: chain.id(),
+            source: String::new(),
+            unique_events: 0,
+            deployment_transaction_hash: deployment.transaction_hash,
+        },
+        None => {
+            info!("fetching deployment information for {} from {}", address, args.rpc_url);
+            let provider =
+                ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+            let mut info = ShadowContractInfo::from_rpc(address, &provider, &chain).await?;
+            info.name = args.name.clone();
+            info
+        }
+    };
+    info.source = if args.forge { "foundry" } else { "hardhat" }.to_string();
+    info.unique_events = artifact.abi.events.len() as u64;
+
+    let mut settings =
+        ShadowContractSettings::from_solc_settings(&artifact.compiler_version, &artifact.settings)?;
+    settings.constructor_arguments = match (&args.constructor_args, &deployment) {
+        (Some(constructor_args), _) => Bytes::from_str(constructor_args)
+            .map_err(|e| eyre!("Invalid constructor arguments: {}", e))?
+            .to_vec(),
+        (None, Some(deployment)) => deployment.encode_args(&artifact.abi)?,
+        (None, None) => {
+            if artifact.abi.constructor.as_ref().is_some_and(|c| !c.inputs.is_empty()) {
+                warn!("{} takes constructor arguments, but none were provided. Pass them with --constructor-args.", args.name);
+            }
+            vec![]
+        }
+    };
+
+    let source = build_source(&artifact, !args.forge)?;
+
+    // use the existing contract group, or create a new one
+    let root = PathBuf::from_str(&args.root)?;
+    let (mut group_info, group_root) = match ShadowContractGroupInfo::from_path(&root) {
+        Ok(group_info) => (group_info, root),
+        Err(_) => {
+            let path = ShadowContractGroupInfo::default().write_folder_structure(root)?;
+            info!("initialized new shadow contract group at {}", path.display());
+            (ShadowContractGroupInfo::from_path(&path)?, path)
+        }
+    };
+
+    let contract_dir =
+        group_root.join(chain.id().to_string()).join(address.to_string().to_lowercase());
+    if contract_dir.exists() {
+        bail!("{} already exists in this contract group", address);
+    }
+
+    info!("writing contract to {}", contract_dir.display());
+    std::fs::create_dir_all(&contract_dir)?;
+    source.write_source_to(&contract_dir)?;
+    let source_json = serde_json::to_string_pretty(&source)?;
+    std::fs::write(contract_dir.join("info.json"), serde_json::to_string_pretty(&info)?)?;
+    std::fs::write(contract_dir.join("settings.json"), serde_json::to_string_pretty(&settings)?)?;
+    std::fs::write(contract_dir.join("source.json"), &source_json)?;
+    std::fs::write(contract_dir.join("original.json"), source_json)?;
+    settings.generate_config(&contract_dir)?;
+
+    // write the artifact's output where `shadow compile` would, so it's usable before the
+    // contract is recompiled
+    let artifact_dir = contract_dir.join("out").join(format!("{}.sol", args.name));
+    std::fs::create_dir_all(&artifact_dir)?;
+    std::fs::write(
+        artifact_dir.join(format!("{}.shadow.json", args.name)),
+        serde_json::to_string_pretty(&artifact.compiler_output())?,
+    )?;
+
+    group_info.update_contracts()?;
+    info!("imported {} (chain {}) into the contract group", address, chain.id());
+
+    Ok(())
+}
+
+/// Builds the contract's source from the artifact. Hardhat sources are laid out like a foundry
+/// project, with `contracts/` moved to `src/` and packages to `lib/`, and remapped to match.
+fn build_source(artifact: &ImportedArtifact, is_hardhat: bool) -> Result<ShadowContractSource> {
+    let mut remappings = artifact.settings["remappings"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|r| r.as_str())
+        .map(|r| Remapping::from_str(r).map_err(|e| eyre!("invalid remapping {}: {}", r, e)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut contract_files = vec![];
+    let mut packages = BTreeSet::new();
+    for (path, content) in &artifact.sources {
+        let file_name = match path.split_once('/') {
+            Some(("contracts", rest)) if is_hardhat => format!("src/{}", rest),
+            Some(("src", _)) | None => path.clone(),
+            Some((package, _)) if is_hardhat => {
+                packages.insert(package.to_string());
+                format!("lib/{}", path)
+            }
+            Some(_) => path.clone(),
+        };
+        contract_files.push(ShadowContractSourceFile { file_name, content: content.clone() });
+    }
+
+    if is_hardhat {
+        if artifact.sources.keys().any(|path| path.starts_with("contracts/")) {
+            remappings.push(Remapping {
+                context: None,
+                name: "contracts/".to_string(),
+                path: "src/".to_string(),
+            });
+        }
+        remappings.extend(packages.into_iter().map(|package| Remapping {
+            context: None,
+            name: format!("{}/", package),
+            path: format!("lib/{}/", package),
+        }));
+    }
+
+    Ok(ShadowContractSource {
+        compiler_version: format!("v{}", artifact.compiler_version.trim_start_matches('v')),
+        language: "Solidity".to_string(),
+        remappings: remappings.into_iter().map(|r| r.into_relative(Path::new("."))).collect(),
+        contract_files,
+    })
+}
@@ -0,0 +1,45 @@
+use clap::Parser;
+
+/// Arguments for the `import` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Import a deployed contract from Hardhat or Foundry build artifacts")]
+pub struct ImportArgs {
+    /// The root of the Hardhat or Foundry project containing the contract's build artifacts.
+    #[clap(long)]
+    pub artifacts_dir: String,
+
+    /// The address of the deployed contract.
+    #[clap(long)]
+    pub address: String,
+
+    /// The chain ID the contract is deployed on.
+    #[clap(long)]
+    pub chain_id: u64,
+
+    /// The name of the contract to import, used to find its build artifact.
+    #[clap(long)]
+    pub name: String,
+
+    /// Import from Foundry's `out/` directory, rather than Hardhat's `artifacts/`.
+    #[clap(long)]
+    pub forge: bool,
+
+    /// The Hardhat network to read the contract's deployment from, i.e. the folder in
+    /// `deployments/`. Defaults to the chain's name.
+    #[clap(long, required = false, conflicts_with = "forge")]
+    pub network: Option<String>,
+
+    /// The hex-encoded constructor arguments the contract was deployed with. Defaults to the
+    /// arguments in the contract's Hardhat deployment, if there is one.
+    #[clap(long, required = false)]
+    pub constructor_args: Option<String>,
+
+    /// The path to the shadow contract group. A new group is created if this isn't one.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain the contract is deployed on, used to fetch its deployer when
+    /// there's no Hardhat deployment.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+}
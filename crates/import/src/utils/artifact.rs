@@ -0,0 +1,169 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use alloy::{json_abi::JsonAbi, primitives::Bytes};
+use eyre::{bail, eyre, OptionExt, Result};
+use serde_json::Value;
+use shadow_common::compiler::CompilerOutput;
+
+/// A compiled contract, read from a Hardhat or Foundry build artifact
+#[derive(Debug, Clone)]
+pub(crate) struct ImportedArtifact {
+    /// The contract's ABI
+    pub(crate) abi: JsonAbi,
+    /// The contract's function signatures, mapped to their selectors
+    pub(crate) method_identifiers: Value,
+    /// The contract's runtime bytecode
+    pub(crate) deployed_bytecode: Bytes,
+    /// The solc version the contract was compiled with
+    pub(crate) compiler_version: String,
+    /// The solc `settings` the contract was compiled with
+    pub(crate) settings: Value,
+    /// The contents of every source file in the compilation, keyed by path
+    pub(crate) sources: BTreeMap<String, String>,
+}
+
+impl ImportedArtifact {
+    /// Reads a contract's artifact from a Hardhat project. Sources and settings are taken from
+    /// the build info the artifact's `.dbg.json` points to.
+    pub(crate) fn from_hardhat(project_root: &Path, name: &str) -> Result<Self> {
+        let artifact_path = find_artifact(&project_root.join("artifacts"), name)?;
+        let artifact = read_json(&artifact_path)?;
+
+        let debug_path = artifact_path.with_file_name(format!("{}.dbg.json", name));
+        let build_info_path = artifact_path.parent().ok_or_eyre("invalid artifact path")?.join(
+            read_json(&debug_path)?["buildInfo"].as_str().ok_or_eyre(format!(
+                "{} has no buildInfo. Try recompiling with `npx hardhat compile`",
+                debug_path.display()
+            ))?,
+        );
+        let build_info = read_json(&build_info_path)?;
+
+        let abi: JsonAbi = serde_json::from_value(artifact["abi"].clone())?;
+        Ok(Self {
+            method_identifiers: method_identifiers(&abi),
+            abi,
+            deployed_bytecode: parse_bytecode(&artifact["deployedBytecode"])?,
+            compiler_version: build_info["solcLongVersion"]
+                .as_str()
+                .or_else(|| build_info["solcVersion"].as_str())
+                .ok_or_eyre("build info has no solc version")?
+                .to_string(),
+            settings: build_info["input"]["settings"].clone(),
+            sources: build_info["input"]["sources"]
+                .as_object()
+                .ok_or_eyre("build info has no sources")?
+                .iter()
+                .map(|(path, source)| {
+                    let content = source["content"]
+                        .as_str()
+                        .ok_or_eyre(format!("build info has no content for {}", path))?;
+                    Ok((path.clone(), content.to_string()))
+                })
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Reads a contract's artifact from a Foundry project. Sources are read from disk, using the
+    /// paths in the artifact's metadata.
+    pub(crate) fn from_forge(project_root: &Path, name: &str) -> Result<Self> {
+        let artifact_path = find_artifact(&project_root.join("out"), name)?;
+        let artifact = read_json(&artifact_path)?;
+        let metadata = match &artifact["metadata"] {
+            Value::String(metadata) => serde_json::from_str(metadata)?,
+            Value::Null => bail!(
+                "{} has no metadata. Add \"metadata\" to `extra_output` in foundry.toml and rebuild",
+                artifact_path.display()
+            ),
+            metadata => metadata.clone(),
+        };
+
+        let abi: JsonAbi = serde_json::from_value(artifact["abi"].clone())?;
+        Ok(Self {
+            method_identifiers: match &artifact["methodIdentifiers"] {
+                Value::Null => method_identifiers(&abi),
+                method_identifiers => method_identifiers.clone(),
+            },
+            abi,
+            deployed_bytecode: parse_bytecode(&artifact["deployedBytecode"])?,
+            compiler_version: metadata["compiler"]["version"]
+                .as_str()
+                .ok_or_eyre("artifact metadata has no compiler version")?
+                .to_string(),
+            settings: metadata["settings"].clone(),
+            sources: metadata["sources"]
+                .as_object()
+                .ok_or_eyre("artifact metadata has no sources")?
+                .keys()
+                .map(|path| {
+                    let content = std::fs::read_to_string(project_root.join(path))
+                        .map_err(|e| eyre!("failed to read source {}: {}", path, e))?;
+                    Ok((path.clone(), content))
+                })
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Builds the [`CompilerOutput`] for this artifact. The bytecode is the artifact's
+    /// `deployedBytecode`, so immutables are unset until the contract is next compiled.
+    pub(crate) fn compiler_output(&self) -> CompilerOutput {
+        CompilerOutput {
+            abi: self.abi.clone(),
+            method_identifiers: self.method_identifiers.clone(),
+            bytecode: self.deployed_bytecode.clone(),
+        }
+    }
+}
+
+/// Finds the artifact named `<name>.json` in a build directory
+fn find_artifact(build_dir: &Path, name: &str) -> Result<PathBuf> {
+    if !build_dir.is_dir() {
+        bail!("{} does not exist. You may need to compile the project first", build_dir.display());
+    }
+
+    let file_name = format!("{}.json", name);
+    let artifacts = walkdir::WalkDir::new(build_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name().to_string_lossy() == file_name)
+        .map(|e| e.into_path())
+        .collect::<Vec<_>>();
+
+    match artifacts.as_slice() {
+        [] => bail!("no artifact for {} found in {}", name, build_dir.display()),
+        [artifact] => Ok(artifact.clone()),
+        _ => bail!(
+            "found {} artifacts for {}: {}",
+            artifacts.len(),
+            name,
+            artifacts.iter().map(|a| a.display().to_string()).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// Reads a JSON file
+fn read_json(path: &Path) -> Result<Value> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| eyre!("failed to read {}: {}", path.display(), e))?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Parses bytecode from either a hex string (Hardhat) or a `{ "object": ... }` object (Foundry)
+fn parse_bytecode(bytecode: &Value) -> Result<Bytes> {
+    let bytecode = bytecode
+        .as_str()
+        .or_else(|| bytecode["object"].as_str())
+        .ok_or_eyre("artifact has no deployed bytecode")?;
+    bytecode.parse().map_err(|e| eyre!("invalid deployed bytecode: {}", e))
+}
+
+/// Builds the `methodIdentifiers` object solc outputs for a contract's ABI
+fn method_identifiers(abi: &JsonAbi) -> Value {
+    Value::Object(
+        abi.functions()
+            .map(|f| (f.signature(), Value::String(hex::encode(f.selector()))))
+            .collect(),
+    )
+}
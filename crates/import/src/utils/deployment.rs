@@ -0,0 +1,92 @@
+use std::path::Path;
+
+use alloy::{
+    dyn_abi::{DynSolType, DynSolValue, Specifier},
+    json_abi::JsonAbi,
+    primitives::{Address, B256},
+};
+use eyre::{bail, eyre, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// A contract deployment recorded by `hardhat-deploy` in `deployments/<network>/<name>.json`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HardhatDeployment {
+    /// The address the contract was deployed to
+    pub(crate) address: Address,
+    /// The hash of the deployment transaction
+    pub(crate) transaction_hash: B256,
+    /// The deployment transaction's receipt
+    pub(crate) receipt: HardhatDeploymentReceipt,
+    /// The constructor arguments, as JSON values
+    #[serde(default)]
+    pub(crate) args: Vec<Value>,
+}
+
+/// The parts of a deployment transaction's receipt we need
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct HardhatDeploymentReceipt {
+    /// The account which sent the deployment transaction
+    pub(crate) from: Address,
+}
+
+impl HardhatDeployment {
+    /// Reads a contract's deployment from a Hardhat project, if it has one
+    pub(crate) fn from_project(
+        project_root: &Path,
+        network: &str,
+        name: &str,
+    ) -> Result<Option<Self>> {
+        let path = project_root.join("deployments").join(network).join(format!("{}.json", name));
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&contents)
+            .map(Some)
+            .map_err(|e| eyre!("invalid deployment at {}: {}", path.display(), e))
+    }
+
+    /// ABI-encodes the deployment's constructor arguments using the contract's ABI
+    pub(crate) fn encode_args(&self, abi: &JsonAbi) -> Result<Vec<u8>> {
+        let inputs = abi.constructor.as_ref().map(|c| c.inputs.clone()).unwrap_or_default();
+        if inputs.len() != self.args.len() {
+            bail!(
+                "the constructor takes {} arguments, but the deployment has {}",
+                inputs.len(),
+                self.args.len()
+            );
+        }
+
+        let values = inputs
+            .iter()
+            .zip(&self.args)
+            .map(|(input, arg)| {
+                let ty = input.resolve()?;
+                ty.coerce_str(&to_literal(arg, &ty))
+                    .map_err(|e| eyre!("invalid constructor argument {}: {}", input.name, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(DynSolValue::Tuple(values).abi_encode_params())
+    }
+}
+
+/// Converts a JSON constructor argument to the literal syntax `coerce_str` expects, where
+/// tuples are wrapped in parentheses rather than brackets
+fn to_literal(value: &Value, ty: &DynSolType) -> String {
+    match (value, ty) {
+        (Value::String(s), DynSolType::String) => format!("{:?}", s),
+        (Value::String(s), _) => s.clone(),
+        (Value::Array(values), DynSolType::Tuple(types)) => format!(
+            "({})",
+            values.iter().zip(types).map(|(v, t)| to_literal(v, t)).collect::<Vec<_>>().join(",")
+        ),
+        (Value::Array(values), DynSolType::Array(ty) | DynSolType::FixedArray(ty, _)) => {
+            format!("[{}]", values.iter().map(|v| to_literal(v, ty)).collect::<Vec<_>>().join(","))
+        }
+        (value, _) => value.to_string(),
+    }
+}
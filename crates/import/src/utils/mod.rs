@@ -0,0 +1,4 @@
+/// Readers for Hardhat and Foundry build artifacts
+pub(crate) mod artifact;
+/// Readers for `hardhat-deploy` deployment files
+pub(crate) mod deployment;
@@ -1,15 +1,130 @@
-use std::{path::PathBuf, str::FromStr};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
-use eyre::Result;
-use shadow_common::ShadowContractGroupInfo;
-use tracing::info;
+use alloy::{
+    network::AnyNetwork,
+    primitives::{Address, Bytes},
+    providers::{Provider, ProviderBuilder},
+    transports::http::reqwest::Url,
+};
+use alloy_chains::Chain;
+use eyre::{bail, eyre, Result};
+use shadow_common::{
+    fs::copy_dir, ShadowContractGroupInfo, ShadowContractInfo, ShadowContractSettings,
+    ShadowContractSource,
+};
+use tracing::{info, warn};
 
 use crate::InitArgs;
 
 /// The `init` subcommand. Initialize a new shadow contract group which may be pinned to IPFS.
 pub async fn init(args: InitArgs) -> Result<()> {
     let output_dir = PathBuf::from_str(&args.root)?;
-    let path = ShadowContractGroupInfo::default().write_folder_structure(output_dir)?;
+    if let Some(project_root) = &args.from_existing {
+        return init_from_existing(&args, &output_dir, &PathBuf::from_str(project_root)?).await;
+    }
+
+    let path = new_group(&args)?.write_folder_structure(output_dir)?;
     info!("initialized new shadow contract group at {}", path.display());
     Ok(())
 }
+
+/// Builds a new contract group, pre-populated with any metadata passed as flags
+fn new_group(args: &InitArgs) -> Result<ShadowContractGroupInfo> {
+    let mut group_info = ShadowContractGroupInfo::default();
+    if let Some(display_name) = &args.display_name {
+        group_info = group_info.with_display_name(display_name);
+    }
+    if let Some(creator) = &args.creator {
+        group_info = group_info
+            .with_creator(creator.parse().map_err(|e| eyre!("Invalid creator address: {}", e))?);
+    }
+    if let Some(readme) = &args.readme {
+        group_info = group_info.with_readme(
+            &std::fs::read_to_string(readme)
+                .map_err(|e| eyre!("Failed to read README from {}: {}", readme, e))?,
+        );
+    }
+
+    Ok(group_info)
+}
+
+/// Adds an existing foundry project to a shadow contract group, creating the group if `root`
+/// isn't one already. This is the inverse of `shadow fetch`.
+async fn init_from_existing(args: &InitArgs, root: &Path, project_root: &Path) -> Result<()> {
+    let address: Address = args
+        .address
+        .as_ref()
+        .ok_or_else(|| eyre!("--address is required with --from-existing"))?
+        .parse()
+        .map_err(|e| eyre!("Invalid address: {}", e))?;
+    let chain_id =
+        args.chain_id.ok_or_else(|| eyre!("--chain-id is required with --from-existing"))?;
+
+    let config_path = project_root.join("foundry.toml");
+    if !config_path.exists() {
+        bail!("{} is not a foundry project: foundry.toml not found", project_root.display());
+    }
+    let mut settings = ShadowContractSettings::from_foundry_config(&config_path)?;
+    if let Some(constructor_args) = &args.constructor_args {
+        settings.constructor_arguments = Bytes::from_str(constructor_args)
+            .map_err(|e| eyre!("Invalid constructor arguments: {}", e))?
+            .to_vec();
+    }
+
+    // use the existing contract group, or create a new one
+    let (mut group_info, group_root) = match ShadowContractGroupInfo::from_path(&root.to_path_buf())
+    {
+        Ok(group_info) => (group_info, root.to_path_buf()),
+        Err(_) => {
+            let path = new_group(args)?.write_folder_structure(root.to_path_buf())?;
+            info!("initialized new shadow contract group at {}", path.display());
+            (ShadowContractGroupInfo::from_path(&path)?, path)
+        }
+    };
+
+    let contract_dir =
+        group_root.join(chain_id.to_string()).join(address.to_string().to_lowercase());
+    if contract_dir.exists() {
+        bail!("{} already exists in this contract group", address);
+    }
+
+    // fetch the deployer and deployment transaction
+    let provider =
+        ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+    let rpc_chain_id = provider
+        .get_chain_id()
+        .await
+        .map_err(|e| eyre!("failed to get chain ID from RPC: {}", e))?;
+    if rpc_chain_id != chain_id {
+        bail!("--chain-id is {}, but the RPC is connected to chain {}", chain_id, rpc_chain_id);
+    }
+    info!("fetching deployment information for {} from {}", address, args.rpc_url);
+    let mut info =
+        ShadowContractInfo::from_rpc(address, &provider, &Chain::from_id(chain_id)).await?;
+    info.source = "foundry".to_string();
+    match &args.name {
+        Some(name) => info.name = name.clone(),
+        None => warn!("--name was not provided. The contract's build artifact is matched by name, so compilation may pick the wrong contract."),
+    }
+
+    // copy the project's sources into the group
+    let src_dir = contract_dir.join("src");
+    copy_dir(&project_root.join("src"), &src_dir)?;
+    let source = ShadowContractSource::from_path(&src_dir, &settings)?;
+
+    info!("writing contract to {}", contract_dir.display());
+    let source_json = serde_json::to_string_pretty(&source)?;
+    std::fs::write(contract_dir.join("info.json"), serde_json::to_string_pretty(&info)?)?;
+    std::fs::write(contract_dir.join("settings.json"), serde_json::to_string_pretty(&settings)?)?;
+    std::fs::write(contract_dir.join("source.json"), &source_json)?;
+    std::fs::write(contract_dir.join("original.json"), source_json)?;
+    settings.generate_config(&contract_dir)?;
+
+    group_info.update_contracts()?;
+    info!("added {} (chain {}) to the contract group", address, chain_id);
+
+    Ok(())
+}
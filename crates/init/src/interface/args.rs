@@ -7,4 +7,42 @@ pub struct InitArgs {
     /// The path to the directory in which to initialize the shadow contract group.
     #[clap(short, long, default_value = ".", required = false)]
     pub root: String,
+
+    /// The display name of the new contract group.
+    #[clap(long, required = false)]
+    pub display_name: Option<String>,
+
+    /// The address of the creator of the new contract group.
+    #[clap(long, required = false)]
+    pub creator: Option<String>,
+
+    /// The path to a file to use as the new contract group's README.md.
+    #[clap(long, required = false)]
+    pub readme: Option<String>,
+
+    /// The root of an existing foundry project to add to the contract group as a shadow
+    /// contract.
+    #[clap(long, required = false, requires_all = ["address", "chain_id"])]
+    pub from_existing: Option<String>,
+
+    /// The address of the deployed contract that the existing foundry project shadows.
+    #[clap(long, required = false, requires = "from_existing")]
+    pub address: Option<String>,
+
+    /// The chain ID the contract is deployed on.
+    #[clap(long, required = false, requires = "from_existing")]
+    pub chain_id: Option<u64>,
+
+    /// The name of the contract in the existing foundry project, used to find its build
+    /// artifact. Defaults to the contract's address.
+    #[clap(long, required = false, requires = "from_existing")]
+    pub name: Option<String>,
+
+    /// The hex-encoded constructor arguments the contract was deployed with.
+    #[clap(long, required = false, requires = "from_existing")]
+    pub constructor_args: Option<String>,
+
+    /// The RPC URL of the chain the contract is deployed on, used to fetch its deployer.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
 }
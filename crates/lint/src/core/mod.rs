@@ -0,0 +1,92 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
+
+use alloy::primitives::Address;
+use eyre::{bail, eyre, Result};
+use shadow_common::{compiler::validate_solidity_syntax, ShadowContractGroupInfo};
+use tracing::{error, info, warn};
+
+use crate::LintArgs;
+
+/// The linters supported by `shadow lint`, in order of preference.
+#[derive(Debug, Clone, Copy)]
+enum Linter {
+    Forge,
+    Solhint,
+}
+
+/// The `lint` subcommand. Checks that a contract's source files compile, then runs `forge lint`
+/// or `solhint` on them.
+pub async fn lint(args: LintArgs) -> Result<()> {
+    let address: Address = args.address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let contract_dir = group_info.contract_path(&address, args.chain_id)?;
+
+    info!("checking syntax of {}", contract_dir.display());
+    validate_solidity_syntax(&contract_dir)?;
+
+    let linter = find_linter(args.fix)?;
+    if args.fix && matches!(linter, Linter::Forge) {
+        warn!("`forge lint` does not support --fix. Install `solhint` to apply fixes.");
+    }
+
+    info!("linting {} with {:?}", contract_dir.display(), linter);
+    run_linter(linter, &contract_dir, args.fix)
+}
+
+/// Picks a linter. `forge lint` is preferred, unless fixes were requested and `solhint` is
+/// installed.
+fn find_linter(fix: bool) -> Result<Linter> {
+    let has_solhint = which::which("solhint").is_ok();
+    let has_forge_lint = which::which("forge").is_ok() &&
+        Command::new("forge")
+            .args(["lint", "--help"])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+
+    match (has_forge_lint, has_solhint) {
+        (_, true) if fix => Ok(Linter::Solhint),
+        (true, _) => Ok(Linter::Forge),
+        (false, true) => Ok(Linter::Solhint),
+        (false, false) => bail!(
+            "no linter found. Update foundry with `foundryup` or install solhint with `npm install -g solhint`."
+        ),
+    }
+}
+
+/// Runs the linter on the contract's `src` directory, failing if it reports errors.
+fn run_linter(linter: Linter, contract_dir: &Path, fix: bool) -> Result<()> {
+    let mut command = match linter {
+        Linter::Forge => {
+            let mut command = Command::new("forge");
+            command.args(["lint", "src"]);
+            command
+        }
+        Linter::Solhint => {
+            let mut command = Command::new("solhint");
+            command.arg("src/**/*.sol");
+            if fix {
+                command.arg("--fix");
+            }
+            command
+        }
+    };
+
+    let status = command.current_dir(contract_dir).status()?;
+    if !status.success() {
+        bail!("{:?} reported errors", linter);
+    }
+
+    info!("no lint errors found");
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use clap::Parser;
+
+/// Arguments for the `lint` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Lint the source files of a contract in a shadow contract group")]
+pub struct LintArgs {
+    /// The address of the contract to lint.
+    pub address: String,
+
+    /// The chain ID of the contract to lint. Required if the address is tracked on multiple
+    /// chains.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// Whether to apply auto-fixable suggestions. Only supported by `solhint`.
+    #[clap(long)]
+    pub fix: bool,
+}
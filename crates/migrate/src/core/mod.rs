@@ -0,0 +1,40 @@
+use std::{path::PathBuf, str::FromStr};
+
+use eyre::{eyre, Result};
+use serde_json::Value;
+use shadow_common::{
+    migrations::{group_version, CURRENT_VERSION},
+    ShadowContractGroupInfo,
+};
+use tracing::{error, info};
+
+use crate::MigrateArgs;
+
+/// The `migrate` subcommand. Migrates a shadow contract group's `info.json` to the current
+/// format version, and writes it back to disk.
+pub async fn migrate(args: MigrateArgs) -> Result<()> {
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let info_file = root_dir.join("info.json");
+    let info_json: Value = std::fs::read_to_string(&info_file)
+        .map_err(|e| eyre!("Failed to read {}: {}", info_file.display(), e))
+        .and_then(|info| Ok(serde_json::from_str(&info)?))
+        .map_err(|e| {
+            error!("This is not part of a shadow contract group.");
+            e
+        })?;
+
+    let version = group_version(&info_json)?;
+    if version == CURRENT_VERSION {
+        info!("contract group is already at version {}", CURRENT_VERSION);
+        return Ok(());
+    }
+
+    // loading the group migrates it in memory
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir)
+        .map_err(|e| eyre!("Failed to migrate shadow contract group: {}", e))?;
+    group_info.save()?;
+
+    info!("migrated contract group from version {} to version {}", version, CURRENT_VERSION);
+
+    Ok(())
+}
@@ -0,0 +1,10 @@
+use clap::Parser;
+
+/// Arguments for the `migrate` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Update a shadow contract group's info.json to the latest format")]
+pub struct MigrateArgs {
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+}
@@ -0,0 +1,105 @@
+use std::{path::PathBuf, str::FromStr};
+
+use alloy::{
+    network::AnyNetwork,
+    providers::{Provider, ProviderBuilder},
+    transports::http::reqwest::Url,
+};
+use eyre::{bail, eyre, Result};
+use shadow_common::ShadowContractGroupInfo;
+use shadow_config::Configuration;
+use tracing::{error, info, warn};
+
+use crate::NetworkSwitchArgs;
+
+/// The `network-switch` subcommand. Sets the configured RPC URL, rescans the contract group,
+/// and checks that each of its contracts has the same code on the new network as on the
+/// previous one.
+pub async fn network_switch(args: NetworkSwitchArgs) -> Result<()> {
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let mut group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    let provider =
+        ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+    let chain_id = provider
+        .get_chain_id()
+        .await
+        .map_err(|e| eyre!("failed to get chain ID from RPC: {}", e))?;
+    info!("switching to {} (chain {})", args.rpc_url, chain_id);
+
+    // update the configured RPC URL
+    Configuration::load()?.set("rpc_url", &args.rpc_url)?;
+
+    // rescan the group's contracts, in case any were added or removed
+    group_info.update_contracts()?;
+    for contract in group_info.contracts.iter().filter(|contract| contract.chain_id != chain_id) {
+        warn!(
+            "{} is tracked on chain {}, but the new RPC is for chain {}",
+            contract.address, contract.chain_id, chain_id
+        );
+    }
+
+    // the previous network's bytecode is used as the expected bytecode. without it, contracts
+    // are only checked for having code
+    let previous_provider = match &args.previous_rpc_url {
+        Some(previous_rpc_url) if previous_rpc_url != &args.rpc_url => Some(
+            ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(previous_rpc_url)?),
+        ),
+        _ => {
+            warn!("no previous RPC URL to compare against, only checking that contracts have code");
+            None
+        }
+    };
+
+    let mut mismatched = 0;
+    for contract in &group_info.contracts {
+        let code = provider
+            .get_code_at(contract.address)
+            .await
+            .map_err(|e| eyre!("failed to fetch bytecode from RPC: {}", e))?;
+        if code.is_empty() {
+            error!("{} has no code on the new network", contract.address);
+            mismatched += 1;
+            continue;
+        }
+
+        let Some(previous_provider) = &previous_provider else {
+            info!("{} has code on the new network", contract.address);
+            continue;
+        };
+        let expected = previous_provider
+            .get_code_at(contract.address)
+            .await
+            .map_err(|e| eyre!("failed to fetch bytecode from the previous RPC: {}", e))?;
+        if code == expected {
+            info!("{} has the expected code on the new network", contract.address);
+        } else {
+            error!("{} has different code on the new network", contract.address);
+            mismatched += 1;
+        }
+    }
+
+    if args.recompile {
+        info!("recompiling contracts against {}", args.rpc_url);
+        group_info
+            .prepare(&args.rpc_url)
+            .await
+            .map_err(|e| eyre!("Failed to compile shadow contract group: {}", e))?;
+    }
+
+    if mismatched > 0 {
+        bail!(
+            "{} of {} contracts don't have the expected code on the new network",
+            mismatched,
+            group_info.contracts.len()
+        );
+    }
+
+    info!("switched to {}", args.rpc_url);
+
+    Ok(())
+}
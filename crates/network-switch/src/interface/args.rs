@@ -0,0 +1,25 @@
+use clap::Parser;
+
+/// Arguments for the `network-switch` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(
+    about = "Switch to a new RPC URL and check that a shadow contract group's contracts exist on it"
+)]
+pub struct NetworkSwitchArgs {
+    /// The RPC URL to switch to.
+    #[clap(short = 'u', long)]
+    pub rpc_url: String,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL being switched from, whose bytecode the new network's is compared against.
+    /// Defaults to your configured RPC URL.
+    #[clap(long, required = false)]
+    pub previous_rpc_url: Option<String>,
+
+    /// Recompile the group's contracts against the new RPC.
+    #[clap(long)]
+    pub recompile: bool,
+}
@@ -0,0 +1,7 @@
+//! The `network-switch` subcommand
+
+mod core;
+mod interface;
+
+pub use core::*;
+pub use interface::*;
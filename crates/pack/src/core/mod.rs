@@ -0,0 +1,183 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use eyre::{bail, eyre, OptionExt, Result};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use shadow_common::{
+    hash::{hash_files_excluding, IGNORED_DIRECTORIES},
+    ShadowContractGroupInfo,
+};
+use tracing::{error, info, warn};
+
+use crate::{PackArgs, PackManifest, UnpackArgs, MANIFEST_FILE_NAME};
+
+/// The `pack` subcommand. Creates a gzipped tarball of a shadow contract group, including a
+/// manifest of content hashes.
+pub async fn pack(args: PackArgs) -> Result<()> {
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    // all files are archived under the group's folder name
+    let root_dir = root_dir.canonicalize()?;
+    let folder_name = root_dir.file_name().ok_or_eyre("invalid contract group path")?.to_owned();
+
+    let output = match args.output {
+        Some(output) => PathBuf::from_str(&output)?,
+        None => PathBuf::from(format!(
+            "{}_{}.tar.gz",
+            group_info
+                .display_name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect::<String>(),
+            group_info.creation_date.format("%m_%d_%Y")
+        )),
+    };
+
+    let ignored: &[&str] = if args.include_artifacts { &[] } else { &IGNORED_DIRECTORIES };
+    // archives from earlier packs usually sit in the group root, so they're never packed
+    let files = hash_files_excluding(&root_dir, ignored)?
+        .into_iter()
+        .filter(|f| !f.path.to_string_lossy().ends_with(".tar.gz"))
+        .collect::<Vec<_>>();
+    let manifest = PackManifest {
+        files: files.iter().map(|f| (normalize(&f.path), hex::encode(f.hash))).collect(),
+    };
+
+    info!("packing {} files from {}", files.len(), root_dir.display());
+    let mut archive =
+        tar::Builder::new(GzEncoder::new(File::create(&output)?, Compression::default()));
+
+    // the manifest sits at the root of the archive, outside the group's folder, so it isn't
+    // extracted with the group's files
+    let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp() as u64);
+    header.set_cksum();
+    archive.append_data(&mut header, MANIFEST_FILE_NAME, manifest_json.as_slice())?;
+
+    for file in &files {
+        archive.append_path_with_name(
+            root_dir.join(&file.path),
+            Path::new(&folder_name).join(&file.path),
+        )?;
+    }
+    archive.into_inner()?.finish()?;
+
+    info!("packed shadow contract group to {}", output.display());
+
+    Ok(())
+}
+
+/// The `unpack` subcommand. Extracts a shadow contract group from a tarball created with `pack`,
+/// optionally verifying its content hashes.
+pub async fn unpack(args: UnpackArgs) -> Result<()> {
+    let output_dir = PathBuf::from_str(&args.root)?;
+    std::fs::create_dir_all(&output_dir)?;
+
+    // find the group folder this archive contains, and read its manifest
+    let mut archive = tar::Archive::new(GzDecoder::new(File::open(&args.archive)?));
+    let mut folder_name = None;
+    let mut manifest = None;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if is_manifest(&entry)? {
+            manifest = Some(serde_json::from_reader::<_, PackManifest>(&mut entry)?);
+            continue;
+        }
+
+        let path = entry.path()?;
+        let first = path.components().next().ok_or_eyre("invalid archive entry")?;
+        match &folder_name {
+            None => folder_name = Some(PathBuf::from(first.as_os_str())),
+            Some(name) if name.as_os_str() != first.as_os_str() => {
+                bail!("archive contains more than one contract group")
+            }
+            _ => {}
+        }
+    }
+    let group_dir = output_dir.join(folder_name.ok_or_eyre("archive is empty")?);
+
+    // group_dir must not exist, unless --force is set
+    if group_dir.exists() {
+        if args.force {
+            std::fs::remove_dir_all(&group_dir)?;
+        } else {
+            error!("Output directory already exists. Use --force to overwrite.");
+            return Err(eyre!("output directory already exists"));
+        }
+    }
+
+    // extract everything but the manifest
+    let mut archive = tar::Archive::new(GzDecoder::new(File::open(&args.archive)?));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !is_manifest(&entry)? {
+            entry.unpack_in(&output_dir)?;
+        }
+    }
+    info!("unpacked shadow contract group to {}", group_dir.display());
+
+    if args.verify {
+        let manifest =
+            manifest.ok_or_else(|| eyre!("archive does not contain a {}", MANIFEST_FILE_NAME))?;
+        verify(&group_dir, &manifest)?;
+        info!("verified all files against {}", MANIFEST_FILE_NAME);
+    }
+
+    Ok(())
+}
+
+/// Whether an archive entry is the manifest, rather than one of the group's files
+fn is_manifest<R: std::io::Read>(entry: &tar::Entry<'_, R>) -> Result<bool> {
+    Ok(entry.path()? == Path::new(MANIFEST_FILE_NAME))
+}
+
+/// Verifies every file in the group directory against the group's manifest. Files which don't
+/// match their hash, are missing, or aren't listed in the manifest all fail verification.
+fn verify(group_dir: &Path, manifest: &PackManifest) -> Result<()> {
+    let files = hash_files_excluding(group_dir, &[])?
+        .into_iter()
+        .map(|f| (normalize(&f.path), hex::encode(f.hash)))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut failures = 0;
+    for (path, expected) in &manifest.files {
+        match files.get(path) {
+            Some(hash) if hash == expected => {}
+            Some(_) => {
+                warn!("content hash mismatch: {}", path);
+                failures += 1;
+            }
+            None => {
+                warn!("missing file: {}", path);
+                failures += 1;
+            }
+        }
+    }
+    for path in files.keys().filter(|path| !manifest.files.contains_key(*path)) {
+        warn!("file not listed in {}: {}", MANIFEST_FILE_NAME, path);
+        failures += 1;
+    }
+
+    if failures > 0 {
+        bail!("{} files failed verification", failures);
+    }
+
+    Ok(())
+}
+
+/// Normalize path separators so manifests are the same on every platform
+fn normalize(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
@@ -0,0 +1,41 @@
+use clap::Parser;
+
+/// Arguments for the `pack` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Create a portable tarball of a shadow contract group")]
+pub struct PackArgs {
+    /// The path to the shadow contract group to pack.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The path to write the archive to. Defaults to `<display_name>_<date>.tar.gz` in the current
+    /// directory. `.tar.gz` files in the group are never packed, so earlier archives aren't
+    /// included in new ones.
+    #[clap(short, long, required = false)]
+    pub output: Option<String>,
+
+    /// Whether to include build artifacts (`out/` and `cache/` directories) in the archive.
+    #[clap(long)]
+    pub include_artifacts: bool,
+}
+
+/// Arguments for the `unpack` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Extract a shadow contract group from a tarball created with `shadow pack`")]
+pub struct UnpackArgs {
+    /// The path to the archive to extract.
+    pub archive: String,
+
+    /// The path to the directory in which to extract the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// Whether to verify the extracted files against the archive's `MANIFEST.json`. Files which
+    /// aren't listed in the manifest fail verification.
+    #[clap(long)]
+    pub verify: bool,
+
+    /// Whether to force overwrite an existing contract group.
+    #[clap(short, long)]
+    pub force: bool,
+}
@@ -0,0 +1,14 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The name of the manifest file included at the root of every archive, outside the group's
+/// folder
+pub const MANIFEST_FILE_NAME: &str = "MANIFEST.json";
+
+/// Lists the content hashes of every file in a packed contract group
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackManifest {
+    /// Map of file paths, relative to the contract group root, to their SHA-256 hashes
+    pub files: BTreeMap<String, String>,
+}
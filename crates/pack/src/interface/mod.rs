@@ -0,0 +1,5 @@
+mod args;
+mod manifest;
+
+pub use args::*;
+pub use manifest::*;
@@ -0,0 +1,7 @@
+//! The `pack` and `unpack` subcommands
+
+mod core;
+mod interface;
+
+pub use core::*;
+pub use interface::*;
@@ -0,0 +1,99 @@
+use std::{collections::BTreeSet, path::PathBuf, str::FromStr};
+
+use alloy::{json_abi::JsonAbi, primitives::Address};
+use eyre::{bail, eyre, Result};
+use shadow_common::{prompt, ShadowContractGroupInfo, BUILD_ARTIFACTS_HINT};
+use tracing::{error, info, warn};
+
+use crate::{
+    four_byte::{signature_exists, submit_signature, SignatureKind},
+    PublishAbiArgs,
+};
+
+/// The `publish-abi` subcommand. Registers the function and event signatures in a shadow
+/// contract's compiled ABI with 4byte.directory, so they can be decoded without the ABI.
+pub async fn publish_abi(args: PublishAbiArgs) -> Result<()> {
+    let address: Address = args.address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let contract = group_info.contract(&address, args.chain_id)?;
+
+    let abi_path = group_info
+        .artifact_path()
+        .join(contract.chain_id.to_string())
+        .join(contract.address.to_string().to_lowercase())
+        .join("abi.json");
+    if !abi_path.exists() {
+        bail!("{} has not been built. {}", address, BUILD_ARTIFACTS_HINT);
+    }
+    let abi: JsonAbi = serde_json::from_str(&std::fs::read_to_string(abi_path)?)?;
+
+    // anonymous events have no topic0, so 4byte.directory can't index them
+    let signatures = abi
+        .functions()
+        .map(|f| (SignatureKind::Function, f.signature(), f.selector().to_string()))
+        .chain(
+            abi.events()
+                .filter(|e| !e.anonymous)
+                .map(|e| (SignatureKind::Event, e.signature(), e.selector().to_string())),
+        )
+        .collect::<BTreeSet<_>>();
+
+    info!("checking {} signatures against 4byte.directory", signatures.len());
+    let mut new_signatures = vec![];
+    for (kind, text_signature, hex_signature) in signatures {
+        if signature_exists(kind, &text_signature, &hex_signature).await? {
+            info!("already registered: {}", text_signature);
+        } else {
+            new_signatures.push((kind, text_signature));
+        }
+    }
+
+    if new_signatures.is_empty() {
+        info!("every signature is already registered");
+        return Ok(());
+    }
+    for (kind, text_signature) in &new_signatures {
+        info!("to register: {} {}", kind, text_signature);
+    }
+    if args.dry_run {
+        info!("would register {} signatures (dry run)", new_signatures.len());
+        return Ok(());
+    }
+
+    // submissions are public and can't be removed
+    if prompt(&format!(
+        "You are about to publish {} signatures to 4byte.directory. This can't be undone. Would you like to continue? (y/N): ",
+        new_signatures.len()
+    ))?
+    .unwrap_or_else(|| "n".to_string())
+    .as_str() !=
+        "y"
+    {
+        warn!("user skipping publishing signatures");
+        return Ok(());
+    }
+
+    let mut failures = 0;
+    for (kind, text_signature) in &new_signatures {
+        match submit_signature(*kind, text_signature).await {
+            Ok(()) => info!("registered: {}", text_signature),
+            Err(e) => {
+                error!("{}", e);
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        bail!("failed to register {} of {} signatures", failures, new_signatures.len());
+    }
+    info!("registered {} signatures", new_signatures.len());
+
+    Ok(())
+}
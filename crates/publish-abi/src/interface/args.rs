@@ -0,0 +1,21 @@
+use clap::Parser;
+
+/// Arguments for the `publish-abi` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Register a shadow contract's function and event signatures with 4byte.directory")]
+pub struct PublishAbiArgs {
+    /// The address of the contract whose signatures to publish.
+    pub address: String,
+
+    /// The chain ID of the contract. Required if the address is tracked on multiple chains.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// Print the signatures which would be submitted, without submitting them.
+    #[clap(long)]
+    pub dry_run: bool,
+}
@@ -0,0 +1,89 @@
+use eyre::{eyre, Result};
+use serde::Deserialize;
+
+/// The 4byte.directory API
+const FOUR_BYTE_API_URL: &str = "https://www.4byte.directory/api/v1";
+
+/// The kinds of signature 4byte.directory stores
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum SignatureKind {
+    /// A function signature, identified by its 4-byte selector
+    Function,
+    /// An event signature, identified by its 32-byte topic0
+    Event,
+}
+
+impl SignatureKind {
+    /// The API endpoint for this kind of signature
+    fn endpoint(&self) -> String {
+        match self {
+            SignatureKind::Function => format!("{}/signatures/", FOUR_BYTE_API_URL),
+            SignatureKind::Event => format!("{}/event-signatures/", FOUR_BYTE_API_URL),
+        }
+    }
+}
+
+impl std::fmt::Display for SignatureKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignatureKind::Function => write!(f, "function"),
+            SignatureKind::Event => write!(f, "event"),
+        }
+    }
+}
+
+/// A page of results from the 4byte.directory signature API
+#[derive(Debug, Deserialize)]
+struct FourByteResponse {
+    results: Vec<FourByteSignature>,
+}
+
+/// A single signature from the 4byte.directory signature API
+#[derive(Debug, Deserialize)]
+struct FourByteSignature {
+    text_signature: String,
+}
+
+/// Checks whether 4byte.directory already has the given text signature, e.g.
+/// `transfer(address,uint256)`, under its hex signature
+pub(crate) async fn signature_exists(
+    kind: SignatureKind,
+    text_signature: &str,
+    hex_signature: &str,
+) -> Result<bool> {
+    let response = reqwest::Client::new()
+        .get(kind.endpoint())
+        .query(&[("hex_signature", hex_signature)])
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(eyre!(
+            "Failed to look up {} on 4byte.directory: {}",
+            hex_signature,
+            response.text().await?
+        ));
+    }
+
+    let response: FourByteResponse = response.json().await?;
+    Ok(response.results.iter().any(|r| r.text_signature == text_signature))
+}
+
+/// Submits a text signature to 4byte.directory
+pub(crate) async fn submit_signature(kind: SignatureKind, text_signature: &str) -> Result<()> {
+    let response = reqwest::Client::new()
+        .post(kind.endpoint())
+        .json(&serde_json::json!({ "text_signature": text_signature }))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(eyre!(
+            "Failed to submit {} to 4byte.directory: {}",
+            text_signature,
+            response.text().await?
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,2 @@
+/// 4byte.directory API utilities
+pub(crate) mod four_byte;
@@ -0,0 +1,109 @@
+use std::{path::PathBuf, str::FromStr};
+
+use alloy::primitives::Address;
+use alloy_chains::NamedChain;
+use eyre::{bail, eyre, Result};
+use shadow_common::{
+    audit::{append_audit_entry, load_audit_log, AuditEntry},
+    ShadowContractGroupInfo,
+};
+use tracing::{error, info, warn};
+
+use crate::{
+    eas::{creator_attestation, AttestationMode},
+    http::pin_to_logs_xyz_ipfs_node,
+    ipfs::{PinataBackend, PinningBackend},
+    BumpArgs,
+};
+
+/// The `bump` subcommand. Updates a shadow contract group's metadata and re-pins it to IPFS
+/// using the artifacts from the last build, without recompiling.
+pub async fn bump(args: BumpArgs) -> Result<()> {
+    // ensure args are valid
+    args.validate().map_err(|e| eyre!("Invalid arguments: {}", e))?;
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let mut group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    if !root_dir.join("out").exists() {
+        bail!("no compiled artifacts found in {}. run `shadow push` first", root_dir.display());
+    }
+
+    // the most recent push of this group, which the new push replaces
+    let contracts =
+        group_info.contracts.iter().map(|contract| contract.address).collect::<Vec<_>>();
+    let supersedes = load_audit_log()?
+        .into_iter()
+        .rev()
+        .find(|entry| entry.creator == group_info.creator && entry.contracts == contracts)
+        .map(|entry| entry.cid);
+    if supersedes.is_none() {
+        warn!("no previous push of this group found in the audit log");
+    }
+
+    // update the metadata
+    if let Some(display_name) = args.display_name {
+        info!("updating display name to '{}'", display_name);
+        group_info.display_name = display_name;
+        group_info.save()?;
+    }
+    if let Some(readme) = &args.readme {
+        info!("updating README.md from {}", readme);
+        std::fs::copy(readme, root_dir.join("README.md"))
+            .map_err(|e| eyre!("Failed to copy README from {}: {}", readme, e))?;
+    }
+
+    // rebuild the IPFS folder structure with the existing artifacts
+    let contract_group_artifact_path = group_info
+        .prepare_prebuilt()
+        .map_err(|e| eyre!("Failed to prepare shadow contract group: {}", e))?;
+
+    // pin the updated folder to IPFS. the CID will differ from the previous push
+    info!("pinning shadow contract group to IPFS");
+    let pin_result = PinataBackend::new(
+        &args.pinata_api_key.expect("pinata_api_key should exist"),
+        &args.pinata_secret_api_key.expect("pinata_secret_api_key should exist"),
+    )
+    .pin_folder(&contract_group_artifact_path, &args.ipfs_gateway_url)
+    .await
+    .map_err(|e| eyre!("Failed to pin shadow contract group to IPFS: {}", e))?;
+    info!("pinned shadow contract group to IPFS at {}", pin_result.ipfs_url);
+
+    // prompt attestation via EAS
+    let creator_address = group_info.creator.as_ref().unwrap_or(&Address::ZERO);
+    let attestation_tx = creator_attestation(
+        &AttestationMode::GroupCid(pin_result.cid.clone()),
+        creator_address,
+        &args.signer,
+        &args.chain,
+        args.no_wait,
+    )
+    .await?;
+
+    info!("pinning IPFS CID to logs.xyz IPFS node");
+    pin_to_logs_xyz_ipfs_node(&pin_result.cid).await?;
+
+    info!(
+        "successfully bumped contract group to: https://logs.xyz/contractGroup/{}",
+        pin_result.cid
+    );
+
+    // record the push in the local audit log
+    append_audit_entry(&AuditEntry {
+        timestamp: chrono::Utc::now(),
+        display_name: group_info.display_name.clone(),
+        cid: pin_result.cid.clone(),
+        attestation_tx,
+        chain: NamedChain::from(&args.chain).to_string(),
+        creator: group_info.creator,
+        contracts,
+        supersedes,
+    })
+    .unwrap_or_else(|e| warn!("failed to write to the audit log: {}", e));
+
+    Ok(())
+}
@@ -1,19 +1,39 @@
+mod bump;
+mod pin_local;
+mod preview;
+mod sign;
+
+pub use bump::*;
+pub use pin_local::*;
+pub use preview::*;
+pub use sign::*;
+
 use std::{path::PathBuf, str::FromStr};
 
 use alloy::primitives::Address;
+use alloy_chains::NamedChain;
 use eyre::{eyre, Result};
-use shadow_common::{forge::ensure_forge_installed, ShadowContractGroupInfo};
-use tracing::{error, info};
+use shadow_common::{
+    audit::{append_audit_entry, AuditEntry},
+    compiler::RemoteCompiler,
+    forge::ensure_forge_installed,
+    ShadowContractGroupInfo,
+};
+use tracing::{error, info, warn};
 
 use crate::{
-    eas::creator_attestation, http::pin_to_logs_xyz_ipfs_node, ipfs::pin_shadow_contract_group,
+    eas::{creator_attestation, AttestationMode},
+    http::pin_to_logs_xyz_ipfs_node,
+    ipfs::{FleekBackend, PinataBackend, PinningBackend},
     PushArgs,
 };
 
 /// The `push` subcommand. Compiles and uploads/pins a shadow contract group to IPFS.
 pub async fn push(args: PushArgs) -> Result<()> {
-    // ensure forge is installed on the system
-    ensure_forge_installed()?;
+    // ensure forge is installed on the system, unless we're not compiling locally
+    if !args.no_compile && !args.remote {
+        ensure_forge_installed()?;
+    }
 
     // ensure args are valid
     args.validate().map_err(|e| eyre!("Invalid arguments: {}", e))?;
@@ -31,27 +51,70 @@ pub async fn push(args: PushArgs) -> Result<()> {
     group_info.validate().map_err(|e| eyre!("Failed to validate shadow contract group: {}", e))?;
 
     // prepare the group for pinning. this will compile all contracts and build the final
-    // IPFS folder structure
-    let contract_group_artifact_path = group_info
-        .prepare(&args.rpc_url)
-        .await
-        .map_err(|e| eyre!("Failed to prepare shadow contract group: {}", e))?;
+    // IPFS folder structure, unless we're reusing artifacts from a previous build
+    let contract_group_artifact_path = if args.no_compile {
+        for contract in group_info.stale_contracts() {
+            warn!(
+                "--no-compile is set, but {} on chain {} has changed since it was compiled",
+                contract.address, contract.chain_id
+            );
+        }
+        group_info.prepare_prebuilt()
+    } else if args.remote {
+        let remote = RemoteCompiler::new(
+            args.remote_compiler_url.as_deref(),
+            args.logs_xyz_api_key.as_deref(),
+        )?;
+        group_info
+            .prepare_with(&args.rpc_url, Some(&remote), args.parallelism, args.parallel_requests)
+            .await
+    } else {
+        group_info.prepare_with(&args.rpc_url, None, args.parallelism, args.parallel_requests).await
+    }
+    .map_err(|e| eyre!("Failed to prepare shadow contract group: {}", e))?;
 
     // pin the created folder to IPFS
     info!("pinning shadow contract group to IPFS");
-    let pin_result = pin_shadow_contract_group(
-        &contract_group_artifact_path,
-        &args.pinata_api_key.expect("pinata_api_key should exist"),
-        &args.pinata_secret_api_key.expect("pinata_secret_api_key should exist"),
-        &args.ipfs_gateway_url,
-    )
-    .await
+    let pin_result = if args.uses_fleek() {
+        FleekBackend::new(
+            args.fleek_api_key.as_deref().expect("fleek_api_key should exist"),
+            args.fleek_api_secret.as_deref().expect("fleek_api_secret should exist"),
+        )
+        .pin_folder(&contract_group_artifact_path, &args.ipfs_gateway_url)
+        .await
+    } else {
+        PinataBackend::new(
+            args.pinata_api_key.as_deref().expect("pinata_api_key should exist"),
+            args.pinata_secret_api_key.as_deref().expect("pinata_secret_api_key should exist"),
+        )
+        .pin_folder(&contract_group_artifact_path, &args.ipfs_gateway_url)
+        .await
+    }
     .map_err(|e| eyre!("Failed to pin shadow contract group to IPFS: {}", e))?;
     info!("pinned shadow contract group to IPFS at {}", pin_result.ipfs_url);
 
     // prompt attestation via EAS
     let creator_address = group_info.creator.as_ref().unwrap_or(&Address::ZERO);
-    creator_attestation(&pin_result.cid, creator_address, &args.signer, &args.chain).await?;
+    let attestation_mode = if args.attest_per_contract {
+        AttestationMode::MultiContract {
+            ipfs_cid: pin_result.cid.clone(),
+            contracts: group_info
+                .contracts
+                .iter()
+                .map(|contract| (contract.chain_id, contract.address))
+                .collect(),
+        }
+    } else {
+        AttestationMode::GroupCid(pin_result.cid.clone())
+    };
+    let attestation_tx = creator_attestation(
+        &attestation_mode,
+        creator_address,
+        &args.signer,
+        &args.chain,
+        args.no_wait,
+    )
+    .await?;
 
     info!("pinning IPFS CID to logs.xyz IPFS node");
     pin_to_logs_xyz_ipfs_node(&pin_result.cid).await?;
@@ -61,5 +124,18 @@ pub async fn push(args: PushArgs) -> Result<()> {
         pin_result.cid
     );
 
+    // record the push in the local audit log
+    append_audit_entry(&AuditEntry {
+        timestamp: chrono::Utc::now(),
+        display_name: group_info.display_name.clone(),
+        cid: pin_result.cid.clone(),
+        attestation_tx,
+        chain: NamedChain::from(&args.chain).to_string(),
+        creator: group_info.creator,
+        contracts: group_info.contracts.iter().map(|contract| contract.address).collect(),
+        supersedes: None,
+    })
+    .unwrap_or_else(|e| warn!("failed to write to the audit log: {}", e));
+
     Ok(())
 }
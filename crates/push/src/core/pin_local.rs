@@ -0,0 +1,54 @@
+use std::{path::PathBuf, str::FromStr};
+
+use eyre::{eyre, Result};
+use shadow_common::{forge::ensure_forge_installed, ShadowContractGroupInfo};
+use tracing::{error, info, warn};
+
+use crate::{
+    ipfs::{KuboBackend, PinningBackend},
+    PinLocalArgs,
+};
+
+/// The `pin-local` subcommand. Compiles a shadow contract group like `shadow push`, and pins it
+/// to a local IPFS node instead of Pinata, without attesting.
+pub async fn pin_local(args: PinLocalArgs) -> Result<()> {
+    // ensure the local IPFS daemon is running before doing any work
+    let backend = KuboBackend::new(&args.api_url);
+    let version = backend.version().await?;
+    info!("using local IPFS node v{} at {}", version, args.api_url);
+
+    // ensure forge is installed on the system, unless we're not compiling
+    if !args.no_compile {
+        ensure_forge_installed()?;
+    }
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let mut group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    // validate and prepare the group exactly as `shadow push` would
+    info!("validating shadow contract group at {}", root_dir.display());
+    group_info.validate().map_err(|e| eyre!("Failed to validate shadow contract group: {}", e))?;
+    let artifact_path = if args.no_compile {
+        warn!("--no-compile is set. pinning existing artifacts, which may be stale");
+        group_info.prepare_prebuilt()
+    } else {
+        group_info.prepare_with(&args.rpc_url, None, args.parallelism, args.parallel_requests).await
+    }
+    .map_err(|e| eyre!("Failed to prepare shadow contract group: {}", e))?;
+
+    // pin the created folder to the local node
+    info!("pinning shadow contract group to the local IPFS node");
+    let pin_result = backend
+        .pin_folder(&artifact_path, &args.ipfs_gateway_url)
+        .await
+        .map_err(|e| eyre!("Failed to pin shadow contract group to IPFS: {}", e))?;
+
+    info!("pinned shadow contract group with CID {}", pin_result.cid);
+    info!("available at {}", pin_result.ipfs_url);
+
+    Ok(())
+}
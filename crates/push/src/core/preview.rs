@@ -0,0 +1,88 @@
+use std::{
+    path::{Component, PathBuf},
+    str::FromStr,
+};
+
+use alloy::primitives::hex;
+use eyre::{eyre, Result};
+use shadow_common::{
+    forge::ensure_forge_installed, hash::hash_files_excluding, ipfs::format_size,
+    ShadowContractGroupInfo,
+};
+use tracing::{error, info};
+
+use crate::PreviewPushArgs;
+
+/// Pinata's storage price, in USD per GB per month, used to estimate the cost of pinning
+const PINATA_USD_PER_GB_MONTH: f64 = 0.07;
+
+/// The `preview-push` subcommand. Compiles a shadow contract group and builds its IPFS folder
+/// structure like `shadow push`, then lists the files which would be pinned, without pinning
+/// them or attesting.
+pub async fn preview_push(args: PreviewPushArgs) -> Result<()> {
+    // ensure forge is installed on the system
+    ensure_forge_installed()?;
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let mut group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    // validate and prepare the group exactly as `shadow push` would
+    info!("validating shadow contract group at {}", root_dir.display());
+    group_info.validate().map_err(|e| eyre!("Failed to validate shadow contract group: {}", e))?;
+    let artifact_path = group_info
+        .prepare_with(&args.rpc_url, None, args.parallelism, args.parallel_requests)
+        .await
+        .map_err(|e| eyre!("Failed to prepare shadow contract group: {}", e))?;
+
+    let files = hash_files_excluding(&artifact_path, &[])?;
+    let mut total_size = 0u64;
+    let mut current_dir: Vec<String> = vec![];
+    println!("{}/", artifact_path.display());
+    for file in &files {
+        let size = std::fs::metadata(artifact_path.join(&file.path))?.len();
+        total_size += size;
+
+        // print any directories between the previous file and this one
+        let components = file
+            .path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+        let (file_name, dirs) = components.split_last().expect("files have a name");
+        let shared = current_dir.iter().zip(dirs).take_while(|(a, b)| a == b).count();
+        for (depth, dir) in dirs.iter().enumerate().skip(shared) {
+            println!("{}{}/", "  ".repeat(depth + 1), dir);
+        }
+        current_dir = dirs.to_vec();
+
+        println!(
+            "{}{}  {}  {}",
+            "  ".repeat(dirs.len() + 1),
+            file_name,
+            format_size(size),
+            hex::encode(file.hash)
+        );
+    }
+
+    let cost = total_size as f64 / 1e9 * PINATA_USD_PER_GB_MONTH;
+    info!(
+        "{} files, {} bytes ({}) would be pinned to IPFS",
+        files.len(),
+        total_size,
+        format_size(total_size)
+    );
+    info!(
+        "estimated Pinata storage cost: ${:.6} per month, at ${} per GB per month",
+        cost, PINATA_USD_PER_GB_MONTH
+    );
+    info!("nothing was pinned. Run `shadow push` to pin the contract group and attest to it");
+
+    Ok(())
+}
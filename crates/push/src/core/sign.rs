@@ -0,0 +1,87 @@
+use alloy::{
+    dyn_abi::TypedData,
+    hex,
+    primitives::{Address, Signature},
+    signers::Signer,
+};
+use eyre::{bail, eyre, Result};
+use tracing::info;
+
+use crate::{eas::get_signer, SignArgs, SupportedChains};
+
+/// What `shadow sign` signs or verifies
+enum Payload {
+    /// An EIP-191 message
+    Message(Vec<u8>),
+    /// EIP-712 typed data
+    TypedData(Box<TypedData>),
+}
+
+/// The `sign` subcommand. Signs a message or EIP-712 typed data with the same signers `shadow
+/// push` attests with, and prints the signature. With `--verify`, checks a signature instead.
+pub async fn sign(args: SignArgs) -> Result<()> {
+    let payload = match (&args.message, &args.typed_data) {
+        (Some(message), _) => Payload::Message(parse_message(message)),
+        (None, Some(path)) => {
+            let typed_data = std::fs::read_to_string(path)
+                .map_err(|e| eyre!("Failed to read {}: {}", path, e))?;
+            Payload::TypedData(Box::new(
+                serde_json::from_str(&typed_data)
+                    .map_err(|e| eyre!("Invalid EIP-712 typed data in {}: {}", path, e))?,
+            ))
+        }
+        (None, None) => bail!("either --message or --typed-data is required"),
+    };
+
+    if let (Some(expected), Some(signature)) = (&args.verify, &args.signature) {
+        let expected: Address = expected.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+        let signature = hex::decode(signature)
+            .ok()
+            .and_then(|bytes| Signature::try_from(bytes.as_slice()).ok())
+            .ok_or_else(|| eyre!("Invalid signature: expected 65 bytes of hex"))?;
+
+        let signer = match &payload {
+            Payload::Message(message) => signature.recover_address_from_msg(message)?,
+            Payload::TypedData(typed_data) => {
+                signature.recover_address_from_prehash(&typed_data.eip712_signing_hash()?)?
+            }
+        };
+        if signer != expected {
+            bail!("the signature is invalid. It was made by {}, not {}", signer, expected);
+        }
+
+        info!("the signature is valid, and was made by {}", expected);
+        return Ok(());
+    }
+
+    // the chain only matters to hardware wallets signing transactions, which isn't done here
+    let signer = get_signer(&args.signer, &SupportedChains::default()).await?;
+    let signature = match &payload {
+        Payload::Message(message) => signer.sign_message(message).await?,
+        Payload::TypedData(typed_data) => signer.sign_dynamic_typed_data(typed_data).await?,
+    };
+    info!("signed with {}", Signer::address(&*signer));
+    println!("0x{}", hex::encode(signature.as_bytes()));
+
+    Ok(())
+}
+
+/// Messages given as 0x-prefixed hex are signed as bytes, and anything else as UTF-8 text
+fn parse_message(message: &str) -> Vec<u8> {
+    message
+        .strip_prefix("0x")
+        .and_then(|hex_message| hex::decode(hex_message).ok())
+        .unwrap_or_else(|| message.as_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_message() {
+        assert_eq!(parse_message("0xdeadbeef"), vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(parse_message("hello"), b"hello".to_vec());
+        assert_eq!(parse_message("0xnot hex"), b"0xnot hex".to_vec());
+    }
+}
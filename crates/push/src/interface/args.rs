@@ -22,6 +22,8 @@ pub enum SignerType {
     Trezor,
     /// Use a Yubikey hardware wallet
     Yubikey,
+    /// Use a Gnosis Safe multisig, proposing the attestation to the safe's owners
+    Safe,
 }
 
 /// supported chains enum
@@ -57,6 +59,19 @@ impl SupportedChains {
         }
     }
 
+    /// Get the UID of the schema for per-contract attestations on the given chain, which is
+    /// `string ipfs_cid,uint256 chain_id,address contract` with no resolver
+    pub fn contract_schema_uid(&self) -> &str {
+        match self {
+            SupportedChains::Base => {
+                "24596dbd6320679e2d93f6af8fea0792ed65eece1979d11e1a941dcf1b3ffe25"
+            }
+            SupportedChains::Sepolia => {
+                "24596dbd6320679e2d93f6af8fea0792ed65eece1979d11e1a941dcf1b3ffe25"
+            }
+        }
+    }
+
     /// Get the EAS address for the given chain
     pub fn eas_address(&self) -> Address {
         match self {
@@ -90,6 +105,22 @@ impl SupportedChains {
             SupportedChains::Sepolia => "sepolia.etherscan.io".to_string(),
         }
     }
+
+    /// Get the Safe Transaction Service url for the given chain
+    pub fn safe_transaction_service_url(&self) -> String {
+        match self {
+            SupportedChains::Base => "https://safe-transaction-base.safe.global".to_string(),
+            SupportedChains::Sepolia => "https://safe-transaction-sepolia.safe.global".to_string(),
+        }
+    }
+
+    /// Get the chain prefix used by the Safe UI for the given chain
+    pub fn safe_ui_prefix(&self) -> String {
+        match self {
+            SupportedChains::Base => "base".to_string(),
+            SupportedChains::Sepolia => "sep".to_string(),
+        }
+    }
 }
 
 /// Arguments for the `push` subcommand
@@ -112,6 +143,15 @@ pub struct PushArgs {
     #[clap(long, required = false, alias = "ipfs-secret-api-key")]
     pub pinata_secret_api_key: Option<String>,
 
+    /// Your Fleek API key. If set alongside `--fleek-api-secret`, the shadow contract group is
+    /// pinned with Fleek instead of Pinata.
+    #[clap(long, required = false, requires = "fleek_api_secret")]
+    pub fleek_api_key: Option<String>,
+
+    /// Your Fleek API secret, used with `--fleek-api-key`.
+    #[clap(long, required = false, requires = "fleek_api_key")]
+    pub fleek_api_secret: Option<String>,
+
     /// Your preferred IPFS gateway, used when displaying the IPFS URL.
     #[clap(
         long,
@@ -128,9 +168,115 @@ pub struct PushArgs {
     /// The RPC URL of the chain to simulate the transaction on.
     #[clap(short = 'u', long, default_value = "http://localhost:8545")]
     pub rpc_url: String,
+
+    /// When attesting with `--signer safe`, propose the attestation to the safe and exit
+    /// immediately, rather than waiting for the safe's owners to confirm it.
+    #[clap(long)]
+    pub no_wait: bool,
+
+    /// Attest to each contract in the group individually, rather than only to the group's IPFS
+    /// CID. All attestations are submitted in a single transaction.
+    #[clap(long)]
+    pub attest_per_contract: bool,
+
+    /// Skip compilation and push the artifacts from the last build in `out/`. These may be
+    /// stale if contracts have changed since they were built.
+    #[clap(long)]
+    pub no_compile: bool,
+
+    /// The maximum number of contracts to compile at once. Defaults to the number of CPUs.
+    #[clap(long, required = false, conflicts_with = "no_compile")]
+    pub parallelism: Option<usize>,
+
+    /// The maximum number of RPC requests to have in flight at once while compiling. Further
+    /// requests are queued.
+    #[clap(long, default_value = "10", conflicts_with = "no_compile")]
+    pub parallel_requests: usize,
+
+    /// Compile via a remote compiler instead of a local `forge` installation.
+    #[clap(long, conflicts_with = "no_compile")]
+    pub remote: bool,
+
+    /// The URL of the remote compiler to use with `--remote`. Defaults to your configured
+    /// `remote_compiler_url`.
+    #[clap(long, required = false)]
+    pub remote_compiler_url: Option<String>,
+
+    /// The API key to use for remote compilation. Defaults to your configured `logs_xyz_api_key`.
+    #[clap(long, required = false)]
+    pub logs_xyz_api_key: Option<String>,
 }
 
 impl PushArgs {
+    /// Validates the configuration arguments.
+    pub fn validate(&self) -> Result<()> {
+        // pinata credentials aren't needed when pinning with fleek
+        if self.uses_fleek() {
+            return Ok(());
+        }
+
+        let _ = self.pinata_api_key.as_ref().ok_or_eyre(
+               "IPFS API key must be set. Use the --pinata-api-key flag or set the IPFS_API_KEY environment variable.")?;
+        let _ = self.pinata_secret_api_key.as_ref().ok_or_eyre(
+               "IPFS secret API key must be set. Use the --pinata-secret-api-key flag or set the IPFS_SECRET_API_KEY environment variable.")?;
+
+        Ok(())
+    }
+
+    /// Whether to pin with Fleek rather than Pinata
+    pub fn uses_fleek(&self) -> bool {
+        self.fleek_api_key.is_some() && self.fleek_api_secret.is_some()
+    }
+}
+
+/// Arguments for the `bump` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Re-push a shadow contract group with updated metadata, without recompiling")]
+pub struct BumpArgs {
+    /// The path to the directory containing the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The new display name of the contract group.
+    #[clap(long, required = false)]
+    pub display_name: Option<String>,
+
+    /// The path to a file to replace the contract group's README.md with.
+    #[clap(long, required = false)]
+    pub readme: Option<String>,
+
+    /// The type of signer you wish to use when attesting.
+    #[clap(short, long, default_value = "private-key", required = false)]
+    pub signer: SignerType,
+
+    /// Your pinata API key, used to pin the shadow contract group to IPFS.
+    #[clap(long, required = false, alias = "ipfs-api-key")]
+    pub pinata_api_key: Option<String>,
+
+    /// Your pinata secret API key, used to pin the shadow contract group to IPFS.
+    #[clap(long, required = false, alias = "ipfs-secret-api-key")]
+    pub pinata_secret_api_key: Option<String>,
+
+    /// Your preferred IPFS gateway, used when displaying the IPFS URL.
+    #[clap(
+        long,
+        required = false,
+        default_value = "https://gateway.pinata.cloud/ipfs/",
+        hide_default_value = true
+    )]
+    pub ipfs_gateway_url: String,
+
+    /// The chain to use when attesting.
+    #[clap(short, long, default_value = "base", required = false)]
+    pub chain: SupportedChains,
+
+    /// When attesting with `--signer safe`, propose the attestation to the safe and exit
+    /// immediately, rather than waiting for the safe's owners to confirm it.
+    #[clap(long)]
+    pub no_wait: bool,
+}
+
+impl BumpArgs {
     /// Validates the configuration arguments.
     pub fn validate(&self) -> Result<()> {
         let _ = self.pinata_api_key.as_ref().ok_or_eyre(
@@ -141,3 +287,92 @@ impl PushArgs {
         Ok(())
     }
 }
+
+/// Arguments for the `preview-push` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Show what `shadow push` would pin to IPFS, without pinning it")]
+pub struct PreviewPushArgs {
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain the contracts are deployed on.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+
+    /// The maximum number of contracts to compile at once. Defaults to the number of CPUs.
+    #[clap(long, required = false)]
+    pub parallelism: Option<usize>,
+
+    /// The maximum number of RPC requests to have in flight at once while compiling. Further
+    /// requests are queued.
+    #[clap(long, default_value = "10")]
+    pub parallel_requests: usize,
+}
+
+/// Arguments for the `pin-local` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Compile a shadow contract group and pin it to a local IPFS node")]
+pub struct PinLocalArgs {
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The URL of the local IPFS node's HTTP API.
+    #[clap(long, default_value = "http://127.0.0.1:5001")]
+    pub api_url: String,
+
+    /// The IPFS gateway used when displaying the IPFS URL. Defaults to the local node's gateway.
+    #[clap(long, default_value = "http://127.0.0.1:8080/ipfs/")]
+    pub ipfs_gateway_url: String,
+
+    /// The RPC URL of the chain the contracts are deployed on.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+
+    /// Skip compilation and pin the artifacts from the last build in `out/`. These may be
+    /// stale if contracts have changed since they were built.
+    #[clap(long)]
+    pub no_compile: bool,
+
+    /// The maximum number of contracts to compile at once. Defaults to the number of CPUs.
+    #[clap(long, required = false, conflicts_with = "no_compile")]
+    pub parallelism: Option<usize>,
+
+    /// The maximum number of RPC requests to have in flight at once while compiling. Further
+    /// requests are queued.
+    #[clap(long, default_value = "10", conflicts_with = "no_compile")]
+    pub parallel_requests: usize,
+}
+
+/// Arguments for the `sign` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Sign or verify a message or EIP-712 typed data with any supported signer")]
+pub struct SignArgs {
+    /// The message to sign or verify, as a 0x-prefixed hex string or plain text. Messages are
+    /// signed per EIP-191, like `personal_sign`.
+    #[clap(
+        long,
+        required_unless_present = "typed_data",
+        conflicts_with = "typed_data",
+        required = false
+    )]
+    pub message: Option<String>,
+
+    /// The path to a JSON file containing EIP-712 typed data to sign or verify, in the format
+    /// accepted by `eth_signTypedData_v4`.
+    #[clap(long, required = false)]
+    pub typed_data: Option<String>,
+
+    /// The type of signer you wish to sign with.
+    #[clap(short, long, default_value = "private-key", required = false)]
+    pub signer: SignerType,
+
+    /// Verify that `--signature` was made by this address, instead of signing.
+    #[clap(long, required = false, requires = "signature")]
+    pub verify: Option<String>,
+
+    /// The 65-byte signature to verify, as hex.
+    #[clap(long, required = false, requires = "verify")]
+    pub signature: Option<String>,
+}
@@ -1,4 +1,4 @@
-//! The `push` subcommand
+//! The `push`, `bump`, `preview-push`, `pin-local` and `sign` subcommands
 
 mod core;
 mod interface;
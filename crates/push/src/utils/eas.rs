@@ -1,9 +1,10 @@
-use std::io::Write;
+use std::{future::Future, io::Write, pin::Pin};
 
 use alloy::{
     dyn_abi::DynSolValue,
     hex::FromHex,
-    network::EthereumWallet,
+    network::{EthereumWallet, TxSigner},
+    primitives::Signature,
     providers::{Provider, ProviderBuilder},
     signers::{
         ledger::LedgerSigner,
@@ -13,15 +14,20 @@ use alloy::{
             LocalSigner, MnemonicBuilder, PrivateKeySigner, YubiSigner,
         },
         trezor::TrezorSigner,
+        Signer,
     },
     sol,
+    sol_types::{Eip712Domain, SolCall},
 };
 use eyre::{bail, eyre, OptionExt, Result};
 use revm::primitives::{Address, Bytes, FixedBytes, U256};
 use tracing::{debug, error, info, trace, warn};
-use EAS::{AttestationRequest, AttestationRequestData};
+use EAS::{AttestationRequest, AttestationRequestData, MultiAttestationRequest};
 
-use crate::{SignerType, SupportedChains};
+use crate::{
+    safe::{safe_attestation, SafeTx},
+    SignerType, SupportedChains,
+};
 
 // Codegen from ABI file to interact with EAS.
 sol!(
@@ -31,19 +37,90 @@ sol!(
     "abi/EAS.json"
 );
 
-/// Attempt to attest with EAS
+/// A signer which is able to sign transactions, arbitrary hashes and Safe transactions
+pub(crate) trait ShadowSigner: Signer + TxSigner<Signature> + Send + Sync {
+    /// Signs a Safe transaction as EIP-712 typed data. Unlike [`Signer::sign_hash`], this is
+    /// supported by hardware wallets.
+    fn sign_safe_tx<'a>(
+        &'a self,
+        tx: &'a SafeTx,
+        domain: &'a Eip712Domain,
+    ) -> Pin<Box<dyn Future<Output = alloy::signers::Result<Signature>> + Send + 'a>>;
+}
+
+impl<T> ShadowSigner for T
+where
+    T: Signer + TxSigner<Signature> + Send + Sync,
+{
+    fn sign_safe_tx<'a>(
+        &'a self,
+        tx: &'a SafeTx,
+        domain: &'a Eip712Domain,
+    ) -> Pin<Box<dyn Future<Output = alloy::signers::Result<Signature>> + Send + 'a>> {
+        self.sign_typed_data(tx, domain)
+    }
+}
+
+/// What an EAS attestation is made to
+#[derive(Debug, Clone)]
+pub(crate) enum AttestationMode {
+    /// A single attestation to the contract group's IPFS CID
+    GroupCid(String),
+    /// One attestation per contract in the group, all submitted in a single `multiAttest`
+    /// transaction. Each attestation's data encodes `(ipfs_cid, chain_id, address)`, under the
+    /// per-contract schema rather than the group schema.
+    MultiContract {
+        /// The contract group's IPFS CID
+        ipfs_cid: String,
+        /// The chain ID and address of each contract
+        contracts: Vec<(u64, Address)>,
+    },
+}
+
+impl AttestationMode {
+    /// The number of attestations which will be made
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            AttestationMode::GroupCid(_) => 1,
+            AttestationMode::MultiContract { contracts, .. } => contracts.len(),
+        }
+    }
+
+    /// Build the calldata of the EAS call which makes the attestations
+    pub(crate) fn calldata(&self, chain: &SupportedChains) -> Result<Bytes> {
+        Ok(match self {
+            AttestationMode::GroupCid(ipfs_cid) => {
+                EAS::attestCall { request: attestation_request(ipfs_cid, chain)? }.abi_encode()
+            }
+            AttestationMode::MultiContract { ipfs_cid, contracts } => EAS::multiAttestCall {
+                multiRequests: multi_attestation_requests(ipfs_cid, contracts, chain)?,
+            }
+            .abi_encode(),
+        }
+        .into())
+    }
+}
+
+/// Attempt to attest with EAS, returning the attestation transaction hash if one was broadcast
 pub(crate) async fn creator_attestation(
-    ipfs_cid: &str,
+    mode: &AttestationMode,
     creator_address: &Address,
     signer_method: &SignerType,
     chain: &SupportedChains,
-) -> Result<()> {
+    no_wait: bool,
+) -> Result<Option<String>> {
     warn!("EAS attestation from {:#020x} required to publish to https://logs.xyz", creator_address);
+
+    // a safe cannot sign on its own, so the attestation must be proposed to the safe's owners
+    if let SignerType::Safe = signer_method {
+        return safe_attestation(mode, creator_address, chain, no_wait).await;
+    }
+
     let signer = match get_signer(signer_method, chain).await {
-        Ok(signer) => signer,
+        Ok(signer) => EthereumWallet::from(signer),
         Err(e) => {
             warn!("failed to get signer: {}", e);
-            return Ok(());
+            return Ok(None);
         }
     };
     if signer.default_signer().address() != *creator_address {
@@ -63,23 +140,32 @@ pub(crate) async fn creator_attestation(
 
     // Get the contract instance
     let eas = EAS::new(chain.eas_address(), provider.clone());
-    let req = AttestationRequest {
-        schema: chain.schema_uid().parse()?,
-        data: AttestationRequestData {
-            recipient: Address::ZERO,
-            expirationTime: 0,
-            revocable: true,
-            refUID: FixedBytes::ZERO,
-            data: Bytes::from_iter(DynSolValue::String(ipfs_cid.to_string()).abi_encode()),
-            value: U256::ZERO,
-        },
-    };
 
     // build the attestation call
     let tx_nonce = provider.get_transaction_count(*creator_address).await?;
-    let attestation_call =
-        eas.attest(req).from(*creator_address).nonce(tx_nonce).chain_id(chain.chain_id());
-    trace!("attestation call: {:#?}", attestation_call);
+    let attestation_tx = match mode {
+        AttestationMode::GroupCid(ipfs_cid) => eas
+            .attest(attestation_request(ipfs_cid, chain)?)
+            .from(*creator_address)
+            .nonce(tx_nonce)
+            .chain_id(chain.chain_id())
+            .into_transaction_request(),
+        AttestationMode::MultiContract { ipfs_cid, contracts } => eas
+            .multiAttest(multi_attestation_requests(ipfs_cid, contracts, chain)?)
+            .from(*creator_address)
+            .nonce(tx_nonce)
+            .chain_id(chain.chain_id())
+            .into_transaction_request(),
+    };
+    trace!("attestation transaction: {:#?}", attestation_tx);
+
+    // show the cost of the attestations before asking for confirmation
+    match provider.estimate_gas(&attestation_tx).await {
+        Ok(gas) => {
+            info!("attesting to {} item(s), with an estimated {} gas", mode.len(), gas)
+        }
+        Err(e) => warn!("failed to estimate gas for the EAS attestation: {}", e),
+    }
 
     // Prompt the user to confirm the attestation
     if prompt("You are about to sign an EAS attestation. Would you like to continue? (y/N): ")?
@@ -88,23 +174,73 @@ pub(crate) async fn creator_attestation(
         "y"
     {
         warn!("user skipping EAS attestation");
-        return Ok(());
+        return Ok(None);
     }
 
     // Send the attestation
-    let attestation_tx_hash =
-        provider.send_transaction(attestation_call.into_transaction_request()).await?;
+    let attestation_tx_hash = provider.send_transaction(attestation_tx).await?;
     info!(
         "EAS attestation broadcast successfully: https://{}/tx/{}",
         chain.explorer_url(),
         attestation_tx_hash.tx_hash()
     );
 
-    Ok(())
+    Ok(Some(attestation_tx_hash.tx_hash().to_string()))
+}
+
+/// Build the EAS attestation request for the given IPFS CID
+pub(crate) fn attestation_request(
+    ipfs_cid: &str,
+    chain: &SupportedChains,
+) -> Result<AttestationRequest> {
+    Ok(AttestationRequest {
+        schema: chain.schema_uid().parse()?,
+        data: AttestationRequestData {
+            recipient: Address::ZERO,
+            expirationTime: 0,
+            revocable: true,
+            refUID: FixedBytes::ZERO,
+            data: Bytes::from_iter(DynSolValue::String(ipfs_cid.to_string()).abi_encode()),
+            value: U256::ZERO,
+        },
+    })
+}
+
+/// Build the EAS `multiAttest` request attesting to each contract in a group with the given
+/// IPFS CID
+pub(crate) fn multi_attestation_requests(
+    ipfs_cid: &str,
+    contracts: &[(u64, Address)],
+    chain: &SupportedChains,
+) -> Result<Vec<MultiAttestationRequest>> {
+    Ok(vec![MultiAttestationRequest {
+        schema: chain.contract_schema_uid().parse()?,
+        data: contracts
+            .iter()
+            .map(|(chain_id, address)| AttestationRequestData {
+                recipient: Address::ZERO,
+                expirationTime: 0,
+                revocable: true,
+                refUID: FixedBytes::ZERO,
+                data: Bytes::from_iter(
+                    DynSolValue::Tuple(vec![
+                        DynSolValue::String(ipfs_cid.to_string()),
+                        DynSolValue::Uint(U256::from(*chain_id), 256),
+                        DynSolValue::Address(*address),
+                    ])
+                    .abi_encode_params(),
+                ),
+                value: U256::ZERO,
+            })
+            .collect(),
+    }])
 }
 
 /// Get the signer for the given method
-async fn get_signer(signer_method: &SignerType, chain: &SupportedChains) -> Result<EthereumWallet> {
+pub(crate) async fn get_signer(
+    signer_method: &SignerType,
+    chain: &SupportedChains,
+) -> Result<Box<dyn ShadowSigner>> {
     debug!("using --signer '{:?}'", signer_method);
     match signer_method {
         SignerType::PrivateKey => {
@@ -115,7 +251,7 @@ async fn get_signer(signer_method: &SignerType, chain: &SupportedChains) -> Resu
                     .map_err(|e| eyre!("invalid private key: {}", e))?,
             )
             .map_err(|e| eyre!("failed to create signer: {}", e))?;
-            Ok(EthereumWallet::from(signer))
+            Ok(Box::new(signer))
         }
         SignerType::Mnemonic => {
             let mnemonic = prompt("Enter your mnemonic (or Enter to skip): ")?
@@ -127,7 +263,7 @@ async fn get_signer(signer_method: &SignerType, chain: &SupportedChains) -> Resu
                 .derivation_path(derivation_path)?
                 .build()
                 .map_err(|e| eyre!("failed to create wallet: {}", e))?;
-            Ok(EthereumWallet::from(signer))
+            Ok(Box::new(signer))
         }
         SignerType::Ledger => {
             let hdpath = prompt("Enter your HDPath (0): ")?
@@ -140,7 +276,7 @@ async fn get_signer(signer_method: &SignerType, chain: &SupportedChains) -> Resu
                 Some(chain.chain_id()),
             )
             .await?;
-            Ok(EthereumWallet::from(signer))
+            Ok(Box::new(signer))
         }
         SignerType::Trezor => {
             let hdpath = prompt("Enter your HDPath (0): ")?
@@ -153,12 +289,12 @@ async fn get_signer(signer_method: &SignerType, chain: &SupportedChains) -> Resu
                 Some(chain.chain_id()),
             )
             .await?;
-            Ok(EthereumWallet::from(signer))
+            Ok(Box::new(signer))
         }
         SignerType::Yubikey => {
             let connector = Connector::usb(&UsbConfig::default());
             let signer = YubiSigner::connect(connector, Credentials::default(), 0);
-            Ok(EthereumWallet::from(signer))
+            Ok(Box::new(signer))
         }
         SignerType::Keystore => {
             let keystore = prompt("Enter the path to your keystore file (or Enter to skip): ")?
@@ -166,13 +302,16 @@ async fn get_signer(signer_method: &SignerType, chain: &SupportedChains) -> Resu
             let password = prompt("Enter your keystore password: ")?
                 .ok_or_eyre("user skipping EAS attestation")?;
             let signer = LocalSigner::decrypt_keystore(keystore, password)?;
-            Ok(EthereumWallet::from(signer))
+            Ok(Box::new(signer))
+        }
+        SignerType::Safe => {
+            bail!("a safe cannot sign directly. use the signer of one of the safe's owners")
         }
     }
 }
 
 /// Prompt the user for input w/ pretty colors :D
-fn prompt(text: &str) -> Result<Option<String>> {
+pub(crate) fn prompt(text: &str) -> Result<Option<String>> {
     let mut input = String::new();
     const YELLOW_ANSI_CODE: &str = "\u{001b}[33m";
     const LIGHT_GRAY_ANSI_CODE: &str = "\u{001b}[90m";
@@ -1,7 +1,16 @@
 use std::path::Path;
 
-use eyre::{eyre, Result};
+use eyre::{eyre, OptionExt, Result};
 use pinata_sdk::{PinByFile, PinataApi};
+use reqwest::{
+    multipart::{Form, Part},
+    Client, StatusCode,
+};
+use serde::Deserialize;
+use walkdir::WalkDir;
+
+/// The base URL of Fleek's IPFS API
+const FLEEK_API_URL: &str = "https://api.fleek.co/ipfs/";
 
 /// Result of pinning a contract group
 #[derive(Debug, Clone)]
@@ -12,23 +21,346 @@ pub(crate) struct PinResult {
     pub(crate) ipfs_url: String,
 }
 
-/// Pins the provided
-pub(crate) async fn pin_shadow_contract_group(
-    path: &Path,
-    api_key: &str,
-    secret_api_key: &str,
-    base_gateway_url: &str,
-) -> Result<PinResult> {
-    let api = PinataApi::new(api_key, secret_api_key)
-        .map_err(|e| eyre!("Failed to create Pinata API client: {}", e))?;
-    api.test_authentication().await.map_err(|_| eyre!("Invalid pinata credentials"))?;
-    let result = api
-        .pin_file(PinByFile::new(format!("{}/", path.to_string_lossy())))
-        .await
-        .map_err(|e| eyre!("Failed to pin file: {}", e))?;
-
-    Ok(PinResult {
-        cid: result.ipfs_hash.clone(),
-        ipfs_url: format!("{}/{}/", base_gateway_url.trim_end_matches('/'), result.ipfs_hash),
-    })
+impl PinResult {
+    /// Builds a [`PinResult`] for the given CID, served from `base_gateway_url`
+    fn new(cid: String, base_gateway_url: &str) -> Self {
+        let ipfs_url = format!("{}/{}/", base_gateway_url.trim_end_matches('/'), cid);
+        Self { cid, ipfs_url }
+    }
+}
+
+/// A service which can pin a folder to IPFS
+pub(crate) trait PinningBackend {
+    /// Pins the folder at `path`, returning its CID and its URL on `base_gateway_url`
+    async fn pin_folder(&self, path: &Path, base_gateway_url: &str) -> Result<PinResult>;
+}
+
+/// Pins folders with Pinata's API
+pub(crate) struct PinataBackend {
+    api_key: String,
+    secret_api_key: String,
+}
+
+impl PinataBackend {
+    pub(crate) fn new(api_key: &str, secret_api_key: &str) -> Self {
+        Self { api_key: api_key.to_string(), secret_api_key: secret_api_key.to_string() }
+    }
+}
+
+impl PinningBackend for PinataBackend {
+    async fn pin_folder(&self, path: &Path, base_gateway_url: &str) -> Result<PinResult> {
+        let api = PinataApi::new(&self.api_key, &self.secret_api_key)
+            .map_err(|e| eyre!("Failed to create Pinata API client: {}", e))?;
+        api.test_authentication().await.map_err(|_| eyre!("Invalid pinata credentials"))?;
+        let result = api
+            .pin_file(PinByFile::new(format!("{}/", path.to_string_lossy())))
+            .await
+            .map_err(|e| eyre!("Failed to pin file: {}", e))?;
+
+        Ok(PinResult::new(result.ipfs_hash, base_gateway_url))
+    }
+}
+
+/// Pins folders with Fleek's IPFS API
+pub(crate) struct FleekBackend {
+    api_key: String,
+    api_secret: String,
+    api_url: String,
+}
+
+/// A single entry of an IPFS `add` response, as returned by Fleek and kubo
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AddEntry {
+    name: String,
+    hash: String,
+}
+
+impl FleekBackend {
+    pub(crate) fn new(api_key: &str, api_secret: &str) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            api_secret: api_secret.to_string(),
+            api_url: FLEEK_API_URL.to_string(),
+        }
+    }
+
+    /// Uses a different base URL for Fleek's API
+    #[cfg(test)]
+    fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.to_string();
+        self
+    }
+}
+
+impl PinningBackend for FleekBackend {
+    async fn pin_folder(&self, path: &Path, base_gateway_url: &str) -> Result<PinResult> {
+        let response = Client::new()
+            .post(format!("{}/add", self.api_url.trim_end_matches('/')))
+            .query(&[("wrap-with-directory", "true"), ("pin", "true")])
+            .basic_auth(&self.api_key, Some(&self.api_secret))
+            .multipart(folder_form(path)?)
+            .send()
+            .await
+            .map_err(|e| eyre!("Failed to reach Fleek: {}", e))?;
+
+        let status = response.status();
+        let body = response.text().await?;
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(eyre!("Invalid fleek credentials"));
+        }
+        if !status.is_success() {
+            return Err(eyre!("Failed to pin folder: Fleek returned {}: {}", status, body));
+        }
+
+        Ok(PinResult::new(folder_cid(&body)?, base_gateway_url))
+    }
+}
+
+/// Pins folders to a local IPFS node, such as kubo, through its HTTP API
+pub(crate) struct KuboBackend {
+    api_url: String,
+}
+
+/// The response of kubo's `version` endpoint
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct KuboVersion {
+    version: String,
+}
+
+impl KuboBackend {
+    pub(crate) fn new(api_url: &str) -> Self {
+        Self { api_url: api_url.trim_end_matches('/').to_string() }
+    }
+
+    /// Returns the version of the local IPFS node, failing if its daemon isn't running. Like
+    /// every kubo RPC endpoint, `version` only accepts POST requests.
+    pub(crate) async fn version(&self) -> Result<String> {
+        let response =
+            Client::new().post(format!("{}/api/v0/version", self.api_url)).send().await.map_err(
+                |e| {
+                    eyre!(
+                        "Failed to reach the local IPFS node at {}. Is `ipfs daemon` running? {}",
+                        self.api_url,
+                        e
+                    )
+                },
+            )?;
+
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(eyre!("Local IPFS node returned {}: {}", status, body));
+        }
+
+        serde_json::from_str::<KuboVersion>(&body)
+            .map(|version| version.version)
+            .map_err(|e| eyre!("Failed to parse local IPFS node's version: {}", e))
+    }
+}
+
+impl PinningBackend for KuboBackend {
+    async fn pin_folder(&self, path: &Path, base_gateway_url: &str) -> Result<PinResult> {
+        let response = Client::new()
+            .post(format!("{}/api/v0/add", self.api_url))
+            .query(&[("recursive", "true"), ("wrap-with-directory", "true"), ("pin", "true")])
+            .multipart(folder_form(path)?)
+            .send()
+            .await
+            .map_err(|e| eyre!("Failed to reach the local IPFS node: {}", e))?;
+
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(eyre!(
+                "Failed to pin folder: local IPFS node returned {}: {}",
+                status,
+                body
+            ));
+        }
+
+        Ok(PinResult::new(folder_cid(&body)?, base_gateway_url))
+    }
+}
+
+/// Builds the multipart form IPFS `add` APIs expect for a folder. Every directory and file
+/// under the folder is added with its path relative to the folder.
+fn folder_form(path: &Path) -> Result<Form> {
+    let mut form = Form::new();
+    for entry in WalkDir::new(path).min_depth(1).sort_by_file_name() {
+        let entry = entry?;
+        let relative_path = entry.path().strip_prefix(path)?.to_string_lossy().to_string();
+        if entry.file_type().is_dir() {
+            form = form.part(
+                "file",
+                Part::bytes(vec![]).file_name(relative_path).mime_str("application/x-directory")?,
+            );
+        } else if entry.file_type().is_file() {
+            let contents = std::fs::read(entry.path())?;
+            form = form.part("file", Part::bytes(contents).file_name(relative_path));
+        }
+    }
+
+    Ok(form)
+}
+
+/// Extracts the folder's CID from an IPFS `add` response, which has one JSON object per line.
+/// The wrapping directory is the entry with an empty name.
+fn folder_cid(body: &str) -> Result<String> {
+    body.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str::<AddEntry>)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| eyre!("Failed to parse IPFS add response: {}", e))?
+        .into_iter()
+        .find(|entry| entry.name.is_empty())
+        .map(|entry| entry.hash)
+        .ok_or_eyre("IPFS add response did not contain the folder's CID")
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    /// Whether a request has been read in full. Only multipart requests have a body.
+    fn request_complete(request: &[u8]) -> bool {
+        let request = String::from_utf8_lossy(request);
+        match request.split_once("\r\n\r\n") {
+            Some((headers, body)) => {
+                !headers.to_lowercase().contains("content-type: multipart") ||
+                    body.trim_end().ends_with("--")
+            }
+            None => false,
+        }
+    }
+
+    /// Serves one request per response on a local port, in order, returning the address to
+    /// send them to
+    async fn mock_api(responses: Vec<(&'static str, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("failed to bind");
+        let address = listener.local_addr().expect("failed to get address");
+
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().await.expect("failed to accept");
+
+                let mut request = vec![];
+                let mut buf = [0u8; 4096];
+                loop {
+                    let n = stream.read(&mut buf).await.expect("failed to read");
+                    request.extend_from_slice(&buf[..n]);
+                    if n == 0 || request_complete(&request) {
+                        break;
+                    }
+                }
+
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.expect("failed to write");
+            }
+        });
+
+        format!("http://{}", address)
+    }
+
+    /// Serves a single request with the given status and body, returning the base URL of a
+    /// mock Fleek API
+    async fn mock_fleek_api(status: &'static str, body: &'static str) -> String {
+        format!("{}/ipfs/", mock_api(vec![(status, body)]).await)
+    }
+
+    fn group_folder() -> tempdir::TempDir {
+        let dir = tempdir::TempDir::new("shadow-pin").expect("failed to create temp dir");
+        std::fs::write(dir.path().join("info.json"), "{}").expect("failed to write");
+        dir
+    }
+
+    #[test]
+    fn test_folder_cid() {
+        let body = concat!(
+            "{\"Name\":\"info.json\",\"Hash\":\"QmFile\",\"Size\":\"10\"}\n",
+            "{\"Name\":\"\",\"Hash\":\"QmFolder\",\"Size\":\"64\"}\n",
+        );
+        assert_eq!(folder_cid(body).unwrap(), "QmFolder");
+        assert!(folder_cid("{\"Name\":\"info.json\",\"Hash\":\"QmFile\"}").is_err());
+        assert!(folder_cid("not json").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fleek_pin_folder() {
+        let api_url = mock_fleek_api(
+            "200 OK",
+            "{\"Name\":\"info.json\",\"Hash\":\"QmFile\",\"Size\":\"10\"}\n{\"Name\":\"\",\"Hash\":\"QmFolder\",\"Size\":\"64\"}\n",
+        )
+        .await;
+        let folder = group_folder();
+
+        let result = FleekBackend::new("key", "secret")
+            .with_api_url(&api_url)
+            .pin_folder(folder.path(), "https://gateway.example/ipfs/")
+            .await
+            .unwrap();
+
+        assert_eq!(result.cid, "QmFolder");
+        assert_eq!(result.ipfs_url, "https://gateway.example/ipfs/QmFolder/");
+    }
+
+    #[tokio::test]
+    async fn test_fleek_invalid_credentials() {
+        let api_url = mock_fleek_api("401 Unauthorized", "{\"error\":\"unauthorized\"}").await;
+        let folder = group_folder();
+
+        let result = FleekBackend::new("key", "wrong")
+            .with_api_url(&api_url)
+            .pin_folder(folder.path(), "https://gateway.example/ipfs/")
+            .await;
+
+        assert!(result.unwrap_err().to_string().contains("Invalid fleek credentials"));
+    }
+
+    #[tokio::test]
+    async fn test_kubo_version() {
+        let api_url =
+            mock_api(vec![("200 OK", "{\"Version\":\"0.29.0\",\"Commit\":\"\",\"Repo\":\"15\"}")])
+                .await;
+
+        assert_eq!(KuboBackend::new(&api_url).version().await.unwrap(), "0.29.0");
+    }
+
+    #[tokio::test]
+    async fn test_kubo_daemon_not_running() {
+        // bind and drop a listener, so nothing is listening on the port
+        let address = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+
+        let result = KuboBackend::new(&format!("http://{}", address)).version().await;
+        assert!(result.unwrap_err().to_string().contains("Is `ipfs daemon` running?"));
+    }
+
+    #[tokio::test]
+    async fn test_kubo_pin_folder() {
+        let api_url = mock_api(vec![(
+            "200 OK",
+            "{\"Name\":\"info.json\",\"Hash\":\"QmFile\",\"Size\":\"10\"}\n{\"Name\":\"\",\"Hash\":\"QmFolder\",\"Size\":\"64\"}\n",
+        )])
+        .await;
+        let folder = group_folder();
+
+        let result = KuboBackend::new(&api_url)
+            .pin_folder(folder.path(), "http://127.0.0.1:8080/ipfs/")
+            .await
+            .unwrap();
+
+        assert_eq!(result.cid, "QmFolder");
+        assert_eq!(result.ipfs_url, "http://127.0.0.1:8080/ipfs/QmFolder/");
+    }
 }
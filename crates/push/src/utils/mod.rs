@@ -4,3 +4,5 @@ pub(crate) mod eas;
 pub(crate) mod http;
 /// IPFS utilities used by the `push` subcommand
 pub(crate) mod ipfs;
+/// Gnosis Safe utilities
+pub(crate) mod safe;
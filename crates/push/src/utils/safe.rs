@@ -0,0 +1,554 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use alloy::{
+    hex::FromHex,
+    network::EthereumWallet,
+    primitives::Signature,
+    providers::{Provider, ProviderBuilder},
+    signers::Signer,
+    sol,
+    sol_types::{eip712_domain, Eip712Domain, SolStruct},
+};
+use clap::ValueEnum;
+use eyre::{bail, eyre, OptionExt, Result};
+use revm::primitives::{Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, trace, warn};
+
+use crate::{
+    eas::{get_signer, prompt, AttestationMode},
+    SignerType, SupportedChains,
+};
+
+// Minimal interface of a Gnosis Safe, used to build and execute safe transactions.
+sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface ISafe {
+        function nonce() external view returns (uint256);
+        function getThreshold() external view returns (uint256);
+        function isOwner(address owner) external view returns (bool);
+        function getOwners() external view returns (address[] memory);
+        function getTransactionHash(
+            address to,
+            uint256 value,
+            bytes calldata data,
+            uint8 operation,
+            uint256 safeTxGas,
+            uint256 baseGas,
+            uint256 gasPrice,
+            address gasToken,
+            address refundReceiver,
+            uint256 _nonce
+        ) external view returns (bytes32);
+        function execTransaction(
+            address to,
+            uint256 value,
+            bytes calldata data,
+            uint8 operation,
+            uint256 safeTxGas,
+            uint256 baseGas,
+            uint256 gasPrice,
+            address gasToken,
+            address refundReceiver,
+            bytes memory signatures
+        ) external payable returns (bool success);
+    }
+);
+
+// The EIP-712 struct which Safe owners sign to confirm a transaction.
+sol!(
+    #[allow(missing_docs)]
+    #[derive(Debug)]
+    struct SafeTx {
+        address to;
+        uint256 value;
+        bytes data;
+        uint8 operation;
+        uint256 safeTxGas;
+        uint256 baseGas;
+        uint256 gasPrice;
+        address gasToken;
+        address refundReceiver;
+        uint256 nonce;
+    }
+);
+
+/// How often to poll the Safe Transaction Service for new confirmations
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// A Safe transaction, as accepted by the Safe Transaction Service
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SafeTransactionData {
+    /// The target of the transaction
+    pub(crate) to: Address,
+    /// The value of the transaction, in wei
+    pub(crate) value: String,
+    /// The calldata of the transaction
+    pub(crate) data: Bytes,
+    /// 0 for `CALL`, 1 for `DELEGATECALL`
+    pub(crate) operation: u8,
+    /// Gas that should be used for the safe transaction
+    pub(crate) safe_tx_gas: String,
+    /// Gas costs that are independent of the transaction execution
+    pub(crate) base_gas: String,
+    /// Gas price used for the refund calculation
+    pub(crate) gas_price: String,
+    /// Token address used for the refund, or zero for ETH
+    pub(crate) gas_token: Address,
+    /// Address of the refund receiver, or zero for `tx.origin`
+    pub(crate) refund_receiver: Address,
+    /// The safe's nonce for this transaction
+    pub(crate) nonce: String,
+}
+
+impl From<&SafeTx> for SafeTransactionData {
+    fn from(tx: &SafeTx) -> Self {
+        Self {
+            to: tx.to,
+            value: tx.value.to_string(),
+            data: tx.data.clone(),
+            operation: tx.operation,
+            safe_tx_gas: tx.safeTxGas.to_string(),
+            base_gas: tx.baseGas.to_string(),
+            gas_price: tx.gasPrice.to_string(),
+            gas_token: tx.gasToken,
+            refund_receiver: tx.refundReceiver,
+            nonce: tx.nonce.to_string(),
+        }
+    }
+}
+
+/// A proposed Safe transaction, as returned by the Safe Transaction Service
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SafeMultisigTransaction {
+    #[serde(default)]
+    confirmations: Vec<SafeConfirmation>,
+    is_executed: bool,
+    transaction_hash: Option<String>,
+}
+
+/// A single owner's confirmation of a Safe transaction
+#[derive(Debug, Clone, Deserialize)]
+struct SafeConfirmation {
+    owner: Address,
+    signature: String,
+}
+
+/// Propose EAS attestations to a Gnosis Safe. One of the safe's owners signs the proposal,
+/// which is then shared with the other owners via the Safe Transaction Service. Unless `no_wait`
+/// is set, waits for the safe's threshold to be reached and broadcasts the transaction.
+pub(crate) async fn safe_attestation(
+    mode: &AttestationMode,
+    creator_address: &Address,
+    chain: &SupportedChains,
+    no_wait: bool,
+) -> Result<Option<String>> {
+    let safe_address: Address =
+        prompt(&format!("Enter the address of your safe ({:#020x}): ", creator_address))?
+            .map(|s| s.parse())
+            .transpose()
+            .map_err(|e| eyre!("invalid safe address: {}", e))?
+            .unwrap_or(*creator_address);
+    if safe_address != *creator_address {
+        bail!(
+            "safe address '{}' does not match creator address '{}'",
+            safe_address,
+            creator_address
+        );
+    }
+
+    let rpc_url = prompt(&format!("Enter an RPC URL for {:?} ({}): ", chain, chain.rpc_url()))?
+        .map(|url| url.parse())
+        .transpose()
+        .map_err(|e| eyre!("invalid RPC URL: {}", e))?
+        .unwrap_or_else(|| chain.rpc_url());
+
+    // one of the safe's owners must sign the proposal
+    let owner_signer_method = prompt("Enter the signer type of a safe owner (private-key): ")?
+        .map(|s| SignerType::from_str(&s, true))
+        .transpose()
+        .map_err(|e| eyre!("invalid signer type: {}", e))?
+        .unwrap_or_default();
+    let owner = get_signer(&owner_signer_method, chain).await?;
+    let owner_address = Signer::address(&owner);
+
+    let provider = ProviderBuilder::new().on_http(rpc_url.clone());
+    let safe = ISafe::new(safe_address, &provider);
+    if !safe.isOwner(owner_address).call().await?._0 {
+        bail!("signer '{}' is not an owner of safe '{}'", owner_address, safe_address);
+    }
+    let owners = safe.getOwners().call().await?._0;
+    let threshold = safe.getThreshold().call().await?._0;
+    let nonce = safe.nonce().call().await?._0;
+    debug!("safe {} has threshold {} and nonce {}", safe_address, threshold, nonce);
+
+    // build the safe transaction wrapping the EAS `attest` or `multiAttest` call
+    let safe_tx = SafeTx {
+        to: chain.eas_address(),
+        value: U256::ZERO,
+        data: mode.calldata(chain)?,
+        operation: 0,
+        safeTxGas: U256::ZERO,
+        baseGas: U256::ZERO,
+        gasPrice: U256::ZERO,
+        gasToken: Address::ZERO,
+        refundReceiver: Address::ZERO,
+        nonce,
+    };
+    trace!("safe transaction: {:#?}", safe_tx);
+
+    // owners sign the transaction as EIP-712 typed data, which hardware wallets support. The
+    // hash must match the safe's own, or their signatures won't be accepted
+    let domain = safe_domain(chain.chain_id(), safe_address);
+    let safe_tx_hash = safe_tx.eip712_signing_hash(&domain);
+    let onchain_safe_tx_hash = safe
+        .getTransactionHash(
+            safe_tx.to,
+            safe_tx.value,
+            safe_tx.data.clone(),
+            safe_tx.operation,
+            safe_tx.safeTxGas,
+            safe_tx.baseGas,
+            safe_tx.gasPrice,
+            safe_tx.gasToken,
+            safe_tx.refundReceiver,
+            safe_tx.nonce,
+        )
+        .call()
+        .await?
+        ._0;
+    if safe_tx_hash != onchain_safe_tx_hash {
+        bail!(
+            "safe transaction hash {} does not match the hash {} computed by safe '{}'. Only Safe v1.3.0 and later are supported",
+            safe_tx_hash,
+            onchain_safe_tx_hash,
+            safe_address
+        );
+    }
+
+    // Prompt the user to confirm the proposal
+    if prompt("You are about to propose an EAS attestation to your safe. Would you like to continue? (y/N): ")?
+        .unwrap_or_else(|| "n".to_string())
+        .as_str() !=
+        "y"
+    {
+        warn!("user skipping EAS attestation");
+        return Ok(None);
+    }
+
+    // sign through the boxed signer itself, so that hardware wallets' typed data support is used
+    let signature = owner.as_ref().sign_safe_tx(&safe_tx, &domain).await?;
+    propose_transaction(
+        chain,
+        &safe_address,
+        &SafeTransactionData::from(&safe_tx),
+        &safe_tx_hash,
+        &owner_address,
+        &signature.as_bytes(),
+    )
+    .await?;
+
+    info!("proposed safe transaction {}", safe_tx_hash);
+    info!(
+        "other owners may approve it at: https://app.safe.global/transactions/tx?safe={}:{}&id=multisig_{}_{}",
+        chain.safe_ui_prefix(),
+        safe_address,
+        safe_address,
+        safe_tx_hash
+    );
+
+    if no_wait {
+        info!("not waiting for confirmations (--no-wait)");
+        return Ok(None);
+    }
+
+    // wait for enough owners to confirm the transaction
+    info!("waiting for {} confirmations...", threshold);
+    let signatures = loop {
+        let proposal = get_transaction(chain, &safe_tx_hash).await?;
+        if proposal.is_executed {
+            info!(
+                "EAS attestation executed by another owner: https://{}/tx/{}",
+                chain.explorer_url(),
+                proposal.transaction_hash.as_deref().unwrap_or_default()
+            );
+            return Ok(proposal.transaction_hash);
+        }
+        let signatures = owner_signatures(&proposal.confirmations, &owners, &safe_tx_hash);
+        if U256::from(signatures.len()) >= threshold {
+            break signatures;
+        }
+
+        debug!("{}/{} confirmations received", signatures.len(), threshold);
+        tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+    };
+
+    // broadcast the safe transaction
+    let provider = ProviderBuilder::new()
+        .with_gas_estimation()
+        .wallet(EthereumWallet::from(owner))
+        .with_chain(chain.into())
+        .on_http(rpc_url);
+    let safe = ISafe::new(safe_address, provider.clone());
+    let tx_nonce = provider.get_transaction_count(owner_address).await?;
+    let exec_call = safe
+        .execTransaction(
+            safe_tx.to,
+            safe_tx.value,
+            safe_tx.data,
+            safe_tx.operation,
+            safe_tx.safeTxGas,
+            safe_tx.baseGas,
+            safe_tx.gasPrice,
+            safe_tx.gasToken,
+            safe_tx.refundReceiver,
+            pack_signatures(&signatures),
+        )
+        .from(owner_address)
+        .nonce(tx_nonce)
+        .chain_id(chain.chain_id());
+
+    let exec_tx_hash = provider.send_transaction(exec_call.into_transaction_request()).await?;
+    info!(
+        "EAS attestation broadcast successfully: https://{}/tx/{}",
+        chain.explorer_url(),
+        exec_tx_hash.tx_hash()
+    );
+
+    Ok(Some(exec_tx_hash.tx_hash().to_string()))
+}
+
+/// The EIP-712 domain which a safe's transactions are hashed and signed under. Safes before
+/// v1.3.0 don't include the chain ID in their domain.
+fn safe_domain(chain_id: u64, safe_address: Address) -> Eip712Domain {
+    eip712_domain! {
+        chain_id: chain_id,
+        verifying_contract: safe_address,
+    }
+}
+
+/// Recovers the signer of a confirmation of `safe_tx_hash`. Owners either sign the hash itself
+/// as EIP-712 typed data, with a `v` of 27 or 28, or with `eth_sign`, which the safe marks by
+/// adding 4 to `v`.
+fn recover_signer(signature: &[u8], safe_tx_hash: &B256) -> Result<Address> {
+    if signature.len() != 65 {
+        bail!("expected a 65 byte signature, got {} bytes", signature.len());
+    }
+
+    let mut signature = signature.to_vec();
+    match signature[64] {
+        27 | 28 => Ok(Signature::try_from(signature.as_slice())?
+            .recover_address_from_prehash(safe_tx_hash)?),
+        31 | 32 => {
+            signature[64] -= 4;
+            Ok(Signature::try_from(signature.as_slice())?.recover_address_from_msg(safe_tx_hash)?)
+        }
+        v => bail!("unsupported signature type (v = {})", v),
+    }
+}
+
+/// Returns the signature of each confirmation made by one of `owners`, keyed by owner address.
+/// Confirmations which don't recover to their owner, or whose owner isn't one of the safe's, are
+/// skipped, and an owner who confirmed more than once is only counted once. Otherwise the safe
+/// rejects the signatures with `GS026`.
+fn owner_signatures(
+    confirmations: &[SafeConfirmation],
+    owners: &[Address],
+    safe_tx_hash: &B256,
+) -> BTreeMap<Address, Bytes> {
+    let mut signatures = BTreeMap::new();
+    for confirmation in confirmations {
+        if !owners.contains(&confirmation.owner) {
+            warn!("ignoring confirmation from {}, who is not an owner", confirmation.owner);
+            continue;
+        }
+
+        let signature = match Bytes::from_hex(&confirmation.signature)
+            .map_err(|e| eyre!("invalid signature: {}", e))
+            .and_then(|signature| Ok((recover_signer(&signature, safe_tx_hash)?, signature)))
+        {
+            Ok((signer, signature)) if signer == confirmation.owner => signature,
+            Ok((signer, _)) => {
+                warn!(
+                    "ignoring confirmation from {}, which was signed by {}",
+                    confirmation.owner, signer
+                );
+                continue;
+            }
+            Err(e) => {
+                warn!("ignoring confirmation from {}: {}", confirmation.owner, e);
+                continue;
+            }
+        };
+        signatures.entry(confirmation.owner).or_insert(signature);
+    }
+
+    signatures
+}
+
+/// Concatenates owner signatures into the `signatures` argument of `execTransaction`, which
+/// must be sorted by owner address
+fn pack_signatures(signatures: &BTreeMap<Address, Bytes>) -> Bytes {
+    Bytes::from(
+        signatures.values().map(|signature| signature.to_vec()).collect::<Vec<_>>().concat(),
+    )
+}
+
+/// Propose a signed transaction to the Safe Transaction Service
+async fn propose_transaction(
+    chain: &SupportedChains,
+    safe_address: &Address,
+    tx: &SafeTransactionData,
+    safe_tx_hash: &B256,
+    sender: &Address,
+    signature: &[u8],
+) -> Result<()> {
+    let mut body = serde_json::to_value(tx)?;
+    let fields = body.as_object_mut().ok_or_eyre("invalid safe transaction")?;
+    fields.insert("contractTransactionHash".to_string(), safe_tx_hash.to_string().into());
+    fields.insert("sender".to_string(), sender.to_string().into());
+    fields.insert("signature".to_string(), format!("0x{}", alloy::hex::encode(signature)).into());
+    fields.insert("origin".to_string(), "shadow-cli".into());
+
+    let response = reqwest::Client::new()
+        .post(format!(
+            "{}/api/v1/safes/{}/multisig-transactions/",
+            chain.safe_transaction_service_url(),
+            safe_address
+        ))
+        .json(&body)
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(eyre!("Failed to propose safe transaction: {}", response.text().await?))
+    }
+}
+
+/// Fetch a proposed transaction from the Safe Transaction Service
+async fn get_transaction(
+    chain: &SupportedChains,
+    safe_tx_hash: &B256,
+) -> Result<SafeMultisigTransaction> {
+    let response = reqwest::get(format!(
+        "{}/api/v1/multisig-transactions/{}/",
+        chain.safe_transaction_service_url(),
+        safe_tx_hash
+    ))
+    .await?;
+
+    if response.status().is_success() {
+        Ok(response.json().await?)
+    } else {
+        Err(eyre!("Failed to fetch safe transaction: {}", response.text().await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::signers::{local::PrivateKeySigner, SignerSync};
+
+    use super::*;
+
+    /// A signer with a fixed private key
+    fn signer(key: u8) -> PrivateKeySigner {
+        PrivateKeySigner::from_bytes(&B256::with_last_byte(key)).expect("valid private key")
+    }
+
+    fn safe_tx() -> SafeTx {
+        SafeTx {
+            to: "0x4200000000000000000000000000000000000021".parse().unwrap(),
+            value: U256::ZERO,
+            data: Bytes::from_hex("0xdeadbeef").unwrap(),
+            operation: 0,
+            safeTxGas: U256::ZERO,
+            baseGas: U256::ZERO,
+            gasPrice: U256::ZERO,
+            gasToken: Address::ZERO,
+            refundReceiver: Address::ZERO,
+            nonce: U256::from(7),
+        }
+    }
+
+    fn confirmation(owner: Address, signature: &[u8]) -> SafeConfirmation {
+        SafeConfirmation { owner, signature: format!("0x{}", alloy::hex::encode(signature)) }
+    }
+
+    #[test]
+    fn test_safe_tx_hash() {
+        let tx = safe_tx();
+        let domain =
+            safe_domain(8453, "0x0000000000000000000000000000000000005afe".parse().unwrap());
+
+        // `SAFE_TX_TYPEHASH` and `DOMAIN_SEPARATOR_TYPEHASH` from the Safe contracts
+        assert_eq!(
+            tx.eip712_type_hash(),
+            B256::from_hex("0xbb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8")
+                .unwrap()
+        );
+        assert_eq!(
+            domain.separator(),
+            B256::from_hex("0x0887efbcab158d0a11aed98a753daffbdba9337ea5fd8584121bdcdef73ab5ed")
+                .unwrap()
+        );
+        assert_eq!(
+            tx.eip712_signing_hash(&domain),
+            B256::from_hex("0x1a78f27ed361e87b6182de32809eaa068adb2feae4d67faa898dd8c17a849206")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_recover_signer() {
+        let owner = signer(1);
+        let safe_tx_hash = safe_tx().eip712_signing_hash(&safe_domain(1, Address::ZERO));
+
+        // EIP-712 signatures are of the hash itself
+        let signature = owner.sign_hash_sync(&safe_tx_hash).unwrap().as_bytes();
+        assert_eq!(recover_signer(&signature, &safe_tx_hash).unwrap(), owner.address());
+
+        // `eth_sign` signatures are of the prefixed hash, with 4 added to v
+        let mut signature = owner.sign_message_sync(safe_tx_hash.as_slice()).unwrap().as_bytes();
+        signature[64] += 4;
+        assert_eq!(recover_signer(&signature, &safe_tx_hash).unwrap(), owner.address());
+
+        // contract signatures aren't supported
+        signature[64] = 0;
+        assert!(recover_signer(&signature, &safe_tx_hash).is_err());
+        assert!(recover_signer(&signature[..64], &safe_tx_hash).is_err());
+    }
+
+    #[test]
+    fn test_pack_signatures() {
+        let safe_tx_hash = safe_tx().eip712_signing_hash(&safe_domain(1, Address::ZERO));
+        let (a, b, c, outsider) = (signer(1), signer(2), signer(3), signer(4));
+        let sign =
+            |signer: &PrivateKeySigner| signer.sign_hash_sync(&safe_tx_hash).unwrap().as_bytes();
+        let owners = [a.address(), b.address(), c.address()];
+
+        let confirmations = [
+            confirmation(b.address(), &sign(&b)),
+            confirmation(a.address(), &sign(&a)),
+            // a duplicate confirmation from an owner
+            confirmation(b.address(), &sign(&b)),
+            // a signer who isn't an owner
+            confirmation(outsider.address(), &sign(&outsider)),
+            // an owner's confirmation signed by someone else
+            confirmation(c.address(), &sign(&outsider)),
+        ];
+        let signatures = owner_signatures(&confirmations, &owners, &safe_tx_hash);
+
+        let mut expected = vec![(a.address(), sign(&a)), (b.address(), sign(&b))];
+        expected.sort_by_key(|(owner, _)| *owner);
+        assert_eq!(
+            signatures.keys().copied().collect::<Vec<_>>(),
+            vec![expected[0].0, expected[1].0]
+        );
+        assert_eq!(pack_signatures(&signatures).to_vec(), [expected[0].1, expected[1].1].concat());
+    }
+}
@@ -0,0 +1,60 @@
+use eyre::{eyre, Result};
+use tracing::{info, warn};
+
+use crate::{
+    http::{get_registry_entry, search_registry},
+    RegistryEntry, RegistryLookupArgs, RegistrySearchArgs,
+};
+
+/// The `registry-lookup` subcommand. Displays a contract group's entry in the logs.xyz registry.
+pub async fn registry_lookup(args: RegistryLookupArgs) -> Result<()> {
+    let response = get_registry_entry(&args.ipfs_cid).await?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&response)?);
+        return Ok(());
+    }
+
+    let entry: RegistryEntry = serde_json::from_value(response)
+        .map_err(|e| eyre!("Failed to parse registry entry: {}", e))?;
+    print_entry(&entry);
+    info!("  gateway: {}{}", args.ipfs_gateway_url, entry.ipfs_cid);
+
+    if args.open {
+        if let Err(e) = open::that(entry.url()) {
+            warn!("failed to open {} in your browser: {}", entry.url(), e);
+        }
+    }
+
+    Ok(())
+}
+
+/// The `registry-search` subcommand. Searches the logs.xyz registry for contract groups.
+pub async fn registry_search(args: RegistrySearchArgs) -> Result<()> {
+    let response = search_registry(&args.query).await?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&response)?);
+        return Ok(());
+    }
+
+    let entries: Vec<RegistryEntry> = serde_json::from_value(response)
+        .map_err(|e| eyre!("Failed to parse registry search results: {}", e))?;
+    info!("found {} contract groups matching '{}'", entries.len(), args.query);
+    for entry in &entries {
+        print_entry(entry);
+    }
+
+    Ok(())
+}
+
+/// Logs a registry entry
+fn print_entry(entry: &RegistryEntry) {
+    info!("{}", entry.display_name.as_deref().unwrap_or("Unnamed Contract Group"));
+    info!("  url: {}", entry.url());
+    info!("  ipfs: ipfs://{}", entry.ipfs_cid);
+    info!("  creator: {}", entry.creator.as_deref().unwrap_or("unknown"));
+    info!("  attestation: {}", entry.attestation_tx_hash.as_deref().unwrap_or("none"));
+    info!("  contracts:");
+    for contract in &entry.contracts {
+        info!("    {} (chain {})", contract.address, contract.chain_id);
+    }
+}
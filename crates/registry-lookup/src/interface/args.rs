@@ -0,0 +1,38 @@
+use clap::Parser;
+
+/// Arguments for the `registry-lookup` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Look up a shadow contract group in the logs.xyz registry")]
+pub struct RegistryLookupArgs {
+    /// The IPFS CID of the contract group to look up.
+    pub ipfs_cid: String,
+
+    /// Whether to open the contract group's logs.xyz page in your browser.
+    #[clap(long)]
+    pub open: bool,
+
+    /// Whether to print the raw registry response as JSON.
+    #[clap(long)]
+    pub json: bool,
+
+    /// Your preferred IPFS gateway, used when displaying the IPFS URL.
+    #[clap(
+        long,
+        required = false,
+        default_value = "https://gateway.pinata.cloud/ipfs/",
+        hide_default_value = true
+    )]
+    pub ipfs_gateway_url: String,
+}
+
+/// Arguments for the `registry-search` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Search the logs.xyz registry for shadow contract groups")]
+pub struct RegistrySearchArgs {
+    /// The search query, e.g. a display name or contract address.
+    pub query: String,
+
+    /// Whether to print the raw registry response as JSON.
+    #[clap(long)]
+    pub json: bool,
+}
@@ -0,0 +1,5 @@
+mod args;
+mod registry;
+
+pub use args::*;
+pub use registry::*;
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// A contract group, as listed in the logs.xyz registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryEntry {
+    /// The IPFS CID of the contract group
+    pub ipfs_cid: String,
+    /// The display name of the contract group
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// The address of the creator of the contract group
+    #[serde(default)]
+    pub creator: Option<String>,
+    /// The contracts in the contract group
+    #[serde(default)]
+    pub contracts: Vec<RegistryContract>,
+    /// The hash of the creator's attestation transaction
+    #[serde(default)]
+    pub attestation_tx_hash: Option<String>,
+}
+
+/// A single contract in a registry entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryContract {
+    /// The address of the contract
+    pub address: String,
+    /// The chain id that the contract is deployed on
+    pub chain_id: u64,
+}
+
+impl RegistryEntry {
+    /// The contract group's page on logs.xyz
+    pub fn url(&self) -> String {
+        format!("https://logs.xyz/contractGroup/{}", self.ipfs_cid)
+    }
+}
@@ -0,0 +1,35 @@
+use eyre::{eyre, Result};
+use serde_json::Value;
+
+/// Fetches the registry entry for the given IPFS CID from `https://logs.xyz/api/contractGroup`.
+pub(crate) async fn get_registry_entry(ipfs_cid: &str) -> Result<Value> {
+    let response = reqwest::Client::new()
+        .get(format!("https://logs.xyz/api/contractGroup/{}", ipfs_cid))
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        Ok(response.json().await?)
+    } else {
+        Err(eyre!(
+            "Failed to look up {} in the logs.xyz registry: {}",
+            ipfs_cid,
+            response.text().await?
+        ))
+    }
+}
+
+/// Searches the logs.xyz registry via `https://logs.xyz/api/search`.
+pub(crate) async fn search_registry(query: &str) -> Result<Value> {
+    let response = reqwest::Client::new()
+        .get("https://logs.xyz/api/search")
+        .query(&[("q", query)])
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        Ok(response.json().await?)
+    } else {
+        Err(eyre!("Failed to search the logs.xyz registry: {}", response.text().await?))
+    }
+}
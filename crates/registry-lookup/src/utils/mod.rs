@@ -0,0 +1,2 @@
+/// HTTP utilities used to query the logs.xyz registry
+pub(crate) mod http;
@@ -0,0 +1,120 @@
+use std::{path::PathBuf, str::FromStr, sync::Arc};
+
+use alloy::{
+    network::AnyNetwork,
+    providers::{Provider, ProviderBuilder},
+    transports::http::reqwest::Url,
+};
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use eyre::{bail, eyre, Result};
+use serde_json::{json, Value};
+use shadow_common::ShadowContractGroupInfo;
+use tokio::net::TcpListener;
+use tracing::{error, info, warn};
+
+use crate::{overrides::ShadowOverrides, RpcProxyArgs};
+
+/// The state shared by the proxy's request handlers
+struct ProxyState {
+    /// The upstream RPC URL
+    upstream: String,
+    client: reqwest::Client,
+    overrides: ShadowOverrides,
+}
+
+/// The `rpc-proxy` subcommand. Serves a JSON-RPC endpoint which forwards requests to the
+/// upstream RPC, but returns shadow bytecode from `eth_getCode` and injects it into `eth_call`
+/// and `eth_estimateGas` as a state override, so any tool pointed at the proxy sees the shadow
+/// contracts.
+pub async fn rpc_proxy(args: RpcProxyArgs) -> Result<()> {
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let mut group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    // compile the group, and load the bytecode of its contracts on the upstream's chain
+    let artifact_path = group_info.prepare(&args.rpc_url).await?;
+    let provider =
+        ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+    let chain_id = provider
+        .get_chain_id()
+        .await
+        .map_err(|e| eyre!("failed to get chain ID from RPC: {}", e))?;
+    let overrides = ShadowOverrides::from_artifacts(&artifact_path, chain_id)?;
+    if overrides.0.is_empty() {
+        bail!("the group has no shadow contracts on chain {}", chain_id);
+    }
+    for address in overrides.0.keys() {
+        info!("serving shadow bytecode for {}", address);
+    }
+
+    let state = Arc::new(ProxyState {
+        upstream: args.rpc_url.clone(),
+        client: reqwest::Client::new(),
+        overrides,
+    });
+    let app = Router::new().route("/", post(handle_request)).with_state(state);
+
+    let listener = TcpListener::bind((args.host.as_str(), args.port))
+        .await
+        .map_err(|e| eyre!("Failed to listen on {}:{}: {}", args.host, args.port, e))?;
+    info!("proxying {} at http://{}", args.rpc_url, listener.local_addr()?);
+    warn!("transactions sent through the proxy execute against the deployed bytecode, not the shadow contracts");
+
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
+/// Handles a single or batched JSON-RPC request
+async fn handle_request(State(state): State<Arc<ProxyState>>, Json(body): Json<Value>) -> Response {
+    let response = match body {
+        Value::Array(requests) => {
+            let mut responses = Vec::with_capacity(requests.len());
+            for request in requests {
+                responses.push(proxy_request(&state, request).await);
+            }
+            Value::Array(responses)
+        }
+        request => proxy_request(&state, request).await,
+    };
+
+    Json(response).into_response()
+}
+
+/// Answers a JSON-RPC request, forwarding it upstream unless it reads a shadow contract's code
+async fn proxy_request(state: &ProxyState, mut request: Value) -> Value {
+    if let Some(response) = state.overrides.rewrite(&mut request) {
+        return response;
+    }
+    if request["method"] == "eth_sendRawTransaction" {
+        warn!("forwarding eth_sendRawTransaction unchanged. it won't execute shadow bytecode");
+    }
+
+    match forward(state, &request).await {
+        Ok(response) => response,
+        Err(e) => {
+            error!("upstream request failed: {}", e);
+            json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": { "code": -32603, "message": format!("upstream request failed: {}", e) }
+            })
+        }
+    }
+}
+
+/// Forwards a JSON-RPC request to the upstream RPC, returning its response
+async fn forward(state: &ProxyState, request: &Value) -> Result<Value> {
+    let response =
+        state.client.post(&state.upstream).json(request).send().await?.error_for_status()?;
+
+    Ok(response.json().await?)
+}
@@ -0,0 +1,22 @@
+use clap::Parser;
+
+/// Arguments for the `rpc-proxy` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Start a local RPC proxy which serves shadow contracts' bytecode")]
+pub struct RpcProxyArgs {
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The upstream RPC URL to forward requests to.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+
+    /// The port to listen on.
+    #[clap(short, long, default_value = "8546")]
+    pub port: u16,
+
+    /// The address to listen on.
+    #[clap(long, default_value = "127.0.0.1")]
+    pub host: String,
+}
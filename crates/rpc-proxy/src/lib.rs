@@ -0,0 +1,9 @@
+//! The `rpc-proxy` subcommand
+
+mod core;
+mod interface;
+mod utils;
+
+pub use core::*;
+pub use interface::*;
+pub(crate) use utils::*;
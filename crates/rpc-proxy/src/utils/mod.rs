@@ -0,0 +1,2 @@
+/// JSON-RPC request rewriting, to inject shadow bytecode
+pub(crate) mod overrides;
@@ -0,0 +1,165 @@
+use std::{collections::HashMap, path::Path};
+
+use alloy::primitives::{hex, Address, Bytes};
+use eyre::{eyre, Result};
+use serde_json::{json, Value};
+
+/// The runtime bytecode of a group's shadow contracts, keyed by address
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ShadowOverrides(pub(crate) HashMap<Address, Bytes>);
+
+impl ShadowOverrides {
+    /// Loads the compiled bytecode of the group's contracts on `chain_id`. Artifacts are laid
+    /// out as `<artifact_path>/<chain id>/<address>/bytecode.hex`.
+    pub(crate) fn from_artifacts(artifact_path: &Path, chain_id: u64) -> Result<Self> {
+        let chain_dir = artifact_path.join(chain_id.to_string());
+        let mut overrides = HashMap::new();
+        if !chain_dir.exists() {
+            return Ok(Self(overrides));
+        }
+
+        for entry in std::fs::read_dir(&chain_dir)? {
+            let contract_dir = entry?.path();
+            let bytecode_path = contract_dir.join("bytecode.hex");
+            if !bytecode_path.exists() {
+                continue;
+            }
+
+            let address = contract_dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.parse::<Address>().ok())
+                .ok_or_else(|| eyre!("{} is not a contract directory", contract_dir.display()))?;
+            let bytecode = std::fs::read_to_string(&bytecode_path)?;
+            overrides.insert(address, hex::decode(bytecode.trim())?.into());
+        }
+
+        Ok(Self(overrides))
+    }
+
+    /// Rewrites a JSON-RPC request so it sees the shadow contracts' bytecode. Returns the
+    /// response if the request can be answered without forwarding it upstream.
+    pub(crate) fn rewrite(&self, request: &mut Value) -> Option<Value> {
+        match request.get("method").and_then(Value::as_str) {
+            Some("eth_getCode") => {
+                let address = request["params"][0].as_str()?.parse::<Address>().ok()?;
+                let code = self.0.get(&address)?;
+                Some(json!({ "jsonrpc": "2.0", "id": request["id"], "result": code }))
+            }
+            Some("eth_call") | Some("eth_estimateGas") => {
+                self.inject_state_overrides(request);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Sets the shadow contracts' code in a call's state override set, which is the third
+    /// parameter of `eth_call` and `eth_estimateGas`. Any other overrides the caller passed are
+    /// kept.
+    fn inject_state_overrides(&self, request: &mut Value) {
+        let Some(params) = request.get_mut("params").and_then(Value::as_array_mut) else {
+            return;
+        };
+        if params.is_empty() {
+            return;
+        }
+
+        // the block is optional, but must be given to pass a state override set
+        if params.len() < 2 {
+            params.push(json!("latest"));
+        }
+        if params.len() < 3 || params[2].is_null() {
+            params.truncate(2);
+            params.push(json!({}));
+        }
+        let Some(state) = params[2].as_object_mut() else {
+            return;
+        };
+
+        for (address, code) in &self.0 {
+            // the caller may have used a differently-cased key for the same address
+            let key = state
+                .keys()
+                .find(|key| key.parse::<Address>().ok() == Some(*address))
+                .cloned()
+                .unwrap_or_else(|| address.to_string());
+            let account = state.entry(key).or_insert_with(|| json!({}));
+            if let Some(account) = account.as_object_mut() {
+                account.insert("code".to_string(), json!(code));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{address, bytes};
+
+    use super::*;
+
+    const SHADOW: Address = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+
+    fn overrides() -> ShadowOverrides {
+        ShadowOverrides(HashMap::from([(SHADOW, bytes!("6080"))]))
+    }
+
+    #[test]
+    fn test_rewrite_get_code() {
+        let mut request = json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "eth_getCode",
+            "params": ["0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", "latest"]
+        });
+        assert_eq!(
+            overrides().rewrite(&mut request),
+            Some(json!({ "jsonrpc": "2.0", "id": 7, "result": "0x6080" }))
+        );
+
+        // other addresses are forwarded
+        let mut request = json!({
+            "jsonrpc": "2.0",
+            "id": 8,
+            "method": "eth_getCode",
+            "params": ["0x0000000000000000000000000000000000000001", "latest"]
+        });
+        assert_eq!(overrides().rewrite(&mut request), None);
+    }
+
+    #[test]
+    fn test_rewrite_call() {
+        let mut request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_call",
+            "params": [{ "to": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", "data": "0x" }]
+        });
+        assert_eq!(overrides().rewrite(&mut request), None);
+        assert_eq!(request["params"][1], json!("latest"));
+        assert_eq!(request["params"][2][SHADOW.to_string()], json!({ "code": "0x6080" }));
+    }
+
+    #[test]
+    fn test_rewrite_call_keeps_caller_overrides() {
+        let mut request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_estimateGas",
+            "params": [
+                { "to": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48" },
+                "0x10",
+                { "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48": { "balance": "0x1" } }
+            ]
+        });
+        overrides().rewrite(&mut request);
+
+        let state = request["params"][2].as_object().unwrap();
+        assert_eq!(state.len(), 1);
+        assert_eq!(
+            state["0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"],
+            json!({ "balance": "0x1", "code": "0x6080" })
+        );
+        assert_eq!(request["params"][1], json!("0x10"));
+    }
+}
@@ -0,0 +1,65 @@
+use std::{path::PathBuf, str::FromStr};
+
+use alloy::json_abi::JsonAbi;
+use eyre::{eyre, Result};
+use shadow_common::{
+    analysis::{default_rules, scan_abi},
+    ShadowContractGroupInfo,
+};
+use tracing::{error, info, warn};
+
+use crate::ScanGroupArgs;
+
+/// The `scan-group` subcommand. Checks the compiled ABI of every contract in a shadow contract
+/// group against the rules in [`shadow_common::analysis`], and prints what they flag.
+pub async fn scan_group(args: ScanGroupArgs) -> Result<()> {
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let artifact_path = group_info.artifact_path();
+
+    let rules = default_rules();
+    let mut findings = vec![];
+    for contract in &group_info.contracts {
+        if args.chain_id.is_some_and(|chain_id| chain_id != contract.chain_id) {
+            continue;
+        }
+
+        let abi_path = contract.output_path(&artifact_path).join("abi.json");
+        if !abi_path.exists() {
+            warn!("skipping {}, which has not been built", contract.address);
+            continue;
+        }
+        let abi: JsonAbi = serde_json::from_str(&std::fs::read_to_string(&abi_path)?)
+            .map_err(|e| eyre!("Failed to parse {}: {}", abi_path.display(), e))?;
+        findings.extend(scan_abi(contract.address, contract.chain_id, &abi, &rules));
+    }
+    findings.sort_by(|a, b| b.severity.cmp(&a.severity));
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&findings)?);
+        return Ok(());
+    }
+    if findings.is_empty() {
+        info!("no findings");
+        return Ok(());
+    }
+
+    for finding in &findings {
+        println!(
+            "[{}] {} ({}) {}: {} ({})",
+            finding.severity,
+            finding.address,
+            finding.chain_id,
+            finding.item,
+            finding.message,
+            finding.rule
+        );
+    }
+    info!("{} findings", findings.len());
+
+    Ok(())
+}
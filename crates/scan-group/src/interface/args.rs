@@ -0,0 +1,18 @@
+use clap::Parser;
+
+/// Arguments for the `scan-group` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Check the ABIs of a shadow contract group against known vulnerability patterns")]
+pub struct ScanGroupArgs {
+    /// Only scan contracts on this chain.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// Print the findings as a JSON array, e.g. for CI.
+    #[clap(long)]
+    pub json: bool,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+}
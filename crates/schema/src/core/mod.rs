@@ -0,0 +1,50 @@
+use eyre::{bail, eyre, Result};
+use schemars::{gen::SchemaSettings, JsonSchema};
+use serde_json::Value;
+use shadow_common::{
+    ShadowContractGroupInfo, ShadowContractInfo, ShadowContractSettings, ShadowContractSource,
+};
+use tracing::{error, info};
+
+use crate::{SchemaArgs, SchemaType};
+
+/// The `schema` subcommand. Prints the JSON schema (draft 7) of a shadow data format, and
+/// optionally validates a file against it.
+pub async fn schema(args: SchemaArgs) -> Result<()> {
+    let schema = match args.schema_type {
+        SchemaType::Info => schema_for::<ShadowContractInfo>()?,
+        SchemaType::Settings => schema_for::<ShadowContractSettings>()?,
+        SchemaType::Source => schema_for::<ShadowContractSource>()?,
+        SchemaType::Group => schema_for::<ShadowContractGroupInfo>()?,
+    };
+
+    let Some(path) = args.validate else {
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    };
+
+    let instance: Value = serde_json::from_str(&std::fs::read_to_string(&path)?)
+        .map_err(|e| eyre!("Failed to parse {}: {}", path, e))?;
+    let compiled = jsonschema::JSONSchema::compile(&schema)
+        .map_err(|e| eyre!("Failed to compile schema: {}", e))?;
+    let errors: Vec<String> = match compiled.validate(&instance) {
+        Ok(()) => vec![],
+        Err(errors) => errors.map(|e| format!("{}: {}", e.instance_path, e)).collect(),
+    };
+
+    if !errors.is_empty() {
+        for e in &errors {
+            error!("{}", e);
+        }
+        bail!("{} does not match the {:?} schema", path, args.schema_type);
+    }
+
+    info!("{} is valid", path);
+    Ok(())
+}
+
+/// Generates the draft 7 JSON schema for `T`
+fn schema_for<T: JsonSchema>() -> Result<Value> {
+    let schema = SchemaSettings::draft07().into_generator().into_root_schema_for::<T>();
+    Ok(serde_json::to_value(schema)?)
+}
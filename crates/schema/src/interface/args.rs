@@ -0,0 +1,27 @@
+use clap::{Parser, ValueEnum};
+
+/// Arguments for the `schema` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Print the JSON schema of a shadow data format")]
+pub struct SchemaArgs {
+    /// The data format to print the schema of.
+    #[clap(value_enum)]
+    pub schema_type: SchemaType,
+
+    /// A JSON file to validate against the schema.
+    #[clap(long, required = false)]
+    pub validate: Option<String>,
+}
+
+/// The data formats which `shadow schema` can describe
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SchemaType {
+    /// A contract's `info.json`
+    Info,
+    /// A contract's `settings.json`
+    Settings,
+    /// A contract's `source.json` or `original.json`
+    Source,
+    /// A contract group's `info.json`
+    Group,
+}
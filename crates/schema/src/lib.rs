@@ -0,0 +1,7 @@
+//! The `schema` subcommand
+
+mod core;
+mod interface;
+
+pub use core::*;
+pub use interface::*;
@@ -0,0 +1,35 @@
+use std::{path::PathBuf, str::FromStr};
+
+use alloy::primitives::Address;
+use eyre::{eyre, Result};
+use shadow_common::ShadowContractGroupInfo;
+use tracing::{error, info, warn};
+
+use crate::SetCreatorArgs;
+
+/// The `set-creator` subcommand. Updates the creator address of a shadow contract group.
+pub async fn set_creator(args: SetCreatorArgs) -> Result<()> {
+    let creator: Address = args.address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(args.group.as_ref().unwrap_or(&args.root))?;
+    let mut group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    let old_creator = group_info.creator.replace(creator);
+    group_info.save()?;
+
+    info!(
+        "updated creator: {} -> {}",
+        old_creator.map(|c| c.to_string()).unwrap_or_else(|| String::from("none")),
+        creator
+    );
+
+    if old_creator.is_some_and(|c| c != creator) {
+        warn!("if this group was already pushed to IPFS, its on-chain attestation still references the old creator. Push the group again to attest with the new creator.");
+    }
+
+    Ok(())
+}
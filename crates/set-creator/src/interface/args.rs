@@ -0,0 +1,17 @@
+use clap::Parser;
+
+/// Arguments for the `set-creator` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Set the creator address of a shadow contract group")]
+pub struct SetCreatorArgs {
+    /// The new creator address.
+    pub address: String,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The path to the shadow contract group. Takes precedence over `--root`.
+    #[clap(long, required = false)]
+    pub group: Option<String>,
+}
@@ -0,0 +1,117 @@
+use std::{path::PathBuf, str::FromStr};
+
+use alloy::{
+    json_abi::{Event, Function, JsonAbi},
+    primitives::{Address, Selector},
+};
+use eyre::{bail, eyre, Result};
+use shadow_common::{ShadowContractGroupInfo, BUILD_ARTIFACTS_HINT};
+use tracing::error;
+
+use crate::ShowAbiArgs;
+
+/// The `show-abi` subcommand. Displays the events and functions in a shadow contract's
+/// compiled ABI.
+pub async fn show_abi(args: ShowAbiArgs) -> Result<()> {
+    let address: Address = args.address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+    let selector = args
+        .selector
+        .as_deref()
+        .map(Selector::from_str)
+        .transpose()
+        .map_err(|e| eyre!("Invalid selector: {}", e))?;
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let contract = group_info.contract(&address, args.chain_id)?;
+
+    let abi_path = group_info
+        .artifact_path()
+        .join(contract.chain_id.to_string())
+        .join(contract.address.to_string().to_lowercase())
+        .join("abi.json");
+    if !abi_path.exists() {
+        bail!("{} has not been built. {}", address, BUILD_ARTIFACTS_HINT);
+    }
+    let abi: JsonAbi = serde_json::from_str(&std::fs::read_to_string(abi_path)?)?;
+
+    let show_events = !args.functions && selector.is_none();
+    let show_functions = !args.events;
+    let events = abi.events().filter(|_| show_events).collect::<Vec<_>>();
+    let functions = abi
+        .functions()
+        .filter(|_| show_functions)
+        .filter(|f| selector.map_or(true, |selector| f.selector() == selector))
+        .collect::<Vec<_>>();
+    if let (Some(selector), true) = (selector, functions.is_empty()) {
+        bail!("{} has no function with selector {}", address, selector);
+    }
+
+    if args.human_readable {
+        for event in &events {
+            println!("{}", event.full_signature());
+        }
+        for function in &functions {
+            println!("{}", function.full_signature());
+        }
+        return Ok(());
+    }
+
+    if show_events {
+        println!("events");
+        for event in &events {
+            print_event(event);
+        }
+        if show_functions {
+            println!();
+        }
+    }
+    if show_functions {
+        println!("functions");
+        for function in &functions {
+            print_function(function);
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints an event's name, topic, and inputs
+fn print_event(event: &Event) {
+    println!(
+        "  {}  {}{}",
+        event.name,
+        event.selector(),
+        if event.anonymous { "  anonymous" } else { "" }
+    );
+    for input in &event.inputs {
+        println!(
+            "    {}{} {}",
+            input.selector_type(),
+            if input.indexed { " indexed" } else { "" },
+            input.name
+        );
+    }
+}
+
+/// Prints a function's name, selector, mutability, inputs, and outputs
+fn print_function(function: &Function) {
+    let state_mutability = format!("{:?}", function.state_mutability).to_lowercase();
+    println!("  {}  {}  {}", function.name, function.selector(), state_mutability);
+    if !function.inputs.is_empty() {
+        println!("    inputs:");
+        for input in &function.inputs {
+            println!("      {} {}", input.selector_type(), input.name);
+        }
+    }
+    if !function.outputs.is_empty() {
+        println!("    outputs:");
+        for output in &function.outputs {
+            println!("      {} {}", output.selector_type(), output.name);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use clap::Parser;
+
+/// Arguments for the `show-abi` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Display the compiled ABI of a shadow contract in a readable format")]
+pub struct ShowAbiArgs {
+    /// The address of the contract.
+    pub address: String,
+
+    /// The chain ID of the contract. Required if the address is tracked on multiple chains.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// Only show functions.
+    #[clap(long, conflicts_with = "events")]
+    pub functions: bool,
+
+    /// Only show events.
+    #[clap(long, conflicts_with = "selector")]
+    pub events: bool,
+
+    /// Only show the function with this 4-byte selector, e.g. `0xa9059cbb`.
+    #[clap(long, required = false)]
+    pub selector: Option<String>,
+
+    /// Print the ABI in the Solidity human-readable format, one signature per line.
+    #[clap(long)]
+    pub human_readable: bool,
+}
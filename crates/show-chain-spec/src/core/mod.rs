@@ -0,0 +1,80 @@
+use alloy::{
+    network::AnyNetwork,
+    primitives::TxHash,
+    providers::{Provider, ProviderBuilder},
+    transports::http::reqwest::Url,
+};
+use eyre::{eyre, OptionExt, Result};
+use shadow_common::env::{get_chain_spec, get_eth_chain_spec_range, ETH_SPEC_TRANSITIONS};
+use tracing::{info, warn};
+
+use crate::ShowChainSpecArgs;
+
+/// The `show-chain-spec` subcommand. Displays the EVM spec that simulations use at a block, and
+/// the range of blocks it's active for.
+pub async fn show_chain_spec(args: ShowChainSpecArgs) -> Result<()> {
+    // ensure args are valid
+    args.validate().map_err(|e| eyre!("Invalid arguments: {}", e))?;
+
+    if args.all {
+        if args.chain_id.is_some_and(|chain_id| chain_id != 1) {
+            warn!("the spec table is only valid for Ethereum mainnet");
+        }
+        for (i, (start, spec)) in ETH_SPEC_TRANSITIONS.iter().enumerate() {
+            let end = ETH_SPEC_TRANSITIONS.get(i + 1).map(|(next, _)| next - 1);
+            println!("{:<16}  {}", format!("{:?}", spec), format_range(*start, end));
+        }
+        return Ok(());
+    }
+
+    let (block_number, chain_id) = match (&args.tx_hash, args.block_number) {
+        (Some(tx_hash), _) => {
+            let tx_hash: TxHash =
+                tx_hash.parse().map_err(|e| eyre!("Invalid transaction hash: {}", e))?;
+            let provider =
+                ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+            let chain_id = match args.chain_id {
+                Some(chain_id) => chain_id,
+                None => provider
+                    .get_chain_id()
+                    .await
+                    .map_err(|e| eyre!("failed to get chain ID from RPC: {}", e))?,
+            };
+
+            info!("fetching transaction details for {}", tx_hash);
+            let block_number = provider
+                .get_transaction_by_hash(tx_hash)
+                .await?
+                .ok_or_eyre("transaction not found")?
+                .block_number
+                .ok_or_eyre("transaction not mined")?;
+            info!("{} was included in block {}", tx_hash, block_number);
+            (block_number, chain_id)
+        }
+        (None, Some(block_number)) => (block_number, args.chain_id.unwrap_or(1)),
+        (None, None) => unreachable!("validated above"),
+    };
+
+    // other chains are always simulated with the latest spec
+    if chain_id != 1 {
+        warn!(
+            "the spec map is only valid for Ethereum mainnet. Simulations on chain {} use the latest spec.",
+            chain_id
+        );
+        println!("{:?}", get_chain_spec(&block_number, &chain_id));
+        return Ok(());
+    }
+
+    let (spec, start, end) = get_eth_chain_spec_range(&block_number);
+    println!("{:?} (blocks {})", spec, format_range(start, end));
+
+    Ok(())
+}
+
+/// Formats an inclusive range of blocks, which is open-ended for the latest spec
+fn format_range(start: u64, end: Option<u64>) -> String {
+    match end {
+        Some(end) => format!("{}..={}", start, end),
+        None => format!("{}..", start),
+    }
+}
@@ -0,0 +1,39 @@
+use clap::Parser;
+use eyre::{bail, Result};
+
+/// Arguments for the `show-chain-spec` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Display the EVM spec used when simulating at a given block")]
+pub struct ShowChainSpecArgs {
+    /// The block number to show the spec of.
+    pub block_number: Option<u64>,
+
+    /// Show the spec of the block this transaction was included in, rather than a block number.
+    #[clap(long, required = false)]
+    pub tx_hash: Option<String>,
+
+    /// Show every Ethereum mainnet spec transition.
+    #[clap(long)]
+    pub all: bool,
+
+    /// The chain ID to show the spec for. If not set, it's fetched from the RPC when
+    /// `--tx-hash` is used, and mainnet is assumed otherwise.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The RPC URL of the chain to fetch the transaction from.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+}
+
+impl ShowChainSpecArgs {
+    /// Validates the configuration arguments.
+    pub fn validate(&self) -> Result<()> {
+        let targets = [self.block_number.is_some(), self.tx_hash.is_some(), self.all];
+        if targets.into_iter().filter(|target| *target).count() != 1 {
+            bail!("exactly one of a block number, --tx-hash or --all must be provided");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+mod args;
+
+pub use args::*;
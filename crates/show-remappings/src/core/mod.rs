@@ -0,0 +1,160 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use alloy::primitives::Address;
+use eyre::{bail, eyre, Result};
+use foundry_compilers::artifacts::Remapping;
+use shadow_common::{ShadowContractGroupInfo, ShadowContractSource};
+use tracing::{error, info, warn};
+
+use crate::ShowRemappingsArgs;
+
+/// A remapping, normalized so that remappings from different files can be compared
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct ResolvedRemapping {
+    /// The remapping's prefix, always ending in `/`
+    name: String,
+    /// The context the remapping applies in, if any
+    context: Option<String>,
+    /// The path the prefix is remapped to, always ending in `/`
+    path: String,
+}
+
+impl ResolvedRemapping {
+    fn new(context: Option<String>, name: &str, path: &str) -> Self {
+        let with_slash = |s: &str| if s.ends_with('/') { s.to_string() } else { format!("{s}/") };
+        Self { name: with_slash(name), context, path: with_slash(path) }
+    }
+}
+
+impl std::fmt::Display for ResolvedRemapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(context) = &self.context {
+            write!(f, "{}:", context)?;
+        }
+        write!(f, "{}={}", self.name, self.path)
+    }
+}
+
+/// The `show-remappings` subcommand. Displays the remappings from a contract's `source.json`,
+/// `foundry.toml` and `remappings.txt`, flagging conflicts between them.
+pub async fn show_remappings(args: ShowRemappingsArgs) -> Result<()> {
+    let address: Address = args.address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let contract_dir = group_info.contract_path(&address, args.chain_id)?;
+
+    // each remapping, along with the files it was found in
+    let mut remappings: BTreeMap<ResolvedRemapping, BTreeSet<&str>> = BTreeMap::new();
+    for remapping in source_remappings(&contract_dir)? {
+        remappings.entry(remapping).or_default().insert("source.json");
+    }
+    for remapping in foundry_config_remappings(&contract_dir)? {
+        remappings.entry(remapping).or_default().insert("foundry.toml");
+    }
+    for remapping in remappings_txt_remappings(&contract_dir)? {
+        remappings.entry(remapping).or_default().insert("remappings.txt");
+    }
+
+    if remappings.is_empty() {
+        info!("{} has no remappings", address);
+        return Ok(());
+    }
+
+    let width = remappings.keys().map(|r| r.to_string().len()).max().unwrap_or(0);
+    let mut missing = 0;
+    for (remapping, sources) in &remappings {
+        println!(
+            "{:<width$}  ({})",
+            remapping.to_string(),
+            sources.iter().copied().collect::<Vec<_>>().join(", ")
+        );
+
+        if args.check && !contract_dir.join(&remapping.path).exists() {
+            warn!("target of {} does not exist", remapping);
+            missing += 1;
+        }
+    }
+
+    // the same prefix remapped to different paths, in the same context
+    let mut targets: BTreeMap<(&str, Option<&str>), Vec<&str>> = BTreeMap::new();
+    for remapping in remappings.keys() {
+        targets
+            .entry((remapping.name.as_str(), remapping.context.as_deref()))
+            .or_default()
+            .push(remapping.path.as_str());
+    }
+    for ((name, _), paths) in targets.iter().filter(|(_, paths)| paths.len() > 1) {
+        warn!("conflicting remappings for {}: {}", name, paths.join(", "));
+    }
+
+    if missing > 0 {
+        bail!("{} remapping targets do not exist", missing);
+    }
+
+    Ok(())
+}
+
+/// The remappings stored in the contract's `source.json`
+fn source_remappings(contract_dir: &Path) -> Result<Vec<ResolvedRemapping>> {
+    let Ok(source) = std::fs::read(contract_dir.join("source.json")) else {
+        return Ok(vec![]);
+    };
+    let source: ShadowContractSource = serde_json::from_slice(&source)?;
+
+    Ok(source
+        .remappings
+        .iter()
+        .map(|r| {
+            ResolvedRemapping::new(r.context.clone(), &r.name, &r.path.original().to_string_lossy())
+        })
+        .collect())
+}
+
+/// The remappings in the default profile of the contract's `foundry.toml`
+fn foundry_config_remappings(contract_dir: &Path) -> Result<Vec<ResolvedRemapping>> {
+    let Ok(config) = std::fs::read_to_string(contract_dir.join("foundry.toml")) else {
+        return Ok(vec![]);
+    };
+    let config: toml::Value = toml::from_str(&config)?;
+
+    config
+        .get("profile")
+        .and_then(|profile| profile.get("default"))
+        .and_then(|profile| profile.get("remappings"))
+        .and_then(|remappings| remappings.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|remapping| remapping.as_str())
+        .map(parse_remapping)
+        .collect()
+}
+
+/// The remappings in the contract's `remappings.txt`
+fn remappings_txt_remappings(contract_dir: &Path) -> Result<Vec<ResolvedRemapping>> {
+    let Ok(remappings) = std::fs::read_to_string(contract_dir.join("remappings.txt")) else {
+        return Ok(vec![]);
+    };
+
+    remappings
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_remapping)
+        .collect()
+}
+
+/// Parses a remapping in `context:name=path` form
+fn parse_remapping(remapping: &str) -> Result<ResolvedRemapping> {
+    let remapping = Remapping::from_str(remapping)
+        .map_err(|e| eyre!("Invalid remapping '{}': {}", remapping, e))?;
+    Ok(ResolvedRemapping::new(remapping.context, &remapping.name, &remapping.path))
+}
@@ -0,0 +1,21 @@
+use clap::Parser;
+
+/// Arguments for the `show-remappings` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Display the remappings used to resolve a shadow contract's imports")]
+pub struct ShowRemappingsArgs {
+    /// The address of the contract.
+    pub address: String,
+
+    /// The chain ID of the contract. Required if the address is tracked on multiple chains.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// Check that each remapping's target path exists on disk.
+    #[clap(long)]
+    pub check: bool,
+}
@@ -0,0 +1,7 @@
+//! The `show-remappings` subcommand
+
+mod core;
+mod interface;
+
+pub use core::*;
+pub use interface::*;
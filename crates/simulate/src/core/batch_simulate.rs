@@ -0,0 +1,214 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+
+use alloy::{json_abi::JsonAbi, primitives::TxHash};
+use eyre::{bail, eyre, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use shadow_common::{
+    abi::get_abis,
+    event::{decode_logs, RawOrDecodedEvent},
+    forge::ensure_forge_installed,
+    ShadowContractGroupInfo,
+};
+use tokio::{sync::Semaphore, task::JoinSet};
+use tracing::{error, info};
+
+use crate::{simulate_prepared, BatchSimulateArgs, SimulateArgs};
+
+/// The result of simulating a single transaction with `batch-simulate`
+#[derive(Debug, Clone, Serialize)]
+struct BatchSimulationResult {
+    tx_hash: TxHash,
+    success: bool,
+    gas_used: Option<u64>,
+    /// The number of events the transaction emitted
+    events: usize,
+    /// The decoded events, with `--verbose`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decoded_events: Option<Vec<Value>>,
+    /// Why the transaction couldn't be simulated, or its events decoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// The `batch-simulate` subcommand. Simulates every transaction listed in a file with shadow
+/// overrides, against a single build of the contract group, and writes a summary of each to a
+/// JSON file.
+pub async fn batch_simulate(args: BatchSimulateArgs) -> Result<()> {
+    // ensure forge is installed on the system
+    ensure_forge_installed()?;
+
+    // blank lines and `#` comments are skipped
+    let input = std::fs::read_to_string(&args.input)
+        .map_err(|e| eyre!("Failed to read {}: {}", args.input, e))?;
+    let tx_hashes = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.parse::<TxHash>().map_err(|e| eyre!("Invalid transaction hash {}: {}", line, e))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if tx_hashes.is_empty() {
+        bail!("no transaction hashes found in {}", args.input);
+    }
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let mut group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    // compile the group once, rather than for every transaction
+    info!("validating shadow contract group at {}", root_dir.display());
+    let artifact_path = group_info.prepare(&args.rpc_url).await?;
+    let abis = Arc::new(get_abis(&artifact_path)?);
+
+    info!("simulating {} transactions, {} at a time", tx_hashes.len(), args.parallelism);
+    let progress = ProgressBar::new(tx_hashes.len() as u64);
+    progress.set_style(ProgressStyle::with_template(
+        "[{elapsed_precise}] {bar:40} {pos}/{len} simulated",
+    )?);
+    progress.enable_steady_tick(Duration::from_millis(100));
+
+    // each simulation makes many RPC requests, so only a few run at once
+    let semaphore = Arc::new(Semaphore::new(args.parallelism.max(1)));
+    let mut tasks = JoinSet::new();
+    for (index, tx_hash) in tx_hashes.iter().enumerate() {
+        let simulate_args = SimulateArgs {
+            chain_id: args.chain_id,
+            ..SimulateArgs::new(tx_hash.to_string(), args.root.clone(), args.rpc_url.clone())
+        };
+        let (semaphore, progress, artifact_path, abis) =
+            (semaphore.clone(), progress.clone(), artifact_path.clone(), abis.clone());
+        let (tx_hash, verbose) = (*tx_hash, args.verbose);
+        tasks.spawn(async move {
+            // the simulation runs in its own task, so a panic only fails this transaction
+            let simulated = tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await?;
+                let result =
+                    simulate_transaction(tx_hash, simulate_args, &artifact_path, &abis, verbose)
+                        .await;
+                Ok::<_, eyre::Report>(result)
+            })
+            .await;
+            progress.inc(1);
+
+            let result = match simulated {
+                Ok(Ok(result)) => result,
+                Ok(Err(e)) => failed_result(tx_hash, &e.to_string()),
+                Err(e) => failed_result(tx_hash, &format!("the simulation panicked: {}", e)),
+            };
+
+            (index, result)
+        });
+    }
+
+    // results are written in the same order as the input
+    let mut results = vec![None; tx_hashes.len()];
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok((index, result)) => results[index] = Some(result),
+            Err(e) => error!("failed to join simulation task: {}", e),
+        }
+    }
+    progress.finish();
+    let results = results.into_iter().flatten().collect::<Vec<_>>();
+
+    std::fs::write(&args.output, serde_json::to_string_pretty(&results)?)?;
+    let succeeded = results.iter().filter(|result| result.success).count();
+    info!(
+        "{} of {} transactions succeeded. wrote results to {}",
+        succeeded,
+        results.len(),
+        args.output
+    );
+
+    Ok(())
+}
+
+/// Simulates a single transaction against the prepared contract group. Failures to simulate or
+/// decode the transaction are recorded in the result's `error`.
+async fn simulate_transaction(
+    tx_hash: TxHash,
+    simulate_args: SimulateArgs,
+    artifact_path: &Path,
+    abis: &[JsonAbi],
+    verbose: bool,
+) -> BatchSimulationResult {
+    let simulated = match simulate_prepared(simulate_args, None, None, artifact_path).await {
+        Ok(Some(simulated)) => simulated,
+        Ok(None) => return failed_result(tx_hash, "the transaction could not be executed"),
+        Err(e) => return failed_result(tx_hash, &e.to_string()),
+    };
+
+    let result = &simulated.executed.result;
+    let (decoded_events, error) = match verbose.then(|| decode_logs(result.logs(), abis)) {
+        Some(Ok(events)) => (Some(events.iter().map(event_to_json).collect()), None),
+        Some(Err(e)) => (None, Some(format!("failed to decode events: {}", e))),
+        None => (None, None),
+    };
+
+    BatchSimulationResult {
+        tx_hash,
+        success: result.is_success(),
+        gas_used: Some(result.gas_used()),
+        events: result.logs().len(),
+        decoded_events,
+        error,
+    }
+}
+
+/// The result of a transaction which couldn't be simulated
+fn failed_result(tx_hash: TxHash, error: &str) -> BatchSimulationResult {
+    BatchSimulationResult {
+        tx_hash,
+        success: false,
+        gas_used: None,
+        events: 0,
+        decoded_events: None,
+        error: Some(error.to_string()),
+    }
+}
+
+/// Formats an event as JSON. Undecodable events have a `null` signature and arguments.
+fn event_to_json(event: &RawOrDecodedEvent) -> Value {
+    let (log, signature, arguments) = match event {
+        RawOrDecodedEvent::Decoded(decoded) => {
+            let (mut indexed, mut body) = (decoded.inner.indexed.iter(), decoded.inner.body.iter());
+            let arguments = decoded
+                .event
+                .inputs
+                .iter()
+                .enumerate()
+                .filter_map(|(i, input)| {
+                    let value = if input.indexed { indexed.next() } else { body.next() }?;
+                    let name = if input.name.is_empty() {
+                        format!("arg{}", i)
+                    } else {
+                        input.name.clone()
+                    };
+                    Some((name, Value::String(format!("{:?}", value))))
+                })
+                .collect::<Map<_, _>>();
+
+            (&decoded.log, Some(decoded.event.signature()), Some(Value::Object(arguments)))
+        }
+        RawOrDecodedEvent::Raw(raw) => (&raw.log, None, None),
+    };
+
+    json!({
+        "address": log.address,
+        "topics": log.topics(),
+        "data": log.data.data,
+        "signature": signature,
+        "arguments": arguments,
+    })
+}
@@ -0,0 +1,140 @@
+use std::{path::PathBuf, str::FromStr};
+
+use alloy::{
+    eips::BlockNumberOrTag,
+    network::AnyNetwork,
+    primitives::{Address, U256},
+    providers::{Provider, ProviderBuilder},
+    sol_types::{SolCall, SolEvent},
+    transports::http::reqwest::Url,
+};
+use eyre::{bail, eyre, OptionExt, Result};
+use revm::{primitives::BlockEnv, EvmBuilder};
+use shadow_common::{
+    abi::get_abis,
+    db::JsonRpcDatabase,
+    env::{get_chain_spec, ReplayBlockEnv},
+    event::decode_logs,
+    forge::ensure_forge_installed,
+    ShadowContractGroupInfo,
+};
+use tracing::{error, info, trace, warn};
+
+use crate::{
+    entry_point::IEntryPoint,
+    evm::{build_sim_env, get_overrides},
+    BundleSimulateArgs, UserOperation,
+};
+
+/// The `bundle-simulate` subcommand. Simulates an ERC-4337 UserOperation being bundled via the
+/// EntryPoint's `handleOps`, with shadow overrides.
+pub async fn bundle_simulate(args: BundleSimulateArgs) -> Result<()> {
+    // ensure forge is installed on the system
+    ensure_forge_installed()?;
+
+    let entry_point: Address =
+        args.entry_point.parse().map_err(|e| eyre!("Invalid --entry-point address: {}", e))?;
+    let user_op: UserOperation = serde_json::from_str(
+        &std::fs::read_to_string(&args.user_op)
+            .map_err(|e| eyre!("Failed to read {}: {}", args.user_op, e))?,
+    )
+    .map_err(|e| eyre!("Failed to parse UserOperation: {}", e))?;
+
+    // the EntryPoint reverts with `AA90 invalid beneficiary` when paying fees to address zero
+    let bundler: Address = match &args.bundler {
+        Some(bundler) => bundler.parse().map_err(|e| eyre!("Invalid --bundler address: {}", e))?,
+        None => user_op.sender,
+    };
+    if bundler.is_zero() {
+        bail!(
+            "--bundler cannot be the zero address, which the EntryPoint rejects as a beneficiary"
+        );
+    }
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let mut group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    // compile the group so we have up-to-date overrides, including any account factory or
+    // paymaster contracts
+    info!("validating shadow contract group at {}", root_dir.display());
+    let artifact_path = group_info.prepare(&args.rpc_url).await?;
+    let overrides = get_overrides(&artifact_path)?;
+    let abis = get_abis(&artifact_path)?;
+    trace!("contract overrides: {:?}", overrides.keys());
+
+    // get a new provider
+    let provider =
+        ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+
+    // get chain ID
+    let chain_id = provider
+        .get_chain_id()
+        .await
+        .map_err(|e| eyre::eyre!("failed to get chain ID from RPC: {}", e))?;
+
+    let block_tag = args.block.map(BlockNumberOrTag::Number).unwrap_or(BlockNumberOrTag::Latest);
+    info!("fetching block details for block {}", block_tag);
+    let block =
+        provider.get_block_by_number(block_tag, false).await?.ok_or_eyre("block not found")?;
+
+    // execute on top of the state after `block`, as if we were in the next block
+    let mut block_env: BlockEnv = ReplayBlockEnv::from(block).with_chain_id(chain_id).into();
+    block_env.number += U256::from(1);
+    let block_number: u64 = block_env.number.try_into()?;
+
+    let calldata =
+        IEntryPoint::handleOpsCall { ops: vec![user_op.clone().into()], beneficiary: bundler }
+            .abi_encode();
+
+    info!("simulating UserOperation from {} via EntryPoint {}", user_op.sender, entry_point);
+    let db = JsonRpcDatabase::try_new(
+        block_env.clone(),
+        chain_id,
+        provider,
+        overrides,
+        Default::default(),
+    )?;
+    let env =
+        build_sim_env(bundler, Some(entry_point), U256::ZERO, calldata.into(), block_env, chain_id);
+    let mut evm = EvmBuilder::default()
+        .with_spec_id(get_chain_spec(&block_number, &chain_id))
+        .with_env(env)
+        .with_db(db)
+        .build();
+
+    let executed =
+        evm.transact_preverified().map_err(|e| eyre!("Failed to simulate bundle: {}", e))?;
+    if !executed.result.is_success() {
+        error!("handleOps failed: {:?}", executed.result);
+        return Ok(());
+    }
+
+    // `handleOps` succeeds even if the UserOperation's execution reverts, so check the
+    // EntryPoint's events for the operation's outcome
+    for log in executed.result.logs().iter().filter(|log| log.address == entry_point) {
+        if let Ok(event) = IEntryPoint::UserOperationEvent::decode_log_data(&log.data, true) {
+            if event.success {
+                info!("UserOperation succeeded, using {} gas", event.actualGasUsed);
+            } else {
+                warn!("UserOperation execution reverted, using {} gas", event.actualGasUsed);
+            }
+        } else if let Ok(event) =
+            IEntryPoint::UserOperationRevertReason::decode_log_data(&log.data, true)
+        {
+            warn!("revert reason: {}", event.revertReason);
+        }
+    }
+
+    let logs = decode_logs(executed.result.logs(), &abis)?;
+    info!(
+        "bundle used {} gas:\n{}",
+        executed.result.gas_used(),
+        logs.into_iter().map(|l| l.to_string()).collect::<Vec<String>>().join("\n")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use std::{collections::HashMap, fmt::Display, path::PathBuf, str::FromStr};
+
+use alloy::{
+    eips::BlockNumberOrTag,
+    hex::FromHex,
+    network::AnyNetwork,
+    primitives::{Address, Bytes, U256},
+    providers::{Provider, ProviderBuilder},
+    transports::http::reqwest::Url,
+};
+use eyre::{eyre, OptionExt, Result};
+use revm::{
+    primitives::{BlockEnv, ExecutionResult},
+    Database, Evm, EvmBuilder,
+};
+use shadow_common::{
+    db::JsonRpcDatabase,
+    env::{get_chain_spec, ReplayBlockEnv},
+    forge::ensure_forge_installed,
+    state::PartialBlockStateDiff,
+    ShadowContractGroupInfo,
+};
+use tracing::{error, info, trace};
+
+use crate::{
+    evm::{build_sim_env, get_overrides},
+    EstimateGasArgs,
+};
+
+/// The `estimate-gas` subcommand. Estimates the gas used by a hypothetical call with shadow
+/// overrides applied.
+pub async fn estimate_gas(args: EstimateGasArgs) -> Result<()> {
+    // ensure forge is installed on the system
+    ensure_forge_installed()?;
+
+    let to: Address = args.to.parse().map_err(|e| eyre!("Invalid --to address: {}", e))?;
+    let from: Address = args.from.parse().map_err(|e| eyre!("Invalid --from address: {}", e))?;
+    let value = U256::from_str(&args.value).map_err(|e| eyre!("Invalid --value: {}", e))?;
+    let calldata =
+        Bytes::from_hex(&args.calldata).map_err(|e| eyre!("Invalid --calldata: {}", e))?;
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let mut group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    // compile the group so we have up-to-date overrides
+    info!("validating shadow contract group at {}", root_dir.display());
+    let artifact_path = group_info.prepare(&args.rpc_url).await?;
+    let overrides = get_overrides(&artifact_path)?;
+    trace!("contract overrides: {:?}", overrides.keys());
+
+    // get a new provider
+    let provider =
+        ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+
+    // get chain ID
+    let chain_id = provider
+        .get_chain_id()
+        .await
+        .map_err(|e| eyre::eyre!("failed to get chain ID from RPC: {}", e))?;
+
+    let block_tag = args.block.map(BlockNumberOrTag::Number).unwrap_or(BlockNumberOrTag::Latest);
+    info!("fetching block details for block {}", block_tag);
+    let block =
+        provider.get_block_by_number(block_tag, false).await?.ok_or_eyre("block not found")?;
+
+    // execute on top of the state after `block`, as if we were in the next block
+    let mut block_env: BlockEnv = ReplayBlockEnv::from(block).with_chain_id(chain_id).into();
+    block_env.number += U256::from(1);
+    let block_number: u64 = block_env.number.try_into()?;
+
+    // the call pays the block's base fee for gas, which revm requires since London. the caller
+    // is given enough ether to pay for it, so it holds its real balance during the call
+    let block_gas_limit = block_env.gas_limit.saturating_to::<u64>();
+    let balance = provider
+        .get_balance(from)
+        .block_id(block_tag.into())
+        .await
+        .map_err(|e| eyre!("failed to get the balance of {}: {}", from, e))?;
+    let caller_state = PartialBlockStateDiff {
+        balance: Some(balance + U256::from(block_gas_limit) * block_env.basefee),
+        ..Default::default()
+    };
+
+    let db = JsonRpcDatabase::try_new(
+        block_env.clone(),
+        chain_id,
+        provider,
+        overrides,
+        HashMap::from([(from, caller_state)]),
+    )?;
+    let env = build_sim_env(from, Some(to), value, calldata, block_env, chain_id);
+    let mut evm = EvmBuilder::default()
+        .with_spec_id(get_chain_spec(&block_number, &chain_id))
+        .with_env(env)
+        .with_db(db)
+        .build();
+
+    let gas_estimate = match estimate(&mut evm)? {
+        Ok(gas_estimate) => gas_estimate,
+        Err(result) => {
+            error!("call failed after using {} gas: {:?}", result.gas_used(), result);
+            return Ok(());
+        }
+    };
+
+    info!(
+        "estimated gas: {} ({:.2}% of a {} gas block)",
+        gas_estimate,
+        gas_estimate as f64 / block_gas_limit as f64 * 100.0,
+        block_gas_limit
+    );
+
+    Ok(())
+}
+
+/// Finds the lowest gas limit the call succeeds with, like `eth_estimateGas`, paying the block's
+/// base fee for gas. Returns the call's result if it fails with the block's gas limit.
+fn estimate<DB>(evm: &mut Evm<'_, (), DB>) -> Result<std::result::Result<u64, ExecutionResult>>
+where
+    DB: Database,
+    DB::Error: Display,
+{
+    let block_gas_limit = evm.context.evm.env.block.gas_limit.saturating_to::<u64>();
+    evm.context.evm.env.tx.gas_price = evm.context.evm.env.block.basefee;
+    evm.context.evm.env.tx.gas_limit = block_gas_limit;
+
+    let result = evm.transact().map_err(|e| eyre!("Failed to estimate gas: {}", e))?.result;
+    if !result.is_success() {
+        return Ok(Err(result));
+    }
+
+    // a call may need more gas than it ends up using, e.g. for refunds or the 63/64 rule. no
+    // gas limit below the gas used can succeed, so the search starts just under it
+    let (mut low, mut high) = (result.gas_used().saturating_sub(1), block_gas_limit);
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        evm.context.evm.env.tx.gas_limit = mid;
+        match evm.transact() {
+            Ok(executed) if executed.result.is_success() => high = mid,
+            _ => low = mid,
+        }
+    }
+
+    Ok(Ok(high))
+}
+
+#[cfg(test)]
+mod tests {
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        primitives::{AccountInfo, Bytecode, SpecId},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_estimate_with_block_env() {
+        let from = Address::repeat_byte(0x11);
+        let to = Address::repeat_byte(0x22);
+        let block_env = BlockEnv {
+            number: U256::from(20_000_000),
+            timestamp: U256::from(1_717_000_000),
+            gas_limit: U256::from(30_000_000),
+            basefee: U256::from(10_000_000_000u64),
+            ..Default::default()
+        };
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            from,
+            AccountInfo { balance: U256::from(10).pow(U256::from(18)), ..Default::default() },
+        );
+        // PUSH1 0x01 PUSH1 0x00 SSTORE STOP
+        db.insert_account_info(
+            to,
+            AccountInfo {
+                code: Some(Bytecode::new_raw(Bytes::from_static(&[
+                    0x60, 0x01, 0x60, 0x00, 0x55, 0x00,
+                ]))),
+                ..Default::default()
+            },
+        );
+
+        let env = build_sim_env(from, Some(to), U256::ZERO, Bytes::new(), block_env, 1);
+        let mut evm =
+            EvmBuilder::default().with_spec_id(SpecId::CANCUN).with_env(env).with_db(db).build();
+
+        // 21000 intrinsic gas, 2 PUSH1s and a cold SSTORE from zero
+        assert_eq!(estimate(&mut evm).unwrap().unwrap(), 21_000 + 3 + 3 + 22_100);
+    }
+}
@@ -0,0 +1,122 @@
+use std::{path::PathBuf, process::Command, str::FromStr};
+
+use eyre::{bail, eyre, OptionExt, Result};
+use tracing::{info, warn};
+
+use crate::{
+    foundry_test::{AccountAssertion, ForkTest, RPC_URL_ENV_VAR},
+    simulate_transaction, GenerateFoundryTestArgs, SimulateArgs,
+};
+
+/// The `generate-foundry-test` subcommand. Simulates a transaction with shadow overrides, and
+/// writes a forge test which replays it on a fork with the shadow bytecode etched in, expecting
+/// the same events to be emitted.
+pub async fn generate_foundry_test(args: GenerateFoundryTestArgs) -> Result<()> {
+    let simulated = simulate_transaction(
+        SimulateArgs {
+            chain_id: args.chain_id,
+            ..SimulateArgs::new(
+                args.transaction_hash.clone(),
+                args.root.clone(),
+                args.rpc_url.clone(),
+            )
+        },
+        None,
+        None,
+    )
+    .await?
+    .filter(|simulated| simulated.executed.result.is_success())
+    .ok_or_eyre("the transaction failed to simulate, so a test can't be generated for it")?;
+
+    let tx = &simulated.tx;
+    let to = match tx.to {
+        Some(to) => to,
+        None => bail!("contract creation transactions aren't supported"),
+    };
+    let block_number = tx.block_number.ok_or_eyre("transaction not mined")?;
+
+    // the test is named after the first 4 bytes of the transaction hash
+    let tx_short = hex::encode(&tx.hash[..4]);
+    let contract_name = format!("ShadowForkTest_{}", tx_short);
+
+    let mut overrides = simulated
+        .overrides
+        .iter()
+        .map(|(address, bytecode)| (*address, bytecode.original_bytes()))
+        .collect::<Vec<_>>();
+    overrides.sort_by_key(|(address, _)| *address);
+
+    // assert the final state of the sender, the recipient, and any account whose storage changed
+    let mut assertions = vec![];
+    if args.assert_storage {
+        for (address, account) in &simulated.executed.state {
+            let mut storage = account
+                .storage
+                .iter()
+                .filter(|(_, slot)| slot.is_changed())
+                .map(|(slot, value)| (*slot, value.present_value()))
+                .collect::<Vec<_>>();
+            if storage.is_empty() && *address != tx.from && *address != to {
+                continue;
+            }
+
+            storage.sort_by_key(|(slot, _)| *slot);
+            assertions.push(AccountAssertion {
+                address: *address,
+                balance: account.info.balance,
+                storage,
+            });
+        }
+        assertions.sort_by_key(|assertion| assertion.address);
+    }
+
+    let test = ForkTest {
+        contract_name: contract_name.clone(),
+        transaction_hash: tx.hash,
+        block_number,
+        from: tx.from,
+        to,
+        value: tx.value,
+        calldata: tx.input.clone(),
+        overrides,
+        logs: simulated.executed.result.logs().to_vec(),
+        assertions,
+    };
+
+    // write the test to `test/` in the group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let test_dir = root_dir.join("test");
+    std::fs::create_dir_all(&test_dir)?;
+    let test_path = test_dir.join(format!("{}.t.sol", contract_name));
+    std::fs::write(&test_path, test.render())
+        .map_err(|e| eyre!("Failed to write {}: {}", test_path.display(), e))?;
+    info!("wrote fork test to {}", test_path.display());
+
+    if !args.run {
+        info!(
+            "run it with `{}=<rpc_url> forge test --match-contract {} -v`",
+            RPC_URL_ENV_VAR, contract_name
+        );
+        return Ok(());
+    }
+
+    if !root_dir.join("foundry.toml").exists() {
+        warn!("no foundry.toml found in {}, using forge's defaults", root_dir.display());
+    }
+
+    info!("running {}", contract_name);
+    let status = Command::new("forge")
+        .arg("test")
+        .arg("--match-contract")
+        .arg(&contract_name)
+        .arg("-v")
+        .env(RPC_URL_ENV_VAR, &args.rpc_url)
+        .current_dir(&root_dir)
+        .status()
+        .map_err(|e| eyre!("Failed to run `forge test`: {}", e))?;
+    if !status.success() {
+        bail!("{} failed", contract_name);
+    }
+
+    Ok(())
+}
@@ -1,38 +1,101 @@
-use std::{path::PathBuf, str::FromStr};
+mod batch_simulate;
+mod bundle_simulate;
+mod estimate_gas;
+mod generate_foundry_test;
+mod replay_range;
+mod replay_receipt;
+mod trace;
+
+pub use batch_simulate::*;
+pub use bundle_simulate::*;
+pub use estimate_gas::*;
+pub use generate_foundry_test::*;
+pub use replay_receipt::*;
+pub use trace::*;
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use alloy::{
-    dyn_abi::EventExt,
-    network::AnyNetwork,
-    primitives::TxHash,
+    network::{AnyNetwork, Network},
+    primitives::{utils::parse_units, Address, Bytes, TxHash, U256},
     providers::{ext::TraceApi, Provider, ProviderBuilder},
     rpc::types::trace::parity::TraceType,
     transports::http::reqwest::Url,
 };
-use eyre::{eyre, OptionExt, Result};
-use revm::EvmBuilder;
+use eyre::{bail, eyre, OptionExt, Result};
+use revm::{
+    inspector_handle_register,
+    primitives::{Bytecode, Env, ExecutionResult, ResultAndState},
+    EvmBuilder,
+};
 use shadow_common::{
+    abi::get_abis,
     db::JsonRpcDatabase,
     env::{get_chain_spec, ReplayBlockEnv},
+    event::decode_logs,
     forge::ensure_forge_installed,
+    rpc_cache::RpcCache,
     ShadowContractGroupInfo,
 };
-use tracing::{error, info, trace};
+use tracing::{error, info, trace, warn};
 
 use crate::{
-    event::{get_abis, try_get_event_abi, FullDecodedEvent, FullRawEvent, RawOrDecodedEvent},
-    evm::{build_sim_env, build_state_diff, get_overrides},
-    SimulateArgs,
+    access_list::load_access_list,
+    event_filter::EventFilter,
+    evm::{
+        apply_transaction_state_diff, build_sim_env, build_state_diff, encode_calldata,
+        get_overrides,
+    },
+    inspector::SimulationInspector,
+    trace::{format_call_tree, Eip3155TraceInspector},
+    SimulateArgs, TraceFormat,
 };
 
+/// A transaction which was simulated with shadow overrides
+pub(crate) struct SimulatedTransaction {
+    /// The simulated transaction
+    pub(crate) tx: <AnyNetwork as Network>::TransactionResponse,
+    /// The shadow bytecode the transaction was simulated with
+    pub(crate) overrides: HashMap<Address, Bytecode>,
+    /// The result of the simulation, and the state it left behind
+    pub(crate) executed: ResultAndState,
+}
+
 /// The `simulate` subcommand. Simulates a transaction with shadow overrides.
 pub async fn simulate(args: SimulateArgs) -> Result<()> {
+    if let Some(range) = args.replay_range.clone() {
+        return replay_range::replay_range(args, &range).await;
+    }
+
+    simulate_transaction(args, None, None).await.map(|_| ())
+}
+
+/// Simulates a transaction with shadow overrides, returning its result. None if the transaction
+/// couldn't be executed.
+pub async fn simulate_result(args: SimulateArgs) -> Result<Option<ExecutionResult>> {
+    Ok(simulate_transaction(args, None, None).await?.map(|simulated| simulated.executed.result))
+}
+
+/// Simulates a transaction with shadow overrides. If the transaction's details are already
+/// known, they're used instead of being fetched from the RPC. If a trace format is given, the
+/// transaction's trace is printed in that format. Returns the simulation, unless the
+/// transaction couldn't be executed.
+pub(crate) async fn simulate_transaction(
+    args: SimulateArgs,
+    tx: Option<<AnyNetwork as Network>::TransactionResponse>,
+    trace_format: Option<TraceFormat>,
+) -> Result<Option<SimulatedTransaction>> {
     // ensure forge is installed on the system
     ensure_forge_installed()?;
 
     // ensure args are valid
     args.validate().map_err(|e| eyre!("Invalid arguments: {}", e))?;
-    let tx_hash: TxHash =
-        args.transaction_hash.parse().map_err(|e| eyre!("Invalid transaction hash: {}", e))?;
 
     // root dir must be a shadow contract group
     let root_dir = PathBuf::from_str(&args.root)?;
@@ -45,20 +108,130 @@ pub async fn simulate(args: SimulateArgs) -> Result<()> {
     info!("validating shadow contract group at {}", root_dir.display());
     let artifact_path = group_info.prepare(&args.rpc_url).await?;
 
+    simulate_prepared(args, tx, trace_format, &artifact_path).await
+}
+
+/// By default a simulated transaction is free. If fees are overridden, pays for gas like the
+/// original transaction would, so gas price dependent logic behaves as it would on-chain. EIP-1559
+/// fees not given on the command line are taken from the original transaction.
+pub(crate) fn apply_fee_overrides(
+    args: &SimulateArgs,
+    env: &mut Env,
+    tx: &<AnyNetwork as Network>::TransactionResponse,
+) -> Result<()> {
+    if args.base_fee.is_none() &&
+        args.priority_fee.is_none() &&
+        args.gas_price.is_none() &&
+        args.max_fee.is_none() &&
+        args.max_priority_fee.is_none()
+    {
+        return Ok(());
+    }
+
+    let parse_gwei = |value: &str, name: &str| -> Result<U256> {
+        Ok(parse_units(value, "gwei").map_err(|e| eyre!("Invalid {}: {}", name, e))?.into())
+    };
+
+    if let Some(base_fee) = &args.base_fee {
+        env.block.basefee = parse_gwei(base_fee, "base fee")?;
+    }
+    if let Some(gas_price) = &args.gas_price {
+        env.tx.gas_price = parse_gwei(gas_price, "gas price")?;
+    } else if args.max_fee.is_some() || args.max_priority_fee.is_some() {
+        // legacy transactions pay their whole gas price, so it's both their max and priority fee
+        let original_max_fee = tx.max_fee_per_gas.or(tx.gas_price).unwrap_or_default();
+        let original_max_priority_fee =
+            tx.max_priority_fee_per_gas.or(tx.gas_price).unwrap_or_default();
+        let max_fee = match &args.max_fee {
+            Some(max_fee) => parse_gwei(max_fee, "max fee")?,
+            None => U256::from(original_max_fee),
+        };
+        let max_priority_fee = match &args.max_priority_fee {
+            Some(max_priority_fee) => parse_gwei(max_priority_fee, "max priority fee")?,
+            None => U256::from(original_max_priority_fee),
+        };
+        if max_priority_fee > max_fee {
+            bail!("the max priority fee {} is above the max fee {}", max_priority_fee, max_fee);
+        }
+        if max_fee < env.block.basefee {
+            bail!("the max fee {} is below the block's base fee {}", max_fee, env.block.basefee);
+        }
+
+        // the EVM pays, and reports through GASPRICE, min(max_fee, base_fee + max_priority_fee)
+        env.tx.gas_price = max_fee;
+        env.tx.gas_priority_fee = Some(max_priority_fee);
+    } else {
+        let priority_fee = match &args.priority_fee {
+            Some(priority_fee) => parse_gwei(priority_fee, "priority fee")?,
+            None => U256::ZERO,
+        };
+        env.tx.gas_price = env.block.basefee + priority_fee;
+    }
+    env.tx.gas_limit = tx.gas.try_into()?;
+    info!("simulating with a gas price of {} wei", env.effective_gas_price());
+
+    Ok(())
+}
+
+/// Replaces the transaction's calldata if `--calldata-override` or `--calldata-abi-encode` were
+/// given
+pub(crate) fn apply_calldata_overrides(args: &SimulateArgs, env: &mut Env) -> Result<()> {
+    let calldata = if let Some(calldata) = &args.calldata_override {
+        Bytes::from_str(calldata).map_err(|e| eyre!("Invalid calldata override: {}", e))?
+    } else if let Some((signature, call_args)) = args.calldata_abi_encode.split_first() {
+        encode_calldata(signature, call_args)?
+    } else {
+        return Ok(());
+    };
+
+    warn!(
+        "simulating with overridden calldata {}. this is no longer a replay of the original transaction.",
+        calldata
+    );
+    env.tx.data = calldata;
+
+    Ok(())
+}
+
+/// Same as [`simulate_transaction`], but with the shadow contract group already compiled to
+/// `artifact_path`, so many transactions can be simulated against a single build.
+pub(crate) async fn simulate_prepared(
+    args: SimulateArgs,
+    tx: Option<<AnyNetwork as Network>::TransactionResponse>,
+    trace_format: Option<TraceFormat>,
+    artifact_path: &Path,
+) -> Result<Option<SimulatedTransaction>> {
+    let tx_hash: TxHash =
+        args.transaction_hash.parse().map_err(|e| eyre!("Invalid transaction hash: {}", e))?;
+    let event_filter = EventFilter::new(&args.event_filter, &args.address_filter)?;
+
     // get a new provider
     let provider =
         ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
 
-    // get chain ID
-    let chain_id = provider
-        .get_chain_id()
-        .await
-        .map_err(|e| eyre::eyre!("failed to get chain ID from RPC: {}", e))?;
+    // get chain ID, unless it was provided
+    let chain_id = match args.chain_id {
+        Some(chain_id) => chain_id,
+        None => provider
+            .get_chain_id()
+            .await
+            .map_err(|e| eyre::eyre!("failed to get chain ID from RPC: {}", e))?,
+    };
 
-    info!("fetching transaction details for {}", tx_hash);
-    let tx =
-        provider.get_transaction_by_hash(tx_hash).await?.ok_or_eyre("transaction not found")?;
-    let block_number = tx.block_number.ok_or_eyre("transaction not mined")?;
+    let tx = match tx {
+        Some(tx) => tx,
+        None => {
+            info!("fetching transaction details for {}", tx_hash);
+            provider.get_transaction_by_hash(tx_hash).await?.ok_or_eyre("transaction not found")?
+        }
+    };
+    let block_number = match args.at_block {
+        Some(at_block) => {
+            info!("simulating in block {} instead of the transaction's block", at_block);
+            at_block
+        }
+        None => tx.block_number.ok_or_eyre("transaction not mined")?,
+    };
 
     info!("fetching block details for block {}", block_number);
     let block = provider
@@ -66,82 +239,311 @@ pub async fn simulate(args: SimulateArgs) -> Result<()> {
         .await?
         .ok_or_eyre("block not found")?;
 
-    info!("fetching block trace for block {}", block_number);
-    let block_trace = provider
-        .trace_replay_block_transactions(
-            block_number.into(),
-            &[TraceType::StateDiff, TraceType::Trace],
-        )
-        .await?;
+    // when replaying the transaction in its own block, the transactions before it in the block
+    // are applied first. in a different block it runs against the state at the block's start
+    let mut partial_block_state_diff = match args.at_block {
+        Some(_) => HashMap::new(),
+        None => {
+            info!("fetching block trace for block {}", block_number);
+            let block_trace = provider
+                .trace_replay_block_transactions(
+                    block_number.into(),
+                    &[TraceType::StateDiff, TraceType::Trace],
+                )
+                .await?;
+
+            build_state_diff(block_trace, tx_hash)?
+        }
+    };
+
+    // apply the state changes of any setup transactions on top of the block's state. these take
+    // precedence over the block's own changes, since they were explicitly requested
+    for pre_state_tx_hash in &args.pre_state_txs {
+        let pre_state_tx_hash: TxHash = pre_state_tx_hash
+            .parse()
+            .map_err(|e| eyre!("Invalid pre-state transaction hash: {}", e))?;
+        let pre_state_tx = provider
+            .get_transaction_by_hash(pre_state_tx_hash)
+            .await?
+            .ok_or_eyre(format!("pre-state transaction {} not found", pre_state_tx_hash))?;
+        let pre_state_block_number = pre_state_tx
+            .block_number
+            .ok_or_eyre(format!("pre-state transaction {} not mined", pre_state_tx_hash))?;
+        if pre_state_block_number > block_number {
+            return Err(eyre!(
+                "pre-state transaction {} is in block {}, after the simulated transaction's block {}",
+                pre_state_tx_hash,
+                pre_state_block_number,
+                block_number
+            ));
+        }
+
+        info!("applying state changes from pre-state transaction {}", pre_state_tx_hash);
+        let pre_state_block_trace = provider
+            .trace_replay_block_transactions(pre_state_block_number.into(), &[TraceType::StateDiff])
+            .await?;
+        apply_transaction_state_diff(
+            &mut partial_block_state_diff,
+            pre_state_block_trace,
+            pre_state_tx_hash,
+        )?;
+    }
 
-    let partial_block_state_diff = build_state_diff(block_trace, tx_hash)?;
-    let overrides = get_overrides(&artifact_path)?;
-    let abis = get_abis(&artifact_path)?;
+    let overrides = get_overrides(artifact_path)?;
+    let abis = get_abis(artifact_path)?;
 
     trace!("contract overrides: {:?}", overrides.keys());
     info!("replaying transaction {}", tx_hash);
 
     let start_time = std::time::Instant::now();
-    let block_env = ReplayBlockEnv::from(block);
+    let mut block_env = ReplayBlockEnv::from(block).with_chain_id(chain_id);
+    if let Some(coinbase) = &args.coinbase {
+        let coinbase = coinbase.parse().map_err(|e| eyre!("Invalid coinbase: {}", e))?;
+        info!("simulating with a coinbase of {}", coinbase);
+        block_env = block_env.with_coinbase(coinbase);
+    }
     let db = JsonRpcDatabase::try_new(
         block_env.clone().into(),
+        chain_id,
         provider,
-        overrides,
+        overrides.clone(),
         partial_block_state_diff,
-    )?;
-    let env = build_sim_env(tx.from, tx.to, tx.value, tx.input.clone(), block_env.into());
+    )?
+    .with_rpc_cache(RpcCache::load(chain_id, block_number, args.json_rpc_cache_mode)?);
+    let mut env =
+        build_sim_env(tx.from, tx.to, tx.value, tx.input.clone(), block_env.into(), chain_id);
+
+    apply_fee_overrides(&args, &mut env, &tx)?;
+    apply_calldata_overrides(&args, &mut env)?;
+
+    // warm the accounts and storage slots in the access list, if one was provided
+    if let Some(path) = &args.apply_access_list {
+        env.tx.access_list = load_access_list(path)?.0;
+        info!("applying access list with {} accounts from {}", env.tx.access_list.len(), path);
+    }
+
+    let mut inspector =
+        SimulationInspector::new(trace_format == Some(TraceFormat::Geth), args.gas_report);
+    if let Some(path) = &args.eip3155_trace {
+        let file = File::create(path)
+            .map_err(|e| eyre!("failed to create EIP-3155 trace file {}: {}", path, e))?;
+        inspector.eip3155_trace = Some(Eip3155TraceInspector::new(
+            BufWriter::new(file),
+            args.trace_memory,
+            args.trace_stack,
+        ));
+    }
+
     let mut evm = EvmBuilder::default()
         .with_spec_id(get_chain_spec(&block_number, &chain_id))
         .with_env(env)
         .with_db(db)
+        .with_external_context(inspector)
+        .append_handler_register(inspector_handle_register)
         .build();
 
-    match evm.transact_preverified() {
+    let result = evm.transact_preverified();
+    evm.context.evm.db.save_rpc_cache()?;
+    match result {
         Ok(executed) => {
+            // the trace and gas report are printed even if the transaction failed, since that's
+            // often why it's being inspected
+            match trace_format {
+                Some(TraceFormat::Human) => {
+                    println!("{}", format_call_tree(evm.context.external.calls.traces()))
+                }
+                Some(TraceFormat::Parity) => {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(evm.context.external.calls.traces())?
+                    )
+                }
+                Some(TraceFormat::Geth) => {
+                    if let Some(struct_logs) = evm.context.external.struct_logs.take() {
+                        let trace = struct_logs.into_trace(
+                            executed.result.gas_used(),
+                            !executed.result.is_success(),
+                            executed.result.output().map(|o| o.as_ref()).unwrap_or_default(),
+                        );
+                        println!("{}", serde_json::to_string_pretty(&trace)?);
+                    }
+                }
+                None => {}
+            }
+            if let Some(gas_report) = &evm.context.external.gas_report {
+                println!("{}", gas_report.render(executed.result.gas_used(), &abis, args.top));
+            }
+            if let (Some(eip3155_trace), Some(path)) =
+                (&mut evm.context.external.eip3155_trace, &args.eip3155_trace)
+            {
+                eip3155_trace
+                    .finish(&executed.result)
+                    .map_err(|e| eyre!("failed to write EIP-3155 trace to {}: {}", path, e))?;
+                info!("wrote EIP-3155 trace to {}", path);
+            }
+
             if !executed.result.is_success() {
                 error!("transaction failed: {:?}", executed.result);
-                return Ok(());
+                return Ok(Some(SimulatedTransaction { tx, overrides, executed }));
+            }
+            info!(
+                "transaction executed in {:?}, using {} gas",
+                start_time.elapsed(),
+                executed.result.gas_used()
+            );
+
+            if args.generate_access_list {
+                let access_list = evm.context.external.access_list.access_list();
+                info!("generated access list with {} accounts", access_list.len());
+                println!("{}", serde_json::to_string(&access_list)?);
             }
-            info!("transaction executed in {:?}", start_time.elapsed());
-
-            let logs = executed
-                .result
-                .logs()
-                .iter()
-                .enumerate()
-                .map(|(transaction_log_index, log)| {
-                    let event_selector =
-                        log.topics().first().cloned().ok_or_eyre("cannot decode anonymous log")?;
-
-                    let events = try_get_event_abi(&event_selector, &abis);
-
-                    for event in events {
-                        if let Ok(decoded) = event.decode_log(log, true) {
-                            return Ok(RawOrDecodedEvent::Decoded(FullDecodedEvent {
-                                inner: decoded,
-                                event,
-                                log: log.clone(),
-                                transaction_log_index,
-                            }));
-                        }
-                    }
 
-                    Ok::<_, eyre::Report>(RawOrDecodedEvent::Raw(FullRawEvent {
-                        log: log.clone(),
-                        transaction_log_index,
-                    }))
-                })
-                .collect::<Result<Vec<_>, _>>()?;
+            // every event is decoded, but only those matching the filters are printed
+            let mut logs = decode_logs(executed.result.logs(), &abis)?;
+            if event_filter.is_active() {
+                let emitted = logs.len();
+                logs.retain(|log| event_filter.matches(log));
+                info!("showing {} of {} events matching the filters", logs.len(), emitted);
+            }
 
             info!(
                 "transaction succeeded:\n{}",
                 logs.into_iter().map(|l| l.to_string()).collect::<Vec<String>>().join("\n")
-            )
+            );
+
+            Ok(Some(SimulatedTransaction { tx, overrides, executed }))
         }
         Err(e) => {
             error!("Failed to simulate transaction: {}", e);
+            Ok(None)
         }
-    };
+    }
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use alloy::{rpc::types::Transaction, serde::WithOtherFields};
+
+    use super::*;
+
+    /// One gwei, in wei
+    const GWEI: u128 = 1_000_000_000;
+
+    fn args() -> SimulateArgs {
+        SimulateArgs::new(String::new(), ".".to_string(), "http://localhost:8545".to_string())
+    }
+
+    fn gwei(value: u128) -> U256 {
+        U256::from(value * GWEI)
+    }
+
+    fn env_with_basefee(basefee: u128) -> Env {
+        let mut env = Env::default();
+        env.block.basefee = gwei(basefee);
+        env
+    }
+
+    /// An EIP-1559 transaction with a max fee of 30 gwei and a max priority fee of 2 gwei
+    fn eip1559_tx() -> WithOtherFields<Transaction> {
+        WithOtherFields::new(Transaction {
+            gas: 100_000,
+            max_fee_per_gas: Some(30 * GWEI),
+            max_priority_fee_per_gas: Some(2 * GWEI),
+            ..Default::default()
+        })
+    }
+
+    /// A legacy transaction with a gas price of 20 gwei
+    fn legacy_tx() -> WithOtherFields<Transaction> {
+        WithOtherFields::new(Transaction {
+            gas: 21_000,
+            gas_price: Some(20 * GWEI),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_apply_fee_overrides_none() {
+        let mut env = env_with_basefee(10);
+        apply_fee_overrides(&args(), &mut env, &eip1559_tx()).unwrap();
+
+        // without overrides, the transaction is free
+        assert_eq!(env.tx.gas_price, U256::ZERO);
+        assert_eq!(env.tx.gas_priority_fee, None);
+    }
+
+    #[test]
+    fn test_apply_fee_overrides_original_fees() {
+        // the max priority fee isn't overridden, so it's taken from the original transaction
+        let args = SimulateArgs { max_fee: Some("40".to_string()), ..args() };
+        let mut env = env_with_basefee(10);
+        apply_fee_overrides(&args, &mut env, &eip1559_tx()).unwrap();
+        assert_eq!(env.tx.gas_price, gwei(40));
+        assert_eq!(env.tx.gas_priority_fee, Some(gwei(2)));
+        assert_eq!(env.tx.gas_limit, 100_000);
+
+        // and the max fee, likewise
+        let args = SimulateArgs { max_priority_fee: Some("1".to_string()), ..args() };
+        let mut env = env_with_basefee(10);
+        apply_fee_overrides(&args, &mut env, &eip1559_tx()).unwrap();
+        assert_eq!(env.tx.gas_price, gwei(30));
+        assert_eq!(env.tx.gas_priority_fee, Some(gwei(1)));
+
+        // legacy transactions' gas price is both their max fee and max priority fee
+        let mut env = env_with_basefee(10);
+        apply_fee_overrides(&args, &mut env, &legacy_tx()).unwrap();
+        assert_eq!(env.tx.gas_price, gwei(20));
+        assert_eq!(env.tx.gas_priority_fee, Some(gwei(1)));
+        assert_eq!(env.tx.gas_limit, 21_000);
+    }
+
+    #[test]
+    fn test_apply_fee_overrides_priority_fee_above_max_fee() {
+        let args = SimulateArgs {
+            max_fee: Some("5".to_string()),
+            max_priority_fee: Some("6".to_string()),
+            ..args()
+        };
+        let error = apply_fee_overrides(&args, &mut env_with_basefee(1), &eip1559_tx())
+            .expect_err("the max priority fee is above the max fee");
+        assert!(error.to_string().contains("is above the max fee"));
+    }
+
+    #[test]
+    fn test_apply_fee_overrides_max_fee_below_basefee() {
+        let args = SimulateArgs { max_fee: Some("40".to_string()), ..args() };
+        let error = apply_fee_overrides(&args, &mut env_with_basefee(50), &eip1559_tx())
+            .expect_err("the max fee is below the base fee");
+        assert!(error.to_string().contains("is below the block's base fee"));
+
+        // an overridden base fee is checked instead of the block's
+        let args = SimulateArgs { base_fee: Some("45".to_string()), ..args };
+        assert!(apply_fee_overrides(&args, &mut env_with_basefee(1), &eip1559_tx()).is_err());
+    }
+
+    #[test]
+    fn test_apply_fee_overrides_effective_gas_price() {
+        // below the max fee, the base fee plus the max priority fee is paid
+        let args = SimulateArgs { max_priority_fee: Some("3".to_string()), ..args() };
+        let mut env = env_with_basefee(10);
+        apply_fee_overrides(&args, &mut env, &eip1559_tx()).unwrap();
+        assert_eq!(env.effective_gas_price(), gwei(13));
+
+        // which is capped at the max fee
+        let mut env = env_with_basefee(28);
+        apply_fee_overrides(&args, &mut env, &eip1559_tx()).unwrap();
+        assert_eq!(env.effective_gas_price(), gwei(30));
+
+        // a priority fee is paid on top of the base fee
+        let args = SimulateArgs { priority_fee: Some("2".to_string()), ..args() };
+        let mut env = env_with_basefee(10);
+        apply_fee_overrides(&args, &mut env, &eip1559_tx()).unwrap();
+        assert_eq!(env.effective_gas_price(), gwei(12));
+
+        // and a gas price is paid as-is
+        let args = SimulateArgs { gas_price: Some("7".to_string()), ..args() };
+        let mut env = env_with_basefee(10);
+        apply_fee_overrides(&args, &mut env, &eip1559_tx()).unwrap();
+        assert_eq!(env.effective_gas_price(), gwei(7));
+    }
 }
@@ -0,0 +1,202 @@
+use std::{path::PathBuf, str::FromStr};
+
+use alloy::{
+    eips::BlockId,
+    network::AnyNetwork,
+    primitives::B256,
+    providers::{ext::TraceApi, Provider, ProviderBuilder},
+    rpc::types::{trace::parity::TraceType, BlockTransactionsKind},
+    transports::http::reqwest::Url,
+};
+use eyre::{bail, eyre, OptionExt, Result};
+use revm::{DatabaseCommit, EvmBuilder};
+use shadow_common::{
+    abi::get_abis,
+    db::JsonRpcDatabase,
+    env::{get_chain_spec, ReplayBlockEnv},
+    event::decode_logs,
+    forge::ensure_forge_installed,
+    rpc_cache::RpcCache,
+    ShadowContractGroupInfo,
+};
+use tracing::{error, info};
+
+use crate::{
+    apply_fee_overrides,
+    event_filter::EventFilter,
+    evm::{build_sim_env, build_state_diff, get_overrides},
+    SimulateArgs,
+};
+
+/// Parses a `<start>:<end>` range of transaction indices, where `end` is exclusive
+fn parse_replay_range(range: &str) -> Result<(usize, usize)> {
+    let (start, end) = range
+        .split_once(':')
+        .ok_or_eyre("the replay range must be given as <start>:<end>, e.g. 0:5")?;
+    let start: usize =
+        start.trim().parse().map_err(|e| eyre!("Invalid replay range start {}: {}", start, e))?;
+    let end: usize =
+        end.trim().parse().map_err(|e| eyre!("Invalid replay range end {}: {}", end, e))?;
+    if start >= end {
+        bail!("the replay range {} is empty. The end index is exclusive.", range);
+    }
+
+    Ok((start, end))
+}
+
+/// `simulate --replay-range`. Replays the transactions with indices `[start, end)` in a block
+/// with shadow overrides, in order. Each transaction is replayed on top of the state changes of
+/// every transaction before it in the block, so the changes made by transaction N are seen by
+/// transaction N+1.
+pub(crate) async fn replay_range(args: SimulateArgs, range: &str) -> Result<()> {
+    let (start, end) = parse_replay_range(range)?;
+    let event_filter = EventFilter::new(&args.event_filter, &args.address_filter)?;
+
+    // the first argument is a block number or hash in this mode
+    let block_id = match args.transaction_hash.parse::<u64>() {
+        Ok(number) => BlockId::number(number),
+        Err(_) => BlockId::hash(
+            args.transaction_hash
+                .parse::<B256>()
+                .map_err(|e| eyre!("Invalid block number or hash: {}", e))?,
+        ),
+    };
+
+    // ensure forge is installed on the system
+    ensure_forge_installed()?;
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let mut group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    // validate that the group is ready for pinning
+    info!("validating shadow contract group at {}", root_dir.display());
+    let artifact_path = group_info.prepare(&args.rpc_url).await?;
+    let overrides = get_overrides(&artifact_path)?;
+    let abis = get_abis(&artifact_path)?;
+
+    // get a new provider
+    let provider =
+        ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+
+    // get chain ID, unless it was provided
+    let chain_id = match args.chain_id {
+        Some(chain_id) => chain_id,
+        None => provider
+            .get_chain_id()
+            .await
+            .map_err(|e| eyre::eyre!("failed to get chain ID from RPC: {}", e))?,
+    };
+
+    info!("fetching block details for block {}", block_id);
+    let block = provider
+        .get_block(block_id, BlockTransactionsKind::Full)
+        .await?
+        .ok_or_eyre("block not found")?;
+    let block_number = block.header.number.ok_or_eyre("block not mined")?;
+    let transactions =
+        block.transactions.as_transactions().ok_or_eyre("block transactions not found")?.to_vec();
+    if end > transactions.len() {
+        bail!(
+            "block {} only has {} transactions, so {} is out of range",
+            block_number,
+            transactions.len(),
+            range
+        );
+    }
+
+    info!("fetching block trace for block {}", block_number);
+    let block_trace = provider
+        .trace_replay_block_transactions(block_number.into(), &[TraceType::StateDiff])
+        .await?;
+
+    let mut block_env = ReplayBlockEnv::from(block).with_chain_id(chain_id);
+    if let Some(coinbase) = &args.coinbase {
+        let coinbase = coinbase.parse().map_err(|e| eyre!("Invalid coinbase: {}", e))?;
+        info!("simulating with a coinbase of {}", coinbase);
+        block_env = block_env.with_coinbase(coinbase);
+    }
+
+    // the state left by every transaction before the range is fetched from the block trace.
+    // the transactions in the range are then replayed on top of it, each committing its state
+    // changes for the next
+    let mut db = JsonRpcDatabase::try_new(
+        block_env.clone().into(),
+        chain_id,
+        provider,
+        overrides,
+        build_state_diff(block_trace, transactions[start].hash)?,
+    )?
+    .with_rpc_cache(RpcCache::load(chain_id, block_number, args.json_rpc_cache_mode)?);
+
+    info!("replaying transactions {} to {} of block {}", start, end - 1, block_number);
+    let mut succeeded = 0;
+    for (index, tx) in transactions.iter().enumerate().take(end).skip(start) {
+        let mut env = build_sim_env(
+            tx.from,
+            tx.to,
+            tx.value,
+            tx.input.clone(),
+            block_env.clone().into(),
+            chain_id,
+        );
+        apply_fee_overrides(&args, &mut env, tx)?;
+
+        let mut evm = EvmBuilder::default()
+            .with_spec_id(get_chain_spec(&block_number, &chain_id))
+            .with_env(env)
+            .with_db(&mut db)
+            .build();
+
+        let executed = match evm.transact_preverified() {
+            Ok(executed) => executed,
+            Err(e) => {
+                error!("[{}] failed to simulate transaction {}: {}", index, tx.hash, e);
+                continue;
+            }
+        };
+        drop(evm);
+
+        // failed transactions still pay for gas and increment the sender's nonce
+        db.commit(executed.state);
+        if !executed.result.is_success() {
+            error!("[{}] transaction {} failed: {:?}", index, tx.hash, executed.result);
+            continue;
+        }
+
+        // every event is decoded, but only those matching the filters are printed
+        let mut logs = decode_logs(executed.result.logs(), &abis)?;
+        logs.retain(|log| event_filter.matches(log));
+        info!(
+            "[{}] transaction {} succeeded, using {} gas:\n{}",
+            index,
+            tx.hash,
+            executed.result.gas_used(),
+            logs.into_iter().map(|l| l.to_string()).collect::<Vec<String>>().join("\n")
+        );
+        succeeded += 1;
+    }
+
+    db.save_rpc_cache()?;
+    info!("replayed {} transactions, {} of which succeeded", end - start, succeeded);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_replay_range() {
+        assert_eq!(parse_replay_range("0:5").unwrap(), (0, 5));
+        assert_eq!(parse_replay_range(" 3 : 4 ").unwrap(), (3, 4));
+        assert!(parse_replay_range("5").is_err());
+        assert!(parse_replay_range("5:5").is_err());
+        assert!(parse_replay_range("6:2").is_err());
+        assert!(parse_replay_range("a:2").is_err());
+    }
+}
@@ -0,0 +1,59 @@
+use std::io::Read;
+
+use alloy::network::{AnyNetwork, Network};
+use eyre::{bail, eyre, Result};
+use serde::de::DeserializeOwned;
+use tracing::info;
+
+use crate::{simulate_transaction, ReplayReceiptArgs, SimulateArgs};
+
+/// The `replay-receipt` subcommand. Simulates the transaction a saved transaction receipt
+/// belongs to, with shadow overrides.
+pub async fn replay_receipt(args: ReplayReceiptArgs) -> Result<()> {
+    let receipt: <AnyNetwork as Network>::ReceiptResponse = match &args.receipt {
+        Some(path) => read_json(path)?,
+        None if args.stdin => {
+            let mut contents = String::new();
+            std::io::stdin().read_to_string(&mut contents)?;
+            serde_json::from_str(&contents).map_err(|e| eyre!("Invalid receipt: {}", e))?
+        }
+        None => bail!("provide a receipt file, or pass --stdin to read one from stdin"),
+    };
+    info!(
+        "replaying transaction {} from block {}, which originally used {} gas",
+        receipt.transaction_hash,
+        receipt.block_number.map(|n| n.to_string()).unwrap_or_else(|| "unknown".to_string()),
+        receipt.gas_used
+    );
+
+    // the transaction itself may be saved too, which saves fetching it from the RPC
+    let tx: Option<<AnyNetwork as Network>::TransactionResponse> =
+        args.tx_json.as_deref().map(read_json).transpose()?;
+    if let Some(tx) = &tx {
+        if tx.hash != receipt.transaction_hash {
+            bail!(
+                "the transaction is {}, but the receipt is for {}",
+                tx.hash,
+                receipt.transaction_hash
+            );
+        }
+    }
+
+    simulate_transaction(
+        SimulateArgs {
+            chain_id: args.chain_id,
+            ..SimulateArgs::new(receipt.transaction_hash.to_string(), args.root, args.rpc_url)
+        },
+        tx,
+        None,
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Reads a JSON file in the format returned by the JSON-RPC API
+fn read_json<T: DeserializeOwned>(path: &str) -> Result<T> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|e| eyre!("Failed to read {}: {}", path, e))?;
+    serde_json::from_str(&contents).map_err(|e| eyre!("Invalid JSON in {}: {}", path, e))
+}
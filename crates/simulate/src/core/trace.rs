@@ -0,0 +1,18 @@
+use eyre::Result;
+
+use crate::{simulate_transaction, SimulateArgs, TraceArgs};
+
+/// The `trace` subcommand. Simulates a transaction with shadow overrides, and prints its trace
+/// in the requested format.
+pub async fn trace(args: TraceArgs) -> Result<()> {
+    simulate_transaction(
+        SimulateArgs {
+            chain_id: args.chain_id,
+            ..SimulateArgs::new(args.transaction_hash, args.root, args.rpc_url)
+        },
+        None,
+        Some(args.output_format),
+    )
+    .await
+    .map(|_| ())
+}
@@ -1,5 +1,6 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use eyre::Result;
+use shadow_common::rpc_cache::RpcCacheMode;
 
 /// Arguments for the `sim` subcommand
 #[derive(Debug, Clone, Parser)]
@@ -15,11 +16,362 @@ pub struct SimulateArgs {
     /// The RPC URL of the chain to simulate the transaction on.
     #[clap(short = 'u', long, default_value = "http://localhost:8545")]
     pub rpc_url: String,
+
+    /// The chain ID of the chain to simulate the transaction on. Fetched from the RPC if not
+    /// provided.
+    #[clap(long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// Override the block's base fee, in gwei.
+    #[clap(long, required = false)]
+    pub base_fee: Option<String>,
+
+    /// The priority fee to pay on top of the base fee, in gwei.
+    #[clap(long, required = false)]
+    pub priority_fee: Option<String>,
+
+    /// Pay this gas price, in gwei, like a legacy transaction.
+    #[clap(long, required = false, conflicts_with_all = ["priority_fee", "max_fee", "max_priority_fee"])]
+    pub gas_price: Option<String>,
+
+    /// The maximum fee per gas of an EIP-1559 transaction, in gwei. Defaults to the original
+    /// transaction's if only `--max-priority-fee` is set.
+    #[clap(long, required = false, conflicts_with = "priority_fee")]
+    pub max_fee: Option<String>,
+
+    /// The maximum priority fee per gas of an EIP-1559 transaction, in gwei. Defaults to the
+    /// original transaction's if only `--max-fee` is set.
+    #[clap(long, required = false, conflicts_with = "priority_fee")]
+    pub max_priority_fee: Option<String>,
+
+    /// Override the block's coinbase, e.g. to simulate the transaction in a block built by a
+    /// specific block builder.
+    #[clap(long, required = false)]
+    pub coinbase: Option<String>,
+
+    /// Output an EIP-2930 access list of the storage slots accessed by the transaction.
+    #[clap(long)]
+    pub generate_access_list: bool,
+
+    /// The path to a JSON file containing an EIP-2930 access list to apply to the transaction.
+    /// Either a bare access list, or an object with an `accessList` field such as an
+    /// `eth_getTransaction` response, is accepted.
+    #[clap(long, required = false, alias = "access-list")]
+    pub apply_access_list: Option<String>,
+
+    /// Transactions whose state changes to apply before simulating, comma separated. Each must
+    /// be from a block at or before the simulated transaction's block.
+    #[clap(long, value_delimiter = ',', required = false)]
+    pub pre_state_txs: Vec<String>,
+
+    /// Replay the transactions with indices `[start, end)` of a block, given as `<start>:<end>`.
+    /// Each is replayed on top of the state left by the ones before it. With this flag, the
+    /// first argument is the block's hash or number rather than a transaction hash.
+    #[clap(
+        long,
+        required = false,
+        conflicts_with_all = ["generate_access_list", "apply_access_list", "pre_state_txs"]
+    )]
+    pub replay_range: Option<String>,
+
+    /// Simulate the transaction's calldata, sender and value in block `n` instead of the
+    /// transaction's own block, against the state at the end of block `n - 1`.
+    #[clap(long, value_name = "n", required = false, conflicts_with = "replay_range")]
+    pub at_block: Option<u64>,
+
+    /// Only print events with this name or signature, e.g. `Transfer`. Matched
+    /// case-insensitively. May be given multiple times. Undecodable events are hidden while
+    /// any filter is active.
+    #[clap(long, value_name = "event_name", required = false)]
+    pub event_filter: Vec<String>,
+
+    /// Only print events emitted by this address. May be given multiple times, and is combined
+    /// with `--event-filter`.
+    #[clap(long, value_name = "address", required = false)]
+    pub address_filter: Vec<String>,
+
+    /// Print the gas used by each function called during the transaction, like
+    /// `forge test --gas-report`.
+    #[clap(long, conflicts_with = "replay_range")]
+    pub gas_report: bool,
+
+    /// The number of most expensive functions to highlight in the gas report.
+    #[clap(long, value_name = "n", default_value = "5", requires = "gas_report")]
+    pub top: usize,
+
+    /// Write an EIP-3155 trace of every opcode executed to this file, one JSON object per line,
+    /// for differential testing against other EVM implementations.
+    #[clap(long, value_name = "output-file", required = false, conflicts_with = "replay_range")]
+    pub eip3155_trace: Option<String>,
+
+    /// Include each opcode's memory in the EIP-3155 trace.
+    #[clap(long, requires = "eip3155_trace")]
+    pub trace_memory: bool,
+
+    /// Include each opcode's stack in the EIP-3155 trace.
+    #[clap(long, requires = "eip3155_trace")]
+    pub trace_stack: bool,
+
+    /// Whether state fetched from the RPC is read from and/or written to the disk cache at
+    /// `~/.shadow/cache/rpc`. In `read` mode, state missing from the cache is an error, so a
+    /// captured transaction replays identically.
+    #[clap(long, value_enum, default_value = "none")]
+    pub json_rpc_cache_mode: RpcCacheMode,
+
+    /// Replace the transaction's calldata with this hex string. The simulation is then no longer
+    /// a replay of the original transaction.
+    #[clap(
+        long,
+        value_name = "hex",
+        required = false,
+        conflicts_with_all = ["calldata_abi_encode", "replay_range"]
+    )]
+    pub calldata_override: Option<String>,
+
+    /// Replace the transaction's calldata with a call ABI-encoded from a human-readable function
+    /// signature and its arguments, e.g. `--calldata-abi-encode "transfer(address,uint256)"
+    /// 0x0000000000000000000000000000000000000001 100`.
+    #[clap(
+        long,
+        value_name = "function-sig",
+        num_args = 1..,
+        allow_hyphen_values = true,
+        conflicts_with = "replay_range"
+    )]
+    pub calldata_abi_encode: Vec<String>,
 }
 
 impl SimulateArgs {
+    /// Creates the arguments to simulate a transaction with every optional flag unset, as if
+    /// passed on the command line without flags. Other subcommands which simulate
+    /// transactions build on this with struct update syntax.
+    pub fn new(transaction_hash: String, root: String, rpc_url: String) -> Self {
+        Self {
+            transaction_hash,
+            root,
+            rpc_url,
+            chain_id: None,
+            base_fee: None,
+            priority_fee: None,
+            gas_price: None,
+            max_fee: None,
+            max_priority_fee: None,
+            coinbase: None,
+            generate_access_list: false,
+            apply_access_list: None,
+            pre_state_txs: vec![],
+            replay_range: None,
+            at_block: None,
+            event_filter: vec![],
+            address_filter: vec![],
+            gas_report: false,
+            top: 5,
+            eip3155_trace: None,
+            trace_memory: false,
+            trace_stack: false,
+            json_rpc_cache_mode: Default::default(),
+            calldata_override: None,
+            calldata_abi_encode: vec![],
+        }
+    }
+
     /// Validates the configuration arguments.
     pub fn validate(&self) -> Result<()> {
         Ok(())
     }
 }
+
+/// Arguments for the `estimate-gas` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Estimate the gas used by a call with shadow overrides")]
+pub struct EstimateGasArgs {
+    /// The address of the contract to call.
+    #[clap(long)]
+    pub to: String,
+
+    /// The calldata to send, as a hex string.
+    #[clap(long, default_value = "0x")]
+    pub calldata: String,
+
+    /// The address to send the call from.
+    #[clap(long, default_value = "0x0000000000000000000000000000000000000000")]
+    pub from: String,
+
+    /// The value to send with the call, in wei.
+    #[clap(long, default_value = "0")]
+    pub value: String,
+
+    /// Estimate gas against the state after this block, rather than the latest block.
+    #[clap(long, required = false)]
+    pub block: Option<u64>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain to estimate gas on.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+}
+
+/// Arguments for the `bundle-simulate` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Simulate an ERC-4337 UserOperation with shadow overrides")]
+pub struct BundleSimulateArgs {
+    /// The path to a JSON file containing the UserOperation to simulate.
+    #[clap(long)]
+    pub user_op: String,
+
+    /// The address of the EntryPoint contract. Defaults to the v0.6 EntryPoint.
+    #[clap(long, default_value = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789")]
+    pub entry_point: String,
+
+    /// The address of the bundler submitting the UserOperation. Also receives its fees.
+    /// Defaults to the UserOperation's sender.
+    #[clap(long, required = false)]
+    pub bundler: Option<String>,
+
+    /// Simulate against the state after this block, rather than the latest block.
+    #[clap(long, required = false)]
+    pub block: Option<u64>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain to simulate the UserOperation on.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+}
+
+/// Arguments for the `replay-receipt` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Simulate the transaction a saved JSON transaction receipt belongs to")]
+pub struct ReplayReceiptArgs {
+    /// The path to a JSON transaction receipt, as returned by `eth_getTransactionReceipt`.
+    #[clap(required_unless_present = "stdin")]
+    pub receipt: Option<String>,
+
+    /// Read the receipt from stdin instead of a file.
+    #[clap(long, conflicts_with = "receipt")]
+    pub stdin: bool,
+
+    /// The path to the JSON transaction, as returned by `eth_getTransactionByHash`. Saves
+    /// fetching it from the RPC.
+    #[clap(long, required = false)]
+    pub tx_json: Option<String>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain to simulate the transaction on.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+
+    /// The chain ID of the chain to simulate the transaction on. Fetched from the RPC if not
+    /// provided.
+    #[clap(long, required = false)]
+    pub chain_id: Option<u64>,
+}
+
+/// The formats a transaction trace can be output in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TraceFormat {
+    /// An indented call tree
+    Human,
+    /// A JSON array of call traces, as returned by Parity / OpenEthereum's
+    /// `trace_replayTransaction`
+    Parity,
+    /// JSON struct logs, as returned by geth's `debug_traceTransaction`
+    Geth,
+}
+
+/// Arguments for the `trace` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Trace a transaction with shadow overrides")]
+pub struct TraceArgs {
+    /// The transaction hash to trace.
+    pub transaction_hash: String,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain to trace the transaction on.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+
+    /// The chain ID of the chain to trace the transaction on. Fetched from the RPC if not
+    /// provided.
+    #[clap(long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The format to output the trace in.
+    #[clap(long, value_enum, default_value = "human")]
+    pub output_format: TraceFormat,
+}
+
+/// Arguments for the `generate-foundry-test` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Generate a forge fork test which replays a transaction with shadow overrides")]
+pub struct GenerateFoundryTestArgs {
+    /// The transaction hash to generate a test for.
+    pub transaction_hash: String,
+
+    /// The path to the shadow contract group. The test is written to its `test/` directory.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain the transaction was included on.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+
+    /// The chain ID of the chain the transaction was included on. Fetched from the RPC if not
+    /// provided.
+    #[clap(long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// Also assert the final balances and changed storage slots of the accounts the transaction
+    /// wrote to.
+    #[clap(long)]
+    pub assert_storage: bool,
+
+    /// Run the generated test with `forge test` once it's written.
+    #[clap(long)]
+    pub run: bool,
+}
+
+/// Arguments for the `batch-simulate` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Simulate a list of transactions from a file with shadow overrides")]
+pub struct BatchSimulateArgs {
+    /// The path to a file containing the transaction hashes to simulate, one per line.
+    #[clap(short, long)]
+    pub input: String,
+
+    /// The path to write the results to, as a JSON array.
+    #[clap(short, long, default_value = "simulations.json")]
+    pub output: String,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain the transactions were included on.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+
+    /// The chain ID of the chain the transactions were included on. Fetched from the RPC if not
+    /// provided.
+    #[clap(long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The maximum number of transactions to simulate at once.
+    #[clap(long, default_value = "4")]
+    pub parallelism: usize,
+
+    /// Include each transaction's decoded events in the results, rather than only their count.
+    #[clap(long)]
+    pub verbose: bool,
+}
@@ -1,3 +1,5 @@
 mod args;
+mod user_op;
 
 pub use args::*;
+pub use user_op::*;
@@ -0,0 +1,33 @@
+use alloy::primitives::{Address, Bytes, U256};
+use serde::{Deserialize, Serialize};
+
+/// An ERC-4337 (EntryPoint v0.6) UserOperation, as submitted to a bundler via
+/// `eth_sendUserOperation`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    /// The account making the operation
+    pub sender: Address,
+    /// Anti-replay nonce
+    pub nonce: U256,
+    /// Factory address and calldata, if the account has not been deployed yet
+    #[serde(default)]
+    pub init_code: Bytes,
+    /// The calldata passed to the account
+    pub call_data: Bytes,
+    /// The gas limit of the account's execution call
+    pub call_gas_limit: U256,
+    /// The gas limit of the verification step
+    pub verification_gas_limit: U256,
+    /// The gas paid for the bundler's overhead
+    pub pre_verification_gas: U256,
+    /// The maximum fee per gas, similar to EIP-1559
+    pub max_fee_per_gas: U256,
+    /// The maximum priority fee per gas, similar to EIP-1559
+    pub max_priority_fee_per_gas: U256,
+    /// Paymaster address and data, if the operation is sponsored
+    #[serde(default)]
+    pub paymaster_and_data: Bytes,
+    /// The signature verified by the account
+    pub signature: Bytes,
+}
@@ -1,4 +1,5 @@
-//! The `push` subcommand
+//! The `simulate`, `estimate-gas`, `bundle-simulate`, `replay-receipt`, `trace` and
+//! `generate-foundry-test` subcommands
 
 mod core;
 mod interface;
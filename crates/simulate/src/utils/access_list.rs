@@ -0,0 +1,80 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use alloy::{
+    eips::eip2930::{AccessList, AccessListItem},
+    primitives::{Address, B256},
+};
+use eyre::{eyre, Result};
+use revm::{
+    interpreter::{opcode, Interpreter},
+    Database, EvmContext, Inspector,
+};
+use serde::Deserialize;
+
+/// An [`Inspector`] which records the storage slots read and written by a transaction, so they
+/// can be output as an EIP-2930 access list
+#[derive(Debug, Default)]
+pub(crate) struct AccessListInspector {
+    /// The storage keys accessed by the transaction, keyed by the account they belong to
+    accessed: BTreeMap<Address, BTreeSet<B256>>,
+}
+
+impl AccessListInspector {
+    /// Returns the recorded storage accesses as an EIP-2930 access list
+    pub(crate) fn access_list(&self) -> AccessList {
+        AccessList(
+            self.accessed
+                .iter()
+                .map(|(address, keys)| AccessListItem {
+                    address: *address,
+                    storage_keys: keys.iter().cloned().collect(),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<DB: Database> Inspector<DB> for AccessListInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        match interp.current_opcode() {
+            opcode::SLOAD | opcode::SSTORE => {
+                // the storage key is always the top of the stack
+                if let Ok(key) = interp.stack().peek(0) {
+                    self.accessed
+                        .entry(interp.contract.target_address)
+                        .or_default()
+                        .insert(B256::from(key));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The JSON formats an access list file may be in
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum AccessListFile {
+    /// A bare access list, as accepted by `cast send --access-list`
+    List(AccessList),
+    /// An object containing an access list, such as an `eth_getTransaction` response or the
+    /// output of `cast access-list --json`
+    Wrapped {
+        #[serde(rename = "accessList", alias = "access_list")]
+        access_list: AccessList,
+    },
+}
+
+/// Loads an EIP-2930 access list from a JSON file. Either a bare access list, or an object with
+/// an `accessList` field, is accepted.
+pub(crate) fn load_access_list(path: &str) -> Result<AccessList> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| eyre!("Failed to read access list at {}: {}", path, e))?;
+    match serde_json::from_str(&contents)
+        .map_err(|e| eyre!("Invalid access list at {}: {}", path, e))?
+    {
+        AccessListFile::List(access_list) | AccessListFile::Wrapped { access_list } => {
+            Ok(access_list)
+        }
+    }
+}
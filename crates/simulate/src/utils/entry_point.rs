@@ -0,0 +1,60 @@
+use alloy::sol;
+
+use crate::UserOperation;
+
+// Minimal interface of the ERC-4337 EntryPoint (v0.6), used to build `handleOps` calls and
+// decode their results.
+sol!(
+    #[allow(missing_docs)]
+    interface IEntryPoint {
+        struct UserOperation {
+            address sender;
+            uint256 nonce;
+            bytes initCode;
+            bytes callData;
+            uint256 callGasLimit;
+            uint256 verificationGasLimit;
+            uint256 preVerificationGas;
+            uint256 maxFeePerGas;
+            uint256 maxPriorityFeePerGas;
+            bytes paymasterAndData;
+            bytes signature;
+        }
+
+        function handleOps(UserOperation[] calldata ops, address payable beneficiary);
+
+        event UserOperationEvent(
+            bytes32 indexed userOpHash,
+            address indexed sender,
+            address indexed paymaster,
+            uint256 nonce,
+            bool success,
+            uint256 actualGasCost,
+            uint256 actualGasUsed
+        );
+        event UserOperationRevertReason(
+            bytes32 indexed userOpHash,
+            address indexed sender,
+            uint256 nonce,
+            bytes revertReason
+        );
+    }
+);
+
+impl From<UserOperation> for IEntryPoint::UserOperation {
+    fn from(op: UserOperation) -> Self {
+        Self {
+            sender: op.sender,
+            nonce: op.nonce,
+            initCode: op.init_code,
+            callData: op.call_data,
+            callGasLimit: op.call_gas_limit,
+            verificationGasLimit: op.verification_gas_limit,
+            preVerificationGas: op.pre_verification_gas,
+            maxFeePerGas: op.max_fee_per_gas,
+            maxPriorityFeePerGas: op.max_priority_fee_per_gas,
+            paymasterAndData: op.paymaster_and_data,
+            signature: op.signature,
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use alloy::primitives::Address;
+use eyre::{eyre, Result};
+use shadow_common::event::RawOrDecodedEvent;
+
+/// Limits the events printed after a simulation to those with one of the given names, emitted
+/// by one of the given addresses. An empty list of names or addresses matches any.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EventFilter {
+    /// Event names or signatures, lowercased
+    names: Vec<String>,
+    addresses: Vec<Address>,
+}
+
+impl EventFilter {
+    /// Creates a filter from `--event-filter` and `--address-filter` values
+    pub(crate) fn new(names: &[String], addresses: &[String]) -> Result<Self> {
+        Ok(Self {
+            names: names.iter().map(|name| name.trim().to_lowercase()).collect(),
+            addresses: addresses
+                .iter()
+                .map(|address| {
+                    address
+                        .parse()
+                        .map_err(|e| eyre!("Invalid --address-filter {}: {}", address, e))
+                })
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Whether any filter was given
+    pub(crate) fn is_active(&self) -> bool {
+        !self.names.is_empty() || !self.addresses.is_empty()
+    }
+
+    /// Whether an event passes every filter. Events which couldn't be decoded have no name, so
+    /// they never match a name filter.
+    pub(crate) fn matches(&self, event: &RawOrDecodedEvent) -> bool {
+        let (address, name) = match event {
+            RawOrDecodedEvent::Raw(raw) => (raw.log.address, None),
+            RawOrDecodedEvent::Decoded(decoded) => (decoded.log.address, Some(&decoded.event)),
+        };
+
+        let name_matches = self.names.is_empty() ||
+            name.is_some_and(|event| {
+                let (name, signature) =
+                    (event.name.to_lowercase(), event.signature().to_lowercase());
+                self.names.iter().any(|filter| *filter == name || *filter == signature)
+            });
+        let address_matches = self.addresses.is_empty() || self.addresses.contains(&address);
+
+        name_matches && address_matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        dyn_abi::EventExt,
+        json_abi::Event,
+        primitives::{address, Log, LogData, B256, U256},
+    };
+    use shadow_common::event::{FullDecodedEvent, FullRawEvent};
+
+    use super::*;
+
+    fn transfer(emitter: Address) -> RawOrDecodedEvent {
+        let event =
+            Event::parse("event Transfer(address indexed from, address indexed to, uint256 value)")
+                .unwrap();
+        let log = Log {
+            address: emitter,
+            data: LogData::new_unchecked(
+                vec![event.selector(), B256::ZERO, B256::ZERO],
+                U256::from(1).to_be_bytes_vec().into(),
+            ),
+        };
+        let inner = event.decode_log(&log.data, true).unwrap();
+
+        RawOrDecodedEvent::Decoded(FullDecodedEvent { inner, event, log, transaction_log_index: 0 })
+    }
+
+    #[test]
+    fn test_event_filter() {
+        let token = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let other = address!("dAC17F958D2ee523a2206206994597C13D831ec7");
+        let raw = RawOrDecodedEvent::Raw(FullRawEvent {
+            log: Log {
+                address: token,
+                data: LogData::new_unchecked(vec![B256::ZERO], vec![].into()),
+            },
+            transaction_log_index: 1,
+        });
+
+        // without filters, every event matches
+        let filter = EventFilter::default();
+        assert!(!filter.is_active());
+        assert!(filter.matches(&raw));
+
+        // names match case-insensitively, by name or signature, and hide raw logs
+        let filter = EventFilter::new(&["transfer".to_string()], &[]).unwrap();
+        assert!(filter.matches(&transfer(token)));
+        assert!(!filter.matches(&raw));
+        let filter =
+            EventFilter::new(&["Transfer(address,address,uint256)".to_string()], &[]).unwrap();
+        assert!(filter.matches(&transfer(token)));
+        let filter = EventFilter::new(&["Approval".to_string()], &[]).unwrap();
+        assert!(!filter.matches(&transfer(token)));
+
+        // address filters keep raw logs, and are ANDed with name filters
+        let filter = EventFilter::new(&[], &[token.to_string()]).unwrap();
+        assert!(filter.matches(&raw));
+        let filter = EventFilter::new(&["Transfer".to_string()], &[token.to_string()]).unwrap();
+        assert!(filter.matches(&transfer(token)));
+        assert!(!filter.matches(&transfer(other)));
+
+        assert!(EventFilter::new(&[], &["0x1234".to_string()]).is_err());
+    }
+}
@@ -1,10 +1,12 @@
-use std::{collections::HashMap, path::PathBuf, str::FromStr};
+use std::{collections::HashMap, path::Path, str::FromStr};
 
 use alloy::{
+    dyn_abi::{DynSolType, JsonAbiExt},
+    json_abi::Function,
     primitives::{Address, Bytes, TxHash},
-    rpc::types::trace::parity::{ChangedType, Delta, TraceResultsWithTransactionHash},
+    rpc::types::trace::parity::{StateDiff, TraceResultsWithTransactionHash},
 };
-use eyre::{OptionExt, Result};
+use eyre::{bail, eyre, OptionExt, Result};
 use hex::FromHex;
 use revm::primitives::{AnalysisKind, BlobExcessGasAndPrice, BlockEnv, Bytecode, Env, TxEnv, U256};
 use shadow_common::state::PartialBlockStateDiff;
@@ -16,10 +18,11 @@ pub(crate) fn build_sim_env(
     original_value: U256,
     original_data: Bytes,
     block: BlockEnv,
+    chain_id: u64,
 ) -> Box<Env> {
     let mut cfg_env = revm::primitives::CfgEnv::default();
     cfg_env.limit_contract_code_size = Some(usize::MAX);
-    cfg_env.chain_id = 1u64;
+    cfg_env.chain_id = chain_id;
     cfg_env.perf_analyse_created_bytecodes = AnalysisKind::Raw;
     Box::new(Env {
         cfg: cfg_env,
@@ -43,7 +46,7 @@ pub(crate) fn build_sim_env(
 }
 
 /// load bytecode overrides from artifact path
-pub(crate) fn get_overrides(artifact_path: &PathBuf) -> Result<HashMap<Address, Bytecode>> {
+pub(crate) fn get_overrides(artifact_path: &Path) -> Result<HashMap<Address, Bytecode>> {
     let mut overrides = HashMap::new();
 
     // walk the artifact_path recursively and collect all `.hex` files
@@ -93,42 +96,168 @@ pub(crate) fn build_state_diff(
         }
 
         if let Some(state_diff) = trace.full_trace.state_diff {
-            state_diff.0.iter().for_each(|(address, diff)| {
-                let account = accounts.entry(*address).or_default();
-
-                match diff.balance {
-                    Delta::Added(balance) => account.balance = Some(balance),
-                    Delta::Removed(_) => account.balance = None,
-                    Delta::Changed(ChangedType { from: _, to }) => account.balance = Some(to),
-                    _ => {}
-                }
-
-                match diff.nonce {
-                    Delta::Added(nonce) => account.nonce = Some(nonce),
-                    Delta::Removed(_) => account.nonce = None,
-                    Delta::Changed(ChangedType { from: _, to }) => account.nonce = Some(to),
-                    _ => {}
-                }
-
-                diff.storage.iter().for_each(|(key, value)| match value {
-                    Delta::Added(value) => {
-                        account
-                            .storage
-                            .insert(U256::from_be_slice(&key.0), U256::from_be_slice(&value.0));
-                    }
-                    Delta::Removed(_) => {
-                        account.storage.remove(&U256::from_be_slice(&key.0));
-                    }
-                    Delta::Changed(ChangedType { from: _, to }) => {
-                        account
-                            .storage
-                            .insert(U256::from_be_slice(&key.0), U256::from_be_slice(&to.0));
-                    }
-                    _ => {}
-                });
-            });
+            apply_state_diff(&mut accounts, &state_diff);
         }
     }
 
     Ok(accounts)
 }
+
+/// Applies the state changes made by a single transaction in a block, on top of `accounts`.
+/// Used to set up state which only exists after transactions other than the one being replayed.
+pub(crate) fn apply_transaction_state_diff(
+    accounts: &mut HashMap<Address, PartialBlockStateDiff>,
+    block_trace: Vec<TraceResultsWithTransactionHash>,
+    transaction_hash: TxHash,
+) -> Result<()> {
+    let trace = block_trace
+        .into_iter()
+        .find(|trace| trace.transaction_hash == transaction_hash)
+        .ok_or_eyre(format!("no trace found for transaction {}", transaction_hash))?;
+
+    if let Some(state_diff) = trace.full_trace.state_diff {
+        apply_state_diff(accounts, &state_diff);
+    }
+
+    Ok(())
+}
+
+/// Applies a transaction's state diff on top of `accounts`
+fn apply_state_diff(
+    accounts: &mut HashMap<Address, PartialBlockStateDiff>,
+    state_diff: &StateDiff,
+) {
+    state_diff.0.iter().for_each(|(address, diff)| {
+        accounts.entry(*address).or_default().apply_account_diff(diff);
+    });
+}
+
+/// ABI-encodes a call from a human-readable function signature, e.g. `transfer(address,uint256)`,
+/// and its arguments
+pub(crate) fn encode_calldata(signature: &str, args: &[String]) -> Result<Bytes> {
+    let function =
+        Function::parse(signature).map_err(|e| eyre!("Invalid function signature: {}", e))?;
+    if function.inputs.len() != args.len() {
+        bail!(
+            "{} takes {} arguments, but {} were given",
+            function.signature(),
+            function.inputs.len(),
+            args.len()
+        );
+    }
+
+    let values = function
+        .inputs
+        .iter()
+        .zip(args)
+        .map(|(input, arg)| {
+            DynSolType::parse(&input.selector_type())
+                .and_then(|ty| ty.coerce_str(arg))
+                .map_err(|e| eyre!("Invalid value {} for {}: {}", arg, input.ty, e))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(function.abi_encode_input(&values)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::evm::*;
+    use alloy::{
+        primitives::B256,
+        rpc::types::{Block, Transaction},
+    };
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        primitives::{AccountInfo, EVMError, InvalidTransaction},
+        EvmBuilder,
+    };
+    use shadow_common::env::ReplayBlockEnv;
+
+    /// Executes a transaction signed for `tx_chain_id` in an environment built for
+    /// `env_chain_id`.
+    fn transact_on_chain(
+        tx_chain_id: u64,
+        env_chain_id: u64,
+    ) -> Result<(), EVMError<std::convert::Infallible>> {
+        let mut env = build_sim_env(
+            Address::repeat_byte(0x11),
+            Some(Address::repeat_byte(0x22)),
+            U256::ZERO,
+            Bytes::new(),
+            BlockEnv::default(),
+            env_chain_id,
+        );
+        env.tx.chain_id = Some(tx_chain_id);
+
+        let mut evm =
+            EvmBuilder::default().with_db(CacheDB::new(EmptyDB::default())).with_env(env).build();
+        evm.transact().map(|_| ())
+    }
+
+    #[test]
+    fn test_encode_calldata() {
+        let calldata = encode_calldata(
+            "transfer(address,uint256)",
+            &["0x0000000000000000000000000000000000000001".to_string(), "100".to_string()],
+        )
+        .unwrap();
+        assert_eq!(
+            calldata.to_string(),
+            "0xa9059cbb\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000064"
+        );
+
+        // the argument count must match the signature
+        assert!(encode_calldata("transfer(address,uint256)", &["100".to_string()]).is_err());
+        assert!(encode_calldata("transfer(address,uint256", &[]).is_err());
+    }
+
+    #[test]
+    fn test_build_sim_env_uses_chain_id() {
+        // a base transaction (chain 8453) executes when the matching chain ID is passed
+        assert!(transact_on_chain(8453, 8453).is_ok());
+
+        // and is rejected in a mainnet environment
+        assert!(matches!(
+            transact_on_chain(8453, 1),
+            Err(EVMError::Transaction(InvalidTransaction::InvalidChainId))
+        ));
+    }
+
+    #[test]
+    fn test_build_sim_env_uses_coinbase() {
+        let coinbase = Address::repeat_byte(0x33);
+        let to = Address::repeat_byte(0x22);
+        let block_env =
+            ReplayBlockEnv::from(Block::<Transaction>::default()).with_coinbase(coinbase);
+        let env = build_sim_env(
+            Address::repeat_byte(0x11),
+            Some(to),
+            U256::ZERO,
+            Bytes::new(),
+            block_env.into(),
+            1,
+        );
+
+        // COINBASE PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+        let code = Bytecode::new_raw(Bytes::from_static(&[
+            0x41, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+        ]));
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            to,
+            AccountInfo { code_hash: code.hash_slow(), code: Some(code), ..Default::default() },
+        );
+
+        let mut evm = EvmBuilder::default().with_db(db).with_env(env).build();
+        let output = evm
+            .transact()
+            .expect("transaction should execute")
+            .result
+            .into_output()
+            .expect("transaction should return");
+        assert_eq!(Address::from_word(B256::from_slice(&output)), coinbase);
+    }
+}
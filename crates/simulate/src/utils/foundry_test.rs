@@ -0,0 +1,192 @@
+use alloy::primitives::{hex, Address, Bytes, Log, TxHash, U256};
+
+/// The cheatcodes used by generated tests. These are declared inline so the test compiles
+/// without `forge-std` installed.
+const VM_INTERFACE: &str = r#"/// The subset of forge's cheatcodes used by this test
+interface Vm {
+    function createSelectFork(string calldata urlOrAlias, uint256 blockNumber) external returns (uint256);
+    function envString(string calldata name) external view returns (string memory);
+    function etch(address target, bytes calldata newRuntimeBytecode) external;
+    function prank(address msgSender) external;
+    function expectEmit(bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData, address emitter) external;
+    function load(address target, bytes32 slot) external view returns (bytes32 data);
+}"#;
+
+/// Emits a log with arbitrary topics and data, so any event can be passed to `vm.expectEmit`
+/// without declaring it.
+const EMIT_LOG_HELPER: &str = r#"    /// Emits a log with the given topics and data, to be matched by `vm.expectEmit`
+    function _emitLog(bytes32[] memory topics, bytes memory data) internal {
+        uint256 length = topics.length;
+        assembly {
+            let ptr := add(data, 0x20)
+            let size := mload(data)
+            let t := add(topics, 0x20)
+            switch length
+            case 0 { log0(ptr, size) }
+            case 1 { log1(ptr, size, mload(t)) }
+            case 2 { log2(ptr, size, mload(t), mload(add(t, 0x20))) }
+            case 3 { log3(ptr, size, mload(t), mload(add(t, 0x20)), mload(add(t, 0x40))) }
+            default {
+                log4(ptr, size, mload(t), mload(add(t, 0x20)), mload(add(t, 0x40)), mload(add(t, 0x60)))
+            }
+        }
+    }"#;
+
+/// The environment variable generated tests read the fork's RPC URL from
+pub(crate) const RPC_URL_ENV_VAR: &str = "SHADOW_RPC_URL";
+
+/// The final balance and storage of an account, asserted after replaying the transaction
+#[derive(Debug, Clone)]
+pub(crate) struct AccountAssertion {
+    pub(crate) address: Address,
+    pub(crate) balance: U256,
+    /// Changed storage slots, and their final values
+    pub(crate) storage: Vec<(U256, U256)>,
+}
+
+/// A forge test which replays a simulated transaction on a fork, with shadow bytecode etched in
+#[derive(Debug, Clone)]
+pub(crate) struct ForkTest {
+    pub(crate) contract_name: String,
+    pub(crate) transaction_hash: TxHash,
+    /// The block the transaction was included in. The fork is created at the block before it.
+    pub(crate) block_number: u64,
+    pub(crate) from: Address,
+    pub(crate) to: Address,
+    pub(crate) value: U256,
+    pub(crate) calldata: Bytes,
+    /// The shadow contracts, and their runtime bytecode
+    pub(crate) overrides: Vec<(Address, Bytes)>,
+    /// The logs the transaction is expected to emit, in order
+    pub(crate) logs: Vec<Log>,
+    pub(crate) assertions: Vec<AccountAssertion>,
+}
+
+impl ForkTest {
+    /// Renders the test as a Solidity source file
+    pub(crate) fn render(&self) -> String {
+        let mut setup = vec![format!(
+            "        vm.createSelectFork(vm.envString(\"{}\"), {});",
+            RPC_URL_ENV_VAR,
+            self.block_number.saturating_sub(1)
+        )];
+        for (address, bytecode) in &self.overrides {
+            setup.push(format!("        vm.etch({}, hex\"{}\");", address, hex::encode(bytecode)));
+        }
+
+        let mut test = vec![];
+        for log in &self.logs {
+            test.push("        {".to_string());
+            test.push(format!(
+                "            bytes32[] memory topics = new bytes32[]({});",
+                log.topics().len()
+            ));
+            for (i, topic) in log.topics().iter().enumerate() {
+                test.push(format!("            topics[{}] = {};", i, topic));
+            }
+            test.push(format!(
+                "            vm.expectEmit(true, true, true, true, {});",
+                log.address
+            ));
+            test.push(format!(
+                "            _emitLog(topics, hex\"{}\");",
+                hex::encode(&log.data.data)
+            ));
+            test.push("        }".to_string());
+        }
+        test.push(format!("        vm.prank({});", self.from));
+        test.push(format!(
+            "        (bool success,) = {}.call{{value: {}}}(hex\"{}\");",
+            self.to,
+            self.value,
+            hex::encode(&self.calldata)
+        ));
+        test.push("        require(success, \"transaction reverted\");".to_string());
+
+        for assertion in &self.assertions {
+            test.push(format!(
+                "        require({}.balance == {}, \"unexpected balance of {}\");",
+                assertion.address, assertion.balance, assertion.address
+            ));
+            for (slot, value) in &assertion.storage {
+                test.push(format!(
+                    "        require(uint256(vm.load({}, bytes32(uint256(0x{:x})))) == {}, \"unexpected value in slot 0x{:x} of {}\");",
+                    assertion.address, slot, value, slot, assertion.address
+                ));
+            }
+        }
+
+        format!(
+            r#"// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+{VM_INTERFACE}
+
+/// Replays transaction {} on a fork of the block before it, with shadow contracts etched in.
+/// Generated by `shadow generate-foundry-test`.
+contract {} {{
+    Vm constant vm = Vm(address(uint160(uint256(keccak256("hevm cheat code")))));
+
+    function setUp() public {{
+{}
+    }}
+
+    function test_replay() public {{
+{}
+    }}
+
+{EMIT_LOG_HELPER}
+}}
+"#,
+            self.transaction_hash,
+            self.contract_name,
+            setup.join("\n"),
+            test.join("\n")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{address, b256, bytes, LogData};
+
+    use super::*;
+
+    #[test]
+    fn test_render_fork_test() {
+        let token = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let from = address!("1111111111111111111111111111111111111111");
+        let topic = b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+        let test = ForkTest {
+            contract_name: "ShadowForkTest_abcdef12".to_string(),
+            transaction_hash: TxHash::ZERO,
+            block_number: 100,
+            from,
+            to: token,
+            value: U256::ZERO,
+            calldata: bytes!("a9059cbb"),
+            overrides: vec![(token, bytes!("6080"))],
+            logs: vec![Log {
+                address: token,
+                data: LogData::new_unchecked(vec![topic], bytes!("01")),
+            }],
+            assertions: vec![AccountAssertion {
+                address: token,
+                balance: U256::from(1),
+                storage: vec![(U256::from(2), U256::from(3))],
+            }],
+        };
+
+        let source = test.render();
+        assert!(source.contains("contract ShadowForkTest_abcdef12 {"));
+        assert!(source.contains("vm.createSelectFork(vm.envString(\"SHADOW_RPC_URL\"), 99);"));
+        assert!(source.contains(&format!("vm.etch({}, hex\"6080\");", token)));
+        assert!(source.contains(&format!("topics[0] = {};", topic)));
+        assert!(source.contains(&format!("vm.expectEmit(true, true, true, true, {});", token)));
+        assert!(source.contains("_emitLog(topics, hex\"01\");"));
+        assert!(source.contains(&format!("vm.prank({});", from)));
+        assert!(source.contains(&format!("{}.call{{value: 0}}(hex\"a9059cbb\");", token)));
+        assert!(source.contains(&format!("require({}.balance == 1", token)));
+        assert!(source.contains(&format!("vm.load({}, bytes32(uint256(0x2)))) == 3", token)));
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use alloy::{
+    json_abi::JsonAbi,
+    primitives::{hex, Address, Selector},
+};
+use revm::{
+    interpreter::{CallInputs, CallOutcome},
+    Database, EvmContext, Inspector,
+};
+
+/// ANSI color for yellow, used to highlight the top gas consumers
+const YELLOW_ANSI_COLOR: &str = "\x1b[33m";
+/// Reset the ANSI color to the default
+const RESET_ANSI_COLOR: &str = "\x1b[0m";
+
+/// The gas used by every call to one function of a contract
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct GasReportEntry {
+    calls: usize,
+    gas_used: u64,
+}
+
+/// An [`Inspector`] which records the gas used by each call frame, grouped by the contract whose
+/// code ran and the selector it was called with. Like `forge test --gas-report`, the gas used by
+/// a frame includes the gas used by the calls it makes.
+#[derive(Debug, Default)]
+pub(crate) struct GasReportInspector {
+    entries: HashMap<(Address, Option<Selector>), GasReportEntry>,
+    /// The contract and selector of the frames currently being executed, innermost last
+    stack: Vec<(Address, Option<Selector>)>,
+}
+
+impl GasReportInspector {
+    /// Records a completed call
+    fn record(&mut self, address: Address, selector: Option<Selector>, gas_used: u64) {
+        let entry = self.entries.entry((address, selector)).or_default();
+        entry.calls += 1;
+        entry.gas_used += gas_used;
+    }
+
+    /// Renders the report as a table, most expensive first, with the top `top` rows
+    /// highlighted. Selectors are looked up in `abis` to show function signatures.
+    pub(crate) fn render(&self, total_gas: u64, abis: &[JsonAbi], top: usize) -> String {
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        entries.sort_by(|(a_key, a), (b_key, b)| {
+            b.gas_used.cmp(&a.gas_used).then_with(|| a_key.cmp(b_key))
+        });
+
+        let functions = entries
+            .iter()
+            .map(|((_, selector), _)| match selector {
+                Some(selector) => abis
+                    .iter()
+                    .flat_map(|abi| abi.functions())
+                    .find(|function| function.selector() == *selector)
+                    .map(|function| function.signature())
+                    .unwrap_or_else(|| format!("0x{}", hex::encode(selector))),
+                None => "fallback".to_string(),
+            })
+            .collect::<Vec<_>>();
+        let function_width = functions.iter().map(String::len).max().unwrap_or(0).max(8);
+
+        let mut lines = vec![format!(
+            "{:<42}  {:<function_width$}  {:>5}  {:>10}  {:>6}",
+            "ADDRESS", "FUNCTION", "CALLS", "GAS", "%"
+        )];
+        for (i, (((address, _), entry), function)) in entries.iter().zip(&functions).enumerate() {
+            let share =
+                if total_gas == 0 { 0.0 } else { entry.gas_used as f64 / total_gas as f64 * 100.0 };
+            let line = format!(
+                "{:<42}  {:<function_width$}  {:>5}  {:>10}  {:>5.1}%",
+                address.to_string(),
+                function,
+                entry.calls,
+                entry.gas_used,
+                share
+            );
+            lines.push(if i < top {
+                format!("{YELLOW_ANSI_COLOR}{}{RESET_ANSI_COLOR}", line)
+            } else {
+                line
+            });
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl<DB: Database> Inspector<DB> for GasReportInspector {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        // calls without calldata run the fallback or receive function
+        let selector = inputs.input.get(..4).map(Selector::from_slice);
+        self.stack.push((inputs.bytecode_address, selector));
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        if let Some((address, selector)) = self.stack.pop() {
+            self.record(address, selector, outcome.result.gas.spent());
+        }
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_gas_report() {
+        let token = Address::repeat_byte(0x11);
+        let abi = JsonAbi::parse(["function transfer(address to, uint256 amount)"]).unwrap();
+        let transfer = abi.functions().next().unwrap().selector();
+
+        let mut report = GasReportInspector::default();
+        report.record(token, Some(transfer), 30_000);
+        report.record(token, Some(transfer), 20_000);
+        report.record(token, Some(Selector::repeat_byte(0xab)), 40_000);
+        report.record(Address::repeat_byte(0x22), None, 10_000);
+
+        let rendered = report.render(100_000, &[abi], 1);
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("ADDRESS"));
+
+        // the most expensive function comes first, and is the only one highlighted
+        assert!(lines[1].starts_with(YELLOW_ANSI_COLOR));
+        assert!(lines[1].contains("transfer(address,uint256)"));
+        assert!(lines[1].contains("    2       50000   50.0%"));
+        assert!(!lines[2].starts_with(YELLOW_ANSI_COLOR));
+        assert!(lines[2].contains("0xabababab"));
+        assert!(lines[3].contains("fallback"));
+        assert!(lines[3].contains("10.0%"));
+    }
+}
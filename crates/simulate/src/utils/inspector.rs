@@ -0,0 +1,99 @@
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    Database, EvmContext, Inspector,
+};
+
+use crate::{
+    access_list::AccessListInspector,
+    gas_report::GasReportInspector,
+    trace::{Eip3155TraceInspector, GethTraceInspector, ParityTraceInspector},
+};
+
+/// The [`Inspector`] used when simulating a transaction, which records its storage accesses and
+/// call traces, and optionally every opcode it executes and the gas used by each function
+#[derive(Debug, Default)]
+pub(crate) struct SimulationInspector {
+    pub(crate) access_list: AccessListInspector,
+    pub(crate) calls: ParityTraceInspector,
+    /// Only present when geth-style struct logs were requested, since recording the stack at
+    /// every step is expensive
+    pub(crate) struct_logs: Option<GethTraceInspector>,
+    /// Only present when a gas report was requested
+    pub(crate) gas_report: Option<GasReportInspector>,
+    /// Only present when an EIP-3155 trace was requested
+    pub(crate) eip3155_trace: Option<Eip3155TraceInspector>,
+}
+
+impl SimulationInspector {
+    /// Creates a new [`SimulationInspector`], which records struct logs if `struct_logs` is set,
+    /// and the gas used by each function if `gas_report` is set
+    pub(crate) fn new(struct_logs: bool, gas_report: bool) -> Self {
+        Self {
+            struct_logs: struct_logs.then(GethTraceInspector::default),
+            gas_report: gas_report.then(GasReportInspector::default),
+            ..Default::default()
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for SimulationInspector {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.access_list.step(interp, context);
+        if let Some(struct_logs) = &mut self.struct_logs {
+            struct_logs.step(interp, context);
+        }
+        if let Some(eip3155_trace) = &mut self.eip3155_trace {
+            eip3155_trace.step(interp, context);
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        if let Some(struct_logs) = &mut self.struct_logs {
+            struct_logs.step_end(interp, context);
+        }
+        if let Some(eip3155_trace) = &mut self.eip3155_trace {
+            eip3155_trace.step_end(interp, context);
+        }
+    }
+
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        if let Some(gas_report) = &mut self.gas_report {
+            gas_report.call(context, inputs);
+        }
+        self.calls.call(context, inputs)
+    }
+
+    fn call_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        let outcome = match &mut self.gas_report {
+            Some(gas_report) => gas_report.call_end(context, inputs, outcome),
+            None => outcome,
+        };
+        self.calls.call_end(context, inputs, outcome)
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.calls.create(context, inputs)
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.calls.create_end(context, inputs, outcome)
+    }
+}
@@ -1,4 +1,16 @@
-/// event utils
-pub(crate) mod event;
+/// EIP-2930 access list utilities
+pub(crate) mod access_list;
+/// ERC-4337 EntryPoint utilities
+pub(crate) mod entry_point;
+/// Filters for the events printed after a simulation
+pub(crate) mod event_filter;
 /// evm utilities
 pub(crate) mod evm;
+/// Forge fork test generation utilities
+pub(crate) mod foundry_test;
+/// Per-function gas reporting
+pub(crate) mod gas_report;
+/// The inspector used when simulating transactions
+pub(crate) mod inspector;
+/// Parity and geth style transaction tracing utilities
+pub(crate) mod trace;
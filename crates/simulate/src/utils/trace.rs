@@ -0,0 +1,669 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+use alloy::primitives::{hex, Address, Bytes, B256, U256, U64};
+use revm::{
+    interpreter::{
+        opcode, CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome,
+        InstructionResult, Interpreter, OpCode,
+    },
+    primitives::ExecutionResult,
+    Database, EvmContext, Inspector,
+};
+use serde::Serialize;
+
+/// A single call frame in the format returned by Parity / OpenEthereum's
+/// `trace_replayTransaction`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ParityTrace {
+    pub(crate) action: ParityAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) result: Option<ParityResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+    pub(crate) subtraces: usize,
+    pub(crate) trace_address: Vec<usize>,
+    #[serde(rename = "type")]
+    pub(crate) kind: ParityTraceType,
+}
+
+/// The kind of a [`ParityTrace`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ParityTraceType {
+    Call,
+    Create,
+}
+
+/// The action a [`ParityTrace`] performed
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub(crate) enum ParityAction {
+    #[serde(rename_all = "camelCase")]
+    Call {
+        call_type: String,
+        from: Address,
+        gas: U64,
+        input: Bytes,
+        to: Address,
+        value: U256,
+    },
+    Create {
+        from: Address,
+        gas: U64,
+        init: Bytes,
+        value: U256,
+    },
+}
+
+/// The result of a successful [`ParityTrace`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub(crate) enum ParityResult {
+    #[serde(rename_all = "camelCase")]
+    Call { gas_used: U64, output: Bytes },
+    #[serde(rename_all = "camelCase")]
+    Create { address: Address, code: Bytes, gas_used: U64 },
+}
+
+/// An [`Inspector`] which records every call and contract creation made by a transaction as
+/// Parity-style call traces
+#[derive(Debug, Default)]
+pub(crate) struct ParityTraceInspector {
+    /// The recorded traces, in the order the frames were entered
+    traces: Vec<ParityTrace>,
+    /// The indices of the frames currently being executed, innermost last
+    stack: Vec<usize>,
+}
+
+impl ParityTraceInspector {
+    /// Returns the recorded call traces
+    pub(crate) fn traces(&self) -> &[ParityTrace] {
+        &self.traces
+    }
+
+    /// Records a new frame as a child of the frame currently being executed
+    fn start_frame(&mut self, action: ParityAction, kind: ParityTraceType) {
+        let trace_address = match self.stack.last() {
+            Some(&parent) => {
+                let parent = &mut self.traces[parent];
+                let mut trace_address = parent.trace_address.clone();
+                trace_address.push(parent.subtraces);
+                parent.subtraces += 1;
+                trace_address
+            }
+            None => vec![],
+        };
+
+        self.stack.push(self.traces.len());
+        self.traces.push(ParityTrace {
+            action,
+            result: None,
+            error: None,
+            subtraces: 0,
+            trace_address,
+            kind,
+        });
+    }
+
+    /// Completes the innermost frame with either its result, or the reason it failed
+    fn end_frame(&mut self, status: InstructionResult, result: ParityResult) {
+        let Some(index) = self.stack.pop() else { return };
+        let trace = &mut self.traces[index];
+        if status.is_ok() {
+            trace.result = Some(result);
+        } else {
+            trace.error = Some(trace_error(status));
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for ParityTraceInspector {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        let call_type = match inputs.scheme {
+            CallScheme::Call => "call",
+            CallScheme::CallCode => "callcode",
+            CallScheme::DelegateCall => "delegatecall",
+            CallScheme::StaticCall => "staticcall",
+        };
+        self.start_frame(
+            ParityAction::Call {
+                call_type: call_type.to_string(),
+                from: inputs.caller,
+                gas: U64::from(inputs.gas_limit),
+                input: inputs.input.clone(),
+                to: inputs.target_address,
+                value: inputs.value.get(),
+            },
+            ParityTraceType::Call,
+        );
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.end_frame(
+            outcome.result.result,
+            ParityResult::Call {
+                gas_used: U64::from(outcome.result.gas.spent()),
+                output: outcome.result.output.clone(),
+            },
+        );
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.start_frame(
+            ParityAction::Create {
+                from: inputs.caller,
+                gas: U64::from(inputs.gas_limit),
+                init: inputs.init_code.clone(),
+                value: inputs.value,
+            },
+            ParityTraceType::Create,
+        );
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.end_frame(
+            outcome.result.result,
+            ParityResult::Create {
+                address: outcome.address.unwrap_or_default(),
+                code: outcome.result.output.clone(),
+                gas_used: U64::from(outcome.result.gas.spent()),
+            },
+        );
+        outcome
+    }
+}
+
+/// Returns the error message Parity uses for a failed frame
+fn trace_error(status: InstructionResult) -> String {
+    match status {
+        InstructionResult::Revert => "Reverted".to_string(),
+        InstructionResult::OutOfGas |
+        InstructionResult::MemoryOOG |
+        InstructionResult::MemoryLimitOOG |
+        InstructionResult::PrecompileOOG |
+        InstructionResult::InvalidOperandOOG => "Out of gas".to_string(),
+        InstructionResult::InvalidJump => "Bad jump destination".to_string(),
+        InstructionResult::OpcodeNotFound | InstructionResult::InvalidEFOpcode => {
+            "Bad instruction".to_string()
+        }
+        InstructionResult::StackUnderflow => "Stack underflow".to_string(),
+        InstructionResult::StackOverflow => "Out of stack".to_string(),
+        InstructionResult::CallTooDeep => "Call depth limit reached".to_string(),
+        status => format!("{:?}", status),
+    }
+}
+
+/// A single executed opcode, in the format returned by geth's `debug_traceTransaction` with the
+/// default struct logger
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StructLog {
+    pub(crate) pc: u64,
+    pub(crate) op: String,
+    pub(crate) gas: u64,
+    pub(crate) gas_cost: u64,
+    pub(crate) depth: u64,
+    pub(crate) stack: Vec<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+}
+
+/// A transaction's execution in the format returned by geth's `debug_traceTransaction` with the
+/// default struct logger
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GethTrace {
+    pub(crate) gas: u64,
+    pub(crate) failed: bool,
+    pub(crate) return_value: String,
+    pub(crate) struct_logs: Vec<StructLog>,
+}
+
+/// An [`Inspector`] which records every opcode executed by a transaction as geth-style struct
+/// logs
+#[derive(Debug, Default)]
+pub(crate) struct GethTraceInspector {
+    /// The recorded struct logs, in execution order
+    struct_logs: Vec<StructLog>,
+    /// The gas remaining before the opcode currently being executed
+    gas_before: u64,
+}
+
+impl GethTraceInspector {
+    /// Returns the recorded struct logs, along with the transaction's outcome, as a geth trace
+    pub(crate) fn into_trace(self, gas_used: u64, failed: bool, output: &[u8]) -> GethTrace {
+        GethTrace {
+            gas: gas_used,
+            failed,
+            return_value: hex::encode(output),
+            struct_logs: self.struct_logs,
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for GethTraceInspector {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        let opcode = interp.current_opcode();
+        self.gas_before = interp.gas.remaining();
+        self.struct_logs.push(StructLog {
+            pc: interp.program_counter() as u64,
+            op: OpCode::new(opcode)
+                .map(|op| op.as_str().to_string())
+                .unwrap_or_else(|| format!("opcode 0x{:02x} not defined", opcode)),
+            gas: self.gas_before,
+            gas_cost: 0,
+            depth: context.journaled_state.depth(),
+            stack: interp.stack.data().clone(),
+            error: None,
+        });
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let Some(log) = self.struct_logs.last_mut() else { return };
+        log.gas_cost = self.gas_before.saturating_sub(interp.gas.remaining());
+        if interp.instruction_result.is_error() {
+            log.error = Some(format!("{:?}", interp.instruction_result));
+        }
+    }
+}
+
+/// A single executed opcode, in the format defined by EIP-3155
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Eip3155Step {
+    pc: u64,
+    op: u8,
+    gas: U64,
+    gas_cost: U64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory: Option<Bytes>,
+    mem_size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stack: Option<Vec<U256>>,
+    return_data: Bytes,
+    depth: u64,
+    refund: u64,
+    op_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage: Option<BTreeMap<B256, B256>>,
+}
+
+/// The summary written after the last step of an EIP-3155 trace. The state root is omitted,
+/// since simulations don't compute one.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Eip3155Summary {
+    output: Bytes,
+    gas_used: U64,
+    pass: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// An [`Inspector`] which streams every opcode executed by a transaction to a writer as an
+/// EIP-3155 trace, one JSON object per line. Memory and the stack are only included if
+/// requested, since they make up most of the trace's size.
+#[derive(Debug)]
+pub(crate) struct Eip3155TraceInspector<W: Write = BufWriter<File>> {
+    writer: W,
+    memory: bool,
+    stack: bool,
+    /// The opcode currently being executed, which is written once its gas cost is known
+    pending: Option<Eip3155Step>,
+    /// The slot read by the SLOAD currently being executed
+    sload_key: Option<B256>,
+    /// The storage slots each contract has read or written so far, which are included with
+    /// every SLOAD and SSTORE, like geth's struct logger
+    storage: HashMap<Address, BTreeMap<B256, B256>>,
+    /// The first error hit while writing the trace, returned by [`Self::finish`]
+    write_error: Option<std::io::Error>,
+}
+
+impl<W: Write> Eip3155TraceInspector<W> {
+    /// Creates a new [`Eip3155TraceInspector`] which writes to `writer`
+    pub(crate) fn new(writer: W, memory: bool, stack: bool) -> Self {
+        Self {
+            writer,
+            memory,
+            stack,
+            pending: None,
+            sload_key: None,
+            storage: HashMap::new(),
+            write_error: None,
+        }
+    }
+
+    /// Writes a line of the trace, keeping the first error so tracing doesn't abort execution
+    fn write_line<T: Serialize>(&mut self, line: &T) {
+        if self.write_error.is_some() {
+            return;
+        }
+
+        let result = serde_json::to_writer(&mut self.writer, line)
+            .map_err(std::io::Error::from)
+            .and_then(|_| writeln!(self.writer));
+        if let Err(e) = result {
+            self.write_error = Some(e);
+        }
+    }
+
+    /// Writes the trace's summary line, and flushes the writer
+    pub(crate) fn finish(&mut self, result: &ExecutionResult) -> std::io::Result<()> {
+        let error = match result {
+            ExecutionResult::Success { .. } => None,
+            ExecutionResult::Revert { .. } => Some("execution reverted".to_string()),
+            ExecutionResult::Halt { reason, .. } => Some(format!("{:?}", reason)),
+        };
+        self.write_line(&Eip3155Summary {
+            output: result.output().cloned().unwrap_or_default(),
+            gas_used: U64::from(result.gas_used()),
+            pass: result.is_success(),
+            error,
+        });
+
+        match self.write_error.take() {
+            Some(e) => Err(e),
+            None => self.writer.flush(),
+        }
+    }
+}
+
+impl<DB: Database, W: Write> Inspector<DB> for Eip3155TraceInspector<W> {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        let op = interp.current_opcode();
+        let address = interp.contract.target_address;
+
+        // an SSTORE's slot and value are on the stack before it executes, but an SLOAD's value
+        // is only known after
+        let storage = match op {
+            opcode::SSTORE => match (interp.stack().peek(0), interp.stack().peek(1)) {
+                (Ok(key), Ok(value)) => {
+                    let slots = self.storage.entry(address).or_default();
+                    slots.insert(B256::from(key.to_be_bytes()), B256::from(value.to_be_bytes()));
+                    Some(slots.clone())
+                }
+                _ => None,
+            },
+            opcode::SLOAD => {
+                self.sload_key =
+                    interp.stack().peek(0).ok().map(|key| B256::from(key.to_be_bytes()));
+                None
+            }
+            _ => None,
+        };
+
+        let memory = interp.shared_memory.context_memory();
+        self.pending = Some(Eip3155Step {
+            pc: interp.program_counter() as u64,
+            op,
+            gas: U64::from(interp.gas.remaining()),
+            gas_cost: U64::ZERO,
+            memory: self.memory.then(|| Bytes::copy_from_slice(memory)),
+            mem_size: memory.len() as u64,
+            stack: self.stack.then(|| interp.stack.data().clone()),
+            return_data: interp.return_data_buffer.clone(),
+            depth: context.journaled_state.depth(),
+            refund: interp.gas.refunded().max(0) as u64,
+            op_name: OpCode::new(op)
+                .map(|op| op.as_str().to_string())
+                .unwrap_or_else(|| format!("opcode 0x{:02x} not defined", op)),
+            error: None,
+            storage,
+        });
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let Some(mut step) = self.pending.take() else { return };
+        step.gas_cost = U64::from(step.gas.to::<u64>().saturating_sub(interp.gas.remaining()));
+        if interp.instruction_result.is_error() {
+            step.error = Some(format!("{:?}", interp.instruction_result));
+        }
+
+        if let (Some(key), Ok(value)) = (self.sload_key.take(), interp.stack().peek(0)) {
+            let slots = self.storage.entry(interp.contract.target_address).or_default();
+            slots.insert(key, B256::from(value.to_be_bytes()));
+            step.storage = Some(slots.clone());
+        }
+
+        self.write_line(&step);
+    }
+}
+
+/// Renders call traces as an indented call tree, one frame per line
+pub(crate) fn format_call_tree(traces: &[ParityTrace]) -> String {
+    traces
+        .iter()
+        .map(|trace| {
+            let indent = "  ".repeat(trace.trace_address.len());
+            let frame = match &trace.action {
+                ParityAction::Call { call_type, from, input, to, value, .. } => {
+                    let selector = input.get(..4).map(hex::encode_prefixed).unwrap_or_default();
+                    let value =
+                        if value.is_zero() { String::new() } else { format!(" value: {}", value) };
+                    format!("{} {} -> {} {}{}", call_type.to_uppercase(), from, to, selector, value)
+                }
+                ParityAction::Create { from, value, .. } => {
+                    let value =
+                        if value.is_zero() { String::new() } else { format!(" value: {}", value) };
+                    format!("CREATE {}{}", from, value)
+                }
+            };
+            let outcome = match (&trace.result, &trace.error) {
+                (Some(ParityResult::Call { gas_used, .. }), _) => format!("ok, {} gas", gas_used),
+                (Some(ParityResult::Create { address, gas_used, .. }), _) => {
+                    format!("created {}, {} gas", address, gas_used)
+                }
+                (None, Some(error)) => format!("failed: {}", error),
+                (None, None) => "incomplete".to_string(),
+            };
+            format!("{}{} [{}]", indent, frame, outcome)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{evm::build_sim_env, trace::*};
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        inspector_handle_register,
+        primitives::{AccountInfo, BlockEnv, Bytecode},
+        EvmBuilder,
+    };
+
+    /// Builds a database containing a contract at `0x22..` which calls `0x33..`, and a contract
+    /// at `0x33..` which writes to storage and returns
+    fn db() -> CacheDB<EmptyDB> {
+        let (caller, callee) = (Address::repeat_byte(0x22), Address::repeat_byte(0x33));
+        let mut db = CacheDB::new(EmptyDB::default());
+
+        // PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 PUSH20 <callee> GAS CALL STOP
+        let mut code = vec![0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73];
+        code.extend_from_slice(callee.as_slice());
+        code.extend_from_slice(&[0x5a, 0xf1, 0x00]);
+        let code = Bytecode::new_raw(code.into());
+        db.insert_account_info(
+            caller,
+            AccountInfo { code_hash: code.hash_slow(), code: Some(code), ..Default::default() },
+        );
+
+        // PUSH1 0x01 PUSH1 0x00 SSTORE STOP
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x01, 0x60, 0x00, 0x55, 0x00]));
+        db.insert_account_info(
+            callee,
+            AccountInfo { code_hash: code.hash_slow(), code: Some(code), ..Default::default() },
+        );
+
+        db
+    }
+
+    #[test]
+    fn test_parity_trace_schema() {
+        let env = build_sim_env(
+            Address::repeat_byte(0x11),
+            Some(Address::repeat_byte(0x22)),
+            U256::ZERO,
+            Bytes::new(),
+            BlockEnv::default(),
+            1,
+        );
+        let mut evm = EvmBuilder::default()
+            .with_db(db())
+            .with_env(env)
+            .with_external_context(ParityTraceInspector::default())
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().expect("transaction should execute");
+
+        let traces = serde_json::to_value(evm.context.external.traces()).unwrap();
+        let traces = traces.as_array().expect("traces should be an array");
+        assert_eq!(traces.len(), 2);
+
+        // the top-level call has one subtrace and an empty trace address
+        let top = &traces[0];
+        assert_eq!(top["type"], "call");
+        assert_eq!(top["action"]["callType"], "call");
+        assert_eq!(top["action"]["from"], "0x1111111111111111111111111111111111111111");
+        assert_eq!(top["action"]["to"], "0x2222222222222222222222222222222222222222");
+        assert_eq!(top["action"]["value"], "0x0");
+        assert!(top["action"]["gas"].as_str().unwrap().starts_with("0x"));
+        assert!(top["result"]["gasUsed"].as_str().unwrap().starts_with("0x"));
+        assert_eq!(top["result"]["output"], "0x");
+        assert_eq!(top["subtraces"], 1);
+        assert_eq!(top["traceAddress"], serde_json::json!([]));
+        assert!(top.get("error").is_none());
+
+        // and the nested call is addressed as its first child
+        let nested = &traces[1];
+        assert_eq!(nested["action"]["to"], "0x3333333333333333333333333333333333333333");
+        assert_eq!(nested["subtraces"], 0);
+        assert_eq!(nested["traceAddress"], serde_json::json!([0]));
+    }
+
+    #[test]
+    fn test_geth_trace_schema() {
+        let env = build_sim_env(
+            Address::repeat_byte(0x11),
+            Some(Address::repeat_byte(0x22)),
+            U256::ZERO,
+            Bytes::new(),
+            BlockEnv::default(),
+            1,
+        );
+        let mut evm = EvmBuilder::default()
+            .with_db(db())
+            .with_env(env)
+            .with_external_context(GethTraceInspector::default())
+            .append_handler_register(inspector_handle_register)
+            .build();
+        let result = evm.transact().expect("transaction should execute").result;
+
+        let inspector = std::mem::take(&mut evm.context.external);
+        let trace = serde_json::to_value(inspector.into_trace(
+            result.gas_used(),
+            !result.is_success(),
+            result.output().map(|o| o.as_ref()).unwrap_or_default(),
+        ))
+        .unwrap();
+
+        assert_eq!(trace["failed"], false);
+        assert_eq!(trace["returnValue"], "");
+        assert!(trace["gas"].as_u64().unwrap() > 0);
+
+        // 9 opcodes in the caller, and 4 in the callee
+        let struct_logs = trace["structLogs"].as_array().expect("structLogs should be an array");
+        assert_eq!(struct_logs.len(), 13);
+
+        let first = &struct_logs[0];
+        assert_eq!(first["pc"], 0);
+        assert_eq!(first["op"], "PUSH1");
+        assert_eq!(first["gasCost"], 3);
+        assert_eq!(first["depth"], 1);
+        assert_eq!(first["stack"], serde_json::json!([]));
+
+        let sstore = struct_logs.iter().find(|log| log["op"] == "SSTORE").unwrap();
+        assert_eq!(sstore["depth"], 2);
+        assert_eq!(sstore["stack"], serde_json::json!(["0x1", "0x0"]));
+    }
+
+    #[test]
+    fn test_eip3155_trace_schema() {
+        let env = build_sim_env(
+            Address::repeat_byte(0x11),
+            Some(Address::repeat_byte(0x22)),
+            U256::ZERO,
+            Bytes::new(),
+            BlockEnv::default(),
+            1,
+        );
+        let mut evm = EvmBuilder::default()
+            .with_db(db())
+            .with_env(env)
+            .with_external_context(Eip3155TraceInspector::new(vec![], false, true))
+            .append_handler_register(inspector_handle_register)
+            .build();
+        let result = evm.transact().expect("transaction should execute").result;
+
+        let inspector = &mut evm.context.external;
+        inspector.finish(&result).unwrap();
+        let lines = String::from_utf8(inspector.writer.clone()).unwrap();
+        let lines = lines
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        // 13 opcodes, followed by the summary
+        assert_eq!(lines.len(), 14);
+
+        let first = &lines[0];
+        assert_eq!(first["pc"], 0);
+        assert_eq!(first["op"], 0x60);
+        assert_eq!(first["opName"], "PUSH1");
+        assert_eq!(first["gasCost"], "0x3");
+        assert_eq!(first["depth"], 1);
+        assert_eq!(first["stack"], serde_json::json!([]));
+        assert!(first.get("memory").is_none());
+
+        let sstore = lines.iter().find(|line| line["opName"] == "SSTORE").unwrap();
+        assert_eq!(sstore["depth"], 2);
+        assert_eq!(sstore["stack"], serde_json::json!(["0x1", "0x0"]));
+        assert_eq!(
+            sstore["storage"]["0x0000000000000000000000000000000000000000000000000000000000000000"],
+            "0x0000000000000000000000000000000000000000000000000000000000000001"
+        );
+
+        let summary = &lines[13];
+        assert_eq!(summary["pass"], true);
+        assert_eq!(summary["output"], "0x");
+        assert!(summary["gasUsed"].as_str().unwrap().starts_with("0x"));
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use alloy::primitives::{hex, Address};
+use eyre::{bail, eyre, OptionExt, Result};
+use regex::Regex;
+use serde_json::Value;
+use shadow_common::{
+    compiler::{find_contract_artifact, line_and_column},
+    ShadowContractGroupInfo, ShadowContractInfo,
+};
+use tracing::{error, warn};
+
+use crate::{
+    source_map::{decode_source_map, instruction_offsets, SourceLocation},
+    SourceMapArgs,
+};
+
+/// ANSI color for gray
+const GRAY_ANSI_COLOR: &str = "\x1b[90m";
+/// Reset the ANSI color to the default
+const RESET_ANSI_COLOR: &str = "\x1b[0m";
+
+/// The `source-map` subcommand. Maps program counters in a shadow contract's runtime bytecode
+/// back to the Solidity source they were compiled from, using the source map in its `forge`
+/// build artifact.
+pub async fn source_map(args: SourceMapArgs) -> Result<()> {
+    let address: Address = args.address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+    let (start, end) = match (&args.pc, &args.range) {
+        (Some(pc), _) => {
+            let pc = parse_pc(pc)?;
+            (pc, pc)
+        }
+        (None, Some(range)) => {
+            let (start, end) =
+                range.split_once(':').ok_or_eyre("--range must be given as <start-pc>:<end-pc>")?;
+            (parse_pc(start)?, parse_pc(end)?)
+        }
+        (None, None) => bail!("either --pc or --range is required"),
+    };
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let contract_dir = group_info.contract_path(&address, args.chain_id)?;
+    let info = ShadowContractInfo::from_path(&contract_dir.join("info.json"))?;
+
+    let out_dir = contract_dir.join("out");
+    if !out_dir.exists() {
+        bail!("{} has not been compiled. Run `shadow compile` first.", address);
+    }
+    let (artifact, artifact_path) = find_contract_artifact(&out_dir, &info.name)?;
+    let bytecode = artifact["deployedBytecode"]["object"]
+        .as_str()
+        .ok_or_else(|| eyre!("{} has no runtime bytecode", artifact_path.display()))?;
+    let source_map = artifact["deployedBytecode"]["sourceMap"]
+        .as_str()
+        .ok_or_else(|| eyre!("{} has no source map", artifact_path.display()))?;
+
+    let locations = decode_source_map(source_map)?;
+    let offsets = instruction_offsets(&decode_bytecode(bytecode)?);
+    let source_files = source_files(&out_dir);
+
+    let mut found = false;
+    let mut previous = None;
+    for (pc, location) in offsets.iter().zip(&locations) {
+        if *pc < start || *pc > end {
+            continue;
+        }
+        found = true;
+
+        // consecutive instructions usually share a location, so only print it once
+        if previous == Some(*location) {
+            continue;
+        }
+        previous = Some(*location);
+        print_location(*pc, location, &source_files, &contract_dir);
+    }
+
+    if !found {
+        bail!(
+            "no instructions found between pc {} and {}. The bytecode has {} instructions with source mappings",
+            start,
+            end,
+            offsets.len().min(locations.len())
+        );
+    }
+
+    Ok(())
+}
+
+/// Parses a program counter, given in decimal or `0x`-prefixed hex
+fn parse_pc(pc: &str) -> Result<usize> {
+    let pc = pc.trim();
+    match pc.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => pc.parse(),
+    }
+    .map_err(|e| eyre!("Invalid program counter {}: {}", pc, e))
+}
+
+/// Decodes an artifact's bytecode. Unlinked library placeholders are zeroed, since only the
+/// instruction boundaries matter here.
+fn decode_bytecode(bytecode: &str) -> Result<Vec<u8>> {
+    let placeholder = Regex::new(r"__\$[0-9a-fA-F]{34}\$__")?;
+    let bytecode = placeholder.replace_all(bytecode.trim_start_matches("0x"), "0".repeat(40));
+
+    hex::decode(bytecode.as_ref()).map_err(|e| eyre!("Invalid bytecode in artifact: {}", e))
+}
+
+/// Maps source file IDs to their paths, using the artifacts in the build directory. Each
+/// artifact records the ID of the file it was compiled from.
+fn source_files(out_dir: &Path) -> HashMap<u32, String> {
+    let mut files = HashMap::new();
+    for entry in walkdir::WalkDir::new(out_dir).into_iter().filter_map(|e| e.ok()).filter(|e| {
+        e.file_type().is_file() && e.path().extension().is_some_and(|ext| ext == "json")
+    }) {
+        let Some(artifact) = std::fs::read_to_string(entry.path())
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Value>(&contents).ok())
+        else {
+            continue;
+        };
+        let Some(id) = artifact["id"].as_u64().and_then(|id| u32::try_from(id).ok()) else {
+            continue;
+        };
+        let path = artifact["ast"]["absolutePath"].as_str().map(str::to_string).or_else(|| {
+            artifact["metadata"]["settings"]["compilationTarget"]
+                .as_object()
+                .and_then(|target| target.keys().next().cloned())
+        });
+        if let Some(path) = path {
+            files.insert(id, path);
+        }
+    }
+
+    files
+}
+
+/// Prints a source location, followed by the source lines it spans
+fn print_location(
+    pc: usize,
+    location: &SourceLocation,
+    source_files: &HashMap<u32, String>,
+    contract_dir: &Path,
+) {
+    let Some(file) = location.file else {
+        println!("pc {pc} (0x{pc:x}): compiler-generated code");
+        return;
+    };
+    let Some(path) = source_files.get(&file) else {
+        println!(
+            "pc {pc} (0x{pc:x}): source file {file}, bytes {}..{}",
+            location.offset,
+            location.offset + location.length
+        );
+        return;
+    };
+    let Ok(source) = std::fs::read_to_string(contract_dir.join(path)) else {
+        warn!("failed to read {}", path);
+        println!(
+            "pc {pc} (0x{pc:x}): {path}, bytes {}..{}",
+            location.offset,
+            location.offset + location.length
+        );
+        return;
+    };
+
+    let Some((line, column)) = line_and_column(&source, location.offset) else {
+        warn!("{} is shorter than its source map expects", path);
+        println!(
+            "pc {pc} (0x{pc:x}): {path}, bytes {}..{}",
+            location.offset,
+            location.offset + location.length
+        );
+        return;
+    };
+    let end_line = line_and_column(&source, location.offset + location.length)
+        .map_or(line, |(end_line, _)| end_line);
+    let jump = match location.jump {
+        'i' => " (into function)",
+        'o' => " (out of function)",
+        _ => "",
+    };
+    println!("pc {pc} (0x{pc:x}): {path}:{line}:{column}{jump}");
+    for (number, text) in
+        source.lines().enumerate().skip(line as usize - 1).take((end_line - line) as usize + 1)
+    {
+        println!("{GRAY_ANSI_COLOR}{:>6} |{RESET_ANSI_COLOR} {}", number + 1, text);
+    }
+}
@@ -0,0 +1,25 @@
+use clap::Parser;
+
+/// Arguments for the `source-map` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Map program counters in a shadow contract's bytecode back to its source")]
+pub struct SourceMapArgs {
+    /// The address of the contract.
+    pub address: String,
+
+    /// The program counter to look up, in decimal or `0x`-prefixed hex.
+    #[clap(long, required_unless_present = "range", conflicts_with = "range")]
+    pub pc: Option<String>,
+
+    /// A range of program counters to look up, as `<start-pc>:<end-pc>`, inclusive.
+    #[clap(long, required = false)]
+    pub range: Option<String>,
+
+    /// The chain ID of the contract. Required if the address is tracked on multiple chains.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+}
@@ -0,0 +1,2 @@
+/// Solidity source map decoding
+pub(crate) mod source_map;
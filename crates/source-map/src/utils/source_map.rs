@@ -0,0 +1,104 @@
+use eyre::{eyre, Result};
+
+/// The `PUSH1` opcode. `PUSH1` through `PUSH32` are followed by 1 to 32 bytes of immediate data.
+const PUSH1: u8 = 0x60;
+/// The `PUSH32` opcode
+const PUSH32: u8 = 0x7f;
+
+/// The source range an instruction was generated from, as encoded in a solc source map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SourceLocation {
+    /// The byte offset of the range in the source file
+    pub(crate) offset: usize,
+    /// The length of the range, in bytes
+    pub(crate) length: usize,
+    /// The source file's ID, or `None` if the instruction wasn't generated from a source file
+    pub(crate) file: Option<u32>,
+    /// Whether the instruction jumps into (`i`) or out of (`o`) a function, or is a regular
+    /// jump (`-`)
+    pub(crate) jump: char,
+}
+
+/// Decodes a solc source map, which has one `s:l:f:j:m` entry per instruction. Empty fields, and
+/// fields left out at the end of an entry, repeat the previous entry's value.
+pub(crate) fn decode_source_map(source_map: &str) -> Result<Vec<SourceLocation>> {
+    let mut locations = Vec::new();
+    let mut current = SourceLocation { offset: 0, length: 0, file: None, jump: '-' };
+    for (i, entry) in source_map.split(';').enumerate() {
+        let fields = entry.split(':').collect::<Vec<_>>();
+        let field = |index: usize| fields.get(index).copied().filter(|field| !field.is_empty());
+        let invalid = |field: &str| eyre!("invalid source map entry {}: {}", i, field);
+
+        if let Some(offset) = field(0) {
+            current.offset = offset.parse().map_err(|_| invalid(offset))?;
+        }
+        if let Some(length) = field(1) {
+            current.length = length.parse().map_err(|_| invalid(length))?;
+        }
+        if let Some(file) = field(2) {
+            // `-1` marks code which doesn't come from a source file
+            current.file = match file.parse::<i64>().map_err(|_| invalid(file))? {
+                file if file < 0 => None,
+                file => Some(file.try_into().map_err(|_| invalid(entry))?),
+            };
+        }
+        if let Some(jump) = field(3) {
+            current.jump = jump.chars().next().ok_or_else(|| invalid(jump))?;
+        }
+
+        locations.push(current);
+    }
+
+    Ok(locations)
+}
+
+/// Returns the program counter of each instruction in the bytecode, in order. Source map
+/// entries are indexed by instruction rather than by program counter, since `PUSH` instructions
+/// span multiple bytes.
+pub(crate) fn instruction_offsets(bytecode: &[u8]) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    let mut pc = 0;
+    while pc < bytecode.len() {
+        offsets.push(pc);
+        let opcode = bytecode[pc];
+        pc += 1;
+        if (PUSH1..=PUSH32).contains(&opcode) {
+            pc += (opcode - PUSH1 + 1) as usize;
+        }
+    }
+
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_source_map() {
+        let locations = decode_source_map("26:50:0:-:0;;;:10;80:2:1:i;::-1:o").unwrap();
+        assert_eq!(locations.len(), 6);
+        assert_eq!(
+            locations[0],
+            SourceLocation { offset: 26, length: 50, file: Some(0), jump: '-' }
+        );
+        assert_eq!(locations[2], locations[0]);
+        assert_eq!(
+            locations[3],
+            SourceLocation { offset: 26, length: 10, file: Some(0), jump: '-' }
+        );
+        assert_eq!(
+            locations[4],
+            SourceLocation { offset: 80, length: 2, file: Some(1), jump: 'i' }
+        );
+        assert_eq!(locations[5], SourceLocation { offset: 80, length: 2, file: None, jump: 'o' });
+        assert!(decode_source_map("a:1:0").is_err());
+    }
+
+    #[test]
+    fn test_instruction_offsets() {
+        // PUSH1 0x80, PUSH1 0x40, MSTORE, PUSH2 0x0102, STOP
+        let bytecode = [0x60, 0x80, 0x60, 0x40, 0x52, 0x61, 0x01, 0x02, 0x00];
+        assert_eq!(instruction_offsets(&bytecode), vec![0, 2, 4, 5, 8]);
+    }
+}
@@ -0,0 +1,134 @@
+use std::{path::PathBuf, str::FromStr};
+
+use alloy::primitives::Address;
+use alloy_chains::Chain;
+use eyre::{eyre, Result};
+use serde::Serialize;
+use shadow_common::{hash::source_changed, ShadowContractGroupInfo, ShadowContractInfo};
+use tracing::error;
+
+use super::Status;
+use crate::{ContractSort, ContractsArgs};
+
+/// A contract in the group, as printed by `contracts --json`. Extends
+/// [`shadow_common::ShadowContractEntry`] with fields computed from the contract's files.
+#[derive(Debug, Clone, Serialize)]
+struct ContractListing {
+    address: Address,
+    chain_id: u64,
+    /// The chain's name, if it's a known chain
+    chain: Option<String>,
+    /// The contract's name, from its `info.json`
+    name: Option<String>,
+    /// Whether the contract's compiled artifacts exist
+    compiled: bool,
+    /// Whether the contract's source differs from the verified original. `None` if its
+    /// `original.json` is missing.
+    modified: Option<bool>,
+}
+
+/// The `contracts` subcommand. Lists the contracts in a shadow contract group, optionally
+/// filtered by chain, address, compilation status and whether they've been modified.
+pub async fn contracts(args: ContractsArgs) -> Result<()> {
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let artifact_path = group_info.artifact_path();
+
+    let chain_id = args
+        .chain
+        .as_deref()
+        .map(|chain| Chain::from_str(chain).map(|chain| chain.id()))
+        .transpose()
+        .map_err(|e| eyre!("Invalid chain: {}", e))?;
+    let address_pattern = args
+        .address_contains
+        .as_deref()
+        .map(|pattern| pattern.trim_start_matches("0x").to_lowercase());
+
+    let mut listings = vec![];
+    for contract in &group_info.contracts {
+        if chain_id.is_some_and(|chain_id| chain_id != contract.chain_id) {
+            continue;
+        }
+        if address_pattern.as_ref().is_some_and(|pattern| {
+            !contract.address.to_string().to_lowercase()[2..].contains(pattern.as_str())
+        }) {
+            continue;
+        }
+
+        let contract_dir = group_info.contract_path(&contract.address, Some(contract.chain_id))?;
+        let listing = ContractListing {
+            address: contract.address,
+            chain_id: contract.chain_id,
+            chain: Chain::from_id(contract.chain_id).named().map(|named| named.to_string()),
+            name: ShadowContractInfo::from_path(&contract_dir.join("info.json"))
+                .ok()
+                .map(|info| info.name),
+            compiled: contract.is_compiled(&artifact_path),
+            modified: source_changed(&contract_dir).ok(),
+        };
+        if args.uncompiled && listing.compiled {
+            continue;
+        }
+        if args.modified && listing.modified != Some(true) {
+            continue;
+        }
+        listings.push(listing);
+    }
+
+    match args.sort {
+        ContractSort::Address => listings.sort_by_key(|listing| listing.address),
+        ContractSort::ChainId => {
+            listings.sort_by_key(|listing| (listing.chain_id, listing.address))
+        }
+        ContractSort::Name => listings.sort_by(|a, b| {
+            a.name.cmp(&b.name).then_with(|| (a.chain_id, a.address).cmp(&(b.chain_id, b.address)))
+        }),
+    }
+
+    if args.count {
+        println!("{}", listings.len());
+        return Ok(());
+    }
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&listings)?);
+        return Ok(());
+    }
+
+    let name_width = listings
+        .iter()
+        .filter_map(|listing| listing.name.as_ref().map(String::len))
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    println!(
+        "{:<42}  {:<16}  {:<name_width$}  {:<8}  MODIFIED",
+        "ADDRESS", "CHAIN", "NAME", "COMPILED"
+    );
+    for listing in &listings {
+        let chain = match &listing.chain {
+            Some(chain) => format!("{} ({})", chain, listing.chain_id),
+            None => listing.chain_id.to_string(),
+        };
+        let compiled = if listing.compiled { Status::Good("Y") } else { Status::Warn("N") };
+        let modified = match listing.modified {
+            Some(true) => Status::Good("Y"),
+            Some(false) => Status::Warn("N"),
+            None => Status::Bad("?"),
+        };
+        println!(
+            "{:<42}  {:<16}  {:<name_width$}  {}  {}",
+            listing.address,
+            chain,
+            listing.name.as_deref().unwrap_or("?"),
+            compiled.render(8),
+            modified.render(0)
+        );
+    }
+
+    Ok(())
+}
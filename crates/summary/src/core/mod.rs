@@ -0,0 +1,142 @@
+mod contracts;
+
+pub use contracts::*;
+
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use eyre::{eyre, Result};
+use shadow_common::{
+    hash::{sha256_file, source_changed},
+    ShadowContractEntry, ShadowContractGroupInfo, DEFAULT_README,
+};
+use tracing::error;
+
+use crate::{
+    constants::{GREEN_ANSI_COLOR, RED_ANSI_COLOR, RESET_ANSI_COLOR, YELLOW_ANSI_COLOR},
+    SummaryArgs,
+};
+
+/// A status cell in the summary table
+#[derive(Debug, Clone, Copy)]
+enum Status {
+    Good(&'static str),
+    Warn(&'static str),
+    Bad(&'static str),
+}
+
+impl Status {
+    /// Pads the status to `width` characters and colorizes it
+    fn render(self, width: usize) -> String {
+        let (color, text) = match self {
+            Status::Good(text) => (GREEN_ANSI_COLOR, text),
+            Status::Warn(text) => (YELLOW_ANSI_COLOR, text),
+            Status::Bad(text) => (RED_ANSI_COLOR, text),
+        };
+        format!("{color}{text:<width$}{RESET_ANSI_COLOR}")
+    }
+}
+
+/// The `summary` subcommand. Displays an overview of a contract group's readiness for
+/// `shadow push`. Never modifies any files.
+pub async fn summary(args: SummaryArgs) -> Result<()> {
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let artifact_path = group_info.artifact_path();
+
+    let has_name = group_info.display_name != "Unnamed Contract Group";
+    let readme_updated =
+        std::fs::read_to_string(root_dir.join("README.md")).is_ok_and(|r| r != DEFAULT_README);
+
+    println!("name:      {}", group_info.display_name);
+    println!(
+        "creator:   {}",
+        group_info.creator.map_or(Status::Bad("NOT SET").render(0), |c| c.to_string())
+    );
+    println!("contracts: {}", group_info.contracts.len());
+    println!(
+        "readme:    {}",
+        if readme_updated { Status::Good("updated") } else { Status::Warn("default") }.render(0)
+    );
+    println!();
+
+    println!(
+        "{:<42}  {:<8}  {:<8}  {:<14}  ORIGINAL-MATCH",
+        "ADDRESS", "CHAIN", "COMPILED", "SOURCE-CHANGED"
+    );
+    let mut all_sources_present = true;
+    for contract in &group_info.contracts {
+        let contract_dir = group_info.contract_path(&contract.address, Some(contract.chain_id))?;
+        let output_dir = contract.output_path(&artifact_path);
+
+        let compiled = if contract.is_compiled(&artifact_path) {
+            Status::Good("Y")
+        } else {
+            Status::Warn("N")
+        };
+        let source_changed = match source_changed(&contract_dir) {
+            Ok(true) => Status::Good("Y"),
+            Ok(false) => Status::Warn("N"),
+            Err(_) => {
+                all_sources_present = false;
+                Status::Bad("?")
+            }
+        };
+        let original_match = original_match(contract, &contract_dir, &output_dir);
+
+        println!(
+            "{:<42}  {:<8}  {}  {}  {}",
+            contract.address,
+            contract.chain_id,
+            compiled.render(8),
+            source_changed.render(14),
+            original_match.render(0)
+        );
+    }
+    println!();
+
+    let ready = has_name &&
+        group_info.creator.is_some() &&
+        readme_updated &&
+        !group_info.contracts.is_empty() &&
+        all_sources_present;
+    if ready {
+        println!("{}", Status::Good("ready to push").render(0));
+    } else {
+        println!(
+            "{}",
+            Status::Bad("not ready to push. `shadow push` will prompt for missing details")
+                .render(0)
+        );
+    }
+
+    Ok(())
+}
+
+/// Whether the compiled artifacts were built against the contract's current `original.json`
+fn original_match(
+    contract: &ShadowContractEntry,
+    contract_dir: &Path,
+    output_dir: &Path,
+) -> Status {
+    let original = contract_dir.join("original.json");
+    let compiled_original = output_dir.join("original.json");
+    if !original.exists() {
+        error!("{} (chain {}) has no original.json", contract.address, contract.chain_id);
+        return Status::Bad("N");
+    }
+    if !compiled_original.exists() {
+        return Status::Warn("-");
+    }
+
+    match (sha256_file(&original), sha256_file(&compiled_original)) {
+        (Ok(a), Ok(b)) if a == b => Status::Good("Y"),
+        _ => Status::Bad("N"),
+    }
+}
@@ -0,0 +1,56 @@
+use clap::{Parser, ValueEnum};
+
+/// Arguments for the `summary` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Show an overview of whether a shadow contract group is ready to push")]
+pub struct SummaryArgs {
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+}
+
+/// The fields the `contracts` subcommand can sort by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ContractSort {
+    Address,
+    #[value(alias = "chain_id")]
+    ChainId,
+    Name,
+}
+
+/// Arguments for the `contracts` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "List the contracts in a shadow contract group, with filters")]
+pub struct ContractsArgs {
+    /// The path to the shadow contract group.
+    #[clap(short, long, alias = "group", default_value = ".", required = false)]
+    pub root: String,
+
+    /// Only show contracts whose source differs from the verified original.
+    #[clap(long)]
+    pub modified: bool,
+
+    /// Only show contracts which haven't been compiled.
+    #[clap(long)]
+    pub uncompiled: bool,
+
+    /// Only show contracts on this chain, by name (e.g. `base`) or ID.
+    #[clap(long, required = false)]
+    pub chain: Option<String>,
+
+    /// Only show contracts whose address contains this hex string.
+    #[clap(long, required = false)]
+    pub address_contains: Option<String>,
+
+    /// The field to sort contracts by.
+    #[clap(long, value_enum, default_value = "address")]
+    pub sort: ContractSort,
+
+    /// Only print the number of matching contracts.
+    #[clap(long, conflicts_with = "json")]
+    pub count: bool,
+
+    /// Print the matching contracts as a JSON array.
+    #[clap(long)]
+    pub json: bool,
+}
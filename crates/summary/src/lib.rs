@@ -0,0 +1,9 @@
+//! The `summary` and `contracts` subcommands
+
+mod core;
+mod interface;
+mod utils;
+
+pub use core::*;
+pub use interface::*;
+pub(crate) use utils::*;
@@ -0,0 +1,8 @@
+/// ANSI color for red
+pub(crate) const RED_ANSI_COLOR: &str = "\x1b[31m";
+/// ANSI color for green
+pub(crate) const GREEN_ANSI_COLOR: &str = "\x1b[32m";
+/// ANSI color for yellow
+pub(crate) const YELLOW_ANSI_COLOR: &str = "\x1b[33m";
+/// Reset the ANSI color to the default
+pub(crate) const RESET_ANSI_COLOR: &str = "\x1b[0m";
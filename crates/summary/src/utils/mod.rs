@@ -0,0 +1,2 @@
+/// ANSI color constants used to colorize the summary
+pub(crate) mod constants;
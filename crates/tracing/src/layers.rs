@@ -93,6 +93,7 @@ impl Layers {
     ///
     /// # Arguments
     /// * `format` - The format for log messages.
+    /// * `default_directive` - Directive for the default logging level.
     /// * `filter` - Additional filter directives as a string.
     /// * `file_info` - Information about the log file including path and rotation strategy.
     ///
@@ -101,11 +102,12 @@ impl Layers {
     pub(crate) fn file(
         &mut self,
         format: LogFormat,
+        default_directive: Directive,
         filter: &str,
         file_info: FileInfo,
     ) -> eyre::Result<FileWorkerGuard> {
         let (writer, guard) = file_info.create_log_writer();
-        let file_filter = build_env_filter(None, filter)?;
+        let file_filter = build_env_filter(Some(default_directive), filter)?;
         let layer = format.apply(file_filter, None, Some(writer));
         self.inner.push(layer);
         Ok(guard)
@@ -129,6 +131,12 @@ impl FileInfo {
         Self { dir, file_name: SHADOW_LOG_FILE_NAME.to_string(), max_size_bytes, max_files }
     }
 
+    /// Sets the name of the log file within the log directory.
+    pub fn with_file_name(mut self, file_name: &str) -> Self {
+        self.file_name = file_name.to_string();
+        self
+    }
+
     /// Creates the log directory if it doesn't exist.
     ///
     /// # Returns
@@ -159,7 +159,12 @@ impl Tracer for ShadowTracer {
         }
 
         let file_guard = if let Some((config, file_info)) = self.file {
-            Some(layers.file(config.format, &config.filters, file_info)?)
+            Some(layers.file(
+                config.format,
+                config.default_directive.parse()?,
+                &config.filters,
+                file_info,
+            )?)
         } else {
             None
         };
@@ -0,0 +1,40 @@
+use std::{path::PathBuf, str::FromStr};
+
+use alloy::primitives::Address;
+use eyre::{bail, eyre, Result};
+use shadow_common::ShadowContractGroupInfo;
+use tracing::{error, info, warn};
+
+use crate::UntrackArgs;
+
+/// The `untrack` subcommand. Removes a contract from a shadow contract group's `info.json`,
+/// optionally deleting its files.
+pub async fn untrack(args: UntrackArgs) -> Result<()> {
+    let address: Address = args.address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let mut group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    let chain_id = group_info.contract(&address, args.chain_id)?.chain_id;
+    if !group_info.remove_contract(address, chain_id)? {
+        bail!("contract {} is not tracked by this contract group", address);
+    }
+    info!("untracked {} (chain {})", address, chain_id);
+
+    let contract_dir = root_dir.join(chain_id.to_string()).join(address.to_string().to_lowercase());
+    if args.delete {
+        std::fs::remove_dir_all(&contract_dir)?;
+        info!("deleted {}", contract_dir.display());
+    } else if contract_dir.exists() {
+        warn!(
+            "{} was left on disk. It will be tracked again by `shadow push` or `shadow sim`, which re-scan the group for contracts. Use --delete to remove it.",
+            contract_dir.display()
+        );
+    }
+
+    Ok(())
+}
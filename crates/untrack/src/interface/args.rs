@@ -0,0 +1,22 @@
+use clap::Parser;
+
+/// Arguments for the `untrack` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Remove a contract from a shadow contract group's contract list")]
+pub struct UntrackArgs {
+    /// The address of the contract to untrack.
+    pub address: String,
+
+    /// The chain ID of the contract to untrack. Required if the address is tracked on multiple
+    /// chains.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// Whether to also delete the contract's directory.
+    #[clap(long)]
+    pub delete: bool,
+}
@@ -0,0 +1,119 @@
+use std::{path::PathBuf, str::FromStr};
+
+use alloy::{
+    network::AnyNetwork,
+    primitives::{hex, Address},
+    providers::{Provider, ProviderBuilder},
+    transports::http::reqwest::Url,
+};
+use eyre::{bail, eyre, Result};
+use shadow_common::{ShadowContractGroupInfo, BUILD_ARTIFACTS_HINT};
+use tracing::{error, info};
+
+use crate::VerifyBytecodeArgs;
+
+/// The length of the CBOR-encoded metadata solc appends to runtime bytecode, including its
+/// 2-byte length suffix
+const METADATA_LENGTH: usize = 53;
+
+/// The `verify-bytecode` subcommand. Compares a shadow contract's compiled bytecode with the
+/// bytecode deployed on-chain, to check that its compiler settings are correct.
+pub async fn verify_bytecode(args: VerifyBytecodeArgs) -> Result<()> {
+    let address: Address = args.address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+    let contract = group_info.contract(&address, args.chain_id)?;
+
+    let bytecode_path = group_info
+        .artifact_path()
+        .join(contract.chain_id.to_string())
+        .join(contract.address.to_string().to_lowercase())
+        .join("bytecode.hex");
+    if !bytecode_path.exists() {
+        bail!("{} has not been built. {}", address, BUILD_ARTIFACTS_HINT);
+    }
+    let compiled = hex::decode(std::fs::read_to_string(&bytecode_path)?.trim())
+        .map_err(|e| eyre!("Invalid bytecode at {}: {}", bytecode_path.display(), e))?;
+
+    let provider =
+        ProviderBuilder::new().network::<AnyNetwork>().on_http(Url::parse(&args.rpc_url)?);
+    let rpc_chain_id = provider
+        .get_chain_id()
+        .await
+        .map_err(|e| eyre!("failed to get chain ID from RPC: {}", e))?;
+    if rpc_chain_id != contract.chain_id {
+        bail!(
+            "{} is tracked on chain {}, but the RPC is for chain {}",
+            address,
+            contract.chain_id,
+            rpc_chain_id
+        );
+    }
+
+    info!("fetching on-chain bytecode for {}", address);
+    let on_chain = provider
+        .get_code_at(address)
+        .await
+        .map_err(|e| eyre!("failed to fetch bytecode from RPC: {}", e))?;
+    if on_chain.is_empty() {
+        bail!("{} has no code on chain {}", address, contract.chain_id);
+    }
+
+    let (compiled, on_chain) = if args.ignore_metadata {
+        (strip_metadata(&compiled), strip_metadata(&on_chain))
+    } else {
+        (compiled.as_slice(), on_chain.as_ref())
+    };
+
+    match first_difference(compiled, on_chain) {
+        None => {
+            info!("compiled bytecode for {} matches on-chain ({} bytes)", address, compiled.len());
+            Ok(())
+        }
+        Some(offset) => {
+            error!(
+                "compiled bytecode for {} differs from on-chain at byte {} (0x{:x}): compiled {}, on-chain {}",
+                address,
+                offset,
+                offset,
+                describe_byte(compiled, offset),
+                describe_byte(on_chain, offset)
+            );
+            error!("compiled is {} bytes, on-chain is {} bytes", compiled.len(), on_chain.len());
+            if !args.ignore_metadata {
+                info!("if only the metadata hash differs, rerun with --ignore-metadata");
+            }
+            bail!("bytecode mismatch for {}", address)
+        }
+    }
+}
+
+/// Strips the trailing compiler metadata from runtime bytecode
+fn strip_metadata(bytecode: &[u8]) -> &[u8] {
+    &bytecode[..bytecode.len().saturating_sub(METADATA_LENGTH)]
+}
+
+/// Returns the offset of the first byte which differs between `a` and `b`, including where one
+/// is longer than the other
+fn first_difference(a: &[u8], b: &[u8]) -> Option<usize> {
+    a.iter().zip(b).position(|(a, b)| a != b).or_else(|| {
+        if a.len() == b.len() {
+            None
+        } else {
+            Some(a.len().min(b.len()))
+        }
+    })
+}
+
+/// Describes the byte at `offset`, or notes that the bytecode ends before it
+fn describe_byte(bytecode: &[u8], offset: usize) -> String {
+    match bytecode.get(offset) {
+        Some(byte) => format!("0x{:02x}", byte),
+        None => "<end of bytecode>".to_string(),
+    }
+}
@@ -0,0 +1,25 @@
+use clap::Parser;
+
+/// Arguments for the `verify-bytecode` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Check that a shadow contract's compiled bytecode matches its on-chain bytecode")]
+pub struct VerifyBytecodeArgs {
+    /// The address of the contract.
+    pub address: String,
+
+    /// The chain ID of the contract. Required if the address is tracked on multiple chains.
+    #[clap(short, long, required = false)]
+    pub chain_id: Option<u64>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain the contract is deployed on.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+
+    /// Strip the CBOR-encoded compiler metadata from the end of both bytecodes before comparing.
+    #[clap(long)]
+    pub ignore_metadata: bool,
+}
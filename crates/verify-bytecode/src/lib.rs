@@ -0,0 +1,7 @@
+//! The `verify-bytecode` subcommand
+
+mod core;
+mod interface;
+
+pub use core::*;
+pub use interface::*;
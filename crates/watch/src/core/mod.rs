@@ -0,0 +1,147 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use alloy::primitives::Address;
+use eyre::{eyre, Result};
+use notify::{recommended_watcher, Event, RecursiveMode, Watcher};
+use shadow_common::{
+    compiler,
+    db::{JsonRpcDatabase, DEFAULT_PARALLEL_REQUESTS},
+    ShadowContractGroupInfo, ShadowContractInfo, ShadowContractSettings,
+};
+use shadow_simulate::SimulateArgs;
+use tokio::sync::mpsc::unbounded_channel;
+use tracing::{error, info};
+
+use crate::WatchArgs;
+
+/// ANSI color for red
+const RED_ANSI_COLOR: &str = "\x1b[31m";
+/// ANSI color for green
+const GREEN_ANSI_COLOR: &str = "\x1b[32m";
+/// Reset the ANSI color to the default
+const RESET_ANSI_COLOR: &str = "\x1b[0m";
+
+/// How long to wait for further changes before recompiling
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// The `watch` subcommand. Watches the `src/` directory of one or every contract in a group,
+/// and recompiles a contract whenever its `.sol` or `.vy` files change. Runs until Ctrl+C is
+/// pressed.
+pub async fn watch(args: WatchArgs) -> Result<()> {
+    // root dir must be a shadow contract group
+    let root_dir = PathBuf::from_str(&args.root)?;
+    let group_info = ShadowContractGroupInfo::from_path(&root_dir).map_err(|e| {
+        error!("This is not part of a shadow contract group.");
+        eyre!("Failed to load shadow contract group: {}", e)
+    })?;
+
+    let contract_dirs = match &args.address {
+        Some(address) => {
+            let address: Address = address.parse().map_err(|e| eyre!("Invalid address: {}", e))?;
+            vec![group_info.contract_path(&address, args.chain_id)?]
+        }
+        None => group_info
+            .contracts
+            .iter()
+            .map(|c| group_info.contract_path(&c.address, Some(c.chain_id)))
+            .collect::<Result<Vec<_>>>()?,
+    };
+
+    // watchers report absolute paths, which we match against each contract's directory
+    let contract_dirs =
+        contract_dirs.iter().map(|dir| dir.canonicalize()).collect::<Result<Vec<_>, _>>()?;
+
+    let (tx, mut rx) = unbounded_channel();
+    let mut watcher = recommended_watcher(move |event: notify::Result<Event>| {
+        if let Ok(event) = event {
+            let _ = tx.send(event);
+        }
+    })?;
+    for contract_dir in &contract_dirs {
+        watcher.watch(&contract_dir.join("src"), RecursiveMode::Recursive)?;
+    }
+    info!("watching {} contracts for changes. press Ctrl+C to stop", contract_dirs.len());
+
+    loop {
+        let event = tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            event = rx.recv() => match event {
+                Some(event) => event,
+                None => break,
+            },
+        };
+
+        // collect changes until things have been quiet for a moment
+        let mut changed = BTreeSet::new();
+        let mut next = Some(event);
+        while let Some(event) = next {
+            for path in event.paths.iter().filter(|p| is_source_file(p)) {
+                if let Some(dir) = contract_dirs.iter().find(|dir| path.starts_with(dir)) {
+                    changed.insert(dir.clone());
+                }
+            }
+            next = tokio::time::timeout(DEBOUNCE, rx.recv()).await.ok().flatten();
+        }
+
+        let mut all_compiled = !changed.is_empty();
+        for contract_dir in &changed {
+            all_compiled &= compile(&args.rpc_url, contract_dir).await;
+        }
+
+        if let (true, Some(transaction_hash)) = (all_compiled, &args.then_simulate) {
+            if let Err(e) = shadow_simulate::simulate(SimulateArgs::new(
+                transaction_hash.clone(),
+                args.root.clone(),
+                args.rpc_url.clone(),
+            ))
+            .await
+            {
+                error!("simulation failed: {}", e);
+            }
+        }
+    }
+
+    info!("stopped watching");
+    Ok(())
+}
+
+/// Whether the path is a Solidity or Vyper source file
+fn is_source_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "sol" || ext == "vy")
+}
+
+/// Compiles the contract in `contract_dir`, printing the result. Returns whether it succeeded.
+async fn compile(rpc_url: &str, contract_dir: &PathBuf) -> bool {
+    let start_time = std::time::Instant::now();
+    let result = async {
+        let info = ShadowContractInfo::from_path(&contract_dir.join("info.json"))?;
+        let settings = ShadowContractSettings::from_path(&contract_dir.join("settings.json"))?;
+        let request_limiter = JsonRpcDatabase::request_limiter(DEFAULT_PARALLEL_REQUESTS);
+        compiler::compile(rpc_url, contract_dir, &settings, &info, request_limiter).await
+    }
+    .await;
+
+    match result {
+        Ok(_) => {
+            println!(
+                "{GREEN_ANSI_COLOR}compiled {} in {:?}{RESET_ANSI_COLOR}",
+                contract_dir.display(),
+                start_time.elapsed()
+            );
+            true
+        }
+        Err(e) => {
+            println!(
+                "{RED_ANSI_COLOR}failed to compile {}: {}{RESET_ANSI_COLOR}",
+                contract_dir.display(),
+                e
+            );
+            false
+        }
+    }
+}
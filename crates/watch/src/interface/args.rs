@@ -0,0 +1,27 @@
+use clap::Parser;
+
+/// Arguments for the `watch` subcommand
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Recompile shadow contracts whenever their source files change")]
+pub struct WatchArgs {
+    /// The address of the contract to watch. Defaults to every contract in the group.
+    #[clap(short, long, required = false)]
+    pub address: Option<String>,
+
+    /// The chain ID of the contract to watch. Required if the address is tracked on multiple
+    /// chains.
+    #[clap(short, long, required = false, requires = "address")]
+    pub chain_id: Option<u64>,
+
+    /// The path to the shadow contract group.
+    #[clap(short, long, default_value = ".", required = false)]
+    pub root: String,
+
+    /// The RPC URL of the chain the contracts are deployed on.
+    #[clap(short = 'u', long, default_value = "http://localhost:8545")]
+    pub rpc_url: String,
+
+    /// A transaction hash to simulate after each successful compile.
+    #[clap(long, required = false)]
+    pub then_simulate: Option<String>,
+}